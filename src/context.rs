@@ -1,5 +1,24 @@
+use crate::fractals::apollonian::state::ApollonianState;
+use crate::fractals::attractor::state::AttractorState;
+use crate::fractals::automaton::state::AutomatonState;
+use crate::fractals::buddhabrot::state::BuddhabrotState;
+use crate::fractals::chaos::state::ChaosState;
+use crate::fractals::curve::state::CurveState;
+use crate::fractals::dla::state::DlaState;
+use crate::fractals::graph::state::GraphState;
 use crate::fractals::ifs::state::IfsState;
+use crate::fractals::interpolation::state::InterpolationState;
+use crate::fractals::julia::state::JuliaState;
+use crate::fractals::kleinian::state::KleinianState;
 use crate::fractals::lsystem::state::LSystemState;
+use crate::fractals::lyapunov::state::LyapunovState;
+use crate::fractals::mandelbrot::state::MandelbrotState;
+use crate::fractals::mandelbulb::state::MandelbulbState;
+use crate::fractals::newton::state::NewtonState;
+use crate::fractals::sandpile::state::SandpileState;
+use crate::fractals::terrain::state::TerrainState;
+use crate::fractals::tiling::state::TilingState;
+use crate::fractals::tree::state::TreeState;
 use crate::fractals::FractalType;
 use crate::graphics::grid::Grid;
 use crate::ui::windows::Window;
@@ -11,6 +30,25 @@ pub struct Context {
     pub fractal_type: FractalType,
     pub ifs_state: IfsState,
     pub lsystem_state: LSystemState,
+    pub newton_state: NewtonState,
+    pub attractor_state: AttractorState,
+    pub buddhabrot_state: BuddhabrotState,
+    pub lyapunov_state: LyapunovState,
+    pub dla_state: DlaState,
+    pub terrain_state: TerrainState,
+    pub curve_state: CurveState,
+    pub apollonian_state: ApollonianState,
+    pub kleinian_state: KleinianState,
+    pub tree_state: TreeState,
+    pub tiling_state: TilingState,
+    pub automaton_state: AutomatonState,
+    pub sandpile_state: SandpileState,
+    pub graph_state: GraphState,
+    pub interpolation_state: InterpolationState,
+    pub julia_state: JuliaState,
+    pub mandelbulb_state: MandelbulbState,
+    pub mandelbrot_state: MandelbrotState,
+    pub chaos_state: ChaosState,
 
     pub windows_sender: Sender<Box<dyn Window>>,
     pub windows_receiver: Receiver<Box<dyn Window>>,
@@ -25,9 +63,62 @@ impl Default for Context {
             fractal_type: Default::default(),
             ifs_state: Default::default(),
             lsystem_state: Default::default(),
+            newton_state: Default::default(),
+            attractor_state: Default::default(),
+            buddhabrot_state: Default::default(),
+            lyapunov_state: Default::default(),
+            dla_state: Default::default(),
+            terrain_state: Default::default(),
+            curve_state: Default::default(),
+            apollonian_state: Default::default(),
+            kleinian_state: Default::default(),
+            tree_state: Default::default(),
+            tiling_state: Default::default(),
+            automaton_state: Default::default(),
+            sandpile_state: Default::default(),
+            graph_state: Default::default(),
+            interpolation_state: Default::default(),
+            julia_state: Default::default(),
+            mandelbulb_state: Default::default(),
+            mandelbrot_state: Default::default(),
+            chaos_state: Default::default(),
 
             windows_sender: sender,
             windows_receiver: receiver,
         }
     }
 }
+
+impl Context {
+    // Buddhabrot samples on background threads, they can be paused only from its own
+    // settings, so they are paused when another fractal is picked
+    pub fn set_fractal_type(&mut self, fractal_type: FractalType) {
+        if fractal_type != FractalType::Buddhabrot && self.buddhabrot_state.is_running() {
+            self.buddhabrot_state.pause();
+        }
+
+        self.fractal_type = fractal_type;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buddhabrot_is_paused_after_switch() {
+        let mut context = Context::default();
+        context.set_fractal_type(FractalType::Buddhabrot);
+        let _ = context.buddhabrot_state.initialize();
+        context.buddhabrot_state.start();
+        assert!(context.buddhabrot_state.is_running());
+
+        // Picking the same type keeps the sampling
+        context.set_fractal_type(FractalType::Buddhabrot);
+        assert!(context.buddhabrot_state.is_running());
+
+        context.set_fractal_type(FractalType::Ifs);
+        assert!(!context.buddhabrot_state.is_running());
+        assert!(context.fractal_type == FractalType::Ifs);
+    }
+}
@@ -8,11 +8,177 @@ pub enum FractalType {
 
     #[strum(serialize = "L-System")]
     LSystem,
+
+    #[strum(serialize = "Newton Fractal")]
+    Newton,
+
+    #[strum(serialize = "Strange Attractor")]
+    Attractor,
+
+    #[strum(serialize = "Buddhabrot")]
+    Buddhabrot,
+
+    #[strum(serialize = "Lyapunov Fractal")]
+    Lyapunov,
+
+    #[strum(serialize = "Diffusion-Limited Aggregation")]
+    Dla,
+
+    #[strum(serialize = "Fractal Terrain")]
+    Terrain,
+
+    #[strum(serialize = "Initiator-Generator Curve")]
+    Curve,
+
+    #[strum(serialize = "Apollonian Gasket")]
+    Apollonian,
+
+    #[strum(serialize = "Kleinian Limit Set")]
+    Kleinian,
+
+    #[strum(serialize = "Recursive Tree")]
+    Tree,
+
+    #[strum(serialize = "Substitution Tiling")]
+    Tiling,
+
+    #[strum(serialize = "Cellular Automaton")]
+    Automaton,
+
+    #[strum(serialize = "Abelian Sandpile")]
+    Sandpile,
+
+    #[strum(serialize = "Function Graph")]
+    Graph,
+
+    #[strum(serialize = "Fractal Interpolation")]
+    Interpolation,
+
+    #[strum(serialize = "Julia Set (Inverse Iteration)")]
+    Julia,
+
+    #[strum(serialize = "Mandelbulb / Mandelbox (3D)")]
+    Mandelbulb,
+
+    #[strum(serialize = "Mandelbrot (Deep Zoom)")]
+    Mandelbrot,
+
+    #[strum(serialize = "Chaos Game (Polygon)")]
+    Chaos,
+}
+
+impl FractalType {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            FractalType::Ifs,
+            FractalType::LSystem,
+            FractalType::Newton,
+            FractalType::Attractor,
+            FractalType::Buddhabrot,
+            FractalType::Lyapunov,
+            FractalType::Dla,
+            FractalType::Terrain,
+            FractalType::Curve,
+            FractalType::Apollonian,
+            FractalType::Kleinian,
+            FractalType::Tree,
+            FractalType::Tiling,
+            FractalType::Automaton,
+            FractalType::Sandpile,
+            FractalType::Graph,
+            FractalType::Interpolation,
+            FractalType::Julia,
+            FractalType::Mandelbulb,
+            FractalType::Mandelbrot,
+            FractalType::Chaos,
+        ]
+        .into_iter()
+    }
 }
 
+pub mod apollonian {
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod attractor {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod automaton {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod buddhabrot {
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+    pub mod worker;
+}
+pub mod chaos {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod curve {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod dla {
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod graph {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
 pub mod ifs {
+    pub mod coloring;
     pub mod examples;
     pub mod model;
+    pub mod seed;
     pub mod serialization;
     pub mod state;
     pub mod system;
@@ -22,6 +188,38 @@ pub mod ifs {
     }
     pub mod validation;
 }
+pub mod interpolation {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod julia {
+    pub mod examples;
+    pub mod model;
+    pub mod overview;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod kleinian {
+    pub mod examples;
+    pub mod mobius;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
 pub mod lsystem {
     pub mod examples;
     pub mod model;
@@ -32,3 +230,80 @@ pub mod lsystem {
     }
     pub mod validation;
 }
+pub mod lyapunov {
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod mandelbrot {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod mandelbulb {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod newton {
+    pub mod model;
+    pub mod polynomial;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod sandpile {
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+    pub mod worker;
+}
+pub mod terrain {
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod tiling {
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
+pub mod tree {
+    pub mod examples;
+    pub mod model;
+    pub mod serialization;
+    pub mod state;
+    pub mod ui {
+        pub mod settings;
+    }
+    pub mod validation;
+}
@@ -0,0 +1,519 @@
+use crate::geometry::circle2d::Circle2D;
+use crate::geometry::point2d::Point2D;
+use crate::math::complex::Complex;
+use crate::ui::styles::gradient::Gradient;
+use egui::{Color32, Stroke};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use strum_macros::Display;
+
+pub const DEFAULT_CURVATURES: [f64; 3] = [2.0, 2.0, 3.0];
+pub const DEFAULT_PAPPUS_RATIO: f64 = 0.6;
+pub const DEFAULT_CHAIN_LENGTH: u32 = 12;
+pub const DEFAULT_LIMIT_CIRCLES: u32 = 4;
+pub const DEFAULT_SIZE_CM: f32 = 10.0;
+pub const DEFAULT_MIN_RADIUS_CM: f32 = 0.05;
+pub const DEFAULT_STROKE_WIDTH: f32 = 1.0;
+pub const MAX_CIRCLES: usize = 200_000;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Construction {
+    // Three mutually tangent circles inside the fourth one, gaps are filled recursively
+    #[default]
+    #[strum(serialize = "Apollonian Gasket")]
+    #[serde(rename = "Apollonian Gasket")]
+    Gasket,
+
+    // Chain of circles in the arbelos, tangent to its both arcs
+    #[strum(serialize = "Pappus Chain")]
+    #[serde(rename = "Pappus Chain")]
+    PappusChain,
+
+    // Images of a ring of circles under inversions in each other
+    #[strum(serialize = "Circle Limit")]
+    #[serde(rename = "Circle Limit")]
+    CircleLimit,
+}
+
+impl Construction {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Construction::Gasket,
+            Construction::PappusChain,
+            Construction::CircleLimit,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Style {
+    #[default]
+    #[strum(serialize = "Outline")]
+    #[serde(rename = "Outline")]
+    Outline,
+
+    #[strum(serialize = "Filled")]
+    #[serde(rename = "Filled")]
+    Filled,
+}
+
+impl Style {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Style::Outline, Style::Filled].into_iter()
+    }
+}
+
+// Curvature is negative for the circle, that encloses the others
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Circle {
+    pub center: Complex,
+    pub curvature: f64,
+    pub depth: u32,
+}
+
+impl Circle {
+    pub fn new(center: Complex, curvature: f64, depth: u32) -> Self {
+        Self {
+            center,
+            curvature,
+            depth,
+        }
+    }
+
+    pub fn radius(&self) -> f64 {
+        1.0 / self.curvature.abs()
+    }
+
+    // Image under the inversion in the other circle
+    fn inverted(&self, mirror: &Circle, depth: u32) -> Option<Self> {
+        let offset = self.center - mirror.center;
+        let power = offset.norm_sqr() - self.radius().powi(2);
+        if power.abs() <= f64::EPSILON {
+            return None;
+        }
+
+        let square = mirror.radius().powi(2);
+        let radius = square * self.radius() / power.abs();
+        Some(Circle::new(
+            mirror.center + offset.scale(square / power),
+            1.0 / radius,
+            depth,
+        ))
+    }
+}
+
+// Curvature of the circle, that encloses three mutually tangent circles,
+// by Descartes' theorem. It's negative, if such a circle exists.
+pub fn outer_curvature([k1, k2, k3]: [f64; 3]) -> f64 {
+    k1 + k2 + k3 - 2.0 * (k1 * k2 + k2 * k3 + k3 * k1).sqrt()
+}
+
+// Second circle, tangent to a, b and c, the first one is d
+fn descartes_flip(a: &Circle, b: &Circle, c: &Circle, d: &Circle, depth: u32) -> Circle {
+    let curvature = 2.0 * (a.curvature + b.curvature + c.curvature) - d.curvature;
+    let weighted = (a.center.scale(a.curvature)
+        + b.center.scale(b.curvature)
+        + c.center.scale(c.curvature))
+    .scale(2.0)
+        - d.center.scale(d.curvature);
+
+    Circle::new(weighted.scale(1.0 / curvature), curvature, depth)
+}
+
+pub struct Model {
+    construction: Construction,
+    curvatures: [f64; 3],
+    pappus_ratio: f64,
+    chain_length: u32,
+    is_filling_gaps: bool,
+    limit_circles: u32,
+
+    size_cm: f32,
+    min_radius_cm: f32,
+
+    style: Style,
+    stroke_width: f32,
+    gradient: Gradient,
+}
+
+impl Model {
+    // Circles in centimeters, in order of their depth
+    pub fn circles(&self) -> Vec<Circle> {
+        match self.construction {
+            Construction::Gasket => self.gasket(),
+            Construction::PappusChain => self.pappus_chain(),
+            Construction::CircleLimit => self.circle_limit(),
+        }
+    }
+
+    pub fn shapes(&self) -> Vec<Circle2D> {
+        let circles = self.circles();
+        let max_depth = circles.iter().map(|circle| circle.depth).max().unwrap_or(0);
+
+        circles
+            .iter()
+            .map(|circle| {
+                let t = match max_depth {
+                    0 => 0.0,
+                    max_depth => circle.depth as f32 / max_depth as f32,
+                };
+                let color = self.gradient.sample(t);
+                let (fill, stroke) = match self.style {
+                    Style::Outline => {
+                        (Color32::TRANSPARENT, Stroke::new(self.stroke_width, color))
+                    },
+                    Style::Filled => (color, Stroke::NONE),
+                };
+
+                Circle2D::new(
+                    Point2D::new(circle.center.re as f32, circle.center.im as f32),
+                    circle.radius() as f32,
+                    fill,
+                    stroke,
+                )
+            })
+            .collect()
+    }
+
+    fn gasket(&self) -> Vec<Circle> {
+        let [k1, k2, k3] = self.curvatures;
+        let (r1, r2, r3) = (1.0 / k1, 1.0 / k2, 1.0 / k3);
+
+        // Triangle of the centers has sides r1 + r2, r1 + r3 and r2 + r3
+        let (a, b, c) = (r1 + r2, r1 + r3, r2 + r3);
+        let x = (a * a + b * b - c * c) / (2.0 * a);
+        let y = (b * b - x * x).max(0.0).sqrt();
+        let first = Circle::new(Complex::ZERO, k1, 0);
+        let second = Circle::new(Complex::new(a, 0.0), k2, 0);
+        let third = Circle::new(Complex::new(x, y), k3, 0);
+
+        // Complex Descartes' theorem gives two candidates for the center
+        let k4 = outer_curvature(self.curvatures);
+        let sum =
+            first.center.scale(k1) + second.center.scale(k2) + third.center.scale(k3);
+        let root = (first.center * second.center).scale(k1 * k2)
+            + (second.center * third.center).scale(k2 * k3)
+            + (first.center * third.center).scale(k1 * k3);
+        let root = root.sqrt().scale(2.0);
+        let tangency_error = |center: Complex| {
+            [&first, &second, &third]
+                .iter()
+                .map(|circle| {
+                    ((center - circle.center).norm() - (1.0 / k4.abs() - circle.radius()))
+                        .abs()
+                })
+                .sum::<f64>()
+        };
+        let candidates = [(sum + root).scale(1.0 / k4), (sum - root).scale(1.0 / k4)];
+        let center = match tangency_error(candidates[0]) < tangency_error(candidates[1]) {
+            true => candidates[0],
+            false => candidates[1],
+        };
+        let outer = Circle::new(center, k4, 0);
+
+        // Outer circle gets the given size and goes to the origin
+        let scale = self.size_cm as f64 * k4.abs();
+        let normalize = |circle: &Circle| {
+            Circle::new(
+                (circle.center - center).scale(scale),
+                circle.curvature / scale,
+                circle.depth,
+            )
+        };
+        let (first, second, third, outer) = (
+            normalize(&first),
+            normalize(&second),
+            normalize(&third),
+            normalize(&outer),
+        );
+
+        let gaps = vec![
+            [first, second, third, outer],
+            [first, second, outer, third],
+            [first, third, outer, second],
+            [second, third, outer, first],
+        ];
+        let mut circles = vec![outer, first, second, third];
+        self.fill_gaps(gaps, &mut circles);
+        circles
+    }
+
+    fn pappus_chain(&self) -> Vec<Circle> {
+        let r = self.pappus_ratio;
+        // Arbelos over the unit diameter, it's scaled to the given size
+        let scale = 2.0 * self.size_cm as f64;
+        let to_cm = |x: f64, y: f64, radius: f64, depth: u32| {
+            Circle::new(
+                Complex::new((x - 0.5) * scale, y * scale),
+                1.0 / (radius * scale),
+                depth,
+            )
+        };
+
+        let mut outer = to_cm(0.5, 0.0, 0.5, 0);
+        outer.curvature = -outer.curvature;
+        let inner = to_cm(r / 2.0, 0.0, r / 2.0, 0);
+
+        // Pappus' formulas for the n-th circle of the chain
+        let chain: Vec<Circle> = (-(self.chain_length as i32)..=self.chain_length as i32)
+            .map(|n| {
+                let n = n as f64;
+                let denominator = n * n * (1.0 - r).powi(2) + r;
+                to_cm(
+                    r * (1.0 + r) / (2.0 * denominator),
+                    n * r * (1.0 - r) / denominator,
+                    (1.0 - r) * r / (2.0 * denominator),
+                    n.abs() as u32 + 1,
+                )
+            })
+            .filter(|circle| circle.radius() >= self.min_radius_cm as f64)
+            .collect();
+
+        let mut circles = vec![outer, inner];
+        circles.extend(&chain);
+
+        if self.is_filling_gaps {
+            let gaps = chain
+                .windows(2)
+                .flat_map(|pair| {
+                    [
+                        [pair[0], pair[1], outer, inner],
+                        [pair[0], pair[1], inner, outer],
+                    ]
+                })
+                .collect();
+            self.fill_gaps(gaps, &mut circles);
+        }
+        circles
+    }
+
+    fn circle_limit(&self) -> Vec<Circle> {
+        let count = self.limit_circles as usize;
+        let size = self.size_cm as f64;
+
+        // Ring of tangent circles, orthogonal to the boundary circle
+        let half_angle = std::f64::consts::PI / count as f64;
+        let distance = size / half_angle.cos();
+        let radius = size * half_angle.tan();
+        let mirrors: Vec<Circle> = (0..count)
+            .map(|index| {
+                let angle = 2.0 * half_angle * index as f64;
+                Circle::new(Complex::from_polar(distance, angle), 1.0 / radius, 1)
+            })
+            .collect();
+
+        let mut circles = vec![Circle::new(Complex::ZERO, 1.0 / size, 0)];
+        circles.extend(&mirrors);
+
+        // Every circle is inverted in all the mirrors, except the last one used
+        let mut queue: VecDeque<(Circle, usize)> = mirrors
+            .iter()
+            .enumerate()
+            .map(|(index, circle)| (*circle, index))
+            .collect();
+        while let Some((circle, last)) = queue.pop_front() {
+            for (index, mirror) in mirrors.iter().enumerate() {
+                if index == last || circles.len() >= MAX_CIRCLES {
+                    continue;
+                }
+                let Some(image) = circle.inverted(mirror, circle.depth + 1) else {
+                    continue;
+                };
+                if image.radius() < self.min_radius_cm as f64 {
+                    continue;
+                }
+                circles.push(image);
+                queue.push_back((image, index));
+            }
+        }
+        circles
+    }
+
+    // Every gap is bounded by three tangent circles, the fourth one is on its other side
+    fn fill_gaps(&self, gaps: Vec<[Circle; 4]>, circles: &mut Vec<Circle>) {
+        let mut queue: VecDeque<[Circle; 4]> = gaps.into();
+        while let Some([a, b, c, d]) = queue.pop_front() {
+            if circles.len() >= MAX_CIRCLES {
+                break;
+            }
+
+            let depth = a.depth.max(b.depth).max(c.depth) + 1;
+            let circle = descartes_flip(&a, &b, &c, &d, depth);
+            if !circle.curvature.is_finite()
+                || circle.radius() < self.min_radius_cm as f64
+            {
+                continue;
+            }
+
+            circles.push(circle);
+            queue.push_back([a, b, circle, c]);
+            queue.push_back([a, c, circle, b]);
+            queue.push_back([b, c, circle, a]);
+        }
+    }
+}
+
+pub struct ModelBuilder {
+    construction: Construction,
+    curvatures: [f64; 3],
+    pappus_ratio: f64,
+    chain_length: u32,
+    is_filling_gaps: bool,
+    limit_circles: u32,
+
+    size_cm: f32,
+    min_radius_cm: f32,
+
+    style: Style,
+    stroke_width: f32,
+    gradient: Gradient,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            construction: Construction::default(),
+            curvatures: DEFAULT_CURVATURES,
+            pappus_ratio: DEFAULT_PAPPUS_RATIO,
+            chain_length: DEFAULT_CHAIN_LENGTH,
+            is_filling_gaps: true,
+            limit_circles: DEFAULT_LIMIT_CIRCLES,
+
+            size_cm: DEFAULT_SIZE_CM,
+            min_radius_cm: DEFAULT_MIN_RADIUS_CM,
+
+            style: Style::default(),
+            stroke_width: DEFAULT_STROKE_WIDTH,
+            gradient: Gradient::default(),
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_construction(mut self, construction: Construction) -> Self {
+        self.construction = construction;
+        self
+    }
+
+    pub fn with_curvatures(mut self, curvatures: [f64; 3]) -> Self {
+        self.curvatures = curvatures;
+        self
+    }
+
+    pub fn with_pappus_chain(
+        mut self, ratio: f64, chain_length: u32, is_filling_gaps: bool,
+    ) -> Self {
+        self.pappus_ratio = ratio;
+        self.chain_length = chain_length;
+        self.is_filling_gaps = is_filling_gaps;
+        self
+    }
+
+    pub fn with_limit_circles(mut self, limit_circles: u32) -> Self {
+        self.limit_circles = limit_circles;
+        self
+    }
+
+    pub fn with_size(mut self, size_cm: f32) -> Self {
+        self.size_cm = size_cm;
+        self
+    }
+
+    pub fn with_min_radius(mut self, min_radius_cm: f32) -> Self {
+        self.min_radius_cm = min_radius_cm;
+        self
+    }
+
+    pub fn with_style(mut self, style: Style, stroke_width: f32) -> Self {
+        self.style = style;
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            construction: self.construction,
+            curvatures: self.curvatures,
+            pappus_ratio: self.pappus_ratio,
+            chain_length: self.chain_length,
+            is_filling_gaps: self.is_filling_gaps,
+            limit_circles: self.limit_circles,
+
+            size_cm: self.size_cm,
+            min_radius_cm: self.min_radius_cm,
+
+            style: self.style,
+            stroke_width: self.stroke_width,
+            gradient: self.gradient,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn are_tangent(a: &Circle, b: &Circle) -> bool {
+        let distance = (a.center - b.center).norm();
+        let external = (distance - (a.radius() + b.radius())).abs();
+        let internal = (distance - (a.radius() - b.radius()).abs()).abs();
+        external.min(internal) < 1e-6
+    }
+
+    #[test]
+    fn gasket_circles_are_tangent() {
+        let circles = ModelBuilder::default()
+            .with_curvatures([2.0, 2.0, 3.0])
+            .with_min_radius(0.5)
+            .build()
+            .circles();
+
+        // Outer circle has the given size, and the first new circle touches the initial ones
+        let (outer, first_new) = (&circles[0], &circles[4]);
+        assert!((outer.radius() - DEFAULT_SIZE_CM as f64).abs() < 1e-9);
+        assert!(outer.center.norm() < 1e-9);
+        for circle in &circles[1..4] {
+            assert!(are_tangent(outer, circle));
+            assert!(are_tangent(first_new, circle));
+        }
+    }
+
+    #[test]
+    fn integral_gasket_curvatures() {
+        // Circles with curvatures -1, 2, 2, 3 generate only integral curvatures
+        let circles = ModelBuilder::default()
+            .with_curvatures([2.0, 2.0, 3.0])
+            .with_size(1.0)
+            .with_min_radius(0.01)
+            .build()
+            .circles();
+
+        assert!(circles.len() > 10);
+        for circle in circles {
+            assert!((circle.curvature - circle.curvature.round()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn pappus_chain_is_tangent_to_arbelos() {
+        let circles = ModelBuilder::default()
+            .with_construction(Construction::PappusChain)
+            .with_pappus_chain(0.4, 3, false)
+            .build()
+            .circles();
+
+        let (outer, inner) = (&circles[0], &circles[1]);
+        assert_eq!(circles.len(), 2 + 7);
+        for pair in circles[2..].windows(2) {
+            assert!(are_tangent(outer, &pair[0]));
+            assert!(are_tangent(inner, &pair[0]));
+            assert!(are_tangent(&pair[0], &pair[1]));
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::fractals::apollonian::model;
+use crate::fractals::apollonian::model::{Construction, Style};
+use crate::fractals::apollonian::state::ApollonianState;
+use crate::fractals::apollonian::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<ApollonianDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &ApollonianState) -> serde_json::Result<String> {
+    let dto = ApollonianDto {
+        construction: state.construction,
+        curvatures: state.curvatures,
+        pappus_ratio: state.pappus_ratio,
+        chain_length: state.chain_length,
+        is_filling_gaps: state.is_filling_gaps,
+        limit_circles: state.limit_circles,
+        size_cm: state.size_cm,
+        min_radius_cm: state.min_radius_cm,
+        style: state.style,
+        stroke_width: state.stroke_width,
+        gradient: (&state.gradient).into(),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_curvatures() -> [f64; 3] {
+    model::DEFAULT_CURVATURES
+}
+
+fn default_pappus_ratio() -> f64 {
+    model::DEFAULT_PAPPUS_RATIO
+}
+
+fn default_chain_length() -> u32 {
+    model::DEFAULT_CHAIN_LENGTH
+}
+
+fn default_filling_gaps() -> bool {
+    true
+}
+
+fn default_limit_circles() -> u32 {
+    model::DEFAULT_LIMIT_CIRCLES
+}
+
+fn default_size() -> f32 {
+    model::DEFAULT_SIZE_CM
+}
+
+fn default_min_radius() -> f32 {
+    model::DEFAULT_MIN_RADIUS_CM
+}
+
+fn default_stroke_width() -> f32 {
+    model::DEFAULT_STROKE_WIDTH
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApollonianDto {
+    #[serde(rename = "Construction", default)]
+    construction: Construction,
+
+    #[serde(rename = "Curvatures", default = "default_curvatures")]
+    curvatures: [f64; 3],
+
+    #[serde(rename = "Pappus Ratio", default = "default_pappus_ratio")]
+    pappus_ratio: f64,
+
+    #[serde(rename = "Chain Length", default = "default_chain_length")]
+    chain_length: u32,
+
+    #[serde(rename = "Fill Gaps", default = "default_filling_gaps")]
+    is_filling_gaps: bool,
+
+    #[serde(rename = "Ring Circles", default = "default_limit_circles")]
+    limit_circles: u32,
+
+    #[serde(rename = "Size", default = "default_size")]
+    size_cm: f32,
+
+    #[serde(rename = "Minimal Radius", default = "default_min_radius")]
+    min_radius_cm: f32,
+
+    #[serde(rename = "Style", default)]
+    style: Style,
+
+    #[serde(rename = "Stroke Width", default = "default_stroke_width")]
+    stroke_width: f32,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+}
+
+impl ApollonianDto {
+    pub fn load(self, state: &mut ApollonianState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.construction = self.construction;
+        state.curvatures = self.curvatures;
+        state.pappus_ratio = self.pappus_ratio;
+        state.chain_length = self.chain_length;
+        state.is_filling_gaps = self.is_filling_gaps;
+        state.limit_circles = self.limit_circles;
+        state.size_cm = self.size_cm;
+        state.min_radius_cm = self.min_radius_cm;
+        state.style = self.style;
+        state.stroke_width = self.stroke_width;
+        state.gradient = self.gradient.into();
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,116 @@
+use crate::fractals::apollonian::model::{Construction, ModelBuilder, Style};
+use crate::fractals::apollonian::validation::ValidationError;
+use crate::fractals::apollonian::{model, validation};
+use crate::geometry::circle2d::Circle2D;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::gradient::Gradient;
+use egui::Shape;
+
+pub struct ApollonianState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub construction: Construction,
+    pub curvatures: [f64; 3],
+    pub pappus_ratio: f64,
+    pub chain_length: u32,
+    pub is_filling_gaps: bool,
+    pub limit_circles: u32,
+
+    pub size_cm: f32,
+    pub min_radius_cm: f32,
+
+    pub style: Style,
+    pub stroke_width: f32,
+    pub gradient: Gradient,
+
+    circles: Vec<Circle2D>,
+}
+
+impl Default for ApollonianState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            construction: Construction::default(),
+            curvatures: model::DEFAULT_CURVATURES,
+            pappus_ratio: model::DEFAULT_PAPPUS_RATIO,
+            chain_length: model::DEFAULT_CHAIN_LENGTH,
+            is_filling_gaps: true,
+            limit_circles: model::DEFAULT_LIMIT_CIRCLES,
+
+            size_cm: model::DEFAULT_SIZE_CM,
+            min_radius_cm: model::DEFAULT_MIN_RADIUS_CM,
+
+            style: Style::default(),
+            stroke_width: model::DEFAULT_STROKE_WIDTH,
+            gradient: Gradient::default(),
+
+            circles: vec![],
+        }
+    }
+}
+
+impl ApollonianState {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Vec<Shape> {
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+            self.circles = ModelBuilder::default()
+                .with_construction(self.construction)
+                .with_curvatures(self.curvatures)
+                .with_pappus_chain(
+                    self.pappus_ratio,
+                    self.chain_length,
+                    self.is_filling_gaps,
+                )
+                .with_limit_circles(self.limit_circles)
+                .with_size(self.size_cm)
+                .with_min_radius(self.min_radius_cm)
+                .with_style(self.style, self.stroke_width)
+                .with_gradient(self.gradient.clone())
+                .build()
+                .shapes();
+        }
+
+        self.circles
+            .iter()
+            .map(|circle| circle.to_screen(params).to_shape())
+            .collect()
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        match self.construction {
+            Construction::Gasket => validation::curvatures_are_valid(&self.curvatures)?,
+            Construction::PappusChain => {
+                validation::pappus_ratio_range(self.pappus_ratio)?;
+                validation::chain_length_range(self.chain_length)?;
+            },
+            Construction::CircleLimit => {
+                validation::limit_circles_range(self.limit_circles)?
+            },
+        }
+        validation::size_range(self.size_cm, self.min_radius_cm)?;
+        validation::stroke_width_range(self.stroke_width)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn circles_count(&self) -> usize {
+        self.circles.len()
+    }
+}
@@ -0,0 +1,287 @@
+use crate::context::Context;
+use crate::fractals::apollonian::model::{Construction, Style};
+use crate::fractals::apollonian::serialization;
+use crate::fractals::apollonian::state::ApollonianState;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct ApollonianSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for ApollonianSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl ApollonianSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.apollonian_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Circles: ");
+            ui.label(context.apollonian_state.circles_count().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.apollonian_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Construction:");
+            egui::ComboBox::from_id_salt("ApollonianConstruction")
+                .selected_text(format!("{}", state.construction))
+                .show_ui(ui, |ui| {
+                    for construction in Construction::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.construction,
+                                construction,
+                                construction.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            match state.construction {
+                Construction::Gasket => {
+                    ui.label("Curvatures:");
+                    ui.horizontal(|ui| {
+                        for curvature in &mut state.curvatures {
+                            changed |= ui
+                                .add(
+                                    DragValue::new(curvature)
+                                        .speed(0.01)
+                                        .range(0.001..=f64::MAX),
+                                )
+                                .changed();
+                        }
+                    })
+                    .response
+                    .on_hover_text(
+                        "Curvatures of three mutually tangent circles, inside the fourth one.",
+                    );
+                    ui.end_row();
+                },
+                Construction::PappusChain => {
+                    ui.label("Arbelos Ratio:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.pappus_ratio, 0.01..=0.99))
+                        .on_hover_text("Diameter of the inner arc relative to the outer one.")
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Chain Length:");
+                    changed |= ui
+                        .add(
+                            DragValue::new(&mut state.chain_length)
+                                .speed(1)
+                                .range(0..=1000),
+                        )
+                        .on_hover_text("Number of circles on each side of the central one.")
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Fill Gaps:");
+                    changed |= ui.checkbox(&mut state.is_filling_gaps, "").changed();
+                    ui.end_row();
+                },
+                Construction::CircleLimit => {
+                    ui.label("Ring Circles:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.limit_circles, 3..=12))
+                        .on_hover_text("Tangent circles, that invert each other.")
+                        .changed();
+                    ui.end_row();
+                },
+            }
+
+            ui.label("Size:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.size_cm)
+                        .speed(0.1)
+                        .range(0.1..=1000.0)
+                        .suffix(" cm"),
+                )
+                .on_hover_text("Radius of the outer circle.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Minimal Radius:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.min_radius_cm)
+                        .speed(0.001)
+                        .range(0.001..=100.0)
+                        .suffix(" cm"),
+                )
+                .on_hover_text("Smaller circles are not drawn.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Style:");
+            egui::ComboBox::from_id_salt("ApollonianStyle")
+                .selected_text(format!("{}", state.style))
+                .show_ui(ui, |ui| {
+                    for style in Style::iter() {
+                        changed |= ui
+                            .selectable_value(&mut state.style, style, style.to_string())
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            if state.style == Style::Outline {
+                ui.label("Stroke Width:");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut state.stroke_width)
+                            .speed(0.1)
+                            .range(0.1..=10.0),
+                    )
+                    .changed();
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Gradient", |ui| {
+            ui.label("Colors go by the depth of the circles.");
+            changed |= state.gradient.show_editor(ui, "Apollonian");
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.apollonian_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.apollonian_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    context.apollonian_state.is_initialized(),
+                    Button::new("Draw"),
+                )
+                .clicked()
+            {
+                context.apollonian_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.apollonian_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.apollonian_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.apollonian_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Construction\": \"Apollonian Gasket\",
+                                \"Curvatures\": [2.0, 2.0, 3.0],
+                                \"Size\": 10.0,
+                                \"Minimal Radius\": 0.05,
+                                \"Style\": \"Filled\"
+                            }
+
+                            Construction: \"Apollonian Gasket\", \"Pappus Chain\"
+                            or \"Circle Limit\".
+                            Curvatures: three mutually tangent circles of the gasket.
+
+                            Other fields: \"Pappus Ratio\", \"Chain Length\", \"Fill Gaps\",
+                            \"Ring Circles\", \"Stroke Width\", \"Gradient\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut ApollonianState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
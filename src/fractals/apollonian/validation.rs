@@ -0,0 +1,192 @@
+use crate::fractals::apollonian::model;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+const MAX_CHAIN_LENGTH: u32 = 1000;
+const LIMIT_CIRCLES_RANGE: std::ops::RangeInclusive<u32> = 3..=12;
+
+pub fn curvatures_are_valid(curvatures: &[f64; 3]) -> Result<(), ValidationError> {
+    if curvatures
+        .iter()
+        .any(|curvature| !(*curvature > 0.0 && curvature.is_finite()))
+    {
+        return Err(ValidationError::BadCurvatureValue);
+    }
+
+    // Otherwise the fourth circle doesn't enclose the other three
+    let outer = model::outer_curvature(*curvatures);
+    if outer >= 0.0 {
+        return Err(ValidationError::CirclesCantBeEnclosed(format!(
+            "Curvature of the outer circle: {:.4}",
+            outer
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn pappus_ratio_range(ratio: f64) -> Result<(), ValidationError> {
+    if !(ratio > 0.0 && ratio < 1.0) {
+        return Err(ValidationError::BadPappusRatio);
+    }
+
+    Ok(())
+}
+
+pub fn chain_length_range(length: u32) -> Result<(), ValidationError> {
+    if length > MAX_CHAIN_LENGTH {
+        return Err(ValidationError::BadChainLength);
+    }
+
+    Ok(())
+}
+
+pub fn limit_circles_range(count: u32) -> Result<(), ValidationError> {
+    if !LIMIT_CIRCLES_RANGE.contains(&count) {
+        return Err(ValidationError::BadLimitCirclesValue);
+    }
+
+    Ok(())
+}
+
+pub fn size_range(size_cm: f32, min_radius_cm: f32) -> Result<(), ValidationError> {
+    if !(size_cm > 0.0 && size_cm.is_finite()) {
+        return Err(ValidationError::BadSizeValue);
+    }
+    if !(min_radius_cm > 0.0 && min_radius_cm < size_cm) {
+        return Err(ValidationError::BadMinRadiusValue);
+    }
+
+    Ok(())
+}
+
+pub fn stroke_width_range(width: f32) -> Result<(), ValidationError> {
+    if !(width > 0.0 && width.is_finite()) {
+        return Err(ValidationError::BadStrokeWidth);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("Curvatures have to be positive numbers.")]
+    BadCurvatureValue,
+
+    #[error("The circles with these curvatures can't be enclosed by a tangent circle.")]
+    CirclesCantBeEnclosed(String),
+
+    #[error("The ratio of the arbelos has to be in range 0..1 (both are exclusive).")]
+    BadPappusRatio,
+
+    #[error("The chain length has to be in range 0..1000.")]
+    BadChainLength,
+
+    #[error("The number of circles in the ring has to be in range 3..12.")]
+    BadLimitCirclesValue,
+
+    #[error("The size has to be greater than 0.")]
+    BadSizeValue,
+
+    #[error("The minimal radius has to be in range 0..size (both are exclusive).")]
+    BadMinRadiusValue,
+
+    #[error("The stroke width has to be greater than 0.")]
+    BadStrokeWidth,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::CirclesCantBeEnclosed(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::apollonian::model::Construction;
+    use crate::fractals::apollonian::state::ApollonianState;
+
+    #[test]
+    fn outer_circle_must_be_a_circle() {
+        // For 1, 1 and 4 the fourth tangent "circle" is a straight line
+        assert_eq!(model::outer_curvature([1.0, 1.0, 4.0]), 0.0);
+        assert!(matches!(
+            curvatures_are_valid(&[1.0, 1.0, 4.0]),
+            Err(ValidationError::CirclesCantBeEnclosed(_))
+        ));
+        assert!(curvatures_are_valid(&[1.0, 1.0, 3.9]).is_ok());
+    }
+
+    #[test]
+    fn outer_curvature_is_reported() {
+        let mut state = ApollonianState::default();
+        state.curvatures = [2.0, 2.0, 2.0];
+        assert!(state.initialize().is_ok());
+
+        state.curvatures = [1.0, 1.0, 9.0];
+        let result = state.initialize();
+
+        // 11 - 2 * sqrt(19)
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Curvature of the outer circle: 2.2822".to_string())
+        );
+    }
+
+    #[test]
+    fn curvatures_are_positive() {
+        for curvatures in [[0.0, 1.0, 1.0], [-1.0, 2.0, 2.0], [f64::INFINITY, 1.0, 1.0]] {
+            assert!(matches!(
+                curvatures_are_valid(&curvatures),
+                Err(ValidationError::BadCurvatureValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn only_current_construction_is_validated() {
+        let mut state = ApollonianState::default();
+        state.construction = Construction::CircleLimit;
+        state.curvatures = [1.0, 1.0, 100.0];
+        state.pappus_ratio = 1.0;
+        assert!(state.initialize().is_ok());
+
+        state.construction = Construction::PappusChain;
+        assert!(matches!(
+            state.initialize(),
+            Err(ValidationError::BadPappusRatio)
+        ));
+    }
+
+    #[test]
+    fn limit_circles_bounds_are_inclusive() {
+        assert!(limit_circles_range(3).is_ok());
+        assert!(limit_circles_range(12).is_ok());
+        assert!(limit_circles_range(2).is_err());
+        assert!(limit_circles_range(13).is_err());
+    }
+
+    #[test]
+    fn min_radius_is_smaller_than_size() {
+        assert!(size_range(10.0, 9.9).is_ok());
+        for min_radius in [10.0, 0.0, f32::NAN] {
+            assert!(matches!(
+                size_range(10.0, min_radius),
+                Err(ValidationError::BadMinRadiusValue)
+            ));
+        }
+    }
+}
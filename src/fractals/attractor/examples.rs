@@ -0,0 +1,72 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static ATTRACTOR_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/attractor/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Clifford")]
+    Clifford,
+
+    #[strum(serialize = "Gumowski-Mira")]
+    GumowskiMira,
+
+    #[strum(serialize = "Hénon")]
+    Henon,
+
+    #[strum(serialize = "Ikeda")]
+    Ikeda,
+
+    #[strum(serialize = "Lorenz")]
+    Lorenz,
+
+    #[strum(serialize = "Peter de Jong")]
+    DeJong,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::Clifford => PathBuf::from(r"Clifford.json"),
+            Example::GumowskiMira => PathBuf::from(r"Gumowski-Mira.json"),
+            Example::Henon => PathBuf::from(r"Henon.json"),
+            Example::Ikeda => PathBuf::from(r"Ikeda.json"),
+            Example::Lorenz => PathBuf::from(r"Lorenz.json"),
+            Example::DeJong => PathBuf::from(r"Peter-de-Jong.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = ATTRACTOR_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::Clifford,
+            Example::GumowskiMira,
+            Example::Henon,
+            Example::Ikeda,
+            Example::Lorenz,
+            Example::DeJong,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
@@ -0,0 +1,501 @@
+use crate::geometry::dot::{Dot, DotBuilder};
+use crate::geometry::point2d::Point2D;
+use crate::ui::styles::colors;
+use crate::ui::styles::gradient::Gradient;
+use crate::ui::windows::message::MessageWindow;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use thiserror::Error;
+
+pub const DEFAULT_ITERATIONS: u32 = 100_000;
+pub const DEFAULT_BURN_IN: u32 = 100;
+pub const DEFAULT_RADIUS: f32 = 0.01;
+pub const MAX_ITERATIONS: u32 = 5_000_000;
+pub const MAX_BURN_IN: u32 = 1_000_000;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Kind {
+    // x' = sin(a * y) + c * cos(a * x), y' = sin(b * x) + d * cos(b * y)
+    #[default]
+    #[strum(serialize = "Clifford")]
+    #[serde(rename = "Clifford")]
+    Clifford,
+
+    // x' = sin(a * y) - cos(b * x), y' = sin(c * x) - cos(d * y)
+    #[strum(serialize = "Peter de Jong")]
+    #[serde(rename = "Peter de Jong")]
+    DeJong,
+
+    // x' = 1 - a * x^2 + y, y' = b * x
+    #[strum(serialize = "Hénon")]
+    #[serde(rename = "Henon")]
+    Henon,
+
+    // t = 0.4 - 6 / (1 + x^2 + y^2), (x', y') = (1, 0) + u * rotate((x, y), t)
+    #[strum(serialize = "Ikeda")]
+    #[serde(rename = "Ikeda")]
+    Ikeda,
+
+    // f(x) = m * x + 2 * (1 - m) * x^2 / (1 + x^2)
+    // x' = y + a * (1 - b * y^2) * y + f(x), y' = -x + f(x')
+    #[strum(serialize = "Gumowski-Mira")]
+    #[serde(rename = "Gumowski-Mira")]
+    GumowskiMira,
+
+    // Continuous system, integrated by RK4 and projected onto a plane
+    #[strum(serialize = "Lorenz")]
+    #[serde(rename = "Lorenz")]
+    Lorenz,
+}
+
+impl Kind {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Kind::Clifford,
+            Kind::DeJong,
+            Kind::Henon,
+            Kind::Ikeda,
+            Kind::GumowskiMira,
+            Kind::Lorenz,
+        ]
+        .into_iter()
+    }
+
+    pub fn parameter_names(&self) -> &'static [&'static str] {
+        match self {
+            Kind::Clifford | Kind::DeJong => &["a", "b", "c", "d"],
+            Kind::Henon => &["a", "b"],
+            Kind::Ikeda => &["u"],
+            Kind::GumowskiMira => &["a", "b", "m"],
+            Kind::Lorenz => &["sigma", "rho", "beta", "dt"],
+        }
+    }
+
+    pub fn default_parameters(&self) -> Vec<f64> {
+        match self {
+            Kind::Clifford => vec![-1.4, 1.6, 1.0, 0.7],
+            Kind::DeJong => vec![1.4, -2.3, 2.4, -2.1],
+            Kind::Henon => vec![1.4, 0.3],
+            Kind::Ikeda => vec![0.918],
+            Kind::GumowskiMira => vec![0.008, 0.05, -0.496],
+            Kind::Lorenz => vec![10.0, 28.0, 8.0 / 3.0, 0.005],
+        }
+    }
+
+    pub fn default_start(&self) -> [f64; 3] {
+        match self {
+            Kind::GumowskiMira => [0.0, 0.5, 0.0],
+            Kind::Lorenz => [1.0, 1.0, 1.0],
+            _ => [0.1, 0.1, 0.0],
+        }
+    }
+
+    // Center of the picture in attractor units and the size of a unit
+    pub fn default_view(&self) -> ([f64; 2], f32) {
+        match self {
+            Kind::Clifford | Kind::DeJong => ([0.0, 0.0], 3.0),
+            Kind::Henon => ([0.0, 0.0], 5.0),
+            Kind::Ikeda => ([0.6, -0.8], 4.0),
+            Kind::GumowskiMira => ([0.0, 0.0], 0.4),
+            Kind::Lorenz => ([0.0, 25.0], 0.25),
+        }
+    }
+
+    pub fn is_continuous(&self) -> bool {
+        *self == Kind::Lorenz
+    }
+}
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Projection {
+    #[strum(serialize = "XY")]
+    #[serde(rename = "XY")]
+    Xy,
+
+    #[default]
+    #[strum(serialize = "XZ")]
+    #[serde(rename = "XZ")]
+    Xz,
+
+    #[strum(serialize = "YZ")]
+    #[serde(rename = "YZ")]
+    Yz,
+}
+
+impl Projection {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Projection::Xy, Projection::Xz, Projection::Yz].into_iter()
+    }
+
+    fn project(&self, [x, y, z]: [f64; 3]) -> [f64; 2] {
+        match self {
+            Projection::Xy => [x, y],
+            Projection::Xz => [x, z],
+            Projection::Yz => [y, z],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Coloring {
+    #[default]
+    #[strum(serialize = "Solid (Black)")]
+    #[serde(rename = "Solid")]
+    Solid,
+
+    // Position of the point in the orbit
+    #[strum(serialize = "Iteration")]
+    #[serde(rename = "Iteration")]
+    Iteration,
+
+    // Distance to the previous point, so fast parts of the orbit stand out
+    #[strum(serialize = "Speed")]
+    #[serde(rename = "Speed")]
+    Speed,
+}
+
+impl Coloring {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Coloring::Solid, Coloring::Iteration, Coloring::Speed].into_iter()
+    }
+
+    pub fn is_gradient_based(&self) -> bool {
+        *self != Coloring::Solid
+    }
+}
+
+pub struct Model {
+    kind: Kind,
+    parameters: Vec<f64>,
+    start: [f64; 3],
+    projection: Projection,
+
+    iterations: u32,
+    burn_in: u32,
+
+    center: [f64; 2],
+    cm_per_unit: f32,
+    radius: f32,
+
+    coloring: Coloring,
+    gradient: Gradient,
+}
+
+impl Model {
+    pub fn dots(&self) -> Result<Vec<Dot>, ModelError> {
+        let mut point = self.start;
+
+        for iteration in 0..self.burn_in {
+            point = self.next_point(point, iteration)?;
+        }
+
+        let mut points: Vec<[f64; 2]> = Vec::with_capacity(self.iterations as usize);
+        for iteration in 0..self.iterations {
+            point = self.next_point(point, self.burn_in.saturating_add(iteration))?;
+            points.push(match self.kind.is_continuous() {
+                true => self.projection.project(point),
+                false => [point[0], point[1]],
+            });
+        }
+
+        let values = self.coloring_values(&points);
+
+        Ok(points
+            .iter()
+            .zip(values)
+            .map(|([x, y], value)| {
+                let color = match self.coloring {
+                    Coloring::Solid => colors::BLACK,
+                    _ => self.gradient.sample(value),
+                };
+
+                DotBuilder::default()
+                    .with_center(Point2D::new(
+                        ((x - self.center[0]) * self.cm_per_unit as f64) as f32,
+                        ((y - self.center[1]) * self.cm_per_unit as f64) as f32,
+                    ))
+                    .with_color(color)
+                    .with_radius_centimeters(self.radius)
+                    .build()
+            })
+            .collect())
+    }
+
+    fn next_point(
+        &self, point: [f64; 3], iteration: u32,
+    ) -> Result<[f64; 3], ModelError> {
+        let next = match self.kind {
+            Kind::Lorenz => self.lorenz_step(point),
+            _ => {
+                let [x, y] = self.map([point[0], point[1]]);
+                [x, y, 0.0]
+            },
+        };
+
+        if !next.iter().all(|value| value.is_finite()) {
+            return Err(ModelError::Diverged(iteration.saturating_add(1)));
+        }
+
+        Ok(next)
+    }
+
+    fn map(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let p = |index: usize| self.parameters.get(index).copied().unwrap_or_default();
+
+        match self.kind {
+            Kind::Clifford => {
+                let (a, b, c, d) = (p(0), p(1), p(2), p(3));
+                [
+                    (a * y).sin() + c * (a * x).cos(),
+                    (b * x).sin() + d * (b * y).cos(),
+                ]
+            },
+            Kind::DeJong => {
+                let (a, b, c, d) = (p(0), p(1), p(2), p(3));
+                [(a * y).sin() - (b * x).cos(), (c * x).sin() - (d * y).cos()]
+            },
+            Kind::Henon => {
+                let (a, b) = (p(0), p(1));
+                [1.0 - a * x * x + y, b * x]
+            },
+            Kind::Ikeda => {
+                let u = p(0);
+                let t = 0.4 - 6.0 / (1.0 + x * x + y * y);
+                let (sin, cos) = t.sin_cos();
+                [1.0 + u * (x * cos - y * sin), u * (x * sin + y * cos)]
+            },
+            Kind::GumowskiMira => {
+                let (a, b, m) = (p(0), p(1), p(2));
+                let f = |x: f64| m * x + 2.0 * (1.0 - m) * x * x / (1.0 + x * x);
+                let next_x = y + a * (1.0 - b * y * y) * y + f(x);
+                [next_x, -x + f(next_x)]
+            },
+            Kind::Lorenz => [x, y],
+        }
+    }
+
+    // One step of the classic Runge-Kutta method
+    fn lorenz_step(&self, point: [f64; 3]) -> [f64; 3] {
+        let p = |index: usize| self.parameters.get(index).copied().unwrap_or_default();
+        let (sigma, rho, beta, dt) = (p(0), p(1), p(2), p(3));
+
+        let derivative =
+            |[x, y, z]: [f64; 3]| [sigma * (y - x), x * (rho - z) - y, x * y - beta * z];
+        let shifted = |point: [f64; 3], delta: [f64; 3], factor: f64| {
+            [
+                point[0] + delta[0] * factor,
+                point[1] + delta[1] * factor,
+                point[2] + delta[2] * factor,
+            ]
+        };
+
+        let k1 = derivative(point);
+        let k2 = derivative(shifted(point, k1, dt / 2.0));
+        let k3 = derivative(shifted(point, k2, dt / 2.0));
+        let k4 = derivative(shifted(point, k3, dt));
+
+        let mut next = point;
+        for axis in 0..3 {
+            next[axis] +=
+                dt / 6.0 * (k1[axis] + 2.0 * k2[axis] + 2.0 * k3[axis] + k4[axis]);
+        }
+        next
+    }
+
+    // Values in range 0..=1 that pick the colors from the gradient
+    fn coloring_values(&self, points: &[[f64; 2]]) -> Vec<f32> {
+        match self.coloring {
+            Coloring::Solid => vec![0.0; points.len()],
+            Coloring::Iteration => {
+                let last = points.len().saturating_sub(1).max(1) as f32;
+                (0..points.len()).map(|index| index as f32 / last).collect()
+            },
+            Coloring::Speed => {
+                let mut speeds: Vec<f32> = Vec::with_capacity(points.len());
+                let mut previous = self.start_projected();
+                for point in points {
+                    speeds
+                        .push(f64::hypot(point[0] - previous[0], point[1] - previous[1])
+                            as f32);
+                    previous = *point;
+                }
+
+                let max = speeds.iter().copied().fold(0.0, f32::max);
+                if max > 0.0 {
+                    speeds.iter_mut().for_each(|speed| *speed /= max);
+                }
+                speeds
+            },
+        }
+    }
+
+    fn start_projected(&self) -> [f64; 2] {
+        match self.kind.is_continuous() {
+            true => self.projection.project(self.start),
+            false => [self.start[0], self.start[1]],
+        }
+    }
+}
+
+pub struct ModelBuilder {
+    kind: Kind,
+    parameters: Vec<f64>,
+    start: [f64; 3],
+    projection: Projection,
+
+    iterations: u32,
+    burn_in: u32,
+
+    center: [f64; 2],
+    cm_per_unit: f32,
+    radius: f32,
+
+    coloring: Coloring,
+    gradient: Gradient,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        let kind = Kind::default();
+        let (center, cm_per_unit) = kind.default_view();
+
+        Self {
+            kind,
+            parameters: kind.default_parameters(),
+            start: kind.default_start(),
+            projection: Projection::default(),
+
+            iterations: DEFAULT_ITERATIONS,
+            burn_in: DEFAULT_BURN_IN,
+
+            center,
+            cm_per_unit,
+            radius: DEFAULT_RADIUS,
+
+            coloring: Coloring::default(),
+            gradient: Gradient::default(),
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_kind(mut self, kind: Kind, parameters: Vec<f64>) -> Self {
+        self.kind = kind;
+        self.parameters = parameters;
+        self
+    }
+
+    pub fn with_start(mut self, start: [f64; 3]) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn with_projection(mut self, projection: Projection) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_burn_in(mut self, burn_in: u32) -> Self {
+        self.burn_in = burn_in;
+        self
+    }
+
+    pub fn with_view(mut self, center: [f64; 2], cm_per_unit: f32) -> Self {
+        self.center = center;
+        self.cm_per_unit = cm_per_unit;
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_coloring(mut self, coloring: Coloring) -> Self {
+        self.coloring = coloring;
+        self
+    }
+
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            kind: self.kind,
+            parameters: self.parameters,
+            start: self.start,
+            projection: self.projection,
+
+            iterations: self.iterations,
+            burn_in: self.burn_in,
+
+            center: self.center,
+            cm_per_unit: self.cm_per_unit,
+            radius: self.radius,
+
+            coloring: self.coloring,
+            gradient: self.gradient,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ModelError {
+    #[error("The orbit has escaped to infinity. Try other parameters or start point.")]
+    Diverged(u32),
+}
+
+impl ModelError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::Diverged(iteration) => Some(format!("Iteration: {}", iteration)),
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Drawing Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn henon_orbit_is_bounded() {
+        let dots = ModelBuilder::default()
+            .with_kind(Kind::Henon, Kind::Henon.default_parameters())
+            .with_view([0.0, 0.0], 1.0)
+            .with_iterations(10_000)
+            .build()
+            .dots()
+            .unwrap_or_default();
+
+        assert_eq!(dots.len(), 10_000);
+        assert!(dots
+            .iter()
+            .all(|dot| dot.point.x.abs() < 1.5 && dot.point.y.abs() < 0.5));
+    }
+
+    #[test]
+    fn diverging_orbit_error() {
+        let result = ModelBuilder::default()
+            .with_kind(Kind::Henon, vec![1.4, 0.3])
+            .with_start([10.0, 10.0, 0.0])
+            .build()
+            .dots();
+
+        assert!(matches!(result, Err(ModelError::Diverged(_))));
+    }
+}
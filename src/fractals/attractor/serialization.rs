@@ -0,0 +1,107 @@
+use crate::fractals::attractor::model;
+use crate::fractals::attractor::model::{Coloring, Kind, Projection};
+use crate::fractals::attractor::state::AttractorState;
+use crate::fractals::attractor::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<AttractorDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &AttractorState) -> serde_json::Result<String> {
+    let dto = AttractorDto {
+        kind: state.kind(),
+        parameters: state.parameters.clone(),
+        start: Some(state.start),
+        projection: state.projection,
+        iterations: state.iterations,
+        burn_in: state.burn_in,
+        center: Some(state.center),
+        cm_per_unit: Some(state.cm_per_unit),
+        radius_cm: state.radius_cm,
+        coloring: state.coloring,
+        gradient: (&state.gradient).into(),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_iterations() -> u32 {
+    model::DEFAULT_ITERATIONS
+}
+
+fn default_burn_in() -> u32 {
+    model::DEFAULT_BURN_IN
+}
+
+fn default_radius() -> f32 {
+    model::DEFAULT_RADIUS
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AttractorDto {
+    #[serde(rename = "Attractor")]
+    kind: Kind,
+
+    #[serde(rename = "Parameters")]
+    parameters: Vec<f64>,
+
+    // Start point, view center and scale fall back to the defaults of the attractor
+    #[serde(rename = "Start", default, skip_serializing_if = "Option::is_none")]
+    start: Option<[f64; 3]>,
+
+    #[serde(rename = "Projection", default)]
+    projection: Projection,
+
+    #[serde(rename = "Iterations", default = "default_iterations")]
+    iterations: u32,
+
+    #[serde(rename = "Burn-in", default = "default_burn_in")]
+    burn_in: u32,
+
+    #[serde(rename = "Center", default, skip_serializing_if = "Option::is_none")]
+    center: Option<[f64; 2]>,
+
+    #[serde(rename = "Scale", default, skip_serializing_if = "Option::is_none")]
+    cm_per_unit: Option<f32>,
+
+    #[serde(rename = "Radius", default = "default_radius")]
+    radius_cm: f32,
+
+    #[serde(rename = "Coloring", default)]
+    coloring: Coloring,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+}
+
+impl AttractorDto {
+    pub fn load(self, state: &mut AttractorState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.set_kind(self.kind);
+        state.parameters = self.parameters;
+        if let Some(start) = self.start {
+            state.start = start;
+        }
+        state.projection = self.projection;
+        state.iterations = self.iterations;
+        state.burn_in = self.burn_in;
+        if let Some(center) = self.center {
+            state.center = center;
+        }
+        if let Some(cm_per_unit) = self.cm_per_unit {
+            state.cm_per_unit = cm_per_unit;
+        }
+        state.radius_cm = self.radius_cm;
+        state.coloring = self.coloring;
+        state.gradient = self.gradient.into();
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,130 @@
+use crate::fractals::attractor::model::{
+    Coloring, Kind, ModelBuilder, ModelError, Projection,
+};
+use crate::fractals::attractor::validation::ValidationError;
+use crate::fractals::attractor::{model, validation};
+use crate::geometry::dot::Dot;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::gradient::Gradient;
+use egui::Shape;
+
+pub struct AttractorState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    dots: Vec<Dot>,
+
+    kind: Kind,
+    pub parameters: Vec<f64>,
+    pub start: [f64; 3],
+    pub projection: Projection,
+
+    pub iterations: u32,
+    pub burn_in: u32,
+
+    pub center: [f64; 2],
+    pub cm_per_unit: f32,
+    pub radius_cm: f32,
+
+    pub coloring: Coloring,
+    pub gradient: Gradient,
+}
+
+impl Default for AttractorState {
+    fn default() -> Self {
+        let kind = Kind::default();
+        let (center, cm_per_unit) = kind.default_view();
+
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            dots: vec![],
+
+            kind,
+            parameters: kind.default_parameters(),
+            start: kind.default_start(),
+            projection: Projection::default(),
+
+            iterations: model::DEFAULT_ITERATIONS,
+            burn_in: model::DEFAULT_BURN_IN,
+
+            center,
+            cm_per_unit,
+            radius_cm: model::DEFAULT_RADIUS,
+
+            coloring: Coloring::default(),
+            gradient: Gradient::default(),
+        }
+    }
+}
+
+impl AttractorState {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Result<Vec<Shape>, ModelError> {
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+            self.dots = vec![];
+            self.dots = ModelBuilder::default()
+                .with_kind(self.kind, self.parameters.clone())
+                .with_start(self.start)
+                .with_projection(self.projection)
+                .with_iterations(self.iterations)
+                .with_burn_in(self.burn_in)
+                .with_view(self.center, self.cm_per_unit)
+                .with_radius(self.radius_cm)
+                .with_coloring(self.coloring)
+                .with_gradient(self.gradient.clone())
+                .build()
+                .dots()?;
+        }
+
+        Ok(self
+            .dots
+            .iter()
+            .map(|dot| dot.to_screen(params).to_shape())
+            .collect())
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    // Parameters, start point and view of a different attractor don't make sense
+    pub fn set_kind(&mut self, kind: Kind) {
+        self.reset_initialization();
+
+        let (center, cm_per_unit) = kind.default_view();
+        self.kind = kind;
+        self.parameters = kind.default_parameters();
+        self.start = kind.default_start();
+        self.center = center;
+        self.cm_per_unit = cm_per_unit;
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::parameters_count(self.kind, &self.parameters)?;
+        validation::numbers_are_finite(&self.parameters)?;
+        validation::numbers_are_finite(&self.start)?;
+        validation::numbers_are_finite(&self.center)?;
+        validation::time_step_range(self.kind, &self.parameters)?;
+        validation::iterations_range(self.iterations)?;
+        validation::burn_in_range(self.burn_in)?;
+        validation::scale_range(self.cm_per_unit)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+}
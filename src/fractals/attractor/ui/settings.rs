@@ -0,0 +1,321 @@
+use crate::context::Context;
+use crate::fractals::attractor::examples::Example;
+use crate::fractals::attractor::model;
+use crate::fractals::attractor::model::{Coloring, Kind, Projection};
+use crate::fractals::attractor::serialization;
+use crate::fractals::attractor::state::AttractorState;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Ui};
+use indoc::indoc;
+
+pub struct AttractorSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for AttractorSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl AttractorSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.attractor_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.attractor_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Attractor:");
+            let mut kind = state.kind();
+            egui::ComboBox::from_id_salt("AttractorKind")
+                .selected_text(format!("{}", kind))
+                .show_ui(ui, |ui| {
+                    for value in Kind::iter() {
+                        ui.selectable_value(&mut kind, value, value.to_string());
+                    }
+                });
+            if kind != state.kind() {
+                state.set_kind(kind);
+            }
+            ui.end_row();
+
+            let names = state.kind().parameter_names();
+            for (name, value) in names.iter().zip(state.parameters.iter_mut()) {
+                ui.label(format!("{}:", name));
+                changed |= ui.add(DragValue::new(value).speed(0.001)).changed();
+                ui.end_row();
+            }
+
+            ui.label("Start Point:");
+            ui.horizontal(|ui| {
+                let axes = match state.kind().is_continuous() {
+                    true => 3,
+                    false => 2,
+                };
+                for (value, prefix) in
+                    state.start.iter_mut().zip(["x: ", "y: ", "z: "]).take(axes)
+                {
+                    changed |= ui
+                        .add(DragValue::new(value).speed(0.01).prefix(prefix))
+                        .changed();
+                }
+            });
+            ui.end_row();
+
+            if state.kind().is_continuous() {
+                ui.label("Projection:");
+                egui::ComboBox::from_id_salt("AttractorProjection")
+                    .selected_text(format!("{}", state.projection))
+                    .show_ui(ui, |ui| {
+                        for projection in Projection::iter() {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut state.projection,
+                                    projection,
+                                    projection.to_string(),
+                                )
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+            }
+
+            ui.label("Iterations:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.iterations)
+                        .speed(100)
+                        .range(1..=model::MAX_ITERATIONS),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Burn-in:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.burn_in)
+                        .speed(1)
+                        .range(0..=model::MAX_BURN_IN),
+                )
+                .on_hover_text(
+                    "Points skipped before the orbit settles on the attractor.",
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Center:");
+            ui.horizontal(|ui| {
+                for (value, prefix) in state.center.iter_mut().zip(["x: ", "y: "]) {
+                    changed |= ui
+                        .add(DragValue::new(value).speed(0.01).prefix(prefix))
+                        .changed();
+                }
+            });
+            ui.end_row();
+
+            ui.label("Scale:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.cm_per_unit)
+                        .speed(0.01)
+                        .range(0.001..=f32::MAX)
+                        .prefix("1 unit = ")
+                        .suffix(" cm"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Dot Radius:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.radius_cm)
+                        .speed(0.001)
+                        .range(0.001..=1.0)
+                        .suffix(" cm"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Coloring:");
+            egui::ComboBox::from_id_salt("AttractorColoring")
+                .selected_text(format!("{}", state.coloring))
+                .show_ui(ui, |ui| {
+                    for coloring in Coloring::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.coloring,
+                                coloring,
+                                coloring.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+        });
+
+        if state.coloring.is_gradient_based() {
+            ui.add_space(5.0);
+            ui.collapsing("Gradient", |ui| {
+                changed |= state.gradient.show_editor(ui, "Attractor");
+            });
+        }
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.attractor_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.attractor_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    context.attractor_state.is_initialized(),
+                    Button::new("Draw"),
+                )
+                .clicked()
+            {
+                context.attractor_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.attractor_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.attractor_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.attractor_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Attractor\": \"Clifford\",
+                                \"Parameters\": [-1.4, 1.6, 1.0, 0.7],
+                                \"Iterations\": 200000,
+                                \"Coloring\": \"Speed\"
+                            }
+
+                            Attractor: \"Clifford\", \"Peter de Jong\", \"Henon\",
+                            \"Ikeda\", \"Gumowski-Mira\" or \"Lorenz\".
+                            Parameters go in the order shown in the settings.
+                            For Lorenz the last parameter is the time step.
+
+                            Other fields: \"Start\" ([x, y, z]), \"Projection\"
+                            (\"XY\", \"XZ\" or \"YZ\"), \"Burn-in\", \"Center\" ([x, y]),
+                            \"Scale\" (cm per unit), \"Radius\", \"Gradient\".
+
+                            You can find other examples in the 'assets/fractals/attractor' folder.
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut AttractorState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
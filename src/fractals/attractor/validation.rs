@@ -0,0 +1,229 @@
+use crate::fractals::attractor::model;
+use crate::fractals::attractor::model::Kind;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+const MAX_TIME_STEP: f64 = 0.1;
+
+pub fn parameters_count(kind: Kind, parameters: &[f64]) -> Result<(), ValidationError> {
+    let expected = kind.parameter_names().len();
+    if parameters.len() != expected {
+        return Err(ValidationError::BadParametersCount(format!(
+            "Attractor: {}\nExpected: {}\nGiven: {}",
+            kind,
+            expected,
+            parameters.len()
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn numbers_are_finite(numbers: &[f64]) -> Result<(), ValidationError> {
+    for (index, number) in numbers.iter().enumerate() {
+        if !number.is_finite() {
+            return Err(ValidationError::NotFiniteNumber(format!(
+                "Position: {}",
+                index + 1
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn time_step_range(kind: Kind, parameters: &[f64]) -> Result<(), ValidationError> {
+    if !kind.is_continuous() {
+        return Ok(());
+    }
+
+    match parameters.last() {
+        Some(dt) if *dt > 0.0 && *dt <= MAX_TIME_STEP => Ok(()),
+        _ => Err(ValidationError::BadTimeStepValue),
+    }
+}
+
+pub fn iterations_range(iterations: u32) -> Result<(), ValidationError> {
+    if !(1..=model::MAX_ITERATIONS).contains(&iterations) {
+        return Err(ValidationError::BadIterationsValue);
+    }
+
+    Ok(())
+}
+
+pub fn burn_in_range(burn_in: u32) -> Result<(), ValidationError> {
+    if burn_in > model::MAX_BURN_IN {
+        return Err(ValidationError::BadBurnInValue);
+    }
+
+    Ok(())
+}
+
+pub fn scale_range(cm_per_unit: f32) -> Result<(), ValidationError> {
+    if !(cm_per_unit > 0.0 && cm_per_unit.is_finite()) {
+        return Err(ValidationError::BadScaleValue);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The number of parameters doesn't match the attractor.")]
+    BadParametersCount(String),
+
+    #[error("There's a number that is not finite.")]
+    NotFiniteNumber(String),
+
+    #[error("The time step 'dt' has to be in range 0..0.1 (0 is exclusive).")]
+    BadTimeStepValue,
+
+    #[error(
+        "The 'iterations' value has to be in range 1..={}.",
+        model::MAX_ITERATIONS
+    )]
+    BadIterationsValue,
+
+    #[error("The 'burn-in' value has to be in range 0..={}.", model::MAX_BURN_IN)]
+    BadBurnInValue,
+
+    #[error("The scale has to be greater than 0.")]
+    BadScaleValue,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadParametersCount(value) | Self::NotFiniteNumber(value) => {
+                Some(value.clone())
+            },
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::attractor::examples::Example;
+    use crate::fractals::attractor::serialization;
+    use crate::fractals::attractor::state::AttractorState;
+
+    #[test]
+    fn default_parameters_of_every_kind() {
+        for kind in Kind::iter() {
+            let mut state = AttractorState::default();
+            state.set_kind(kind);
+
+            let result = state.initialize();
+
+            assert!(result.is_ok(), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn parameters_count_is_reported() {
+        let result = parameters_count(Kind::Henon, &[1.4]);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Attractor: Hénon\nExpected: 2\nGiven: 1".to_string())
+        );
+    }
+
+    #[test]
+    fn not_finite_start_position() {
+        let mut state = AttractorState::default();
+        state.start[1] = f64::INFINITY;
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Position: 2".to_string())
+        );
+    }
+
+    #[test]
+    fn time_step_is_last_parameter() {
+        assert!(
+            time_step_range(Kind::Lorenz, &[10.0, 28.0, 2.67, MAX_TIME_STEP]).is_ok()
+        );
+        for dt in [0.0, -0.01, MAX_TIME_STEP * 2.0] {
+            assert!(matches!(
+                time_step_range(Kind::Lorenz, &[10.0, 28.0, 2.67, dt]),
+                Err(ValidationError::BadTimeStepValue)
+            ));
+        }
+        assert!(matches!(
+            time_step_range(Kind::Lorenz, &[]),
+            Err(ValidationError::BadTimeStepValue)
+        ));
+    }
+
+    #[test]
+    fn discrete_maps_have_no_time_step() {
+        // The last parameter of the Hénon map is a coefficient, it may be negative
+        assert!(time_step_range(Kind::Henon, &[1.4, -0.3]).is_ok());
+    }
+
+    #[test]
+    fn iterations_bounds_are_inclusive() {
+        assert!(iterations_range(1).is_ok());
+        assert!(iterations_range(model::MAX_ITERATIONS).is_ok());
+        for iterations in [0, model::MAX_ITERATIONS + 1] {
+            assert!(matches!(
+                iterations_range(iterations),
+                Err(ValidationError::BadIterationsValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn burn_in_limit_is_inclusive() {
+        assert!(burn_in_range(0).is_ok());
+        assert!(burn_in_range(model::MAX_BURN_IN).is_ok());
+        assert!(matches!(
+            burn_in_range(u32::MAX),
+            Err(ValidationError::BadBurnInValue)
+        ));
+    }
+
+    #[test]
+    fn limits_are_in_messages() {
+        assert_eq!(
+            ValidationError::BadIterationsValue.to_string(),
+            "The 'iterations' value has to be in range 1..=5000000."
+        );
+        assert_eq!(
+            ValidationError::BadBurnInValue.to_string(),
+            "The 'burn-in' value has to be in range 0..=1000000."
+        );
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = AttractorState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
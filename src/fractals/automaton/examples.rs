@@ -0,0 +1,82 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static AUTOMATON_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/automaton/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Day and Night")]
+    DayAndNight,
+
+    #[strum(serialize = "Game of Life")]
+    GameOfLife,
+
+    #[strum(serialize = "Gosper Glider Gun")]
+    GosperGliderGun,
+
+    #[strum(serialize = "Replicator")]
+    Replicator,
+
+    #[strum(serialize = "Rule 30")]
+    Rule30,
+
+    #[strum(serialize = "Rule 90")]
+    Rule90,
+
+    #[strum(serialize = "Rule 110")]
+    Rule110,
+
+    #[strum(serialize = "Rule 150")]
+    Rule150,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::DayAndNight => PathBuf::from(r"Day-and-Night.json"),
+            Example::GameOfLife => PathBuf::from(r"Game-of-Life.json"),
+            Example::GosperGliderGun => PathBuf::from(r"Gosper-Glider-Gun.json"),
+            Example::Replicator => PathBuf::from(r"Replicator.json"),
+            Example::Rule30 => PathBuf::from(r"Rule-30.json"),
+            Example::Rule90 => PathBuf::from(r"Rule-90.json"),
+            Example::Rule110 => PathBuf::from(r"Rule-110.json"),
+            Example::Rule150 => PathBuf::from(r"Rule-150.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = AUTOMATON_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::DayAndNight,
+            Example::GameOfLife,
+            Example::GosperGliderGun,
+            Example::Replicator,
+            Example::Rule30,
+            Example::Rule90,
+            Example::Rule110,
+            Example::Rule150,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
@@ -0,0 +1,478 @@
+use crate::math::random;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_WOLFRAM_RULE: u32 = 90;
+pub const DEFAULT_LIFE_RULE: &str = "B3/S23";
+pub const DEFAULT_WIDTH: u32 = 257;
+pub const DEFAULT_HEIGHT: u32 = 257;
+pub const DEFAULT_GENERATIONS: u32 = 128;
+pub const DEFAULT_DENSITY: f64 = 0.5;
+pub const DEFAULT_CELL_SIZE_CM: f32 = 0.1;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Kind {
+    // Row of cells, the next state depends on the cell and its two neighbors
+    #[default]
+    #[strum(serialize = "Elementary (1D)")]
+    #[serde(rename = "Elementary")]
+    Elementary,
+
+    // Grid of cells, the next state depends on the cell and the number of its 8 neighbors
+    #[strum(serialize = "Outer-Totalistic (2D)")]
+    #[serde(rename = "Outer-Totalistic")]
+    OuterTotalistic,
+}
+
+impl Kind {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Kind::Elementary, Kind::OuterTotalistic].into_iter()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum InitialCondition {
+    #[default]
+    #[strum(serialize = "Single Cell")]
+    #[serde(rename = "Single Cell")]
+    SingleCell,
+
+    #[strum(serialize = "Random")]
+    #[serde(rename = "Random")]
+    Random,
+
+    // Cells from the text, placed in the center
+    #[strum(serialize = "Pattern")]
+    #[serde(rename = "Pattern")]
+    Pattern,
+}
+
+impl InitialCondition {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            InitialCondition::SingleCell,
+            InitialCondition::Random,
+            InitialCondition::Pattern,
+        ]
+        .into_iter()
+    }
+}
+
+// Numbers of alive neighbors, that make the dead cell alive or keep the alive cell alive
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LifeRule {
+    pub birth: [bool; 9],
+    pub survival: [bool; 9],
+}
+
+impl LifeRule {
+    // Rule in B/S notation, like "B3/S23"
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut rule = LifeRule::default();
+        // Exactly one part of each kind, in any order
+        let (mut has_birth, mut has_survival) = (false, false);
+
+        for part in text.split('/').map(str::trim) {
+            let mut letters = part.chars();
+            let (counts, is_seen) = match letters.next()?.to_ascii_uppercase() {
+                'B' => (&mut rule.birth, &mut has_birth),
+                'S' => (&mut rule.survival, &mut has_survival),
+                _ => return None,
+            };
+            if *is_seen {
+                return None;
+            }
+            *is_seen = true;
+
+            for letter in letters {
+                let count = letter.to_digit(10).filter(|count| *count <= 8)?;
+                counts[count as usize] = true;
+            }
+        }
+
+        (has_birth && has_survival).then_some(rule)
+    }
+
+    fn next(&self, is_alive: bool, neighbors: usize) -> bool {
+        match is_alive {
+            true => self.survival[neighbors],
+            false => self.birth[neighbors],
+        }
+    }
+}
+
+// Cells of the pattern by rows, alive cells are 'O', '1', '*' or '#'
+pub fn parse_pattern(text: &str) -> Result<Vec<Vec<bool>>, char> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.chars()
+                .map(|letter| match letter {
+                    'O' | 'o' | '1' | '*' | '#' => Ok(true),
+                    '.' | '0' | '_' | '-' => Ok(false),
+                    other => Err(other),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Cells by rows, the top row goes first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Cells {
+    pub width: usize,
+    pub height: usize,
+    pub values: Vec<bool>,
+}
+
+impl Cells {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            values: vec![false; width * height],
+        }
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.values.iter().filter(|value| **value).count()
+    }
+}
+
+pub struct Model {
+    kind: Kind,
+    wolfram_rule: u8,
+    life_rule: LifeRule,
+
+    width: usize,
+    height: usize,
+    generations: u32,
+    is_wrapping: bool,
+
+    initial_condition: InitialCondition,
+    density: f64,
+    seed: Option<u64>,
+    pattern: Vec<Vec<bool>>,
+}
+
+impl Model {
+    // Space-time diagram for the elementary automata, the last generation for the others
+    pub fn cells(&self) -> Cells {
+        match self.kind {
+            Kind::Elementary => self.elementary(),
+            Kind::OuterTotalistic => self.outer_totalistic(),
+        }
+    }
+
+    fn elementary(&self) -> Cells {
+        let width = self.width;
+        let mut cells = Cells::new(width, self.generations as usize + 1);
+        let mut row = self.initial_cells(width, 1).values;
+
+        for generation in 0..cells.height {
+            cells.values[generation * width..(generation + 1) * width]
+                .copy_from_slice(&row);
+
+            let cell = |index: isize| -> u8 {
+                if self.is_wrapping {
+                    row[index.rem_euclid(width as isize) as usize] as u8
+                } else if (0..width as isize).contains(&index) {
+                    row[index as usize] as u8
+                } else {
+                    0
+                }
+            };
+            let next = (0..width as isize)
+                .map(|index| {
+                    let neighborhood =
+                        cell(index - 1) << 2 | cell(index) << 1 | cell(index + 1);
+                    self.wolfram_rule >> neighborhood & 1 == 1
+                })
+                .collect();
+            row = next;
+        }
+
+        cells
+    }
+
+    fn outer_totalistic(&self) -> Cells {
+        let (width, height) = (self.width, self.height);
+        let mut cells = self.initial_cells(width, height);
+        let mut next = cells.clone();
+
+        for _ in 0..self.generations {
+            for y in 0..height {
+                for x in 0..width {
+                    let mut neighbors = 0;
+                    for (dx, dy) in NEIGHBORS {
+                        let (nx, ny) = (x as isize + dx, y as isize + dy);
+                        let (nx, ny) = if self.is_wrapping {
+                            (
+                                nx.rem_euclid(width as isize),
+                                ny.rem_euclid(height as isize),
+                            )
+                        } else if (0..width as isize).contains(&nx)
+                            && (0..height as isize).contains(&ny)
+                        {
+                            (nx, ny)
+                        } else {
+                            continue;
+                        };
+                        neighbors +=
+                            cells.values[ny as usize * width + nx as usize] as usize;
+                    }
+
+                    let index = y * width + x;
+                    next.values[index] =
+                        self.life_rule.next(cells.values[index], neighbors);
+                }
+            }
+            std::mem::swap(&mut cells, &mut next);
+        }
+
+        cells
+    }
+
+    fn initial_cells(&self, width: usize, height: usize) -> Cells {
+        let mut cells = Cells::new(width, height);
+
+        match self.initial_condition {
+            InitialCondition::SingleCell => {
+                cells.values[height / 2 * width + width / 2] = true;
+            },
+            InitialCondition::Random => {
+                let mut rng = random::rng(self.seed);
+                for value in cells.values.iter_mut() {
+                    *value = rng.gen_bool(self.density);
+                }
+            },
+            InitialCondition::Pattern => {
+                let pattern_height = self.pattern.len();
+                let pattern_width = self.pattern.iter().map(Vec::len).max().unwrap_or(0);
+                let top = (height as isize - pattern_height as isize) / 2;
+                let left = (width as isize - pattern_width as isize) / 2;
+
+                for (row_index, row) in self.pattern.iter().enumerate() {
+                    for (column, value) in row.iter().enumerate() {
+                        let (x, y) = (left + column as isize, top + row_index as isize);
+                        if (0..width as isize).contains(&x)
+                            && (0..height as isize).contains(&y)
+                        {
+                            cells.values[y as usize * width + x as usize] = *value;
+                        }
+                    }
+                }
+            },
+        }
+
+        cells
+    }
+}
+
+const NEIGHBORS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+pub struct ModelBuilder {
+    kind: Kind,
+    wolfram_rule: u8,
+    life_rule: LifeRule,
+
+    width: usize,
+    height: usize,
+    generations: u32,
+    is_wrapping: bool,
+
+    initial_condition: InitialCondition,
+    density: f64,
+    seed: Option<u64>,
+    pattern: Vec<Vec<bool>>,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            kind: Kind::default(),
+            wolfram_rule: DEFAULT_WOLFRAM_RULE as u8,
+            life_rule: LifeRule::parse(DEFAULT_LIFE_RULE).unwrap_or_default(),
+
+            width: DEFAULT_WIDTH as usize,
+            height: DEFAULT_HEIGHT as usize,
+            generations: DEFAULT_GENERATIONS,
+            is_wrapping: false,
+
+            initial_condition: InitialCondition::default(),
+            density: DEFAULT_DENSITY,
+            seed: None,
+            pattern: vec![],
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_kind(mut self, kind: Kind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_wolfram_rule(mut self, rule: u8) -> Self {
+        self.wolfram_rule = rule;
+        self
+    }
+
+    pub fn with_life_rule(mut self, rule: LifeRule) -> Self {
+        self.life_rule = rule;
+        self
+    }
+
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width as usize;
+        self.height = height as usize;
+        self
+    }
+
+    pub fn with_generations(mut self, generations: u32) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    pub fn with_wrapping(mut self, is_wrapping: bool) -> Self {
+        self.is_wrapping = is_wrapping;
+        self
+    }
+
+    pub fn with_initial_condition(mut self, initial_condition: InitialCondition) -> Self {
+        self.initial_condition = initial_condition;
+        self
+    }
+
+    pub fn with_random(mut self, density: f64, seed: Option<u64>) -> Self {
+        self.density = density;
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_pattern(mut self, pattern: Vec<Vec<bool>>) -> Self {
+        self.pattern = pattern;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            kind: self.kind,
+            wolfram_rule: self.wolfram_rule,
+            life_rule: self.life_rule,
+
+            width: self.width,
+            height: self.height,
+            generations: self.generations,
+            is_wrapping: self.is_wrapping,
+
+            initial_condition: self.initial_condition,
+            density: self.density,
+            seed: self.seed,
+            pattern: self.pattern,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_90_gives_pascal_triangle_modulo_2() {
+        let cells = ModelBuilder::default()
+            .with_wolfram_rule(90)
+            .with_size(65, 1)
+            .with_generations(32)
+            .build()
+            .cells();
+
+        // Row n of the diagram holds the binomial coefficients of n modulo 2,
+        // so it has 2^(number of ones in n) alive cells
+        for generation in 0..=32 {
+            let row = &cells.values[generation * 65..(generation + 1) * 65];
+            let alive = row.iter().filter(|value| **value).count();
+            assert_eq!(alive, 1 << (generation as u32).count_ones());
+        }
+    }
+
+    #[test]
+    fn glider_moves_diagonally() {
+        let life = LifeRule::parse("B3/S23").unwrap_or_default();
+        let glider = parse_pattern(".O.\n..O\nOOO").unwrap_or_default();
+        let cells = |generations| {
+            ModelBuilder::default()
+                .with_kind(Kind::OuterTotalistic)
+                .with_life_rule(life)
+                .with_size(12, 12)
+                .with_generations(generations)
+                .with_initial_condition(InitialCondition::Pattern)
+                .with_pattern(glider.clone())
+                .build()
+                .cells()
+        };
+
+        // After 4 generations the glider is the same, shifted by one cell
+        let (start, end) = (cells(0), cells(4));
+        assert_eq!(start.alive_count(), 5);
+        for y in 0..11 {
+            for x in 0..11 {
+                assert_eq!(start.values[y * 12 + x], end.values[(y + 1) * 12 + x + 1]);
+            }
+        }
+    }
+
+    #[test]
+    fn life_rules_are_parsed() {
+        let rule = LifeRule::parse("b36/s23").unwrap_or_default();
+
+        assert!(rule.birth[3] && rule.birth[6] && !rule.birth[2]);
+        assert!(rule.survival[2] && rule.survival[3] && !rule.survival[4]);
+        assert_eq!(LifeRule::parse("B3/S29"), None);
+        assert_eq!(LifeRule::parse("B3"), None);
+    }
+
+    #[test]
+    fn life_rule_parts_are_not_repeated() {
+        assert_eq!(LifeRule::parse("B3/B6"), None);
+        assert_eq!(LifeRule::parse("S23/S4"), None);
+        assert_eq!(LifeRule::parse("B3/S23/S4"), None);
+        // Order of the parts doesn't matter, and a part may be empty
+        assert_eq!(LifeRule::parse("S23/B3"), LifeRule::parse("B3/S23"));
+        assert!(LifeRule::parse("B2/S").is_some());
+    }
+
+    #[test]
+    fn pattern_is_centered_and_clipped() {
+        let cells = |pattern: &str, size: u32| {
+            ModelBuilder::default()
+                .with_kind(Kind::OuterTotalistic)
+                .with_size(size, size)
+                .with_generations(0)
+                .with_initial_condition(InitialCondition::Pattern)
+                .with_pattern(parse_pattern(pattern).unwrap_or_default())
+                .build()
+                .cells()
+        };
+
+        // Larger pattern keeps its middle part
+        let clipped = cells("O...O\n.OOO.\n.OOO.\n.OOO.\nO...O", 3);
+        assert_eq!(clipped.values, vec![true; 9]);
+
+        // Rows may be ragged, blank lines and spaces around are skipped
+        let ragged = cells("  O\n\n OOO  \n", 5);
+        let alive: Vec<usize> = (0..25).filter(|index| ragged.values[*index]).collect();
+        assert_eq!(alive, vec![6, 11, 12, 13]);
+    }
+}
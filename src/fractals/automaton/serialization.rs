@@ -0,0 +1,147 @@
+use crate::fractals::automaton::model;
+use crate::fractals::automaton::model::{InitialCondition, Kind};
+use crate::fractals::automaton::state::AutomatonState;
+use crate::fractals::automaton::validation::ValidationError;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<AutomatonDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &AutomatonState) -> serde_json::Result<String> {
+    let dto = AutomatonDto {
+        kind: state.kind,
+        wolfram_rule: state.wolfram_rule,
+        life_rule: state.life_rule.clone(),
+        width: state.width,
+        height: state.height,
+        generations: state.generations,
+        is_wrapping: state.is_wrapping,
+        initial_condition: state.initial_condition,
+        density: state.density,
+        seed: state.seed,
+        pattern: state.pattern.lines().map(str::to_string).collect(),
+        cell_size_cm: state.cell_size_cm,
+        alive_color: [
+            state.alive_color.r(),
+            state.alive_color.g(),
+            state.alive_color.b(),
+        ],
+        dead_color: state
+            .dead_color
+            .map(|color| [color.r(), color.g(), color.b()]),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_wolfram_rule() -> u32 {
+    model::DEFAULT_WOLFRAM_RULE
+}
+
+fn default_life_rule() -> String {
+    model::DEFAULT_LIFE_RULE.to_string()
+}
+
+fn default_width() -> u32 {
+    model::DEFAULT_WIDTH
+}
+
+fn default_height() -> u32 {
+    model::DEFAULT_HEIGHT
+}
+
+fn default_generations() -> u32 {
+    model::DEFAULT_GENERATIONS
+}
+
+fn default_density() -> f64 {
+    model::DEFAULT_DENSITY
+}
+
+fn default_cell_size() -> f32 {
+    model::DEFAULT_CELL_SIZE_CM
+}
+
+fn default_alive_color() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AutomatonDto {
+    #[serde(rename = "Kind", default)]
+    kind: Kind,
+
+    #[serde(rename = "Wolfram Rule", default = "default_wolfram_rule")]
+    wolfram_rule: u32,
+
+    #[serde(rename = "B/S Rule", default = "default_life_rule")]
+    life_rule: String,
+
+    #[serde(rename = "Width", default = "default_width")]
+    width: u32,
+
+    #[serde(rename = "Height", default = "default_height")]
+    height: u32,
+
+    #[serde(rename = "Generations", default = "default_generations")]
+    generations: u32,
+
+    #[serde(rename = "Wrapping", default)]
+    is_wrapping: bool,
+
+    #[serde(rename = "Initial Condition", default)]
+    initial_condition: InitialCondition,
+
+    #[serde(rename = "Density", default = "default_density")]
+    density: f64,
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+
+    #[serde(rename = "Pattern", default, skip_serializing_if = "Vec::is_empty")]
+    pattern: Vec<String>,
+
+    #[serde(rename = "Cell Size", default = "default_cell_size")]
+    cell_size_cm: f32,
+
+    #[serde(rename = "Alive Color", default = "default_alive_color")]
+    alive_color: [u8; 3],
+
+    #[serde(
+        rename = "Dead Color",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    dead_color: Option<[u8; 3]>,
+}
+
+impl AutomatonDto {
+    pub fn load(self, state: &mut AutomatonState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.kind = self.kind;
+        state.wolfram_rule = self.wolfram_rule;
+        state.life_rule = self.life_rule;
+        state.width = self.width;
+        state.height = self.height;
+        state.generations = self.generations;
+        state.is_wrapping = self.is_wrapping;
+        state.initial_condition = self.initial_condition;
+        state.density = self.density;
+        state.seed = self.seed;
+        state.pattern = self.pattern.join("\n");
+        state.cell_size_cm = self.cell_size_cm;
+
+        let [r, g, b] = self.alive_color;
+        state.alive_color = Color32::from_rgb(r, g, b);
+        state.dead_color = self.dead_color.map(|[r, g, b]| Color32::from_rgb(r, g, b));
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
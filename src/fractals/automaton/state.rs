@@ -0,0 +1,145 @@
+use crate::fractals::automaton::model::{
+    parse_pattern, Cells, InitialCondition, Kind, LifeRule, ModelBuilder,
+};
+use crate::fractals::automaton::validation::ValidationError;
+use crate::fractals::automaton::{model, validation};
+use crate::geometry::point2d::Point2D;
+use crate::graphics::raster::Raster;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::colors;
+use egui::{Color32, Rect, Shape};
+
+pub struct AutomatonState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub kind: Kind,
+    pub wolfram_rule: u32,
+    pub life_rule: String,
+
+    pub width: u32,
+    pub height: u32,
+    pub generations: u32,
+    pub is_wrapping: bool,
+
+    pub initial_condition: InitialCondition,
+    pub density: f64,
+    pub seed: Option<u64>,
+    pub pattern: String,
+
+    pub cell_size_cm: f32,
+    pub alive_color: Color32,
+    pub dead_color: Option<Color32>,
+
+    cells: Cells,
+    raster: Raster,
+}
+
+impl Default for AutomatonState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            kind: Kind::default(),
+            wolfram_rule: model::DEFAULT_WOLFRAM_RULE,
+            life_rule: model::DEFAULT_LIFE_RULE.to_string(),
+
+            width: model::DEFAULT_WIDTH,
+            height: model::DEFAULT_HEIGHT,
+            generations: model::DEFAULT_GENERATIONS,
+            is_wrapping: false,
+
+            initial_condition: InitialCondition::default(),
+            density: model::DEFAULT_DENSITY,
+            seed: None,
+            pattern: String::new(),
+
+            cell_size_cm: model::DEFAULT_CELL_SIZE_CM,
+            alive_color: colors::WHITE,
+            dead_color: None,
+
+            cells: Cells::default(),
+            raster: Raster::new("Automaton"),
+        }
+    }
+}
+
+impl AutomatonState {
+    pub fn shapes(&mut self, ctx: &egui::Context, params: &CanvasParams) -> Vec<Shape> {
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+
+            self.cells = ModelBuilder::default()
+                .with_kind(self.kind)
+                .with_wolfram_rule(self.wolfram_rule as u8)
+                .with_life_rule(LifeRule::parse(&self.life_rule).unwrap_or_default())
+                .with_size(self.width, self.height)
+                .with_generations(self.generations)
+                .with_wrapping(self.is_wrapping)
+                .with_initial_condition(self.initial_condition)
+                .with_random(self.density, self.seed)
+                .with_pattern(parse_pattern(&self.pattern).unwrap_or_default())
+                .build()
+                .cells();
+
+            let pixels = self
+                .cells
+                .values
+                .iter()
+                .map(|is_alive| match is_alive {
+                    true => self.alive_color,
+                    false => self.dead_color.unwrap_or(Color32::TRANSPARENT),
+                })
+                .collect();
+            self.raster
+                .set_pixels(self.cells.width, self.cells.height, pixels);
+        }
+
+        // The grid is centered at the origin, one texel per cell
+        let (half_width, half_height) = (
+            self.cells.width as f32 * self.cell_size_cm / 2.0,
+            self.cells.height as f32 * self.cell_size_cm / 2.0,
+        );
+        let top_left = params.point_cm_to_px(Point2D::new(-half_width, half_height));
+        let bottom_right = params.point_cm_to_px(Point2D::new(half_width, -half_height));
+        let rect = Rect::from_min_max(top_left.to_pos2(), bottom_right.to_pos2());
+
+        vec![self.raster.shape(ctx, rect)]
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        match self.kind {
+            Kind::Elementary => validation::wolfram_rule_range(self.wolfram_rule)?,
+            Kind::OuterTotalistic => validation::life_rule_is_valid(&self.life_rule)?,
+        }
+        validation::size_range(self.width, self.height)?;
+        validation::cells_count(self.kind, self.width, self.height, self.generations)?;
+        match self.initial_condition {
+            InitialCondition::SingleCell => {},
+            InitialCondition::Random => validation::density_range(self.density)?,
+            InitialCondition::Pattern => validation::pattern_is_valid(&self.pattern)?,
+        }
+        validation::cell_size_range(self.cell_size_cm)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.cells.alive_count()
+    }
+}
@@ -0,0 +1,356 @@
+use crate::context::Context;
+use crate::fractals::automaton::examples::Example;
+use crate::fractals::automaton::model::{InitialCondition, Kind};
+use crate::fractals::automaton::serialization;
+use crate::fractals::automaton::state::AutomatonState;
+use crate::fractals::automaton::validation::{MAX_SIDE, MAX_WOLFRAM_RULE};
+use crate::io;
+use crate::math::random;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, TextEdit, TextStyle, Ui};
+use indoc::indoc;
+
+pub struct AutomatonSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for AutomatonSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl AutomatonSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.automaton_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Alive Cells: ");
+            ui.label(context.automaton_state.alive_count().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.automaton_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Kind:");
+            egui::ComboBox::from_id_salt("AutomatonKind")
+                .selected_text(format!("{}", state.kind))
+                .show_ui(ui, |ui| {
+                    for kind in Kind::iter() {
+                        changed |= ui
+                            .selectable_value(&mut state.kind, kind, kind.to_string())
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Rule:");
+            match state.kind {
+                Kind::Elementary => {
+                    changed |= ui
+                        .add(Slider::new(&mut state.wolfram_rule, 0..=MAX_WOLFRAM_RULE))
+                        .on_hover_text(
+                            "Bit k of the rule number is the next state of the cell,\n\
+                             whose neighborhood reads k in binary.",
+                        )
+                        .changed();
+                },
+                Kind::OuterTotalistic => {
+                    changed |= ui
+                        .text_edit_singleline(&mut state.life_rule)
+                        .on_hover_text(
+                            "Neighbor counts for the birth and the survival, like B3/S23.",
+                        )
+                        .changed();
+                },
+            }
+            ui.end_row();
+
+            ui.label("Width:");
+            changed |= ui
+                .add(DragValue::new(&mut state.width).speed(1).range(1..=MAX_SIDE))
+                .changed();
+            ui.end_row();
+
+            if state.kind == Kind::OuterTotalistic {
+                ui.label("Height:");
+                changed |= ui
+                    .add(DragValue::new(&mut state.height).speed(1).range(1..=MAX_SIDE))
+                    .changed();
+                ui.end_row();
+            }
+
+            ui.label("Generations:");
+            changed |= ui
+                .add(DragValue::new(&mut state.generations).speed(1))
+                .changed();
+            ui.end_row();
+
+            ui.label("Wrapping:");
+            changed |= ui
+                .checkbox(&mut state.is_wrapping, "")
+                .on_hover_text("Opposite edges of the grid are neighbors.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Initial Condition:");
+            egui::ComboBox::from_id_salt("AutomatonInitialCondition")
+                .selected_text(format!("{}", state.initial_condition))
+                .show_ui(ui, |ui| {
+                    for condition in InitialCondition::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.initial_condition,
+                                condition,
+                                condition.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            if state.initial_condition == InitialCondition::Random {
+                ui.label("Density:");
+                changed |= ui
+                    .add(Slider::new(&mut state.density, 0.0..=1.0))
+                    .on_hover_text("Probability of every cell to be alive.")
+                    .changed();
+                ui.end_row();
+
+                ui.label("Seed: ");
+                ui.horizontal(|ui| {
+                    let mut is_seeded = state.seed.is_some();
+                    if ui
+                        .checkbox(&mut is_seeded, "")
+                        .on_hover_text("With a fixed seed, every run is identical.")
+                        .changed()
+                    {
+                        state.seed = is_seeded.then_some(random::DEFAULT_SEED);
+                        changed = true;
+                    }
+                    if let Some(seed) = &mut state.seed {
+                        changed |= ui.add(DragValue::new(seed).speed(1)).changed();
+                    }
+                });
+                ui.end_row();
+            }
+
+            ui.label("Cell Size:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.cell_size_cm)
+                        .speed(0.01)
+                        .range(0.01..=10.0)
+                        .suffix(" cm"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Alive Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.alive_color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+
+            ui.label("Dead Color:");
+            ui.horizontal(|ui| {
+                let mut is_filled = state.dead_color.is_some();
+                if ui
+                    .checkbox(&mut is_filled, "")
+                    .on_hover_text("Without the color, dead cells are transparent.")
+                    .changed()
+                {
+                    state.dead_color = is_filled.then_some(colors::BLACK);
+                    changed = true;
+                }
+                if let Some(color) = &mut state.dead_color {
+                    changed |= egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        color,
+                        egui::color_picker::Alpha::Opaque,
+                    )
+                    .changed();
+                }
+            });
+            ui.end_row();
+        });
+
+        if state.initial_condition == InitialCondition::Pattern {
+            ui.add_space(5.0);
+
+            ui.label("Pattern:").on_hover_text(
+                "Rows of cells, 'O' is alive and '.' is dead.\n\
+                 The pattern is placed in the center of the grid.",
+            );
+            changed |= ui
+                .add(
+                    TextEdit::multiline(&mut state.pattern)
+                        .font(TextStyle::Monospace)
+                        .desired_width(f32::INFINITY),
+                )
+                .changed();
+        }
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.automaton_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.automaton_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    context.automaton_state.is_initialized(),
+                    Button::new("Draw"),
+                )
+                .clicked()
+            {
+                context.automaton_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.automaton_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.automaton_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.automaton_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Kind\": \"Outer-Totalistic\",
+                                \"B/S Rule\": \"B3/S23\",
+                                \"Width\": 64,
+                                \"Height\": 64,
+                                \"Generations\": 100,
+                                \"Initial Condition\": \"Pattern\",
+                                \"Pattern\": [\".O.\", \"..O\", \"OOO\"]
+                            }
+
+                            Kind: \"Elementary\" or \"Outer-Totalistic\".
+                            Wolfram Rule: 0..255, used by the elementary automata,
+                            which are drawn as space-time diagrams, time goes down.
+                            B/S Rule: used by the outer-totalistic automata, which are
+                            drawn in the state after the last generation.
+                            Initial Condition: \"Single Cell\", \"Random\" (with
+                            \"Density\" and \"Seed\") or \"Pattern\".
+
+                            Other fields: \"Wrapping\", \"Cell Size\" (cm),
+                            \"Alive Color\", \"Dead Color\" (RGB).
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut AutomatonState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use crate::fractals::automaton::model::{parse_pattern, Kind, LifeRule};
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub const MAX_WOLFRAM_RULE: u32 = 255;
+pub const MAX_SIDE: u32 = 4096;
+const MAX_CELLS: u64 = 4_000_000;
+const MAX_UPDATES: u64 = 200_000_000;
+
+pub fn wolfram_rule_range(rule: u32) -> Result<(), ValidationError> {
+    if rule > MAX_WOLFRAM_RULE {
+        return Err(ValidationError::BadWolframRule);
+    }
+
+    Ok(())
+}
+
+pub fn life_rule_is_valid(rule: &str) -> Result<(), ValidationError> {
+    if LifeRule::parse(rule).is_none() {
+        return Err(ValidationError::BadLifeRule(rule.to_string()));
+    }
+
+    Ok(())
+}
+
+pub fn size_range(width: u32, height: u32) -> Result<(), ValidationError> {
+    if !(1..=MAX_SIDE).contains(&width) || !(1..=MAX_SIDE).contains(&height) {
+        return Err(ValidationError::BadSizeValue);
+    }
+
+    Ok(())
+}
+
+// Space-time diagram of the 1D automaton grows with every generation,
+// the 2D one keeps its size but takes a full pass over the grid per generation
+pub fn cells_count(
+    kind: Kind, width: u32, height: u32, generations: u32,
+) -> Result<(), ValidationError> {
+    let (cells, updates) = match kind {
+        Kind::Elementary => {
+            let cells = width as u64 * (generations as u64 + 1);
+            (cells, cells)
+        },
+        Kind::OuterTotalistic => {
+            let cells = width as u64 * height as u64;
+            (cells, cells * generations as u64)
+        },
+    };
+
+    if cells > MAX_CELLS {
+        return Err(ValidationError::TooManyCells(format!(
+            "Cells: {}, maximum: {}.",
+            cells, MAX_CELLS
+        )));
+    }
+    if updates > MAX_UPDATES {
+        return Err(ValidationError::TooManyUpdates(format!(
+            "Cell updates: {}, maximum: {}.",
+            updates, MAX_UPDATES
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn density_range(density: f64) -> Result<(), ValidationError> {
+    if !(0.0..=1.0).contains(&density) {
+        return Err(ValidationError::BadDensityValue);
+    }
+
+    Ok(())
+}
+
+pub fn pattern_is_valid(pattern: &str) -> Result<(), ValidationError> {
+    match parse_pattern(pattern) {
+        Ok(rows) if rows.is_empty() => Err(ValidationError::EmptyPattern),
+        Ok(_) => Ok(()),
+        Err(symbol) => Err(ValidationError::BadPatternSymbol(format!(
+            "Unknown symbol: '{}'.",
+            symbol
+        ))),
+    }
+}
+
+pub fn cell_size_range(cell_size_cm: f32) -> Result<(), ValidationError> {
+    if !(cell_size_cm > 0.0 && cell_size_cm.is_finite()) {
+        return Err(ValidationError::BadCellSize);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The Wolfram rule has to be in range 0..255.")]
+    BadWolframRule,
+
+    #[error("The rule has to be in B/S notation, like \"B3/S23\".")]
+    BadLifeRule(String),
+
+    #[error("The width and the height have to be in range 1..4096.")]
+    BadSizeValue,
+
+    #[error("Too many cells, decrease the size or the number of generations.")]
+    TooManyCells(String),
+
+    #[error("Too many cell updates, decrease the size or the number of generations.")]
+    TooManyUpdates(String),
+
+    #[error("The density has to be in range 0..1.")]
+    BadDensityValue,
+
+    #[error("The pattern has no cells.")]
+    EmptyPattern,
+
+    #[error("Alive cells in the pattern are 'O', '1', '*' or '#', dead cells are '.', '0', '_' or '-'.")]
+    BadPatternSymbol(String),
+
+    #[error("The cell size has to be greater than 0.")]
+    BadCellSize,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadLifeRule(value)
+            | Self::TooManyCells(value)
+            | Self::TooManyUpdates(value)
+            | Self::BadPatternSymbol(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::automaton::examples::Example;
+    use crate::fractals::automaton::model::InitialCondition;
+    use crate::fractals::automaton::serialization;
+    use crate::fractals::automaton::state::AutomatonState;
+
+    #[test]
+    fn only_current_rule_is_validated() {
+        let mut state = AutomatonState::default();
+        state.kind = Kind::Elementary;
+        state.life_rule = "B3/S2x".to_string();
+        assert!(state.initialize().is_ok());
+
+        state.kind = Kind::OuterTotalistic;
+        state.wolfram_rule = MAX_WOLFRAM_RULE + 1;
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("B3/S2x".to_string())
+        );
+    }
+
+    #[test]
+    fn cells_limits_are_inclusive() {
+        // 2000 x 2000 is exactly the limit of cells, 50 generations of updates
+        assert!(cells_count(Kind::OuterTotalistic, 2000, 2000, 50).is_ok());
+        assert!(matches!(
+            cells_count(Kind::OuterTotalistic, 2000, 2000, 51),
+            Err(ValidationError::TooManyUpdates(_))
+        ));
+        assert!(matches!(
+            cells_count(Kind::OuterTotalistic, 2000, 2001, 1),
+            Err(ValidationError::TooManyCells(_))
+        ));
+    }
+
+    #[test]
+    fn elementary_diagram_ignores_height() {
+        assert!(cells_count(Kind::Elementary, 4000, MAX_SIDE, 999).is_ok());
+
+        let result = cells_count(Kind::Elementary, 4000, 1, 1000);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Cells: 4004000, maximum: 4000000.".to_string())
+        );
+    }
+
+    #[test]
+    fn largest_counts_dont_overflow() {
+        for kind in Kind::iter() {
+            assert!(cells_count(kind, MAX_SIDE, MAX_SIDE, u32::MAX).is_err());
+        }
+    }
+
+    #[test]
+    fn blank_pattern_is_empty() {
+        assert!(matches!(
+            pattern_is_valid(" \n\n  \n"),
+            Err(ValidationError::EmptyPattern)
+        ));
+
+        let result = pattern_is_valid("OO\nO?");
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Unknown symbol: '?'.".to_string())
+        );
+    }
+
+    #[test]
+    fn density_bounds_are_inclusive() {
+        assert!(density_range(0.0).is_ok());
+        assert!(density_range(1.0).is_ok());
+        assert!(density_range(f64::NAN).is_err());
+
+        // Density of other initial conditions is not checked
+        let mut state = AutomatonState::default();
+        state.initial_condition = InitialCondition::SingleCell;
+        state.density = 2.0;
+        assert!(state.initialize().is_ok());
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = AutomatonState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
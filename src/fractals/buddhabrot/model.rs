@@ -0,0 +1,348 @@
+use crate::math::complex::Complex;
+use crate::math::random;
+use egui::Color32;
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_ITERATIONS: [u32; 3] = [5000, 500, 50];
+pub const DEFAULT_MIN_ITERATIONS: u32 = 0;
+pub const DEFAULT_CENTER: [f64; 2] = [-0.4, 0.0];
+pub const DEFAULT_SIZE: f64 = 3.2;
+pub const DEFAULT_RESOLUTION: usize = 500;
+pub const DEFAULT_GAMMA: f32 = 0.5;
+pub const DEFAULT_UNITS_PER_CM: f64 = 0.1;
+
+// Orbits are only plotted, if c is picked inside of this radius
+const SAMPLING_RADIUS: f64 = 2.0;
+const ESCAPE_RADIUS_SQR: f64 = 4.0;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Mode {
+    // Orbits of the points that escape
+    #[default]
+    #[strum(serialize = "Buddhabrot")]
+    #[serde(rename = "Buddhabrot")]
+    Buddhabrot,
+
+    // Orbits of the points that stay bounded
+    #[strum(serialize = "Anti-Buddhabrot")]
+    #[serde(rename = "Anti-Buddhabrot")]
+    AntiBuddhabrot,
+}
+
+// Everything that defines what is accumulated in the histogram
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub mode: Mode,
+    // Iteration limits of the red, green and blue channels
+    pub iterations: [u32; 3],
+    pub min_iterations: u32,
+
+    pub center: [f64; 2],
+    pub size: f64,
+    pub resolution: usize,
+    // Real axis goes vertically, as on the classic pictures
+    pub is_rotated: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            iterations: DEFAULT_ITERATIONS,
+            min_iterations: DEFAULT_MIN_ITERATIONS,
+
+            center: DEFAULT_CENTER,
+            size: DEFAULT_SIZE,
+            resolution: DEFAULT_RESOLUTION,
+            is_rotated: true,
+        }
+    }
+}
+
+impl Config {
+    // Histogram cell of a point of the complex plane
+    fn cell(&self, z: Complex) -> Option<usize> {
+        let left = self.center[0] - self.size / 2.0;
+        let bottom = self.center[1] - self.size / 2.0;
+        let scale = self.resolution as f64 / self.size;
+
+        let (column, row) = match self.is_rotated {
+            true => ((z.im - bottom) * scale, (z.re - left) * scale),
+            false => ((z.re - left) * scale, (bottom + self.size - z.im) * scale),
+        };
+
+        let limit = self.resolution as f64;
+        if !(0.0..limit).contains(&column) || !(0.0..limit).contains(&row) {
+            return None;
+        }
+
+        Some(row as usize * self.resolution + column as usize)
+    }
+}
+
+pub struct Histogram {
+    resolution: usize,
+    channels: [Vec<u32>; 3],
+    samples: u64,
+}
+
+impl Histogram {
+    pub fn new(resolution: usize) -> Self {
+        let cells = resolution * resolution;
+        Self {
+            resolution,
+            channels: [vec![0; cells], vec![0; cells], vec![0; cells]],
+            samples: 0,
+        }
+    }
+
+    pub fn from_channels(
+        resolution: usize, channels: [Vec<u32>; 3], samples: u64,
+    ) -> Option<Self> {
+        let cells = resolution * resolution;
+        if channels.iter().any(|channel| channel.len() != cells) {
+            return None;
+        }
+
+        Some(Self {
+            resolution,
+            channels,
+            samples,
+        })
+    }
+
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    pub fn channels(&self) -> &[Vec<u32>; 3] {
+        &self.channels
+    }
+
+    // Number of sampled points c, including the ones that weren't plotted
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    pub fn clear(&mut self) {
+        for channel in &mut self.channels {
+            channel.iter_mut().for_each(|count| *count = 0);
+        }
+        self.samples = 0;
+    }
+
+    pub fn add(&mut self, batch: &Batch) {
+        for (channel, hits) in self.channels.iter_mut().zip(&batch.hits) {
+            for cell in hits {
+                let count = &mut channel[*cell as usize];
+                *count = count.saturating_add(1);
+            }
+        }
+        self.samples += batch.samples;
+    }
+
+    // Every channel is normalized by its own maximum, gamma brings out faint orbits
+    pub fn pixels(&self, gamma: f32) -> Vec<Color32> {
+        let maximums = self
+            .channels
+            .each_ref()
+            .map(|channel| channel.iter().copied().max().unwrap_or(0).max(1) as f32);
+
+        (0..self.resolution * self.resolution)
+            .map(|index| {
+                let [red, green, blue] = [0, 1, 2].map(|channel| {
+                    let value = self.channels[channel][index] as f32 / maximums[channel];
+                    (value.powf(gamma) * 255.0).round() as u8
+                });
+                Color32::from_rgb(red, green, blue)
+            })
+            .collect()
+    }
+}
+
+// Cells, that the orbits of a few samples fell into. It's small next to the histogram,
+// so every thread keeps its own and adds it to the shared one under the lock.
+#[derive(Default)]
+pub struct Batch {
+    hits: [Vec<u32>; 3],
+    samples: u64,
+}
+
+impl Batch {
+    pub fn clear(&mut self) {
+        self.hits.iter_mut().for_each(Vec::clear);
+        self.samples = 0;
+    }
+}
+
+pub struct Sampler {
+    config: Config,
+    rng: StdRng,
+    orbit: Vec<Complex>,
+}
+
+impl Sampler {
+    pub fn new(config: Config, seed: Option<u64>) -> Self {
+        let longest = config.iterations.iter().copied().max().unwrap_or(0) as usize;
+        Self {
+            config,
+            rng: random::rng(seed),
+            orbit: Vec::with_capacity(longest),
+        }
+    }
+
+    // Samples points, until their orbits take the given number of iterations,
+    // or until it's stopped. Orbits of the Anti-Buddhabrot are long, so the budget
+    // keeps the batches short, and the stop is checked after every sample.
+    pub fn run(&mut self, budget: u64, batch: &mut Batch, is_stopped: impl Fn() -> bool) {
+        let mut spent = 0;
+
+        while spent < budget && !is_stopped() {
+            let c = self.random_point();
+            batch.samples += 1;
+            spent += 1;
+
+            let is_anti = self.config.mode == Mode::AntiBuddhabrot;
+            // Points of the main cardioid and the period-2 bulb never escape
+            if !is_anti && is_in_main_bulbs(c) {
+                continue;
+            }
+
+            let escaped_at = self.trace_orbit(c);
+            spent += self.orbit.len() as u64;
+
+            for (channel, limit) in self.config.iterations.iter().enumerate() {
+                let length = match (is_anti, escaped_at) {
+                    (false, Some(at))
+                        if at <= *limit && at >= self.config.min_iterations =>
+                    {
+                        at as usize
+                    },
+                    (true, None) => *limit as usize,
+                    (true, Some(at)) if at > *limit => *limit as usize,
+                    _ => continue,
+                };
+
+                for z in self.orbit.iter().take(length) {
+                    if let Some(cell) = self.config.cell(*z) {
+                        batch.hits[channel].push(cell as u32);
+                    }
+                }
+            }
+        }
+    }
+
+    // Stores the orbit and returns the iteration of the escape, if it happened
+    fn trace_orbit(&mut self, c: Complex) -> Option<u32> {
+        let longest = self.config.iterations.iter().copied().max().unwrap_or(0);
+
+        self.orbit.clear();
+        let mut z = Complex::ZERO;
+        for iteration in 0..longest {
+            z = z * z + c;
+            self.orbit.push(z);
+            if z.norm_sqr() > ESCAPE_RADIUS_SQR {
+                return Some(iteration + 1);
+            }
+        }
+
+        None
+    }
+
+    fn random_point(&mut self) -> Complex {
+        loop {
+            let c = Complex::new(
+                self.rng.gen_range(-SAMPLING_RADIUS..SAMPLING_RADIUS),
+                self.rng.gen_range(-SAMPLING_RADIUS..SAMPLING_RADIUS),
+            );
+            if c.norm_sqr() <= SAMPLING_RADIUS * SAMPLING_RADIUS {
+                return c;
+            }
+        }
+    }
+}
+
+fn is_in_main_bulbs(c: Complex) -> bool {
+    let shifted = Complex::new(c.re - 0.25, c.im);
+    let q = shifted.norm_sqr();
+    let in_cardioid = q * (q + shifted.re) <= 0.25 * c.im * c.im;
+    let in_bulb = (c.re + 1.0) * (c.re + 1.0) + c.im * c.im <= 1.0 / 16.0;
+
+    in_cardioid || in_bulb
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(config: Config, budget: u64) -> Histogram {
+        let mut histogram = Histogram::new(config.resolution);
+        let mut batch = Batch::default();
+        Sampler::new(config, Some(1)).run(budget, &mut batch, || false);
+        histogram.add(&batch);
+        histogram
+    }
+
+    #[test]
+    fn escaping_orbits_fill_histogram() {
+        let config = Config {
+            iterations: [100, 50, 20],
+            resolution: 50,
+            ..Default::default()
+        };
+
+        let histogram = sample(config, 100_000);
+
+        assert!(histogram.samples() > 0);
+        assert!(histogram
+            .channels()
+            .iter()
+            .all(|channel| channel.iter().any(|count| *count > 0)));
+    }
+
+    #[test]
+    fn batches_add_up() {
+        let config = Config {
+            resolution: 20,
+            ..Default::default()
+        };
+        let mut histogram = Histogram::new(config.resolution);
+        let mut sampler = Sampler::new(config, Some(1));
+        let (mut first, mut second) = (Batch::default(), Batch::default());
+        sampler.run(50_000, &mut first, || false);
+        sampler.run(50_000, &mut second, || false);
+
+        histogram.add(&first);
+        histogram.add(&second);
+
+        assert_eq!(histogram.samples(), first.samples + second.samples);
+        for (channel, counts) in histogram.channels().iter().enumerate() {
+            let total: usize = counts.iter().map(|count| *count as usize).sum();
+            assert_eq!(
+                total,
+                first.hits[channel].len() + second.hits[channel].len()
+            );
+        }
+    }
+
+    #[test]
+    fn budget_bounds_long_orbits() {
+        // Every bounded orbit takes all 100_000 iterations
+        let config = Config {
+            mode: Mode::AntiBuddhabrot,
+            iterations: [100_000; 3],
+            resolution: 20,
+            ..Default::default()
+        };
+
+        let histogram = sample(config.clone(), 1);
+        assert_eq!(histogram.samples(), 1);
+
+        let mut batch = Batch::default();
+        Sampler::new(config, Some(1)).run(u64::MAX, &mut batch, || true);
+        assert_eq!(batch.samples, 0);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::fractals::buddhabrot::model;
+use crate::fractals::buddhabrot::model::Mode;
+use crate::fractals::buddhabrot::state::BuddhabrotState;
+use crate::fractals::buddhabrot::validation::ValidationError;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<BuddhabrotDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &BuddhabrotState) -> serde_json::Result<String> {
+    let histogram = state
+        .histogram_snapshot()
+        .map(|(samples, [red, green, blue])| HistogramDto {
+            samples,
+            red,
+            green,
+            blue,
+        });
+
+    let dto = BuddhabrotDto {
+        mode: state.mode,
+        iterations: state.iterations,
+        min_iterations: state.min_iterations,
+        center: state.center,
+        size: state.size,
+        resolution: state.resolution,
+        is_rotated: state.is_rotated,
+        gamma: state.gamma,
+        units_per_cm: state.units_per_cm,
+        histogram,
+    };
+
+    // Histogram may be huge, so the file is not pretty-printed
+    serde_json::to_string(&dto)
+}
+
+fn default_iterations() -> [u32; 3] {
+    model::DEFAULT_ITERATIONS
+}
+
+fn default_center() -> [f64; 2] {
+    model::DEFAULT_CENTER
+}
+
+fn default_size() -> f64 {
+    model::DEFAULT_SIZE
+}
+
+fn default_resolution() -> usize {
+    model::DEFAULT_RESOLUTION
+}
+
+fn default_rotated() -> bool {
+    true
+}
+
+fn default_gamma() -> f32 {
+    model::DEFAULT_GAMMA
+}
+
+fn default_units_per_cm() -> f64 {
+    model::DEFAULT_UNITS_PER_CM
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BuddhabrotDto {
+    #[serde(rename = "Mode", default)]
+    mode: Mode,
+
+    // Red, green and blue channels
+    #[serde(rename = "Iterations", default = "default_iterations")]
+    iterations: [u32; 3],
+
+    #[serde(rename = "Min Iterations", default)]
+    min_iterations: u32,
+
+    #[serde(rename = "Center", default = "default_center")]
+    center: [f64; 2],
+
+    #[serde(rename = "Size", default = "default_size")]
+    size: f64,
+
+    #[serde(rename = "Resolution", default = "default_resolution")]
+    resolution: usize,
+
+    #[serde(rename = "Rotated", default = "default_rotated")]
+    is_rotated: bool,
+
+    #[serde(rename = "Gamma", default = "default_gamma")]
+    gamma: f32,
+
+    #[serde(rename = "Units per cm", default = "default_units_per_cm")]
+    units_per_cm: f64,
+
+    #[serde(rename = "Histogram", default, skip_serializing_if = "Option::is_none")]
+    histogram: Option<HistogramDto>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HistogramDto {
+    #[serde(rename = "Samples")]
+    samples: u64,
+
+    #[serde(rename = "Red")]
+    red: Vec<u32>,
+
+    #[serde(rename = "Green")]
+    green: Vec<u32>,
+
+    #[serde(rename = "Blue")]
+    blue: Vec<u32>,
+}
+
+impl BuddhabrotDto {
+    pub fn load(self, state: &mut BuddhabrotState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.mode = self.mode;
+        state.iterations = self.iterations;
+        state.min_iterations = self.min_iterations;
+        state.center = self.center;
+        state.size = self.size;
+        state.resolution = self.resolution;
+        state.is_rotated = self.is_rotated;
+        state.gamma = self.gamma;
+        state.units_per_cm = self.units_per_cm;
+
+        let result = state.initialize().and_then(|_| match self.histogram {
+            Some(histogram) => state.load_histogram(
+                histogram.samples,
+                [histogram.red, histogram.green, histogram.blue],
+            ),
+            None => Ok(()),
+        });
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
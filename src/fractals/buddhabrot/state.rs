@@ -0,0 +1,219 @@
+use crate::fractals::buddhabrot::model;
+use crate::fractals::buddhabrot::model::{Config, Histogram, Mode};
+use crate::fractals::buddhabrot::validation;
+use crate::fractals::buddhabrot::validation::ValidationError;
+use crate::fractals::buddhabrot::worker::Worker;
+use crate::geometry::point2d::Point2D;
+use crate::graphics::raster::Raster;
+use crate::ui::components::canvas::CanvasParams;
+use egui::{Rect, Shape};
+use std::sync::{Arc, Mutex};
+
+// Seconds between the texture updates while the worker is running
+const REFRESH_INTERVAL: f64 = 0.5;
+
+pub struct BuddhabrotState {
+    is_initialized: bool,
+    is_refresh_requested: bool,
+
+    pub mode: Mode,
+    pub iterations: [u32; 3],
+    pub min_iterations: u32,
+
+    pub center: [f64; 2],
+    pub size: f64,
+    pub resolution: usize,
+    pub is_rotated: bool,
+
+    pub gamma: f32,
+    pub units_per_cm: f64,
+
+    histogram: Arc<Mutex<Histogram>>,
+    // Settings, that the histogram was accumulated with
+    histogram_config: Option<Config>,
+    worker: Option<Worker>,
+
+    raster: Raster,
+    rendered_samples: u64,
+    last_refresh: f64,
+}
+
+impl Default for BuddhabrotState {
+    fn default() -> Self {
+        let config = Config::default();
+
+        Self {
+            is_initialized: false,
+            is_refresh_requested: false,
+
+            mode: config.mode,
+            iterations: config.iterations,
+            min_iterations: config.min_iterations,
+
+            center: config.center,
+            size: config.size,
+            resolution: config.resolution,
+            is_rotated: config.is_rotated,
+
+            gamma: model::DEFAULT_GAMMA,
+            units_per_cm: model::DEFAULT_UNITS_PER_CM,
+
+            histogram: Arc::new(Mutex::new(Histogram::new(0))),
+            histogram_config: None,
+            worker: None,
+
+            raster: Raster::new("Buddhabrot"),
+            rendered_samples: 0,
+            last_refresh: 0.0,
+        }
+    }
+}
+
+impl BuddhabrotState {
+    pub fn shapes(&mut self, ctx: &egui::Context, params: &CanvasParams) -> Vec<Shape> {
+        if self.histogram_config.is_none() {
+            return vec![];
+        }
+
+        if let Some(worker) = &mut self.worker {
+            worker.step();
+            ctx.request_repaint();
+        }
+
+        // Progressive refinement: the picture is updated while orbits are accumulated
+        let time = ctx.input(|input| input.time);
+        let samples = self.samples();
+        let is_refresh_time = samples != self.rendered_samples
+            && (self.worker.is_none() || time - self.last_refresh >= REFRESH_INTERVAL);
+        if self.is_refresh_requested || is_refresh_time {
+            self.is_refresh_requested = false;
+            self.last_refresh = time;
+            self.rendered_samples = samples;
+
+            if let Ok(histogram) = self.histogram.lock() {
+                let resolution = histogram.resolution();
+                self.raster.set_pixels(
+                    resolution,
+                    resolution,
+                    histogram.pixels(self.gamma),
+                );
+            }
+        }
+
+        vec![self.raster.shape(ctx, self.screen_rect(params))]
+    }
+
+    // Histogram region is centered at the origin of the canvas
+    fn screen_rect(&self, params: &CanvasParams) -> Rect {
+        let half_size = (self.size / self.units_per_cm / 2.0) as f32;
+        let top_left = Point2D::new(-half_size, half_size).to_screen(params);
+        let bottom_right = Point2D::new(half_size, -half_size).to_screen(params);
+
+        Rect::from_two_pos(top_left.to_pos2(), bottom_right.to_pos2())
+    }
+
+    pub fn config(&self) -> Config {
+        Config {
+            mode: self.mode,
+            iterations: self.iterations,
+            min_iterations: self.min_iterations,
+
+            center: self.center,
+            size: self.size,
+            resolution: self.resolution,
+            is_rotated: self.is_rotated,
+        }
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::iterations_range(&self.iterations)?;
+        validation::min_iterations_range(self.min_iterations, &self.iterations)?;
+        validation::region_is_valid(&self.center, self.size)?;
+        validation::resolution_range(self.resolution)?;
+        validation::gamma_range(self.gamma)?;
+        validation::scale_range(self.units_per_cm)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    // Accumulated orbits don't match new settings, so the rendering stops
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+        self.pause();
+    }
+
+    // Gamma and scale don't change the histogram, only the picture
+    pub fn request_refresh(&mut self) {
+        self.is_refresh_requested = true;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.worker.is_some()
+    }
+
+    // Continues the accumulation, if the histogram was made with the same settings
+    pub fn start(&mut self) {
+        debug_assert!(self.is_initialized);
+
+        let config = self.config();
+        if self.histogram_config.as_ref() != Some(&config) {
+            self.histogram = Arc::new(Mutex::new(Histogram::new(config.resolution)));
+            self.histogram_config = Some(config.clone());
+        }
+
+        self.worker = Some(Worker::start(config, Arc::clone(&self.histogram)));
+    }
+
+    pub fn pause(&mut self) {
+        self.worker = None;
+        self.is_refresh_requested = true;
+    }
+
+    pub fn clear(&mut self) {
+        self.pause();
+        if let Ok(mut histogram) = self.histogram.lock() {
+            histogram.clear();
+        }
+    }
+
+    pub fn samples(&self) -> u64 {
+        match self.histogram.lock() {
+            Ok(histogram) => histogram.samples(),
+            Err(_) => 0,
+        }
+    }
+
+    // Histogram for saving, if it matches current settings
+    pub fn histogram_snapshot(&self) -> Option<(u64, [Vec<u32>; 3])> {
+        if self.histogram_config.as_ref() != Some(&self.config()) {
+            return None;
+        }
+
+        let histogram = self.histogram.lock().ok()?;
+        if histogram.samples() == 0 {
+            return None;
+        }
+        Some((histogram.samples(), histogram.channels().clone()))
+    }
+
+    pub fn load_histogram(
+        &mut self, samples: u64, channels: [Vec<u32>; 3],
+    ) -> Result<(), ValidationError> {
+        debug_assert!(self.is_initialized);
+
+        let histogram = Histogram::from_channels(self.resolution, channels, samples)
+            .ok_or(ValidationError::HistogramSizeMismatch)?;
+
+        self.pause();
+        self.histogram = Arc::new(Mutex::new(histogram));
+        self.histogram_config = Some(self.config());
+
+        Ok(())
+    }
+}
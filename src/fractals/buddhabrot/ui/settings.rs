@@ -0,0 +1,272 @@
+use crate::context::Context;
+use crate::fractals::buddhabrot::model::Mode;
+use crate::fractals::buddhabrot::serialization;
+use crate::fractals::buddhabrot::state::BuddhabrotState;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct BuddhabrotSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for BuddhabrotSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl BuddhabrotSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        let state = &mut context.buddhabrot_state;
+
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if state.is_running() {
+                ui.label(RichText::new("Rendering...").color(colors::LIME));
+            } else if state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Samples: ");
+            ui.label(state.samples().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let mut changed = false;
+        let mut display_changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Mode:");
+            egui::ComboBox::from_id_salt("BuddhabrotMode")
+                .selected_text(format!("{}", state.mode))
+                .show_ui(ui, |ui| {
+                    for mode in [Mode::Buddhabrot, Mode::AntiBuddhabrot] {
+                        changed |= ui
+                            .selectable_value(&mut state.mode, mode, mode.to_string())
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            for (channel, name) in ["Red", "Green", "Blue"].iter().enumerate() {
+                ui.label(format!("{} Iterations:", name));
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut state.iterations[channel])
+                            .speed(10)
+                            .range(1..=u32::MAX),
+                    )
+                    .changed();
+                ui.end_row();
+            }
+
+            if state.mode == Mode::Buddhabrot {
+                ui.label("Min Iterations:");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut state.min_iterations)
+                            .speed(1)
+                            .range(0..=u32::MAX),
+                    )
+                    .on_hover_text("Orbits that escape faster are not plotted.")
+                    .changed();
+                ui.end_row();
+            }
+
+            ui.label("Center:");
+            ui.horizontal(|ui| {
+                let re = ui.add(DragValue::new(&mut state.center[0]).speed(0.01));
+                let im = ui.add(
+                    DragValue::new(&mut state.center[1])
+                        .speed(0.01)
+                        .prefix("i: "),
+                );
+                changed |= re.changed() || im.changed();
+            });
+            ui.end_row();
+
+            ui.label("Size:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.size)
+                        .speed(0.01)
+                        .range(1e-12..=f64::MAX),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Resolution:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.resolution)
+                        .speed(1)
+                        .range(16..=2048)
+                        .suffix(" px"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Rotated:");
+            changed |= ui
+                .checkbox(&mut state.is_rotated, "")
+                .on_hover_text("Real axis goes vertically.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Gamma:");
+            display_changed |=
+                ui.add(Slider::new(&mut state.gamma, 0.05..=2.0)).changed();
+            ui.end_row();
+
+            ui.label("Scale:");
+            display_changed |= ui
+                .add(
+                    DragValue::new(&mut state.units_per_cm)
+                        .speed(0.001)
+                        .range(1e-12..=f64::MAX)
+                        .prefix("1 cm = ")
+                        .suffix(" units"),
+                )
+                .changed();
+            ui.end_row();
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+        if display_changed {
+            state.request_refresh();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(!state.is_initialized(), Button::new("Initialize"))
+                .clicked()
+            {
+                if let Err(err) = state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if state.is_running() {
+                if ui.button("Pause").clicked() {
+                    state.pause();
+                }
+            } else if ui
+                .add_enabled(state.is_initialized(), Button::new("Render"))
+                .on_hover_text("Continues accumulating, if the settings are the same.")
+                .clicked()
+            {
+                state.start();
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Clear Histogram").clicked() {
+                state.clear();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.buddhabrot_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.buddhabrot_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.buddhabrot_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Mode\": \"Buddhabrot\",
+                                \"Iterations\": [5000, 500, 50],
+                                \"Min Iterations\": 0,
+                                \"Center\": [-0.4, 0.0],
+                                \"Size\": 3.2,
+                                \"Resolution\": 500
+                            }
+
+                            Mode: \"Buddhabrot\" or \"Anti-Buddhabrot\".
+                            Iterations: limits of the red, green and blue channels.
+                            Other fields: \"Rotated\", \"Gamma\", \"Units per cm\".
+
+                            Saved files also keep the accumulated \"Histogram\",
+                            so the rendering can be continued after loading.
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut BuddhabrotState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
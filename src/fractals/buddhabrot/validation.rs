@@ -0,0 +1,172 @@
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+const MAX_ITERATIONS: u32 = 1_000_000;
+const MIN_RESOLUTION: usize = 16;
+const MAX_RESOLUTION: usize = 2048;
+
+pub fn iterations_range(iterations: &[u32; 3]) -> Result<(), ValidationError> {
+    if iterations
+        .iter()
+        .any(|value| !(1..=MAX_ITERATIONS).contains(value))
+    {
+        return Err(ValidationError::BadIterationsValue);
+    }
+
+    Ok(())
+}
+
+pub fn min_iterations_range(
+    min_iterations: u32, iterations: &[u32; 3],
+) -> Result<(), ValidationError> {
+    if iterations.iter().any(|value| min_iterations >= *value) {
+        return Err(ValidationError::BadMinIterationsValue);
+    }
+
+    Ok(())
+}
+
+pub fn region_is_valid(center: &[f64; 2], size: f64) -> Result<(), ValidationError> {
+    if !(center.iter().all(|value| value.is_finite()) && size > 0.0 && size.is_finite()) {
+        return Err(ValidationError::BadRegion);
+    }
+
+    Ok(())
+}
+
+pub fn resolution_range(resolution: usize) -> Result<(), ValidationError> {
+    if !(MIN_RESOLUTION..=MAX_RESOLUTION).contains(&resolution) {
+        return Err(ValidationError::BadResolutionValue);
+    }
+
+    Ok(())
+}
+
+pub fn gamma_range(gamma: f32) -> Result<(), ValidationError> {
+    if !(gamma > 0.0 && gamma.is_finite()) {
+        return Err(ValidationError::BadGammaValue);
+    }
+
+    Ok(())
+}
+
+pub fn scale_range(units_per_cm: f64) -> Result<(), ValidationError> {
+    if !(units_per_cm > 0.0 && units_per_cm.is_finite()) {
+        return Err(ValidationError::BadScaleValue);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("Iteration limits have to be in range 1..1000000.")]
+    BadIterationsValue,
+
+    #[error("The minimum of iterations has to be lower than every channel limit.")]
+    BadMinIterationsValue,
+
+    #[error("The region has to have a finite center and a size greater than 0.")]
+    BadRegion,
+
+    #[error("The resolution has to be in range 16..2048.")]
+    BadResolutionValue,
+
+    #[error("The gamma has to be greater than 0.")]
+    BadGammaValue,
+
+    #[error("The scale has to be greater than 0.")]
+    BadScaleValue,
+
+    #[error("The saved histogram doesn't match the resolution.")]
+    HistogramSizeMismatch,
+}
+
+impl ValidationError {
+    pub fn window(&self) -> MessageWindow {
+        MessageWindow::error(&format!("Validation Error: {}", self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::buddhabrot::state::BuddhabrotState;
+
+    #[test]
+    fn every_channel_limit_is_checked() {
+        assert!(iterations_range(&[1, MAX_ITERATIONS, 20]).is_ok());
+        for iterations in [[100, 0, 20], [100, 50, MAX_ITERATIONS + 1]] {
+            assert!(matches!(
+                iterations_range(&iterations),
+                Err(ValidationError::BadIterationsValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn min_iterations_below_lowest_limit() {
+        assert!(min_iterations_range(19, &[100, 50, 20]).is_ok());
+        assert!(matches!(
+            min_iterations_range(20, &[100, 50, 20]),
+            Err(ValidationError::BadMinIterationsValue)
+        ));
+    }
+
+    #[test]
+    fn region_needs_finite_center_and_size() {
+        assert!(region_is_valid(&[-0.5, 0.0], 3.0).is_ok());
+        for (center, size) in [
+            ([-0.5, 0.0], 0.0),
+            ([-0.5, 0.0], f64::INFINITY),
+            ([f64::NAN, 0.0], 3.0),
+        ] {
+            assert!(matches!(
+                region_is_valid(&center, size),
+                Err(ValidationError::BadRegion)
+            ));
+        }
+    }
+
+    #[test]
+    fn resolution_bounds_are_inclusive() {
+        assert!(resolution_range(MIN_RESOLUTION).is_ok());
+        assert!(resolution_range(MAX_RESOLUTION).is_ok());
+        for resolution in [MIN_RESOLUTION - 1, MAX_RESOLUTION + 1] {
+            assert!(matches!(
+                resolution_range(resolution),
+                Err(ValidationError::BadResolutionValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn histogram_size_mismatch() {
+        let mut state = BuddhabrotState::default();
+        state.resolution = 20;
+        let _ = state.initialize();
+
+        let result = state.load_histogram(1, [vec![0; 400], vec![0; 400], vec![0; 399]]);
+
+        assert!(matches!(
+            result,
+            Err(ValidationError::HistogramSizeMismatch)
+        ));
+    }
+
+    #[test]
+    fn loaded_histogram_is_saved_while_settings_match() {
+        let mut state = BuddhabrotState::default();
+        state.resolution = 16;
+        let _ = state.initialize();
+        let channels = [vec![1; 256], vec![2; 256], vec![3; 256]];
+
+        let result = state.load_histogram(5, channels.clone());
+
+        assert!(result.is_ok());
+        assert_eq!(state.histogram_snapshot(), Some((5, channels)));
+
+        state.min_iterations += 1;
+        assert_eq!(state.histogram_snapshot(), None);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::fractals::buddhabrot::model::{Batch, Config, Histogram, Sampler};
+use std::sync::{Arc, Mutex};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread::JoinHandle;
+
+// Orbit iterations between the additions to the shared histogram
+const BATCH_ITERATIONS: u64 = 200_000;
+
+// Accumulates orbits into the shared histogram until dropped.
+// On native platforms every core runs its own sampler,
+// on the web there are no threads, so a batch is sampled on every frame.
+pub struct Worker {
+    #[cfg(not(target_arch = "wasm32"))]
+    is_stopped: Arc<AtomicBool>,
+    #[cfg(not(target_arch = "wasm32"))]
+    handles: Vec<JoinHandle<()>>,
+
+    #[cfg(target_arch = "wasm32")]
+    sampler: Sampler,
+    #[cfg(target_arch = "wasm32")]
+    batch: Batch,
+    #[cfg(target_arch = "wasm32")]
+    histogram: Arc<Mutex<Histogram>>,
+}
+
+impl Worker {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start(config: Config, histogram: Arc<Mutex<Histogram>>) -> Self {
+        let is_stopped = Arc::new(AtomicBool::new(false));
+        let threads = std::thread::available_parallelism().map_or(1, |value| value.get());
+
+        let handles = (0..threads)
+            .map(|_| {
+                let config = config.clone();
+                let histogram = Arc::clone(&histogram);
+                let is_stopped = Arc::clone(&is_stopped);
+
+                std::thread::spawn(move || {
+                    let is_stopped = || is_stopped.load(Ordering::Relaxed);
+                    let mut sampler = Sampler::new(config, None);
+                    let mut batch = Batch::default();
+
+                    while !is_stopped() {
+                        sampler.run(BATCH_ITERATIONS, &mut batch, is_stopped);
+                        match histogram.lock() {
+                            Ok(mut histogram) => histogram.add(&batch),
+                            Err(_) => return,
+                        }
+                        batch.clear();
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            is_stopped,
+            handles,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn start(config: Config, histogram: Arc<Mutex<Histogram>>) -> Self {
+        Self {
+            sampler: Sampler::new(config, None),
+            batch: Batch::default(),
+            histogram,
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn step(&mut self) {}
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn step(&mut self) {
+        self.sampler
+            .run(BATCH_ITERATIONS, &mut self.batch, || false);
+        if let Ok(mut histogram) = self.histogram.lock() {
+            histogram.add(&self.batch);
+        }
+        self.batch.clear();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.is_stopped.store(true, Ordering::Relaxed);
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
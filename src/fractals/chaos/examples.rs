@@ -0,0 +1,77 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static CHAOS_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/chaos/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Hexagon (Custom Offsets)")]
+    HexagonCustom,
+
+    #[strum(serialize = "Pentagon (Not Neighbor)")]
+    PentagonNotNeighbor,
+
+    #[strum(serialize = "Sierpinski Carpet")]
+    SierpinskiCarpet,
+
+    #[strum(serialize = "Sierpinski Triangle")]
+    SierpinskiTriangle,
+
+    #[strum(serialize = "Square (No Repeat)")]
+    SquareNoRepeat,
+
+    #[strum(serialize = "Square (Not Neighbor)")]
+    SquareNotNeighbor,
+
+    #[strum(serialize = "Vicsek Fractal")]
+    Vicsek,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::HexagonCustom => PathBuf::from(r"Hexagon-Custom.json"),
+            Example::PentagonNotNeighbor => PathBuf::from(r"Pentagon-Not-Neighbor.json"),
+            Example::SierpinskiCarpet => PathBuf::from(r"Sierpinski-Carpet.json"),
+            Example::SierpinskiTriangle => PathBuf::from(r"Sierpinski-Triangle.json"),
+            Example::SquareNoRepeat => PathBuf::from(r"Square-No-Repeat.json"),
+            Example::SquareNotNeighbor => PathBuf::from(r"Square-Not-Neighbor.json"),
+            Example::Vicsek => PathBuf::from(r"Vicsek.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = CHAOS_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::HexagonCustom,
+            Example::PentagonNotNeighbor,
+            Example::SierpinskiCarpet,
+            Example::SierpinskiTriangle,
+            Example::SquareNoRepeat,
+            Example::SquareNotNeighbor,
+            Example::Vicsek,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
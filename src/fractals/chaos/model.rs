@@ -0,0 +1,319 @@
+use crate::math::random;
+use egui::Color32;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_VERTICES: u32 = 4;
+pub const DEFAULT_RATIO: f32 = 0.5;
+pub const DEFAULT_ITERATIONS: u32 = 50000;
+pub const DEFAULT_SIZE_CM: f32 = 10.0;
+pub const DEFAULT_RADIUS: f32 = 0.025;
+pub const DEFAULT_COLOR: Color32 = Color32::from_rgb(0, 0, 0);
+
+pub const MIN_VERTICES: u32 = 3;
+pub const MAX_VERTICES: u32 = 16;
+pub const MAX_ITERATIONS: u32 = 2_000_000;
+
+// First jumps are not drawn, the point comes close to the fractal after them
+const BURN_IN: u32 = 20;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Restriction {
+    #[default]
+    #[strum(serialize = "None")]
+    #[serde(rename = "None")]
+    None,
+
+    #[strum(serialize = "No Repeat")]
+    #[serde(rename = "No Repeat")]
+    NoRepeat,
+
+    #[strum(serialize = "Not Neighbor")]
+    #[serde(rename = "Not Neighbor")]
+    NotNeighbor,
+
+    #[strum(serialize = "Custom Offsets")]
+    #[serde(rename = "Custom Offsets")]
+    Custom,
+}
+
+impl Restriction {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Restriction::None,
+            Restriction::NoRepeat,
+            Restriction::NotNeighbor,
+            Restriction::Custom,
+        ]
+        .into_iter()
+    }
+
+    // Offsets from the previous target, that can't be picked next,
+    // counted counterclockwise around the polygon of the given number of targets
+    pub fn forbidden_offsets(&self, ring: usize, custom: &[u32]) -> Vec<usize> {
+        match self {
+            Restriction::None => vec![],
+            Restriction::NoRepeat => vec![0],
+            Restriction::NotNeighbor => vec![1, ring - 1],
+            Restriction::Custom => custom
+                .iter()
+                .map(|offset| *offset as usize)
+                .filter(|offset| *offset < ring)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Coloring {
+    #[default]
+    #[strum(serialize = "Single Color")]
+    #[serde(rename = "Single Color")]
+    Single,
+
+    #[strum(serialize = "By Target")]
+    #[serde(rename = "By Target")]
+    Target,
+}
+
+impl Coloring {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Coloring::Single, Coloring::Target].into_iter()
+    }
+}
+
+// Number of the targets on the boundary, they go around it counterclockwise
+pub fn ring_size(vertices: u32, has_midpoints: bool) -> usize {
+    match has_midpoints {
+        true => 2 * vertices as usize,
+        false => vertices as usize,
+    }
+}
+
+pub struct Model {
+    // Boundary targets first, in the order around the polygon, then the center
+    targets: Vec<[f32; 2]>,
+    // Targets, that can be picked after every target
+    allowed: Vec<Vec<usize>>,
+
+    ratio: f32,
+    iterations: u32,
+    seed: Option<u64>,
+}
+
+impl Model {
+    // Points of the game and the targets, they have jumped towards
+    pub fn points(&self) -> Vec<([f32; 2], usize)> {
+        let mut rng = random::rng(self.seed);
+        let mut points = Vec::with_capacity(self.iterations as usize);
+
+        let mut previous = rng.gen_range(0..self.targets.len());
+        let mut point = self.targets[previous];
+
+        for iteration in 0..BURN_IN + self.iterations {
+            let allowed = &self.allowed[previous];
+            if allowed.is_empty() {
+                break;
+            }
+            let target = allowed[rng.gen_range(0..allowed.len())];
+
+            let [x, y] = self.targets[target];
+            point = [
+                point[0] + (x - point[0]) * self.ratio,
+                point[1] + (y - point[1]) * self.ratio,
+            ];
+            previous = target;
+
+            if iteration >= BURN_IN {
+                points.push((point, target));
+            }
+        }
+
+        points
+    }
+
+    pub fn targets(&self) -> &[[f32; 2]] {
+        &self.targets
+    }
+}
+
+pub struct ModelBuilder {
+    vertices: u32,
+    has_midpoints: bool,
+    has_center: bool,
+    size: f32,
+
+    restriction: Restriction,
+    custom_offsets: Vec<u32>,
+
+    ratio: f32,
+    iterations: u32,
+    seed: Option<u64>,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            vertices: DEFAULT_VERTICES,
+            has_midpoints: false,
+            has_center: false,
+            size: DEFAULT_SIZE_CM,
+
+            restriction: Restriction::default(),
+            custom_offsets: vec![],
+
+            ratio: DEFAULT_RATIO,
+            iterations: DEFAULT_ITERATIONS,
+            seed: None,
+        }
+    }
+}
+
+impl ModelBuilder {
+    // Size is the radius of the circle around the polygon
+    pub fn with_polygon(mut self, vertices: u32, size: f32) -> Self {
+        self.vertices = vertices;
+        self.size = size;
+        self
+    }
+
+    pub fn with_extra_targets(mut self, has_midpoints: bool, has_center: bool) -> Self {
+        self.has_midpoints = has_midpoints;
+        self.has_center = has_center;
+        self
+    }
+
+    pub fn with_restriction(
+        mut self, restriction: Restriction, custom: Vec<u32>,
+    ) -> Self {
+        self.restriction = restriction;
+        self.custom_offsets = custom;
+        self
+    }
+
+    pub fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        let ring = ring_size(self.vertices, self.has_midpoints);
+
+        // Polygon stands on its bottom edge, the first vertex is its left end
+        let vertex = |index: u32| {
+            let angle = -std::f32::consts::FRAC_PI_2
+                - std::f32::consts::PI / self.vertices as f32
+                + std::f32::consts::TAU * index as f32 / self.vertices as f32;
+            [self.size * angle.cos(), self.size * angle.sin()]
+        };
+
+        let mut targets = Vec::with_capacity(ring + 1);
+        for index in 0..self.vertices {
+            let [x, y] = vertex(index);
+            targets.push([x, y]);
+            if self.has_midpoints {
+                let [next_x, next_y] = vertex(index + 1);
+                targets.push([(x + next_x) / 2.0, (y + next_y) / 2.0]);
+            }
+        }
+        if self.has_center {
+            targets.push([0.0, 0.0]);
+        }
+
+        // Offsets are counted around the boundary, the center has none of its own,
+        // so it only can't be repeated, when repeats are forbidden
+        let forbidden = self
+            .restriction
+            .forbidden_offsets(ring, &self.custom_offsets);
+        let allowed = (0..targets.len())
+            .map(|previous| {
+                (0..targets.len())
+                    .filter(|next| {
+                        if previous < ring && *next < ring {
+                            !forbidden.contains(&((next + ring - previous) % ring))
+                        } else {
+                            previous != *next || !forbidden.contains(&0)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Model {
+            targets,
+            allowed,
+
+            ratio: self.ratio,
+            iterations: self.iterations,
+            seed: self.seed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restrictions_are_kept() {
+        for restriction in [Restriction::NoRepeat, Restriction::NotNeighbor] {
+            let model = ModelBuilder::default()
+                .with_restriction(restriction, vec![])
+                .with_iterations(10000)
+                .with_seed(Some(random::DEFAULT_SEED))
+                .build();
+            let forbidden = restriction.forbidden_offsets(4, &[]);
+
+            let targets: Vec<usize> = model
+                .points()
+                .into_iter()
+                .map(|(_, target)| target)
+                .collect();
+            assert_eq!(targets.len(), 10000);
+            for pair in targets.windows(2) {
+                let offset = (pair[1] + 4 - pair[0]) % 4;
+                assert!(!forbidden.contains(&offset), "{}: {:?}", restriction, pair);
+            }
+        }
+    }
+
+    #[test]
+    fn extra_targets() {
+        let model = ModelBuilder::default()
+            .with_polygon(4, 1.0)
+            .with_extra_targets(true, true)
+            .build();
+
+        assert_eq!(model.targets().len(), 9);
+        assert_eq!(ring_size(4, true), 8);
+        // Midpoint of the bottom edge
+        let [x, y] = model.targets()[1];
+        assert!(x.abs() < 1e-6 && (y + f32::sqrt(0.5)).abs() < 1e-6);
+        assert_eq!(model.targets()[8], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn points_stay_inside_polygon() {
+        let model = ModelBuilder::default()
+            .with_polygon(5, 2.0)
+            .with_ratio(0.4)
+            .with_iterations(1000)
+            .build();
+
+        assert!(model
+            .points()
+            .iter()
+            .all(|([x, y], _)| x.hypot(*y) <= 2.0 + 1e-4));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::fractals::chaos::model;
+use crate::fractals::chaos::model::{Coloring, Restriction};
+use crate::fractals::chaos::state::ChaosState;
+use crate::fractals::chaos::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<ChaosDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &ChaosState) -> serde_json::Result<String> {
+    let dto = ChaosDto {
+        vertices: state.vertices,
+        ratio: state.ratio,
+        has_midpoints: state.has_midpoints,
+        has_center: state.has_center,
+        restriction: state.restriction,
+        custom_offsets: state.custom_offsets.clone(),
+        iterations: state.iterations,
+        seed: state.seed,
+        size_cm: state.size_cm,
+        radius_cm: state.radius_cm,
+        coloring: state.coloring,
+        color: [state.color.r(), state.color.g(), state.color.b()],
+        gradient: (&state.gradient).into(),
+        is_targets_shown: state.is_targets_shown,
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_vertices() -> u32 {
+    model::DEFAULT_VERTICES
+}
+
+fn default_ratio() -> f32 {
+    model::DEFAULT_RATIO
+}
+
+fn default_iterations() -> u32 {
+    model::DEFAULT_ITERATIONS
+}
+
+fn default_size() -> f32 {
+    model::DEFAULT_SIZE_CM
+}
+
+fn default_radius() -> f32 {
+    model::DEFAULT_RADIUS
+}
+
+fn default_color() -> [u8; 3] {
+    let color = model::DEFAULT_COLOR;
+    [color.r(), color.g(), color.b()]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ChaosDto {
+    #[serde(rename = "Vertices", default = "default_vertices")]
+    vertices: u32,
+
+    #[serde(rename = "Jump Ratio", default = "default_ratio")]
+    ratio: f32,
+
+    #[serde(rename = "Midpoints", default)]
+    has_midpoints: bool,
+
+    #[serde(rename = "Center", default)]
+    has_center: bool,
+
+    #[serde(rename = "Restriction", default)]
+    restriction: Restriction,
+
+    #[serde(
+        rename = "Forbidden Offsets",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    custom_offsets: Vec<u32>,
+
+    #[serde(rename = "Iterations", default = "default_iterations")]
+    iterations: u32,
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+
+    #[serde(rename = "Size", default = "default_size")]
+    size_cm: f32,
+
+    #[serde(rename = "Radius", default = "default_radius")]
+    radius_cm: f32,
+
+    #[serde(rename = "Coloring", default)]
+    coloring: Coloring,
+
+    #[serde(rename = "Color", default = "default_color")]
+    color: [u8; 3],
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+
+    #[serde(rename = "Show Targets", default = "default_true")]
+    is_targets_shown: bool,
+}
+
+impl ChaosDto {
+    pub fn load(self, state: &mut ChaosState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.vertices = self.vertices;
+        state.ratio = self.ratio;
+        state.has_midpoints = self.has_midpoints;
+        state.has_center = self.has_center;
+        state.restriction = self.restriction;
+        state.custom_offsets = self.custom_offsets;
+        state.iterations = self.iterations;
+        state.seed = self.seed;
+        state.size_cm = self.size_cm;
+        state.radius_cm = self.radius_cm;
+        state.coloring = self.coloring;
+        state.gradient = self.gradient.into();
+        state.is_targets_shown = self.is_targets_shown;
+
+        let [r, g, b] = self.color;
+        state.color = Color32::from_rgb(r, g, b);
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,178 @@
+use crate::fractals::chaos::model::{Coloring, ModelBuilder, Restriction};
+use crate::fractals::chaos::validation::ValidationError;
+use crate::fractals::chaos::{model, validation};
+use crate::geometry::dot::{Dot, DotBuilder};
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::colors;
+use crate::ui::styles::gradient::Gradient;
+use egui::{Color32, Shape};
+
+// Targets are shown larger than the dots of the game
+const TARGET_RADIUS_CM: f32 = 0.12;
+
+pub struct ChaosState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub vertices: u32,
+    pub ratio: f32,
+    pub has_midpoints: bool,
+    pub has_center: bool,
+
+    pub restriction: Restriction,
+    pub custom_offsets: Vec<u32>,
+
+    pub iterations: u32,
+    pub seed: Option<u64>,
+
+    pub size_cm: f32,
+    pub radius_cm: f32,
+    pub coloring: Coloring,
+    pub color: Color32,
+    pub gradient: Gradient,
+    pub is_targets_shown: bool,
+
+    dots: Vec<Dot>,
+    targets: Vec<Dot>,
+}
+
+impl Default for ChaosState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            vertices: model::DEFAULT_VERTICES,
+            ratio: model::DEFAULT_RATIO,
+            has_midpoints: false,
+            has_center: false,
+
+            restriction: Restriction::default(),
+            custom_offsets: vec![],
+
+            iterations: model::DEFAULT_ITERATIONS,
+            seed: None,
+
+            size_cm: model::DEFAULT_SIZE_CM,
+            radius_cm: model::DEFAULT_RADIUS,
+            coloring: Coloring::default(),
+            color: model::DEFAULT_COLOR,
+            gradient: Gradient::default(),
+            is_targets_shown: true,
+
+            dots: vec![],
+            targets: vec![],
+        }
+    }
+}
+
+impl ChaosState {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Vec<Shape> {
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+            self.play();
+        }
+
+        let mut shapes: Vec<Shape> = self
+            .dots
+            .iter()
+            .map(|dot| dot.to_screen(params).to_shape())
+            .collect();
+        if self.is_targets_shown {
+            shapes.extend(
+                self.targets
+                    .iter()
+                    .map(|dot| dot.to_screen(params).to_shape()),
+            );
+        }
+
+        shapes
+    }
+
+    fn play(&mut self) {
+        let model = ModelBuilder::default()
+            .with_polygon(self.vertices, self.size_cm)
+            .with_extra_targets(self.has_midpoints, self.has_center)
+            .with_restriction(self.restriction, self.custom_offsets.clone())
+            .with_ratio(self.ratio)
+            .with_iterations(self.iterations)
+            .with_seed(self.seed)
+            .build();
+
+        let targets_count = model.targets().len();
+        let target_color = |target: usize| match self.coloring {
+            Coloring::Single => self.color,
+            Coloring::Target => self
+                .gradient
+                .sample(target as f32 / (targets_count - 1).max(1) as f32),
+        };
+
+        self.dots = model
+            .points()
+            .into_iter()
+            .map(|([x, y], target)| {
+                DotBuilder::default()
+                    .with_center(Point2D::new(x, y))
+                    .with_color(target_color(target))
+                    .with_radius_centimeters(self.radius_cm)
+                    .build()
+            })
+            .collect();
+
+        self.targets = model
+            .targets()
+            .iter()
+            .enumerate()
+            .map(|(index, [x, y])| {
+                let color = match self.coloring {
+                    Coloring::Single => colors::DARK_RED,
+                    Coloring::Target => target_color(index),
+                };
+                DotBuilder::default()
+                    .with_center(Point2D::new(*x, *y))
+                    .with_color(color)
+                    .with_radius_centimeters(TARGET_RADIUS_CM)
+                    .build()
+            })
+            .collect();
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::vertices_range(self.vertices)?;
+        validation::ratio_range(self.ratio)?;
+        validation::iterations_range(self.iterations)?;
+        validation::offsets_are_valid(
+            self.restriction,
+            &self.custom_offsets,
+            model::ring_size(self.vertices, self.has_midpoints),
+        )?;
+        validation::size_range(self.size_cm)?;
+        validation::radius_range(self.radius_cm)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn dots_count(&self) -> usize {
+        self.dots.len()
+    }
+
+    // Number of the targets around the polygon, offsets are counted among them
+    pub fn ring_size(&self) -> usize {
+        model::ring_size(self.vertices, self.has_midpoints)
+    }
+}
@@ -0,0 +1,352 @@
+use crate::context::Context;
+use crate::fractals::chaos::examples::Example;
+use crate::fractals::chaos::model::{
+    Coloring, Restriction, MAX_ITERATIONS, MAX_VERTICES, MIN_VERTICES,
+};
+use crate::fractals::chaos::serialization;
+use crate::fractals::chaos::state::ChaosState;
+use crate::io;
+use crate::math::random;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct ChaosSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for ChaosSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl ChaosSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.chaos_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Dots: ");
+            ui.label(context.chaos_state.dots_count().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.chaos_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Vertices:");
+            changed |= ui
+                .add(Slider::new(
+                    &mut state.vertices,
+                    MIN_VERTICES..=MAX_VERTICES,
+                ))
+                .changed();
+            ui.end_row();
+
+            ui.label("Jump Ratio:");
+            changed |= ui
+                .add(Slider::new(&mut state.ratio, 0.01..=1.0))
+                .on_hover_text("Part of the way to the target, that every jump covers.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Edge Midpoints:");
+            changed |= ui
+                .checkbox(&mut state.has_midpoints, "")
+                .on_hover_text("Midpoints of the edges are targets too.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Center:");
+            changed |= ui
+                .checkbox(&mut state.has_center, "")
+                .on_hover_text("Center of the polygon is a target too.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Restriction:");
+            egui::ComboBox::from_id_salt("ChaosRestriction")
+                .selected_text(format!("{}", state.restriction))
+                .show_ui(ui, |ui| {
+                    for restriction in Restriction::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.restriction,
+                                restriction,
+                                restriction.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Iterations:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.iterations)
+                        .speed(1000)
+                        .range(1..=MAX_ITERATIONS),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Seed: ");
+            ui.horizontal(|ui| {
+                let mut is_seeded = state.seed.is_some();
+                if ui
+                    .checkbox(&mut is_seeded, "")
+                    .on_hover_text("With a fixed seed, every game is identical.")
+                    .changed()
+                {
+                    state.seed = is_seeded.then_some(random::DEFAULT_SEED);
+                    changed = true;
+                }
+                if let Some(seed) = &mut state.seed {
+                    changed |= ui.add(DragValue::new(seed).speed(1)).changed();
+                }
+            });
+            ui.end_row();
+
+            ui.label("Size:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.size_cm)
+                        .speed(0.1)
+                        .range(0.1..=1000.0)
+                        .suffix(" cm"),
+                )
+                .on_hover_text("Radius of the circle around the polygon.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Dot Radius:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.radius_cm)
+                        .speed(0.001)
+                        .range(0.001..=1.0)
+                        .suffix(" cm"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Coloring:");
+            egui::ComboBox::from_id_salt("ChaosColoring")
+                .selected_text(format!("{}", state.coloring))
+                .show_ui(ui, |ui| {
+                    for coloring in Coloring::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.coloring,
+                                coloring,
+                                coloring.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            if state.coloring == Coloring::Single {
+                ui.label("Color:");
+                changed |= egui::color_picker::color_edit_button_srgba(
+                    ui,
+                    &mut state.color,
+                    egui::color_picker::Alpha::Opaque,
+                )
+                .changed();
+                ui.end_row();
+            }
+
+            ui.label("Show Targets:");
+            changed |= ui.checkbox(&mut state.is_targets_shown, "").changed();
+            ui.end_row();
+        });
+
+        if state.restriction == Restriction::Custom {
+            ui.add_space(5.0);
+
+            ui.label("Forbidden Offsets:").on_hover_text(
+                "Targets, that can't be picked after the previous one,\n\
+                 counted counterclockwise around the polygon from it.\n\
+                 0 is the previous target itself.",
+            );
+            ui.horizontal_wrapped(|ui| {
+                for offset in 0..state.ring_size() as u32 {
+                    let mut is_forbidden = state.custom_offsets.contains(&offset);
+                    if ui.checkbox(&mut is_forbidden, offset.to_string()).changed() {
+                        state.custom_offsets.retain(|value| *value != offset);
+                        if is_forbidden {
+                            state.custom_offsets.push(offset);
+                            state.custom_offsets.sort();
+                        }
+                        changed = true;
+                    }
+                }
+            });
+        }
+
+        if state.coloring == Coloring::Target {
+            ui.add_space(5.0);
+            ui.collapsing("Gradient", |ui| {
+                changed |= state.gradient.show_editor(ui, "Chaos");
+            });
+        }
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.chaos_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.chaos_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.chaos_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.chaos_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.chaos_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.chaos_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.chaos_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Vertices\": 6,
+                                \"Jump Ratio\": 0.5,
+                                \"Restriction\": \"Custom Offsets\",
+                                \"Forbidden Offsets\": [0, 2, 4],
+                                \"Iterations\": 150000
+                            }
+
+                            On every step the point jumps towards a random target,
+                            covering the part of the way, given by the jump ratio.
+
+                            Targets: the vertices of the regular polygon, and,
+                            if \"Midpoints\" or \"Center\" are true, the midpoints
+                            of its edges or its center.
+                            Restriction: \"None\", \"No Repeat\", \"Not Neighbor\" or
+                            \"Custom Offsets\". Offsets are counted counterclockwise
+                            from the previous target around the polygon, through
+                            the midpoints, if they are targets.
+
+                            Other fields: \"Seed\", \"Size\" (cm), \"Radius\" (cm),
+                            \"Coloring\" (\"Single Color\" or \"By Target\"),
+                            \"Color\", \"Gradient\", \"Show Targets\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut ChaosState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
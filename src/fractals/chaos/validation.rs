@@ -0,0 +1,205 @@
+use crate::fractals::chaos::model;
+use crate::fractals::chaos::model::Restriction;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub fn vertices_range(vertices: u32) -> Result<(), ValidationError> {
+    if !(model::MIN_VERTICES..=model::MAX_VERTICES).contains(&vertices) {
+        return Err(ValidationError::BadVerticesCount);
+    }
+
+    Ok(())
+}
+
+pub fn ratio_range(ratio: f32) -> Result<(), ValidationError> {
+    if !(ratio > 0.0 && ratio <= 1.0) {
+        return Err(ValidationError::BadRatio);
+    }
+
+    Ok(())
+}
+
+pub fn iterations_range(iterations: u32) -> Result<(), ValidationError> {
+    if !(1..=model::MAX_ITERATIONS).contains(&iterations) {
+        return Err(ValidationError::BadIterationsCount);
+    }
+
+    Ok(())
+}
+
+// Offsets are counted around the boundary targets, and at least one has to stay allowed
+pub fn offsets_are_valid(
+    restriction: Restriction, custom: &[u32], ring: usize,
+) -> Result<(), ValidationError> {
+    if restriction == Restriction::Custom {
+        if let Some(offset) = custom.iter().find(|offset| **offset as usize >= ring) {
+            return Err(ValidationError::BadOffset(format!(
+                "Offset {}, there are {} targets around the polygon.",
+                offset, ring
+            )));
+        }
+    }
+
+    let mut forbidden = restriction.forbidden_offsets(ring, custom);
+    forbidden.sort();
+    forbidden.dedup();
+    if forbidden.len() >= ring {
+        return Err(ValidationError::AllOffsetsForbidden);
+    }
+
+    Ok(())
+}
+
+pub fn size_range(size_cm: f32) -> Result<(), ValidationError> {
+    if !(size_cm > 0.0 && size_cm.is_finite()) {
+        return Err(ValidationError::BadSize);
+    }
+
+    Ok(())
+}
+
+pub fn radius_range(radius_cm: f32) -> Result<(), ValidationError> {
+    if !(radius_cm > 0.0 && radius_cm.is_finite()) {
+        return Err(ValidationError::BadRadius);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The number of vertices has to be in range 3..=16.")]
+    BadVerticesCount,
+
+    #[error("Jump ratio has to be in range (0, 1].")]
+    BadRatio,
+
+    #[error("The number of iterations has to be in range 1..=2000000.")]
+    BadIterationsCount,
+
+    #[error(
+        "Forbidden offset has to be less than the number of targets around the polygon."
+    )]
+    BadOffset(String),
+
+    #[error("At least one offset from the previous target has to be allowed.")]
+    AllOffsetsForbidden,
+
+    #[error("Size of the polygon has to be greater than 0.")]
+    BadSize,
+
+    #[error("Radius of the dots has to be greater than 0.")]
+    BadRadius,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadOffset(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::chaos::examples::Example;
+    use crate::fractals::chaos::serialization;
+    use crate::fractals::chaos::state::ChaosState;
+
+    #[test]
+    fn offset_beyond_polygon_is_reported() {
+        let mut state = ChaosState::default();
+        state.vertices = 4;
+        state.restriction = Restriction::Custom;
+        state.custom_offsets = vec![1, 4];
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Offset 4, there are 4 targets around the polygon.".to_string())
+        );
+    }
+
+    #[test]
+    fn midpoints_extend_offsets() {
+        assert!(offsets_are_valid(
+            Restriction::Custom,
+            &[4, 7],
+            model::ring_size(4, true)
+        )
+        .is_ok());
+        assert!(
+            offsets_are_valid(Restriction::Custom, &[4], model::ring_size(4, false))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn offsets_of_other_restriction_are_ignored() {
+        // Custom offsets are kept, while another restriction is picked
+        for restriction in [Restriction::None, Restriction::NoRepeat] {
+            assert!(offsets_are_valid(restriction, &[0, 1, 2, 10], 3).is_ok());
+        }
+    }
+
+    #[test]
+    fn repeated_offsets_count_once() {
+        assert!(offsets_are_valid(Restriction::Custom, &[0, 0, 1, 1], 3).is_ok());
+        assert!(matches!(
+            offsets_are_valid(Restriction::Custom, &[2, 0, 1, 0], 3),
+            Err(ValidationError::AllOffsetsForbidden)
+        ));
+    }
+
+    #[test]
+    fn triangle_allows_repeat_without_neighbors() {
+        // Both other vertices are neighbors, only the same one is left
+        assert!(offsets_are_valid(Restriction::NotNeighbor, &[], 3).is_ok());
+        assert!(offsets_are_valid(Restriction::Custom, &[1, 2], 3).is_ok());
+    }
+
+    #[test]
+    fn ratio_of_one_is_valid() {
+        assert!(ratio_range(1.0).is_ok());
+        for ratio in [0.0, 1.01, f32::NAN] {
+            assert!(matches!(ratio_range(ratio), Err(ValidationError::BadRatio)));
+        }
+    }
+
+    #[test]
+    fn vertices_bounds_are_inclusive() {
+        assert!(vertices_range(model::MIN_VERTICES).is_ok());
+        assert!(vertices_range(model::MAX_VERTICES).is_ok());
+        assert!(vertices_range(model::MIN_VERTICES - 1).is_err());
+        assert!(vertices_range(model::MAX_VERTICES + 1).is_err());
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = ChaosState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static CURVE_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/curve/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Cesàro Fractal")]
+    CesaroFractal,
+
+    #[strum(serialize = "Heighway Dragon")]
+    HeighwayDragon,
+
+    #[strum(serialize = "Koch Curve")]
+    KochCurve,
+
+    #[strum(serialize = "Koch Snowflake")]
+    KochSnowflake,
+
+    #[strum(serialize = "Lévy C Curve")]
+    LevyCCurve,
+
+    #[strum(serialize = "Minkowski Sausage")]
+    MinkowskiSausage,
+
+    #[strum(serialize = "Quadratic Koch Curve")]
+    QuadraticKochCurve,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::CesaroFractal => PathBuf::from(r"Cesaro-Fractal.json"),
+            Example::HeighwayDragon => PathBuf::from(r"Heighway-Dragon.json"),
+            Example::KochCurve => PathBuf::from(r"Koch-Curve.json"),
+            Example::KochSnowflake => PathBuf::from(r"Koch-Snowflake.json"),
+            Example::LevyCCurve => PathBuf::from(r"Levy-C-Curve.json"),
+            Example::MinkowskiSausage => PathBuf::from(r"Minkowski-Sausage.json"),
+            Example::QuadraticKochCurve => PathBuf::from(r"Quadratic-Koch-Curve.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = CURVE_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::CesaroFractal,
+            Example::HeighwayDragon,
+            Example::KochCurve,
+            Example::KochSnowflake,
+            Example::LevyCCurve,
+            Example::MinkowskiSausage,
+            Example::QuadraticKochCurve,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
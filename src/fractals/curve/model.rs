@@ -0,0 +1,261 @@
+use crate::geometry::line2d::Line2D;
+use crate::geometry::point2d::Point2D;
+use egui::{Color32, Stroke};
+
+pub const DEFAULT_DEPTH: u32 = 4;
+pub const DEFAULT_STROKE_WIDTH: f32 = 1.0;
+pub const DEFAULT_COLOR: Color32 = Color32::BLACK;
+pub const MAX_LINES: usize = 1_000_000;
+
+// Koch curve over the 20 cm segment
+pub fn default_initiator() -> Vec<[f32; 2]> {
+    vec![[-10.0, 0.0], [10.0, 0.0]]
+}
+
+pub fn default_generator() -> Generator {
+    let height = 3f32.sqrt() / 6.0;
+    Generator::new(vec![
+        [0.0, 0.0],
+        [1.0 / 3.0, 0.0],
+        [0.5, height],
+        [2.0 / 3.0, 0.0],
+        [1.0, 0.0],
+    ])
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Segment {
+    // Generator is mirrored over the segment
+    pub is_flipped: bool,
+    // Generator goes from the end of the segment to its start
+    pub is_reversed: bool,
+}
+
+// Polyline from (0, 0) to (1, 0), that replaces every segment of the initiator
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generator {
+    pub points: Vec<[f32; 2]>,
+    // One per every pair of consecutive points
+    pub segments: Vec<Segment>,
+}
+
+impl Generator {
+    pub fn new(points: Vec<[f32; 2]>) -> Self {
+        let segments = vec![Segment::default(); points.len().saturating_sub(1)];
+        Self { points, segments }
+    }
+
+    pub fn with_segments(mut self, segments: Vec<Segment>) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    // Points drawn on the canvas are moved, rotated and scaled,
+    // so that the first one goes to (0, 0) and the last one goes to (1, 0)
+    pub fn from_drawing(points: &[[f32; 2]]) -> Option<Self> {
+        let (first, last) = (points.first()?, points.last()?);
+        let (dx, dy) = (last[0] - first[0], last[1] - first[1]);
+        let length_squared = dx * dx + dy * dy;
+        if points.len() < 2 || length_squared <= f32::EPSILON {
+            return None;
+        }
+
+        let normalized = points
+            .iter()
+            .map(|point| {
+                let (x, y) = (point[0] - first[0], point[1] - first[1]);
+                [
+                    (x * dx + y * dy) / length_squared,
+                    (y * dx - x * dy) / length_squared,
+                ]
+            })
+            .collect();
+
+        Some(Self::new(normalized))
+    }
+
+    pub fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(1)
+    }
+}
+
+pub struct Model {
+    initiator: Vec<[f32; 2]>,
+    generator: Generator,
+    depth: u32,
+
+    stroke: Stroke,
+}
+
+impl Model {
+    pub fn lines(&self) -> Vec<Line2D> {
+        let mut lines: Vec<Line2D> = Vec::new();
+
+        for pair in self.initiator.windows(2) {
+            self.replace(pair[0], pair[1], false, self.depth, &mut lines);
+        }
+
+        lines
+    }
+
+    fn replace(
+        &self, start: [f32; 2], end: [f32; 2], is_flipped: bool, depth: u32,
+        lines: &mut Vec<Line2D>,
+    ) {
+        if depth == 0 {
+            lines.push(Line2D::new(
+                Point2D::new(start[0], start[1]),
+                Point2D::new(end[0], end[1]),
+                self.stroke,
+            ));
+            return;
+        }
+
+        // Similarity, that maps (0, 0) to start and (1, 0) to end
+        let (dx, dy) = (end[0] - start[0], end[1] - start[1]);
+        let sign = if is_flipped { -1.0 } else { 1.0 };
+        let transform = |[x, y]: [f32; 2]| {
+            let y = y * sign;
+            [start[0] + x * dx - y * dy, start[1] + x * dy + y * dx]
+        };
+
+        for (pair, segment) in self
+            .generator
+            .points
+            .windows(2)
+            .zip(&self.generator.segments)
+        {
+            let (from, to) = (transform(pair[0]), transform(pair[1]));
+            let (from, to) = match segment.is_reversed {
+                true => (to, from),
+                false => (from, to),
+            };
+            self.replace(from, to, is_flipped ^ segment.is_flipped, depth - 1, lines);
+        }
+    }
+}
+
+pub struct ModelBuilder {
+    initiator: Vec<[f32; 2]>,
+    generator: Generator,
+    depth: u32,
+
+    stroke: Stroke,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            initiator: default_initiator(),
+            generator: default_generator(),
+            depth: DEFAULT_DEPTH,
+
+            stroke: Stroke::new(DEFAULT_STROKE_WIDTH, DEFAULT_COLOR),
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_initiator(mut self, initiator: Vec<[f32; 2]>) -> Self {
+        self.initiator = initiator;
+        self
+    }
+
+    pub fn with_generator(mut self, generator: Generator) -> Self {
+        self.generator = generator;
+        self
+    }
+
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn with_stroke(mut self, width: f32, color: Color32) -> Self {
+        self.stroke = Stroke::new(width, color);
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            initiator: self.initiator,
+            generator: self.generator,
+            depth: self.depth,
+
+            stroke: self.stroke,
+        }
+    }
+}
+
+// Number of lines in the curve, None on overflow
+pub fn line_count(
+    initiator: &[[f32; 2]], generator: &Generator, depth: u32,
+) -> Option<usize> {
+    initiator
+        .len()
+        .saturating_sub(1)
+        .checked_mul(generator.segment_count().checked_pow(depth)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn koch_curve_keeps_endpoints() {
+        let lines = ModelBuilder::default().with_depth(2).build().lines();
+
+        let first = lines
+            .first()
+            .map(|line| line.start.clone())
+            .unwrap_or_default();
+        let last = lines
+            .last()
+            .map(|line| line.end.clone())
+            .unwrap_or_default();
+        assert_eq!(lines.len(), 16);
+        assert!((first.x + 10.0).abs() < 1e-4 && first.y.abs() < 1e-4);
+        assert!((last.x - 10.0).abs() < 1e-4 && last.y.abs() < 1e-4);
+    }
+
+    #[test]
+    fn flipped_segment_is_mirrored() {
+        let generator = Generator::new(vec![[0.0, 0.0], [0.5, 0.5], [1.0, 0.0]])
+            .with_segments(vec![
+                Segment {
+                    is_flipped: true,
+                    is_reversed: false,
+                },
+                Segment::default(),
+            ]);
+
+        let lines = ModelBuilder::default()
+            .with_initiator(vec![[0.0, 0.0], [2.0, 0.0]])
+            .with_generator(generator)
+            .with_depth(2)
+            .build()
+            .lines();
+
+        // First segment goes from (0, 0) to (1, 1), its bump is on the right side
+        let bump = lines[0].end.clone();
+        assert!((bump.x - 1.0).abs() < 1e-4 && bump.y.abs() < 1e-4);
+    }
+
+    #[test]
+    fn drawing_is_normalized() {
+        let generator = Generator::from_drawing(&[[1.0, 1.0], [1.0, 2.0], [1.0, 3.0]])
+            .unwrap_or_else(|| Generator::new(vec![]));
+
+        assert_eq!(generator.points, vec![[0.0, 0.0], [0.5, 0.0], [1.0, 0.0]]);
+        assert_eq!(generator.segment_count(), 2);
+    }
+
+    #[test]
+    fn closed_drawing_is_rejected() {
+        // Endpoints at the same place can't be moved to (0, 0) and (1, 0)
+        let closed = [[1.0, 1.0], [2.0, 2.0], [1.0, 1.0]];
+
+        assert!(Generator::from_drawing(&closed).is_none());
+        assert!(Generator::from_drawing(&[[1.0, 1.0]]).is_none());
+    }
+}
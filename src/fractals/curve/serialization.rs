@@ -0,0 +1,102 @@
+use crate::fractals::curve::model;
+use crate::fractals::curve::model::{Generator, Segment};
+use crate::fractals::curve::state::CurveState;
+use crate::fractals::curve::validation::ValidationError;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<CurveDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &CurveState) -> serde_json::Result<String> {
+    let dto = CurveDto {
+        initiator: state.initiator.clone(),
+        generator: state.generator.points.clone(),
+        segments: state
+            .generator
+            .segments
+            .iter()
+            .map(|segment| SegmentDto {
+                is_flipped: segment.is_flipped,
+                is_reversed: segment.is_reversed,
+            })
+            .collect(),
+        depth: state.depth,
+        stroke_width: state.stroke_width,
+        color: [state.color.r(), state.color.g(), state.color.b()],
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_depth() -> u32 {
+    model::DEFAULT_DEPTH
+}
+
+fn default_stroke_width() -> f32 {
+    model::DEFAULT_STROKE_WIDTH
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SegmentDto {
+    #[serde(rename = "Flipped", default)]
+    is_flipped: bool,
+
+    #[serde(rename = "Reversed", default)]
+    is_reversed: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CurveDto {
+    #[serde(rename = "Initiator")]
+    initiator: Vec<[f32; 2]>,
+
+    #[serde(rename = "Generator")]
+    generator: Vec<[f32; 2]>,
+
+    // Flags of the generator segments, all segments are plain if empty
+    #[serde(rename = "Segments", default, skip_serializing_if = "Vec::is_empty")]
+    segments: Vec<SegmentDto>,
+
+    #[serde(rename = "Depth", default = "default_depth")]
+    depth: u32,
+
+    #[serde(rename = "Stroke Width", default = "default_stroke_width")]
+    stroke_width: f32,
+
+    #[serde(rename = "Color", default)]
+    color: [u8; 3],
+}
+
+impl CurveDto {
+    pub fn load(self, state: &mut CurveState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        let mut generator = Generator::new(self.generator);
+        if !self.segments.is_empty() {
+            generator = generator.with_segments(
+                self.segments
+                    .into_iter()
+                    .map(|segment| Segment {
+                        is_flipped: segment.is_flipped,
+                        is_reversed: segment.is_reversed,
+                    })
+                    .collect(),
+            );
+        }
+
+        let [r, g, b] = self.color;
+        state.initiator = self.initiator;
+        state.generator = generator;
+        state.depth = self.depth;
+        state.stroke_width = self.stroke_width;
+        state.color = Color32::from_rgb(r, g, b);
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,209 @@
+use crate::fractals::curve::model;
+use crate::fractals::curve::model::{Generator, ModelBuilder};
+use crate::fractals::curve::validation;
+use crate::fractals::curve::validation::ValidationError;
+use crate::geometry::dot::DotBuilder;
+use crate::geometry::line2d::Line2D;
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::colors;
+use egui::{Color32, Response, Shape, Stroke};
+use strum_macros::Display;
+
+const PREVIEW_STROKE_WIDTH: f32 = 2.0;
+const PREVIEW_DOT_RADIUS_CM: f32 = 0.15;
+
+// Polyline, that is being drawn on the canvas with clicks
+#[derive(Copy, Clone, Debug, Display, PartialEq)]
+pub enum Drawing {
+    #[strum(serialize = "Initiator")]
+    Initiator,
+
+    #[strum(serialize = "Generator")]
+    Generator,
+}
+
+pub struct CurveState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub initiator: Vec<[f32; 2]>,
+    pub generator: Generator,
+    pub depth: u32,
+
+    pub stroke_width: f32,
+    pub color: Color32,
+
+    pub is_snapped: bool,
+    drawing: Option<Drawing>,
+    drawn_points: Vec<[f32; 2]>,
+
+    lines: Vec<Line2D>,
+}
+
+impl Default for CurveState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            initiator: model::default_initiator(),
+            generator: model::default_generator(),
+            depth: model::DEFAULT_DEPTH,
+
+            stroke_width: model::DEFAULT_STROKE_WIDTH,
+            color: model::DEFAULT_COLOR,
+
+            is_snapped: true,
+            drawing: None,
+            drawn_points: vec![],
+
+            lines: vec![],
+        }
+    }
+}
+
+impl CurveState {
+    pub fn shapes(&mut self, params: &CanvasParams, response: &Response) -> Vec<Shape> {
+        if self.drawing.is_some() {
+            self.handle_click(params, response);
+            return self.drawing_shapes(params);
+        }
+
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+            self.lines = ModelBuilder::default()
+                .with_initiator(self.initiator.clone())
+                .with_generator(self.generator.clone())
+                .with_depth(self.depth)
+                .with_stroke(self.stroke_width, self.color)
+                .build()
+                .lines();
+        }
+
+        self.lines
+            .iter()
+            .map(|line| line.to_screen(params).to_shape())
+            .collect()
+    }
+
+    // Every click on the canvas adds a point
+    fn handle_click(&mut self, params: &CanvasParams, response: &Response) {
+        if !response.clicked() {
+            return;
+        }
+        let Some(position) = response.interact_pointer_pos() else {
+            return;
+        };
+
+        let mut point = params.point_px_to_cm(Point2D::from_pos2(position));
+        if self.is_snapped {
+            let unit = params.unit_length;
+            point = Point2D::new(
+                (point.x / unit).round() * unit,
+                (point.y / unit).round() * unit,
+            );
+        }
+        self.drawn_points.push([point.x, point.y]);
+    }
+
+    fn drawing_shapes(&self, params: &CanvasParams) -> Vec<Shape> {
+        let points: Vec<Point2D> = self
+            .drawn_points
+            .iter()
+            .map(|[x, y]| Point2D::new(*x, *y))
+            .collect();
+
+        let stroke = Stroke::new(PREVIEW_STROKE_WIDTH, colors::BLACK);
+        let lines = points.windows(2).map(|pair| {
+            Line2D::new(pair[0].clone(), pair[1].clone(), stroke)
+                .to_screen(params)
+                .to_shape()
+        });
+        let dots = points.iter().enumerate().map(|(index, point)| {
+            // First point is green, the last one is red
+            let color = match index {
+                0 => colors::LIME,
+                _ if index + 1 == points.len() => colors::RED,
+                _ => colors::BLACK,
+            };
+            DotBuilder::default()
+                .with_center(point.clone())
+                .with_color(color)
+                .with_radius_centimeters(PREVIEW_DOT_RADIUS_CM)
+                .build()
+                .to_screen(params)
+                .to_shape()
+        });
+
+        lines.chain(dots).collect()
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::initiator_is_valid(&self.initiator)?;
+        validation::generator_is_valid(&self.generator)?;
+        validation::depth_range(self.depth)?;
+        validation::lines_count(&self.initiator, &self.generator, self.depth)?;
+        validation::stroke_width_range(self.stroke_width)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn drawing(&self) -> Option<Drawing> {
+        self.drawing
+    }
+
+    pub fn drawn_points_count(&self) -> usize {
+        self.drawn_points.len()
+    }
+
+    pub fn start_drawing(&mut self, drawing: Drawing) {
+        self.drawing = Some(drawing);
+        self.drawn_points = vec![];
+    }
+
+    pub fn undo_point(&mut self) {
+        self.drawn_points.pop();
+    }
+
+    pub fn cancel_drawing(&mut self) {
+        self.drawing = None;
+        self.drawn_points = vec![];
+    }
+
+    // Replaces the initiator or the generator with the drawn polyline
+    pub fn finish_drawing(&mut self) -> Result<(), ValidationError> {
+        let points = std::mem::take(&mut self.drawn_points);
+
+        match self.drawing.take() {
+            Some(Drawing::Initiator) => {
+                validation::initiator_is_valid(&points)?;
+                self.initiator = points;
+            },
+            Some(Drawing::Generator) => {
+                let generator = Generator::from_drawing(&points)
+                    .ok_or(ValidationError::NotEnoughGeneratorPoints)?;
+                self.generator = generator;
+            },
+            None => {},
+        }
+
+        self.reset_initialization();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+use crate::context::Context;
+use crate::fractals::curve::examples::Example;
+use crate::fractals::curve::serialization;
+use crate::fractals::curve::state::{CurveState, Drawing};
+use crate::fractals::curve::validation::MAX_DEPTH;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct CurveSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for CurveSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl CurveSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if let Some(drawing) = context.curve_state.drawing() {
+                ui.label(
+                    RichText::new(format!(
+                        "Drawing {}...",
+                        drawing.to_string().to_lowercase()
+                    ))
+                    .color(colors::LIME),
+                );
+            } else if context.curve_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        if context.curve_state.drawing().is_some() {
+            self.show_drawing(ui, context);
+            return;
+        }
+
+        let state = &mut context.curve_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Depth:");
+            changed |= ui
+                .add(Slider::new(&mut state.depth, 0..=MAX_DEPTH))
+                .on_hover_text("How many times the segments are replaced.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Stroke Width:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.stroke_width)
+                        .speed(0.1)
+                        .range(0.1..=10.0),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Initiator", |ui| {
+            let mut removed: Option<usize> = None;
+            Grid::new("InitiatorGrid").num_columns(3).show(ui, |ui| {
+                let can_remove = state.initiator.len() > 2;
+                for (index, point) in state.initiator.iter_mut().enumerate() {
+                    ui.label(format!("{}.", index + 1));
+                    ui.horizontal(|ui| {
+                        changed |=
+                            ui.add(DragValue::new(&mut point[0]).speed(0.1)).changed();
+                        changed |=
+                            ui.add(DragValue::new(&mut point[1]).speed(0.1)).changed();
+                    });
+                    if ui.add_enabled(can_remove, Button::new("Remove")).clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(index) = removed {
+                state.initiator.remove(index);
+                changed = true;
+            }
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Add Point").clicked() {
+                    let last = state.initiator.last().copied().unwrap_or_default();
+                    state.initiator.push([last[0] + 1.0, last[1]]);
+                    changed = true;
+                }
+            });
+        });
+
+        ui.collapsing("Generator Segments", |ui| {
+            Grid::new("GeneratorGrid").num_columns(3).show(ui, |ui| {
+                for (index, segment) in state.generator.segments.iter_mut().enumerate() {
+                    ui.label(format!("{}.", index + 1));
+                    changed |= ui
+                        .checkbox(&mut segment.is_flipped, "Flip")
+                        .on_hover_text("Mirrors the generator over this segment.")
+                        .changed();
+                    changed |= ui
+                        .checkbox(&mut segment.is_reversed, "Reverse")
+                        .on_hover_text(
+                            "Places the generator from the end of this segment.",
+                        )
+                        .changed();
+                    ui.end_row();
+                }
+            });
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        for drawing in [Drawing::Initiator, Drawing::Generator] {
+            ui.vertical_centered_justified(|ui| {
+                if ui
+                    .button(format!("Draw {} on Canvas", drawing))
+                    .on_hover_text("Every click on the canvas adds a point.")
+                    .clicked()
+                {
+                    context.curve_state.start_drawing(drawing);
+                }
+            });
+            ui.add_space(2.0);
+        }
+
+        ui.add_space(8.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.curve_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.curve_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.curve_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.curve_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.curve_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.curve_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.curve_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Initiator\": [[-10.0, 0.0], [10.0, 0.0]],
+                                \"Generator\": [[0.0, 0.0], [0.5, 0.5], [1.0, 0.0]],
+                                \"Segments\": [
+                                    { \"Flipped\": false, \"Reversed\": false },
+                                    { \"Flipped\": false, \"Reversed\": true }
+                                ],
+                                \"Depth\": 12
+                            }
+
+                            Initiator: polyline in centimeters.
+                            Generator: polyline from [0, 0] to [1, 0], every segment
+                            of the initiator is replaced by its scaled copy.
+                            Segments: optional flags of the generator segments.
+
+                            Other fields: \"Stroke Width\", \"Color\" ([r, g, b]).
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn show_drawing(&mut self, ui: &mut Ui, context: &mut Context) {
+        let state = &mut context.curve_state;
+
+        Grid::new("DrawingGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Points:");
+            ui.label(state.drawn_points_count().to_string());
+            ui.end_row();
+
+            ui.label("Snap to Grid:");
+            ui.checkbox(&mut state.is_snapped, "")
+                .on_hover_text("Points are rounded to the grid units.");
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+        ui.label("Click on the canvas to add points.");
+        if state.drawing() == Some(Drawing::Generator) {
+            ui.label("The first and the last points set the direction and the scale.");
+        }
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Finish").clicked() {
+                if let Err(err) = state.finish_drawing() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(state.drawn_points_count() > 0, Button::new("Undo Point"))
+                .clicked()
+            {
+                state.undo_point();
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Cancel").clicked() {
+                state.cancel_drawing();
+            }
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut CurveState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use crate::fractals::curve::model;
+use crate::fractals::curve::model::Generator;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub const MAX_DEPTH: u32 = 12;
+const ENDPOINT_TOLERANCE: f32 = 1e-3;
+
+pub fn initiator_is_valid(initiator: &[[f32; 2]]) -> Result<(), ValidationError> {
+    if initiator.len() < 2 {
+        return Err(ValidationError::NotEnoughInitiatorPoints);
+    }
+    if initiator.iter().flatten().any(|value| !value.is_finite()) {
+        return Err(ValidationError::BadPointValue);
+    }
+
+    Ok(())
+}
+
+pub fn generator_is_valid(generator: &Generator) -> Result<(), ValidationError> {
+    let points = &generator.points;
+    if points.len() < 2 {
+        return Err(ValidationError::NotEnoughGeneratorPoints);
+    }
+    if points.iter().flatten().any(|value| !value.is_finite()) {
+        return Err(ValidationError::BadPointValue);
+    }
+
+    let is_near = |point: &[f32; 2], x: f32| {
+        (point[0] - x).abs() < ENDPOINT_TOLERANCE && point[1].abs() < ENDPOINT_TOLERANCE
+    };
+    let is_first_valid = points.first().is_some_and(|point| is_near(point, 0.0));
+    let is_last_valid = points.last().is_some_and(|point| is_near(point, 1.0));
+    if !is_first_valid || !is_last_valid {
+        return Err(ValidationError::BadGeneratorEndpoints);
+    }
+
+    if generator.segments.len() != generator.segment_count() {
+        return Err(ValidationError::SegmentsCountMismatch(format!(
+            "Segments: {}\nExpected: {}",
+            generator.segments.len(),
+            generator.segment_count()
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn depth_range(depth: u32) -> Result<(), ValidationError> {
+    if depth > MAX_DEPTH {
+        return Err(ValidationError::BadDepthValue);
+    }
+
+    Ok(())
+}
+
+pub fn lines_count(
+    initiator: &[[f32; 2]], generator: &Generator, depth: u32,
+) -> Result<(), ValidationError> {
+    match model::line_count(initiator, generator, depth) {
+        Some(count) if count <= model::MAX_LINES => Ok(()),
+        count => Err(ValidationError::TooManyLines(match count {
+            Some(count) => format!("Lines: {}\nMaximum: {}", count, model::MAX_LINES),
+            None => format!("Maximum: {}", model::MAX_LINES),
+        })),
+    }
+}
+
+pub fn stroke_width_range(width: f32) -> Result<(), ValidationError> {
+    if !(width > 0.0 && width.is_finite()) {
+        return Err(ValidationError::BadStrokeWidth);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The initiator needs at least 2 points.")]
+    NotEnoughInitiatorPoints,
+
+    #[error("The generator needs at least 2 points.")]
+    NotEnoughGeneratorPoints,
+
+    #[error("The generator has to start at (0, 0) and end at (1, 0).")]
+    BadGeneratorEndpoints,
+
+    #[error("The number of segment flags doesn't match the generator.")]
+    SegmentsCountMismatch(String),
+
+    #[error("Coordinates of the points have to be finite numbers.")]
+    BadPointValue,
+
+    #[error("The depth has to be in range 0..12.")]
+    BadDepthValue,
+
+    #[error("The curve has too many lines, decrease the depth.")]
+    TooManyLines(String),
+
+    #[error("The stroke width has to be greater than 0.")]
+    BadStrokeWidth,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::SegmentsCountMismatch(value) => Some(value.clone()),
+            Self::TooManyLines(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::curve::examples::Example;
+    use crate::fractals::curve::serialization;
+    use crate::fractals::curve::state::CurveState;
+
+    #[test]
+    fn endpoints_within_tolerance() {
+        let generator = Generator::new(vec![[0.0005, 0.0], [0.5, 0.3], [1.0, -0.0005]]);
+        assert!(generator_is_valid(&generator).is_ok());
+
+        let generator = Generator::new(vec![[0.0, 0.0], [0.5, 0.3], [1.002, 0.0]]);
+        assert!(matches!(
+            generator_is_valid(&generator),
+            Err(ValidationError::BadGeneratorEndpoints)
+        ));
+    }
+
+    #[test]
+    fn segments_count_is_reported() {
+        let generator = Generator::new(vec![[0.0, 0.0], [0.5, 0.3], [1.0, 0.0]])
+            .with_segments(vec![Default::default()]);
+
+        let result = generator_is_valid(&generator);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Segments: 1\nExpected: 2".to_string())
+        );
+    }
+
+    #[test]
+    fn straight_generator_reaches_max_depth() {
+        // Every level replaces a line with a single line, so the count doesn't grow
+        let initiator = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]];
+        let generator = Generator::new(vec![[0.0, 0.0], [1.0, 0.0]]);
+
+        assert!(lines_count(&initiator, &generator, MAX_DEPTH).is_ok());
+    }
+
+    #[test]
+    fn overflowing_lines_count() {
+        let initiator = [[0.0, 0.0], [1.0, 0.0]];
+        let points = (0..=1000)
+            .map(|index| [index as f32 / 1000.0, 0.0])
+            .collect();
+        let generator = Generator::new(points);
+
+        let result = lines_count(&initiator, &generator, MAX_DEPTH);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some(format!("Maximum: {}", model::MAX_LINES))
+        );
+    }
+
+    #[test]
+    fn too_many_lines() {
+        let mut state = CurveState::default();
+        state.depth = MAX_DEPTH;
+
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::TooManyLines(_))));
+    }
+
+    #[test]
+    fn single_point_initiator() {
+        assert!(matches!(
+            initiator_is_valid(&[[0.0, 0.0]]),
+            Err(ValidationError::NotEnoughInitiatorPoints)
+        ));
+        assert!(matches!(
+            initiator_is_valid(&[[0.0, 0.0], [f32::NAN, 1.0]]),
+            Err(ValidationError::BadPointValue)
+        ));
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = CurveState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
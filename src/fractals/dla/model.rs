@@ -0,0 +1,394 @@
+use crate::math::random;
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_PARTICLES: u32 = 5000;
+pub const DEFAULT_PARTICLES_PER_FRAME: u32 = 25;
+pub const DEFAULT_SEED_SIZE: u32 = 40;
+pub const DEFAULT_STICKING_PROBABILITY: f64 = 1.0;
+pub const DEFAULT_SPACING: f32 = 0.1;
+pub const MAX_PARTICLES: u32 = 100_000;
+
+// Walkers start a bit outside of the cluster and are relaunched, if they go too far
+const LAUNCH_GAP: f64 = 5.0;
+const KILL_RADIUS_FACTOR: f64 = 3.0;
+// Far from the cluster walkers make long jumps instead of single steps
+const JUMP_GAP: f64 = 4.0;
+const GRID_MARGIN: i32 = 20;
+
+const NEIGHBORS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum SeedGeometry {
+    #[default]
+    #[strum(serialize = "Point")]
+    #[serde(rename = "Point")]
+    Point,
+
+    // Horizontal segment through the origin
+    #[strum(serialize = "Line")]
+    #[serde(rename = "Line")]
+    Line,
+
+    // Ring around the origin
+    #[strum(serialize = "Circle")]
+    #[serde(rename = "Circle")]
+    Circle,
+}
+
+impl SeedGeometry {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            SeedGeometry::Point,
+            SeedGeometry::Line,
+            SeedGeometry::Circle,
+        ]
+        .into_iter()
+    }
+
+    // Cells of the seed, size is the length of the line or the radius of the circle
+    fn cells(&self, size: u32) -> Vec<(i32, i32)> {
+        let size = size as i32;
+        match self {
+            SeedGeometry::Point => vec![(0, 0)],
+            SeedGeometry::Line => (-size / 2..=size / 2).map(|x| (x, 0)).collect(),
+            SeedGeometry::Circle => {
+                let mut cells: Vec<(i32, i32)> = Vec::new();
+                // Enough angles to make the ring connected
+                let steps = (size.max(1) * 8) as usize;
+                for step in 0..steps {
+                    let angle = std::f64::consts::TAU * step as f64 / steps as f64;
+                    let cell = (
+                        (size as f64 * angle.cos()).round() as i32,
+                        (size as f64 * angle.sin()).round() as i32,
+                    );
+                    if !cells.contains(&cell) {
+                        cells.push(cell);
+                    }
+                }
+                cells
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub particles: usize,
+    // In lattice cells
+    pub radius_of_gyration: f64,
+    // Slope of log N(r) over log r, None for too small clusters
+    pub dimension: Option<f64>,
+}
+
+// On-lattice aggregation of random walkers
+pub struct Cluster {
+    grid_radius: i32,
+    occupied: Vec<bool>,
+
+    // Seed cells go first, then the particles in order of arrival
+    cells: Vec<(i32, i32)>,
+    seed_count: usize,
+    max_radius: f64,
+
+    sticking_probability: f64,
+    rng: StdRng,
+}
+
+impl Cluster {
+    pub fn new(
+        geometry: SeedGeometry, seed_size: u32, sticking_probability: f64,
+        particles: u32, seed: Option<u64>,
+    ) -> Self {
+        let seed_cells = geometry.cells(seed_size);
+        let seed_radius = seed_cells
+            .iter()
+            .map(|(x, y)| f64::hypot(*x as f64, *y as f64))
+            .fold(0.0, f64::max);
+
+        // DLA clusters grow as N^(1/1.7), doubled to leave room for branches
+        let growth = 2.0 * (particles as f64).powf(0.6);
+        let grid_radius = (seed_radius + growth) as i32 + GRID_MARGIN * 2;
+        let side = (grid_radius * 2 + 1) as usize;
+
+        let mut cluster = Self {
+            grid_radius,
+            occupied: vec![false; side * side],
+
+            cells: Vec::with_capacity(seed_cells.len() + particles as usize),
+            seed_count: seed_cells.len(),
+            max_radius: seed_radius,
+
+            sticking_probability,
+            rng: random::rng(seed),
+        };
+        for cell in seed_cells {
+            cluster.occupy(cell);
+        }
+        cluster
+    }
+
+    pub fn cells(&self) -> &[(i32, i32)] {
+        &self.cells
+    }
+
+    pub fn seed_count(&self) -> usize {
+        self.seed_count
+    }
+
+    pub fn particle_count(&self) -> usize {
+        self.cells.len() - self.seed_count
+    }
+
+    // Cluster can't grow further, if it almost touches the border of the grid
+    pub fn is_full(&self) -> bool {
+        self.launch_radius() >= (self.grid_radius - GRID_MARGIN) as f64
+    }
+
+    // Returns the number of particles that stuck
+    pub fn grow(&mut self, particles: u32) -> u32 {
+        let mut stuck = 0;
+        while stuck < particles && !self.is_full() {
+            let cell = self.walk();
+            self.occupy(cell);
+            stuck += 1;
+        }
+        stuck
+    }
+
+    pub fn statistics(&self) -> Statistics {
+        let particles = &self.cells[self.seed_count..];
+
+        let radius_of_gyration = match particles.is_empty() {
+            true => 0.0,
+            false => {
+                let count = particles.len() as f64;
+                let (sum_x, sum_y) = particles.iter().fold((0.0, 0.0), |sum, (x, y)| {
+                    (sum.0 + *x as f64, sum.1 + *y as f64)
+                });
+                let (center_x, center_y) = (sum_x / count, sum_y / count);
+                let squares: f64 = particles
+                    .iter()
+                    .map(|(x, y)| {
+                        (*x as f64 - center_x).powi(2) + (*y as f64 - center_y).powi(2)
+                    })
+                    .sum();
+                (squares / count).sqrt()
+            },
+        };
+
+        Statistics {
+            particles: particles.len(),
+            radius_of_gyration,
+            dimension: self.mass_radius_dimension(),
+        }
+    }
+
+    // Least squares fit of log N(r) = D * log r + C,
+    // radii go from a few cells to the half of the cluster, where it's not screened yet
+    fn mass_radius_dimension(&self) -> Option<f64> {
+        const RADII: usize = 12;
+        const MIN_RADIUS: f64 = 3.0;
+
+        let max_radius = self.max_radius / 2.0;
+        if max_radius <= MIN_RADIUS * 2.0 {
+            return None;
+        }
+
+        let ratio = (max_radius / MIN_RADIUS).powf(1.0 / (RADII - 1) as f64);
+        let radii: Vec<f64> = (0..RADII)
+            .map(|index| MIN_RADIUS * ratio.powi(index as i32))
+            .collect();
+
+        let mut counts = [0usize; RADII];
+        for (x, y) in &self.cells[self.seed_count..] {
+            let distance = f64::hypot(*x as f64, *y as f64);
+            if let Some(index) = radii.iter().position(|radius| distance <= *radius) {
+                counts[index] += 1;
+            }
+        }
+
+        let mut points: Vec<(f64, f64)> = Vec::with_capacity(RADII);
+        let mut total = 0;
+        for (radius, count) in radii.iter().zip(counts) {
+            total += count;
+            if total > 0 {
+                points.push((radius.ln(), (total as f64).ln()));
+            }
+        }
+        if points.len() < 3 {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let (sum_x, sum_y) = points
+            .iter()
+            .fold((0.0, 0.0), |sum, (x, y)| (sum.0 + x, sum.1 + y));
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+        let covariance: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+        match variance > 0.0 {
+            true => Some(covariance / variance),
+            false => None,
+        }
+    }
+
+    // Walks a particle from the launch circle until it sticks
+    fn walk(&mut self) -> (i32, i32) {
+        let mut position = self.launch_position();
+
+        loop {
+            let distance = f64::hypot(position.0 as f64, position.1 as f64);
+
+            if distance > self.kill_radius() {
+                position = self.launch_position();
+                continue;
+            }
+
+            let gap = distance - self.max_radius - JUMP_GAP;
+            if gap > 1.0 {
+                let angle = self.rng.gen_range(0.0..std::f64::consts::TAU);
+                position = (
+                    position.0 + (gap * angle.cos()).round() as i32,
+                    position.1 + (gap * angle.sin()).round() as i32,
+                );
+                continue;
+            }
+
+            let touches_cluster = NEIGHBORS
+                .iter()
+                .any(|(dx, dy)| self.is_occupied((position.0 + dx, position.1 + dy)));
+            if touches_cluster && self.rng.gen_bool(self.sticking_probability) {
+                return position;
+            }
+
+            let (dx, dy) = NEIGHBORS[self.rng.gen_range(0..NEIGHBORS.len())];
+            let next = (position.0 + dx, position.1 + dy);
+            if !self.is_occupied(next) {
+                position = next;
+            }
+        }
+    }
+
+    fn launch_position(&mut self) -> (i32, i32) {
+        let angle = self.rng.gen_range(0.0..std::f64::consts::TAU);
+        let radius = self.launch_radius();
+        (
+            (radius * angle.cos()).round() as i32,
+            (radius * angle.sin()).round() as i32,
+        )
+    }
+
+    fn launch_radius(&self) -> f64 {
+        self.max_radius + LAUNCH_GAP
+    }
+
+    fn kill_radius(&self) -> f64 {
+        (self.launch_radius() * KILL_RADIUS_FACTOR).min((self.grid_radius - 2) as f64)
+    }
+
+    fn index(&self, (x, y): (i32, i32)) -> Option<usize> {
+        if x.abs() > self.grid_radius || y.abs() > self.grid_radius {
+            return None;
+        }
+        let side = self.grid_radius * 2 + 1;
+        Some(((y + self.grid_radius) * side + x + self.grid_radius) as usize)
+    }
+
+    fn is_occupied(&self, cell: (i32, i32)) -> bool {
+        self.index(cell).is_some_and(|index| self.occupied[index])
+    }
+
+    fn occupy(&mut self, cell: (i32, i32)) {
+        if let Some(index) = self.index(cell) {
+            self.occupied[index] = true;
+            self.cells.push(cell);
+            self.max_radius = self
+                .max_radius
+                .max(f64::hypot(cell.0 as f64, cell.1 as f64));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_cluster_is_fractal() {
+        let mut cluster = Cluster::new(SeedGeometry::Point, 1, 1.0, 3000, Some(1));
+
+        let stuck = cluster.grow(3000);
+        let statistics = cluster.statistics();
+
+        assert_eq!(stuck, 3000);
+        assert_eq!(statistics.particles, 3000);
+        assert!(statistics.radius_of_gyration > 0.0);
+        // Known value is about 1.71, small clusters are rough
+        let dimension = statistics.dimension.unwrap_or_default();
+        assert!(
+            dimension > 1.4 && dimension < 2.0,
+            "dimension: {}",
+            dimension
+        );
+    }
+
+    #[test]
+    fn line_seed_cells() {
+        let cluster = Cluster::new(SeedGeometry::Line, 10, 1.0, 10, Some(1));
+
+        assert_eq!(cluster.seed_count(), 11);
+        assert_eq!(cluster.particle_count(), 0);
+    }
+
+    #[test]
+    fn ring_seed_is_connected() {
+        for size in [2, 50] {
+            let cells = SeedGeometry::Circle.cells(size);
+
+            for (index, (x, y)) in cells.iter().enumerate() {
+                assert!(!cells[index + 1..].contains(&(*x, *y)));
+                assert!((f64::hypot(*x as f64, *y as f64) - size as f64).abs() <= 1.0);
+                // Diagonal neighbors are enough, walkers can't pass between them
+                let neighbors = cells
+                    .iter()
+                    .filter(|(nx, ny)| (nx - x).abs().max((ny - y).abs()) == 1)
+                    .count();
+                assert!(neighbors >= 2, "size: {}, cell: {:?}", size, (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn particles_stick_next_to_cluster() {
+        let mut cluster = Cluster::new(SeedGeometry::Line, 6, 0.3, 300, Some(2));
+        cluster.grow(300);
+
+        let cells = cluster.cells();
+        for (index, (x, y)) in cells.iter().enumerate().skip(cluster.seed_count()) {
+            let earlier = &cells[..index];
+            assert!(!earlier.contains(&(*x, *y)));
+            assert!(NEIGHBORS
+                .iter()
+                .any(|(dx, dy)| earlier.contains(&(x + dx, y + dy))));
+        }
+    }
+
+    #[test]
+    fn full_grid_stops_growth() {
+        // Grid is sized for 10 particles, so it fills up long before 100000
+        let mut cluster = Cluster::new(SeedGeometry::Point, 1, 1.0, 10, Some(1));
+
+        let stuck = cluster.grow(100_000);
+
+        assert!(cluster.is_full());
+        assert!((10..100_000).contains(&stuck));
+        assert_eq!(cluster.grow(1), 0);
+    }
+}
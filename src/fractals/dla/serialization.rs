@@ -0,0 +1,99 @@
+use crate::fractals::dla::model;
+use crate::fractals::dla::model::SeedGeometry;
+use crate::fractals::dla::state::DlaState;
+use crate::fractals::dla::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<DlaDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &DlaState) -> serde_json::Result<String> {
+    let dto = DlaDto {
+        seed_geometry: state.seed_geometry,
+        seed_size: state.seed_size,
+        sticking_probability: state.sticking_probability,
+        particles: state.particles,
+        particles_per_frame: state.particles_per_frame,
+        spacing_cm: state.spacing_cm,
+        gradient: (&state.gradient).into(),
+        seed: state.seed,
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_seed_size() -> u32 {
+    model::DEFAULT_SEED_SIZE
+}
+
+fn default_sticking_probability() -> f64 {
+    model::DEFAULT_STICKING_PROBABILITY
+}
+
+fn default_particles() -> u32 {
+    model::DEFAULT_PARTICLES
+}
+
+fn default_particles_per_frame() -> u32 {
+    model::DEFAULT_PARTICLES_PER_FRAME
+}
+
+fn default_spacing() -> f32 {
+    model::DEFAULT_SPACING
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DlaDto {
+    #[serde(rename = "Seed Geometry", default)]
+    seed_geometry: SeedGeometry,
+
+    #[serde(rename = "Seed Size", default = "default_seed_size")]
+    seed_size: u32,
+
+    #[serde(
+        rename = "Sticking Probability",
+        default = "default_sticking_probability"
+    )]
+    sticking_probability: f64,
+
+    #[serde(rename = "Particles", default = "default_particles")]
+    particles: u32,
+
+    #[serde(
+        rename = "Particles per Frame",
+        default = "default_particles_per_frame"
+    )]
+    particles_per_frame: u32,
+
+    #[serde(rename = "Spacing", default = "default_spacing")]
+    spacing_cm: f32,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+}
+
+impl DlaDto {
+    pub fn load(self, state: &mut DlaState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.seed_geometry = self.seed_geometry;
+        state.seed_size = self.seed_size;
+        state.sticking_probability = self.sticking_probability;
+        state.particles = self.particles;
+        state.particles_per_frame = self.particles_per_frame;
+        state.spacing_cm = self.spacing_cm;
+        state.gradient = self.gradient.into();
+        state.seed = self.seed;
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,180 @@
+use crate::fractals::dla::model::{Cluster, SeedGeometry, Statistics};
+use crate::fractals::dla::validation::ValidationError;
+use crate::fractals::dla::{model, validation};
+use crate::geometry::dot::{Dot, DotBuilder};
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::colors;
+use crate::ui::styles::gradient::Gradient;
+use egui::Shape;
+
+pub struct DlaState {
+    is_initialized: bool,
+    is_running: bool,
+
+    pub seed_geometry: SeedGeometry,
+    pub seed_size: u32,
+    pub sticking_probability: f64,
+    pub particles: u32,
+    pub particles_per_frame: u32,
+
+    pub spacing_cm: f32,
+    pub gradient: Gradient,
+    pub seed: Option<u64>,
+
+    cluster: Option<Cluster>,
+    // Settings change makes the cluster stale, it can't grow under the new ones
+    is_resumable: bool,
+    // Particles of the current cluster, the setting may be changed since its start
+    target_particles: u32,
+    dots: Vec<Dot>,
+    statistics: Option<Statistics>,
+}
+
+impl Default for DlaState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_running: false,
+
+            seed_geometry: SeedGeometry::default(),
+            seed_size: model::DEFAULT_SEED_SIZE,
+            sticking_probability: model::DEFAULT_STICKING_PROBABILITY,
+            particles: model::DEFAULT_PARTICLES,
+            particles_per_frame: model::DEFAULT_PARTICLES_PER_FRAME,
+
+            spacing_cm: model::DEFAULT_SPACING,
+            gradient: Gradient::default(),
+            seed: None,
+
+            cluster: None,
+            is_resumable: false,
+            target_particles: model::DEFAULT_PARTICLES,
+            dots: vec![],
+            statistics: None,
+        }
+    }
+}
+
+impl DlaState {
+    pub fn shapes(&mut self, ctx: &egui::Context, params: &CanvasParams) -> Vec<Shape> {
+        if self.is_running {
+            self.grow();
+            ctx.request_repaint();
+        }
+
+        self.dots
+            .iter()
+            .map(|dot| dot.to_screen(params).to_shape())
+            .collect()
+    }
+
+    // Live animation: a few particles stick on every frame
+    fn grow(&mut self) {
+        let Some(cluster) = &mut self.cluster else {
+            self.is_running = false;
+            return;
+        };
+
+        let target = self.target_particles as usize;
+        let remaining = target.saturating_sub(cluster.particle_count());
+        let batch = (self.particles_per_frame as usize).min(remaining) as u32;
+        let stuck = cluster.grow(batch);
+
+        let from = self.dots.len();
+        for (index, (x, y)) in cluster.cells().iter().enumerate().skip(from) {
+            // Seed is black, particles are colored by the time of their arrival
+            let color = match index.checked_sub(cluster.seed_count()) {
+                Some(arrival) => self.gradient.sample(arrival as f32 / target as f32),
+                None => colors::BLACK,
+            };
+
+            self.dots.push(
+                DotBuilder::default()
+                    .with_center(Point2D::new(
+                        *x as f32 * self.spacing_cm,
+                        *y as f32 * self.spacing_cm,
+                    ))
+                    .with_color(color)
+                    .with_radius_centimeters(self.spacing_cm / 2.0)
+                    .build(),
+            );
+        }
+
+        if stuck > 0 || self.statistics.is_none() {
+            self.statistics = Some(cluster.statistics());
+        }
+
+        if stuck < batch || cluster.particle_count() >= target {
+            self.is_running = false;
+        }
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::seed_size_range(self.seed_geometry, self.seed_size)?;
+        validation::sticking_probability_range(self.sticking_probability)?;
+        validation::particles_range(self.particles)?;
+        validation::particles_per_frame_range(self.particles_per_frame)?;
+        validation::spacing_range(self.spacing_cm)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+        self.is_running = false;
+        self.is_resumable = false;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running
+    }
+
+    pub fn is_resumable(&self) -> bool {
+        self.is_resumable && self.cluster.is_some()
+    }
+
+    // Grows a new cluster from the seed
+    pub fn start(&mut self) {
+        debug_assert!(self.is_initialized);
+
+        self.cluster = Some(Cluster::new(
+            self.seed_geometry,
+            self.seed_size,
+            self.sticking_probability,
+            self.particles,
+            self.seed,
+        ));
+        self.is_resumable = true;
+        self.target_particles = self.particles;
+        self.dots = vec![];
+        self.statistics = None;
+        self.is_running = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.is_running = false;
+    }
+
+    pub fn resume(&mut self) {
+        self.is_running = self.is_resumable();
+    }
+
+    pub fn clear(&mut self) {
+        self.is_running = false;
+        self.cluster = None;
+        self.is_resumable = false;
+        self.dots = vec![];
+        self.statistics = None;
+    }
+
+    pub fn statistics(&self) -> Option<&Statistics> {
+        self.statistics.as_ref()
+    }
+}
@@ -0,0 +1,296 @@
+use crate::context::Context;
+use crate::fractals::dla::model::SeedGeometry;
+use crate::fractals::dla::serialization;
+use crate::fractals::dla::state::DlaState;
+use crate::io;
+use crate::math::random;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct DlaSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for DlaSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl DlaSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        let state = &mut context.dla_state;
+
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if state.is_running() {
+                ui.label(RichText::new("Growing...").color(colors::LIME));
+            } else if state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            if let Some(statistics) = state.statistics() {
+                ui.label("Particles: ");
+                ui.label(format!("{} / {}", statistics.particles, state.particles));
+                ui.end_row();
+
+                ui.label("Radius of Gyration: ");
+                ui.label(format!("{:.2} cells", statistics.radius_of_gyration));
+                ui.end_row();
+
+                ui.label("Mass-Radius Dimension: ");
+                match statistics.dimension {
+                    Some(dimension) => ui.label(format!("{:.3}", dimension)),
+                    None => ui.label("Cluster is too small."),
+                };
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Seed Geometry:");
+            egui::ComboBox::from_id_salt("DlaSeedGeometry")
+                .selected_text(format!("{}", state.seed_geometry))
+                .show_ui(ui, |ui| {
+                    for geometry in SeedGeometry::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.seed_geometry,
+                                geometry,
+                                geometry.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            match state.seed_geometry {
+                SeedGeometry::Point => {},
+                SeedGeometry::Line | SeedGeometry::Circle => {
+                    let label = match state.seed_geometry {
+                        SeedGeometry::Line => "Line Length:",
+                        _ => "Circle Radius:",
+                    };
+                    ui.label(label);
+                    changed |= ui
+                        .add(
+                            DragValue::new(&mut state.seed_size)
+                                .speed(1)
+                                .range(1..=1000)
+                                .suffix(" cells"),
+                        )
+                        .changed();
+                    ui.end_row();
+                },
+            }
+
+            ui.label("Sticking Probability:");
+            changed |= ui
+                .add(Slider::new(&mut state.sticking_probability, 0.01..=1.0))
+                .on_hover_text("Lower values make the cluster denser.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Particles:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.particles)
+                        .speed(10)
+                        .range(1..=100_000),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Particles per Frame:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.particles_per_frame)
+                        .speed(1)
+                        .range(1..=10_000),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Spacing:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.spacing_cm)
+                        .speed(0.01)
+                        .range(0.01..=10.0)
+                        .suffix(" cm"),
+                )
+                .on_hover_text("Distance between the neighboring cells of the lattice.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Seed: ");
+            ui.horizontal(|ui| {
+                let mut is_seeded = state.seed.is_some();
+                if ui
+                    .checkbox(&mut is_seeded, "")
+                    .on_hover_text("With a fixed seed, every cluster is identical.")
+                    .changed()
+                {
+                    state.seed = is_seeded.then_some(random::DEFAULT_SEED);
+                    changed = true;
+                }
+                if let Some(seed) = &mut state.seed {
+                    changed |= ui.add(DragValue::new(seed).speed(1)).changed();
+                }
+            });
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Gradient", |ui| {
+            changed |= state.gradient.show_editor(ui, "Dla");
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(!state.is_initialized(), Button::new("Initialize"))
+                .clicked()
+            {
+                if let Err(err) = state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(state.is_initialized(), Button::new("Grow New Cluster"))
+                .clicked()
+            {
+                state.start();
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if state.is_running() {
+                if ui.button("Pause").clicked() {
+                    state.pause();
+                }
+            } else if ui
+                .add_enabled(
+                    state.is_initialized() && state.is_resumable(),
+                    Button::new("Resume"),
+                )
+                .clicked()
+            {
+                state.resume();
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Clear").clicked() {
+                state.clear();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.dla_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.dla_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.dla_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Seed Geometry\": \"Line\",
+                                \"Seed Size\": 40,
+                                \"Sticking Probability\": 0.5,
+                                \"Particles\": 5000
+                            }
+
+                            Seed Geometry: \"Point\", \"Line\" or \"Circle\".
+                            Seed Size: length of the line or radius of the circle, in cells.
+
+                            Other fields: \"Particles per Frame\", \"Spacing\" (cm),
+                            \"Gradient\", \"Seed\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut DlaState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
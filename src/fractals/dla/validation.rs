@@ -0,0 +1,171 @@
+use crate::fractals::dla::model;
+use crate::fractals::dla::model::SeedGeometry;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+const MAX_SEED_SIZE: u32 = 1000;
+const MAX_PARTICLES_PER_FRAME: u32 = 10_000;
+
+pub fn seed_size_range(geometry: SeedGeometry, size: u32) -> Result<(), ValidationError> {
+    let min = match geometry {
+        SeedGeometry::Point => return Ok(()),
+        SeedGeometry::Line => 1,
+        SeedGeometry::Circle => 2,
+    };
+
+    if !(min..=MAX_SEED_SIZE).contains(&size) {
+        return Err(ValidationError::BadSeedSize(format!(
+            "Seed: {}\nRange: {}..{}",
+            geometry, min, MAX_SEED_SIZE
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn sticking_probability_range(probability: f64) -> Result<(), ValidationError> {
+    if !(probability > 0.0 && probability <= 1.0) {
+        return Err(ValidationError::BadStickingProbability);
+    }
+
+    Ok(())
+}
+
+pub fn particles_range(particles: u32) -> Result<(), ValidationError> {
+    if !(1..=model::MAX_PARTICLES).contains(&particles) {
+        return Err(ValidationError::BadParticlesValue);
+    }
+
+    Ok(())
+}
+
+pub fn particles_per_frame_range(particles: u32) -> Result<(), ValidationError> {
+    if !(1..=MAX_PARTICLES_PER_FRAME).contains(&particles) {
+        return Err(ValidationError::BadParticlesPerFrameValue);
+    }
+
+    Ok(())
+}
+
+pub fn spacing_range(spacing_cm: f32) -> Result<(), ValidationError> {
+    if !(spacing_cm > 0.0 && spacing_cm.is_finite()) {
+        return Err(ValidationError::SpacingOutOfRange);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The seed size is out of range.")]
+    BadSeedSize(String),
+
+    #[error("The sticking probability has to be in range 0..1 (0 is exclusive).")]
+    BadStickingProbability,
+
+    #[error("The number of particles has to be in range 1..100000.")]
+    BadParticlesValue,
+
+    #[error("Particles per frame have to be in range 1..10000.")]
+    BadParticlesPerFrameValue,
+
+    #[error("The spacing has to be greater than 0.")]
+    SpacingOutOfRange,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadSeedSize(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::dla::state::DlaState;
+
+    #[test]
+    fn seed_size_depends_on_geometry() {
+        // Point has no size, line needs a cell, circle needs a radius of 2 to be a ring
+        assert!(seed_size_range(SeedGeometry::Point, 0).is_ok());
+        assert!(seed_size_range(SeedGeometry::Line, 1).is_ok());
+        assert!(seed_size_range(SeedGeometry::Circle, 2).is_ok());
+        assert!(seed_size_range(SeedGeometry::Circle, MAX_SEED_SIZE).is_ok());
+        for (geometry, size) in [
+            (SeedGeometry::Line, 0),
+            (SeedGeometry::Circle, 1),
+            (SeedGeometry::Line, MAX_SEED_SIZE + 1),
+        ] {
+            assert!(matches!(
+                seed_size_range(geometry, size),
+                Err(ValidationError::BadSeedSize(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn seed_size_range_is_reported() {
+        let mut state = DlaState::default();
+        state.seed_geometry = SeedGeometry::Circle;
+        state.seed_size = 1;
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Seed: Circle\nRange: 2..1000".to_string())
+        );
+    }
+
+    #[test]
+    fn sticking_probability_of_one_is_valid() {
+        assert!(sticking_probability_range(1.0).is_ok());
+        for probability in [0.0, 1.0 + f64::EPSILON, f64::NAN] {
+            assert!(matches!(
+                sticking_probability_range(probability),
+                Err(ValidationError::BadStickingProbability)
+            ));
+        }
+    }
+
+    #[test]
+    fn particles_bounds_are_inclusive() {
+        assert!(particles_range(1).is_ok());
+        assert!(particles_range(model::MAX_PARTICLES).is_ok());
+        assert!(particles_range(0).is_err());
+        assert!(particles_range(model::MAX_PARTICLES + 1).is_err());
+        assert!(particles_per_frame_range(MAX_PARTICLES_PER_FRAME).is_ok());
+        assert!(particles_per_frame_range(0).is_err());
+    }
+
+    #[test]
+    fn stale_cluster_is_not_resumed() {
+        let mut state = DlaState::default();
+        let _ = state.initialize();
+        state.start();
+        state.pause();
+        assert!(state.is_resumable());
+
+        // Fewer particles, than the cluster may already have
+        state.particles = 1;
+        state.reset_initialization();
+        let result = state.initialize();
+        state.resume();
+
+        assert!(result.is_ok());
+        assert!(!state.is_resumable());
+        assert!(!state.is_running());
+    }
+}
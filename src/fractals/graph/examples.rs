@@ -0,0 +1,79 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static GRAPH_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/graph/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Blancmange")]
+    Blancmange,
+
+    #[strum(serialize = "Brownian Motion")]
+    BrownianMotion,
+
+    #[strum(serialize = "Cantor Staircase")]
+    CantorStaircase,
+
+    #[strum(serialize = "Riemann")]
+    Riemann,
+
+    #[strum(serialize = "Rough Brownian Motion")]
+    RoughBrownianMotion,
+
+    #[strum(serialize = "Smooth Brownian Motion")]
+    SmoothBrownianMotion,
+
+    #[strum(serialize = "Weierstrass")]
+    Weierstrass,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::Blancmange => PathBuf::from(r"Blancmange.json"),
+            Example::BrownianMotion => PathBuf::from(r"Brownian-Motion.json"),
+            Example::CantorStaircase => PathBuf::from(r"Cantor-Staircase.json"),
+            Example::Riemann => PathBuf::from(r"Riemann.json"),
+            Example::RoughBrownianMotion => PathBuf::from(r"Rough-Brownian-Motion.json"),
+            Example::SmoothBrownianMotion => {
+                PathBuf::from(r"Smooth-Brownian-Motion.json")
+            },
+            Example::Weierstrass => PathBuf::from(r"Weierstrass.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = GRAPH_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::Blancmange,
+            Example::BrownianMotion,
+            Example::CantorStaircase,
+            Example::Riemann,
+            Example::RoughBrownianMotion,
+            Example::SmoothBrownianMotion,
+            Example::Weierstrass,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
@@ -0,0 +1,364 @@
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use strum_macros::Display;
+
+pub const DEFAULT_A: f64 = 0.5;
+pub const DEFAULT_B: f64 = 3.0;
+pub const DEFAULT_WEIGHT: f64 = 0.5;
+pub const DEFAULT_HURST: f64 = 0.5;
+pub const DEFAULT_DOMAIN: [f64; 2] = [0.0, 1.0];
+pub const DEFAULT_SCALE_CM: f32 = 15.0;
+pub const DEFAULT_STROKE_WIDTH: f32 = 1.5;
+pub const DEFAULT_COLOR: Color32 = Color32::from_rgb(255, 200, 80);
+
+// Levels of detail, beyond them the doubles run out of precision anyway
+const MAX_LEVELS: u32 = 52;
+// Smallest term of a series, that still changes the graph
+const MIN_AMPLITUDE: f64 = 1e-12;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Function {
+    // Sum of a^n cos(b^n πx)
+    #[default]
+    #[strum(serialize = "Weierstrass")]
+    #[serde(rename = "Weierstrass")]
+    Weierstrass,
+
+    // Sum of w^n s(2^n x), where s is the distance to the nearest integer
+    #[strum(serialize = "Takagi (Blancmange)")]
+    #[serde(rename = "Takagi")]
+    Takagi,
+
+    #[strum(serialize = "Cantor Staircase")]
+    #[serde(rename = "Cantor")]
+    Cantor,
+
+    // Sum of sin(n²πx) / n²
+    #[strum(serialize = "Riemann")]
+    #[serde(rename = "Riemann")]
+    Riemann,
+
+    #[strum(serialize = "Fractional Brownian Motion")]
+    #[serde(rename = "Fractional Brownian Motion")]
+    BrownianMotion,
+}
+
+impl Function {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Function::Weierstrass,
+            Function::Takagi,
+            Function::Cantor,
+            Function::Riemann,
+            Function::BrownianMotion,
+        ]
+        .into_iter()
+    }
+}
+
+pub struct Model {
+    function: Function,
+    a: f64,
+    b: f64,
+    weight: f64,
+    hurst: f64,
+    seed: u64,
+    domain: [f64; 2],
+}
+
+impl Model {
+    // Points of the graph between the given arguments, clipped by the domain.
+    // Details smaller than the distance between the samples are left out,
+    // so they show up only after zooming in.
+    pub fn samples(&self, from: f64, to: f64, count: usize) -> Vec<[f64; 2]> {
+        let (from, to) = (from.max(self.domain[0]), to.min(self.domain[1]));
+        if from >= to || count == 0 {
+            return vec![];
+        }
+
+        let step = (to - from) / count as f64;
+        (0..=count)
+            .map(|index| {
+                let x = match index == count {
+                    true => to,
+                    false => from + index as f64 * step,
+                };
+                [x, self.value(x, step)]
+            })
+            .collect()
+    }
+
+    pub fn value(&self, x: f64, resolution: f64) -> f64 {
+        match self.function {
+            Function::Weierstrass => self.weierstrass(x, resolution),
+            Function::Takagi => self.takagi(x, resolution),
+            Function::Cantor => cantor(x, resolution),
+            Function::Riemann => riemann(x, resolution),
+            Function::BrownianMotion => self.brownian_motion(x, resolution),
+        }
+    }
+
+    fn weierstrass(&self, x: f64, resolution: f64) -> f64 {
+        let (mut amplitude, mut frequency) = (1.0, 1.0);
+        let mut sum = 0.0;
+
+        for _ in 0..MAX_LEVELS {
+            if frequency * resolution > 2.0 || amplitude < MIN_AMPLITUDE {
+                break;
+            }
+            sum += amplitude * (frequency * PI * x).cos();
+            amplitude *= self.a;
+            frequency *= self.b;
+        }
+
+        sum
+    }
+
+    fn takagi(&self, x: f64, resolution: f64) -> f64 {
+        let (mut amplitude, mut frequency) = (1.0, 1.0);
+        let mut sum = 0.0;
+
+        for _ in 0..MAX_LEVELS {
+            if frequency * resolution > 2.0 || amplitude < MIN_AMPLITUDE {
+                break;
+            }
+            let t = frequency * x;
+            sum += amplitude * (t - t.round()).abs();
+            amplitude *= self.weight;
+            frequency *= 2.0;
+        }
+
+        sum
+    }
+
+    // Midpoint displacement over the domain, every midpoint gets the same random
+    // offset on every redrawing, so the path doesn't change while zooming in
+    fn brownian_motion(&self, x: f64, resolution: f64) -> f64 {
+        let length = self.domain[1] - self.domain[0];
+        let t = ((x - self.domain[0]) / length).clamp(0.0, 1.0);
+
+        let deviation = length.powf(self.hurst);
+        let (mut left, mut right) = (0.0, deviation * gaussian(self.seed, 0, 0));
+        let (mut start, mut width, mut index) = (0.0, 1.0, 0u64);
+        let decay = 0.5f64.powf(self.hurst);
+        let mut displacement =
+            deviation * (1.0 - 2f64.powf(2.0 * self.hurst - 2.0)).sqrt();
+
+        for level in 1..=MAX_LEVELS {
+            if width * length < resolution / 2.0 {
+                break;
+            }
+
+            displacement *= decay;
+            let middle = (left + right) / 2.0
+                + displacement * gaussian(self.seed, level, 2 * index + 1);
+
+            width /= 2.0;
+            if t < start + width {
+                right = middle;
+                index *= 2;
+            } else {
+                left = middle;
+                start += width;
+                index = 2 * index + 1;
+            }
+        }
+
+        left + (right - left) * ((t - start) / width).clamp(0.0, 1.0)
+    }
+}
+
+// Ternary digits of the argument up to the resolution, then a straight line
+fn cantor(x: f64, resolution: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let (mut t, mut width) = (x, 1.0);
+    let (mut value, mut increment) = (0.0, 1.0);
+
+    for _ in 0..MAX_LEVELS {
+        if width < resolution {
+            break;
+        }
+
+        t *= 3.0;
+        let digit = t.floor();
+        t -= digit;
+        width /= 3.0;
+        increment /= 2.0;
+
+        match digit as u32 {
+            0 => {},
+            1 => return value + increment,
+            _ => value += increment,
+        }
+    }
+
+    value + increment * t
+}
+
+fn riemann(x: f64, resolution: f64) -> f64 {
+    let terms = ((2.0 / resolution).sqrt() as u64).clamp(1, 1 << 12);
+
+    (1..=terms)
+        .map(|n| {
+            let square = (n * n) as f64;
+            (square * PI * x).sin() / square
+        })
+        .sum()
+}
+
+// Standard normal value, that depends only on the arguments
+fn gaussian(seed: u64, level: u32, index: u64) -> f64 {
+    let hash = split_mix(seed ^ split_mix((level as u64) << 56 ^ index));
+    let first = ((hash >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+    let second = (split_mix(hash) >> 11) as f64 / (1u64 << 53) as f64;
+
+    (-2.0 * first.ln()).sqrt() * (2.0 * PI * second).cos()
+}
+
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+pub struct ModelBuilder {
+    function: Function,
+    a: f64,
+    b: f64,
+    weight: f64,
+    hurst: f64,
+    seed: u64,
+    domain: [f64; 2],
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            function: Function::default(),
+            a: DEFAULT_A,
+            b: DEFAULT_B,
+            weight: DEFAULT_WEIGHT,
+            hurst: DEFAULT_HURST,
+            seed: 0,
+            domain: DEFAULT_DOMAIN,
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_function(mut self, function: Function) -> Self {
+        self.function = function;
+        self
+    }
+
+    pub fn with_weierstrass(mut self, a: f64, b: f64) -> Self {
+        self.a = a;
+        self.b = b;
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_brownian_motion(mut self, hurst: f64, seed: u64) -> Self {
+        self.hurst = hurst;
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_domain(mut self, domain: [f64; 2]) -> Self {
+        self.domain = domain;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            function: self.function,
+            a: self.a,
+            b: self.b,
+            weight: self.weight,
+            hurst: self.hurst,
+            seed: self.seed,
+            domain: self.domain,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takagi_known_values() {
+        let model = ModelBuilder::default()
+            .with_function(Function::Takagi)
+            .build();
+
+        // The maximum T(1/3) = 2/3 and T(1/2) = 1/2 for the weight 1/2
+        assert!((model.value(1.0 / 3.0, 1e-12) - 2.0 / 3.0).abs() < 1e-9);
+        assert!((model.value(0.5, 1e-12) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cantor_known_values() {
+        assert_eq!(cantor(0.5, 1e-15), 0.5);
+        assert!((cantor(0.25, 1e-15) - 1.0 / 3.0).abs() < 1e-9);
+        assert!((cantor(0.75, 1e-15) - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn brownian_motion_keeps_coarse_points() {
+        let model = ModelBuilder::default()
+            .with_function(Function::BrownianMotion)
+            .with_brownian_motion(0.7, 42)
+            .build();
+
+        // Zooming in adds midpoints, but the dyadic points stay where they were
+        for x in [0.0, 0.25, 0.5, 0.625, 1.0] {
+            let coarse = model.value(x, 1.0 / 16.0);
+            let fine = model.value(x, 1e-9);
+            assert!((coarse - fine).abs() < 1e-12, "{}: {} {}", x, coarse, fine);
+        }
+    }
+
+    #[test]
+    fn samples_are_clipped_by_domain() {
+        let model = ModelBuilder::default()
+            .with_function(Function::Cantor)
+            .with_domain([0.0, 1.0])
+            .build();
+
+        let samples = model.samples(-10.0, 10.0, 100);
+
+        assert_eq!(samples.len(), 101);
+        assert_eq!(samples.first(), Some(&[0.0, 0.0]));
+        assert_eq!(samples.last(), Some(&[1.0, 1.0]));
+        assert!(samples.windows(2).all(|pair| pair[0][1] <= pair[1][1]));
+
+        assert!(model.samples(2.0, 3.0, 100).is_empty());
+        assert!(model.samples(0.0, 1.0, 0).is_empty());
+    }
+
+    #[test]
+    fn coarse_resolution_leaves_out_details() {
+        let model = ModelBuilder::default()
+            .with_function(Function::Weierstrass)
+            .with_weierstrass(0.5, 3.0)
+            .build();
+
+        // Only the first term, cos(πx), is wider than the samples
+        let x = 0.3;
+        assert!((model.value(x, 1.0) - (PI * x).cos()).abs() < 1e-12);
+        assert!((model.value(x, 1e-9) - (PI * x).cos()).abs() > 1e-3);
+    }
+}
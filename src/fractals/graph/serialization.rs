@@ -0,0 +1,127 @@
+use crate::fractals::graph::model;
+use crate::fractals::graph::model::Function;
+use crate::fractals::graph::state::GraphState;
+use crate::fractals::graph::validation::ValidationError;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<GraphDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &GraphState) -> serde_json::Result<String> {
+    let dto = GraphDto {
+        function: state.function,
+        a: state.a,
+        b: state.b,
+        weight: state.weight,
+        hurst: state.hurst,
+        seed: state.seed,
+        domain: state.domain,
+        scale_cm: state.scale_cm,
+        vertical_scale: state.vertical_scale,
+        stroke_width: state.stroke_width,
+        color: [state.color.r(), state.color.g(), state.color.b()],
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_a() -> f64 {
+    model::DEFAULT_A
+}
+
+fn default_b() -> f64 {
+    model::DEFAULT_B
+}
+
+fn default_weight() -> f64 {
+    model::DEFAULT_WEIGHT
+}
+
+fn default_hurst() -> f64 {
+    model::DEFAULT_HURST
+}
+
+fn default_domain() -> [f64; 2] {
+    model::DEFAULT_DOMAIN
+}
+
+fn default_scale() -> f32 {
+    model::DEFAULT_SCALE_CM
+}
+
+fn default_vertical_scale() -> f32 {
+    1.0
+}
+
+fn default_stroke_width() -> f32 {
+    model::DEFAULT_STROKE_WIDTH
+}
+
+fn default_color() -> [u8; 3] {
+    let color = model::DEFAULT_COLOR;
+    [color.r(), color.g(), color.b()]
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GraphDto {
+    #[serde(rename = "Function", default)]
+    function: Function,
+
+    #[serde(rename = "A", default = "default_a")]
+    a: f64,
+
+    #[serde(rename = "B", default = "default_b")]
+    b: f64,
+
+    #[serde(rename = "Weight", default = "default_weight")]
+    weight: f64,
+
+    #[serde(rename = "Hurst Exponent", default = "default_hurst")]
+    hurst: f64,
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+
+    #[serde(rename = "Domain", default = "default_domain")]
+    domain: [f64; 2],
+
+    #[serde(rename = "Scale", default = "default_scale")]
+    scale_cm: f32,
+
+    #[serde(rename = "Vertical Scale", default = "default_vertical_scale")]
+    vertical_scale: f32,
+
+    #[serde(rename = "Stroke Width", default = "default_stroke_width")]
+    stroke_width: f32,
+
+    #[serde(rename = "Color", default = "default_color")]
+    color: [u8; 3],
+}
+
+impl GraphDto {
+    pub fn load(self, state: &mut GraphState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.function = self.function;
+        state.a = self.a;
+        state.b = self.b;
+        state.weight = self.weight;
+        state.hurst = self.hurst;
+        state.seed = self.seed;
+        state.domain = self.domain;
+        state.scale_cm = self.scale_cm;
+        state.vertical_scale = self.vertical_scale;
+        state.stroke_width = self.stroke_width;
+
+        let [r, g, b] = self.color;
+        state.color = Color32::from_rgb(r, g, b);
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,148 @@
+use crate::fractals::graph::model::{Function, Model, ModelBuilder};
+use crate::fractals::graph::validation::ValidationError;
+use crate::fractals::graph::{model, validation};
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use egui::{Color32, Pos2, Shape, Stroke};
+
+pub struct GraphState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub function: Function,
+    pub a: f64,
+    pub b: f64,
+    pub weight: f64,
+    pub hurst: f64,
+    pub seed: Option<u64>,
+    pub domain: [f64; 2],
+
+    pub scale_cm: f32,
+    pub vertical_scale: f32,
+    pub stroke_width: f32,
+    pub color: Color32,
+
+    model: Option<Model>,
+    // Graph points in cm for the visible part of the domain
+    points: Vec<Point2D>,
+    drawn_params: Option<CanvasParams>,
+}
+
+impl Default for GraphState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            function: Function::default(),
+            a: model::DEFAULT_A,
+            b: model::DEFAULT_B,
+            weight: model::DEFAULT_WEIGHT,
+            hurst: model::DEFAULT_HURST,
+            seed: None,
+            domain: model::DEFAULT_DOMAIN,
+
+            scale_cm: model::DEFAULT_SCALE_CM,
+            vertical_scale: 1.0,
+            stroke_width: model::DEFAULT_STROKE_WIDTH,
+            color: model::DEFAULT_COLOR,
+
+            model: None,
+            points: vec![],
+            drawn_params: None,
+        }
+    }
+}
+
+impl GraphState {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Vec<Shape> {
+        let Some(model) = &self.model else {
+            return vec![];
+        };
+
+        // Sampling again on every pan or zoom, so the new details show up
+        let is_params_changed = self
+            .drawn_params
+            .as_ref()
+            .is_some_and(|drawn_params| drawn_params != params);
+
+        if self.is_drawing_requested || is_params_changed {
+            self.is_drawing_requested = false;
+            self.drawn_params = Some(params.clone());
+
+            let rect = params.screen_rect();
+            let left = params.point_px_to_cm(Point2D::from_pos2(rect.left_top()));
+            let right = params.point_px_to_cm(Point2D::from_pos2(rect.right_bottom()));
+            let scale = self.scale_cm as f64;
+
+            // One sample per pixel of the canvas width
+            self.points = model
+                .samples(
+                    left.x as f64 / scale,
+                    right.x as f64 / scale,
+                    rect.width().max(1.0) as usize,
+                )
+                .into_iter()
+                .map(|[x, y]| {
+                    Point2D::new(
+                        (x * scale) as f32,
+                        (y * scale) as f32 * self.vertical_scale,
+                    )
+                })
+                .collect();
+        }
+
+        let points: Vec<Pos2> = self
+            .points
+            .iter()
+            .map(|point| point.to_screen(params).to_pos2())
+            .collect();
+
+        vec![Shape::line(
+            points,
+            Stroke::new(self.stroke_width, self.color),
+        )]
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::weierstrass_range(self.a, self.b)?;
+        validation::weight_range(self.weight)?;
+        validation::hurst_range(self.hurst)?;
+        validation::domain_is_valid(&self.domain)?;
+        validation::scale_range(self.scale_cm, self.vertical_scale)?;
+        validation::stroke_width_range(self.stroke_width)?;
+
+        // The random path has to stay the same while zooming, so the seed is fixed here
+        let seed = self.seed.unwrap_or_else(rand::random);
+
+        self.model = Some(
+            ModelBuilder::default()
+                .with_function(self.function)
+                .with_weierstrass(self.a, self.b)
+                .with_weight(self.weight)
+                .with_brownian_motion(self.hurst, seed)
+                .with_domain(self.domain)
+                .build(),
+        );
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn samples_count(&self) -> usize {
+        self.points.len()
+    }
+}
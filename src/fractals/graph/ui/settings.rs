@@ -0,0 +1,314 @@
+use crate::context::Context;
+use crate::fractals::graph::examples::Example;
+use crate::fractals::graph::model::Function;
+use crate::fractals::graph::serialization;
+use crate::fractals::graph::state::GraphState;
+use crate::fractals::graph::validation::MAX_SCALE_CM;
+use crate::io;
+use crate::math::random;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct GraphSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for GraphSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl GraphSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.graph_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Samples: ");
+            ui.label(context.graph_state.samples_count().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.graph_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Function:");
+            egui::ComboBox::from_id_salt("GraphFunction")
+                .selected_text(format!("{}", state.function))
+                .show_ui(ui, |ui| {
+                    for function in Function::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.function,
+                                function,
+                                function.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            match state.function {
+                Function::Weierstrass => {
+                    ui.label("a:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.a, 0.01..=0.99))
+                        .on_hover_text("Amplitude ratio of the neighboring terms.")
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("b:");
+                    changed |= ui
+                        .add(DragValue::new(&mut state.b).speed(0.1).range(1.01..=100.0))
+                        .on_hover_text(
+                            "Frequency ratio of the neighboring terms.\n\
+                             The graph is nowhere differentiable for ab ≥ 1.",
+                        )
+                        .changed();
+                    ui.end_row();
+                },
+                Function::Takagi => {
+                    ui.label("Weight:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.weight, 0.01..=0.99))
+                        .on_hover_text("Amplitude ratio of the neighboring terms.")
+                        .changed();
+                    ui.end_row();
+                },
+                Function::BrownianMotion => {
+                    ui.label("Hurst Exponent:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.hurst, 0.01..=0.99))
+                        .on_hover_text(
+                            "0.5 is the ordinary Brownian motion,\n\
+                             lower values give rougher paths.",
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Seed: ");
+                    ui.horizontal(|ui| {
+                        let mut is_seeded = state.seed.is_some();
+                        if ui
+                            .checkbox(&mut is_seeded, "")
+                            .on_hover_text("With a fixed seed, every path is identical.")
+                            .changed()
+                        {
+                            state.seed = is_seeded.then_some(random::DEFAULT_SEED);
+                            changed = true;
+                        }
+                        if let Some(seed) = &mut state.seed {
+                            changed |= ui.add(DragValue::new(seed).speed(1)).changed();
+                        }
+                    });
+                    ui.end_row();
+                },
+                Function::Cantor | Function::Riemann => {},
+            }
+
+            ui.label("Domain:");
+            ui.horizontal(|ui| {
+                let from = ui.add(DragValue::new(&mut state.domain[0]).speed(0.01));
+                let to = ui.add(DragValue::new(&mut state.domain[1]).speed(0.01));
+                changed |= from.changed() || to.changed();
+            });
+            ui.end_row();
+
+            ui.label("Scale:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.scale_cm)
+                        .speed(0.1)
+                        .range(0.1..=MAX_SCALE_CM)
+                        .suffix(" cm"),
+                )
+                .on_hover_text("Length of the unit interval on the canvas.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Vertical Scale:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.vertical_scale)
+                        .speed(0.01)
+                        .range(0.01..=100.0),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Stroke Width:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.stroke_width)
+                        .speed(0.1)
+                        .range(0.1..=10.0),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.graph_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.graph_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.graph_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.graph_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.graph_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.graph_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.graph_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Function\": \"Weierstrass\",
+                                \"A\": 0.5,
+                                \"B\": 3.0,
+                                \"Domain\": [-1.0, 1.0],
+                                \"Scale\": 10.0
+                            }
+
+                            Function: \"Weierstrass\" (uses \"A\" and \"B\"),
+                            \"Takagi\" (uses \"Weight\"), \"Cantor\", \"Riemann\"
+                            or \"Fractional Brownian Motion\" (uses \"Hurst Exponent\"
+                            and optional \"Seed\").
+                            Domain: interval of the argument, that is drawn.
+                            Scale: length of the unit interval on the canvas (cm).
+                            Other fields: \"Vertical Scale\", \"Stroke Width\",
+                            \"Color\" (RGB).
+
+                            The graph is sampled once per pixel of the visible part,
+                            so zooming in or increasing the scale shows finer details.
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut GraphState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub const MAX_SCALE_CM: f32 = 100_000.0;
+
+pub fn weierstrass_range(a: f64, b: f64) -> Result<(), ValidationError> {
+    if !(a > 0.0 && a < 1.0) {
+        return Err(ValidationError::BadAmplitudeValue);
+    }
+    if !(b > 1.0 && b.is_finite()) {
+        return Err(ValidationError::BadFrequencyValue);
+    }
+
+    Ok(())
+}
+
+pub fn weight_range(weight: f64) -> Result<(), ValidationError> {
+    if !(weight > 0.0 && weight < 1.0) {
+        return Err(ValidationError::BadWeightValue);
+    }
+
+    Ok(())
+}
+
+pub fn hurst_range(hurst: f64) -> Result<(), ValidationError> {
+    if !(hurst > 0.0 && hurst < 1.0) {
+        return Err(ValidationError::BadHurstValue);
+    }
+
+    Ok(())
+}
+
+pub fn domain_is_valid(domain: &[f64; 2]) -> Result<(), ValidationError> {
+    if !(domain[0].is_finite() && domain[1].is_finite() && domain[0] < domain[1]) {
+        return Err(ValidationError::EmptyDomain(format!(
+            "Domain: [{}, {}].",
+            domain[0], domain[1]
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn scale_range(scale_cm: f32, vertical_scale: f32) -> Result<(), ValidationError> {
+    if !(scale_cm > 0.0 && scale_cm <= MAX_SCALE_CM) {
+        return Err(ValidationError::BadScaleValue);
+    }
+    if !(vertical_scale > 0.0 && vertical_scale.is_finite()) {
+        return Err(ValidationError::BadVerticalScaleValue);
+    }
+
+    Ok(())
+}
+
+pub fn stroke_width_range(stroke_width: f32) -> Result<(), ValidationError> {
+    if !(stroke_width > 0.0 && stroke_width.is_finite()) {
+        return Err(ValidationError::BadStrokeWidth);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("Amplitude ratio a has to be in range (0, 1).")]
+    BadAmplitudeValue,
+
+    #[error("Frequency ratio b has to be greater than 1.")]
+    BadFrequencyValue,
+
+    #[error("Weight has to be in range (0, 1).")]
+    BadWeightValue,
+
+    #[error("Hurst exponent has to be in range (0, 1).")]
+    BadHurstValue,
+
+    #[error("The start of the domain has to be less than its end.")]
+    EmptyDomain(String),
+
+    #[error("Scale has to be in range (0, 100000] cm.")]
+    BadScaleValue,
+
+    #[error("Vertical scale has to be greater than 0.")]
+    BadVerticalScaleValue,
+
+    #[error("Stroke width has to be greater than 0.")]
+    BadStrokeWidth,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::EmptyDomain(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::graph::examples::Example;
+    use crate::fractals::graph::serialization;
+    use crate::fractals::graph::state::GraphState;
+
+    #[test]
+    fn weierstrass_bounds_are_exclusive() {
+        assert!(weierstrass_range(0.99, 1.01).is_ok());
+        assert!(matches!(
+            weierstrass_range(1.0, 3.0),
+            Err(ValidationError::BadAmplitudeValue)
+        ));
+        assert!(matches!(
+            weierstrass_range(0.0, 3.0),
+            Err(ValidationError::BadAmplitudeValue)
+        ));
+        for b in [1.0, f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                weierstrass_range(0.5, b),
+                Err(ValidationError::BadFrequencyValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn reversed_domain_is_reported() {
+        let mut state = GraphState::default();
+        state.domain = [2.0, 1.0];
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Domain: [2, 1].".to_string())
+        );
+        assert!(domain_is_valid(&[1.0, 1.0]).is_err());
+        assert!(domain_is_valid(&[f64::NEG_INFINITY, 1.0]).is_err());
+    }
+
+    #[test]
+    fn scale_limit_is_inclusive() {
+        assert!(scale_range(MAX_SCALE_CM, 1.0).is_ok());
+        assert!(matches!(
+            scale_range(MAX_SCALE_CM * 2.0, 1.0),
+            Err(ValidationError::BadScaleValue)
+        ));
+        assert!(matches!(
+            scale_range(1.0, 0.0),
+            Err(ValidationError::BadVerticalScaleValue)
+        ));
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = GraphState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use crate::geometry::dot::Dot;
+use crate::ui::styles::gradient::Gradient;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use strum_macros::Display;
+
+pub const DEFAULT_HISTORY_LENGTH: u32 = 3;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum PointColoring {
+    #[default]
+    #[strum(serialize = "By System")]
+    #[serde(rename = "By System")]
+    BySystem,
+
+    #[strum(serialize = "Transform History")]
+    #[serde(rename = "Transform History")]
+    TransformHistory,
+
+    #[strum(serialize = "Density")]
+    #[serde(rename = "Density")]
+    Density,
+
+    #[strum(serialize = "Distance from Origin")]
+    #[serde(rename = "Distance")]
+    Distance,
+
+    #[strum(serialize = "Iteration Index")]
+    #[serde(rename = "Iteration")]
+    Iteration,
+}
+
+impl PointColoring {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            PointColoring::BySystem,
+            PointColoring::TransformHistory,
+            PointColoring::Density,
+            PointColoring::Distance,
+            PointColoring::Iteration,
+        ]
+        .into_iter()
+    }
+
+    pub fn is_gradient_based(&self) -> bool {
+        !matches!(self, PointColoring::BySystem)
+    }
+
+    // Every dot has its transform history value in range 0..=1, see `TransformBlend`
+    pub fn apply(&self, dots: &mut [Dot], history: &[f32], gradient: &Gradient) {
+        debug_assert!(dots.len() == history.len());
+
+        let values: Vec<f32> = match self {
+            PointColoring::BySystem => return,
+            PointColoring::TransformHistory => history.to_vec(),
+            PointColoring::Density => density(dots),
+            PointColoring::Distance => distance(dots),
+            PointColoring::Iteration => {
+                let last = dots.len().saturating_sub(1).max(1) as f32;
+                (0..dots.len()).map(|index| index as f32 / last).collect()
+            },
+        };
+
+        for (dot, value) in dots.iter_mut().zip(values) {
+            dot.color = gradient.sample(value);
+        }
+    }
+}
+
+// Average position of the last `length` transforms, that made the point.
+// Positions of the transforms are spread evenly over 0..=1.
+#[derive(Clone)]
+pub struct TransformBlend {
+    transforms: usize,
+    length: usize,
+    recent: VecDeque<usize>,
+    sum: f32,
+}
+
+impl TransformBlend {
+    pub fn new(transforms: usize, length: u32) -> Self {
+        let length = length.max(1) as usize;
+        Self {
+            transforms,
+            length,
+            recent: VecDeque::with_capacity(length),
+            sum: 0.0,
+        }
+    }
+
+    // Value of the point after one more transform
+    pub fn push(&mut self, transform: usize) -> f32 {
+        if self.recent.len() == self.length {
+            if let Some(oldest) = self.recent.pop_front() {
+                self.sum -= self.position(oldest);
+            }
+        }
+        self.recent.push_back(transform);
+        self.sum += self.position(transform);
+
+        (self.sum / self.recent.len() as f32).clamp(0.0, 1.0)
+    }
+
+    // Value of the point, that was made by the given transforms, no more than `length`
+    // of them are taken. It's 0 for the points, that weren't transformed yet.
+    pub fn average(&self, transforms: impl IntoIterator<Item = usize>) -> f32 {
+        let (sum, count) = transforms
+            .into_iter()
+            .take(self.length)
+            .fold((0.0, 0), |(sum, count), transform| {
+                (sum + self.position(transform), count + 1)
+            });
+
+        match count {
+            0 => 0.0,
+            _ => sum / count as f32,
+        }
+    }
+
+    fn position(&self, transform: usize) -> f32 {
+        if self.transforms > 1 {
+            transform as f32 / (self.transforms - 1) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+// Hits per cell of the dot diameter size, logarithmically scaled
+fn density(dots: &[Dot]) -> Vec<f32> {
+    let cell = dots
+        .first()
+        .map_or(1.0, |dot| dot.radius * 2.0)
+        .max(f32::EPSILON);
+    let key = |dot: &Dot| {
+        (
+            (dot.point.x / cell).floor() as i64,
+            (dot.point.y / cell).floor() as i64,
+        )
+    };
+
+    let mut hits: HashMap<(i64, i64), u32> = HashMap::new();
+    for dot in dots {
+        *hits.entry(key(dot)).or_insert(0) += 1;
+    }
+
+    let max = hits.values().copied().max().unwrap_or(1) as f32;
+    let max_log = (1.0 + max).ln();
+
+    dots.iter()
+        .map(|dot| {
+            let count = hits.get(&key(dot)).copied().unwrap_or(0) as f32;
+            (1.0 + count).ln() / max_log
+        })
+        .collect()
+}
+
+fn distance(dots: &[Dot]) -> Vec<f32> {
+    let distances: Vec<f32> = dots
+        .iter()
+        .map(|dot| f32::hypot(dot.point.x, dot.point.y))
+        .collect();
+
+    let max = distances.iter().copied().fold(0.0, f32::max);
+    if max <= f32::EPSILON {
+        return vec![0.0; distances.len()];
+    }
+
+    distances.into_iter().map(|value| value / max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::dot::DotBuilder;
+    use crate::geometry::point2d::Point2D;
+    use egui::Color32;
+
+    fn dots(points: &[(f32, f32)]) -> Vec<Dot> {
+        points
+            .iter()
+            .map(|(x, y)| {
+                DotBuilder::default()
+                    .with_center(Point2D::new(*x, *y))
+                    .with_radius_centimeters(0.5)
+                    .build()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn densest_cell_is_one() {
+        // Three dots share a cell, the last one is alone
+        let dots = dots(&[(0.1, 0.1), (0.2, 0.2), (0.3, 0.3), (5.0, 5.0)]);
+
+        let values = density(&dots);
+
+        assert!(values.iter().all(|value| (0.0..=1.0).contains(value)));
+        assert_eq!(values[..3], [1.0; 3]);
+        assert!(values[3] > 0.0 && values[3] < 1.0);
+    }
+
+    #[test]
+    fn farthest_dot_is_one() {
+        let values = distance(&dots(&[(0.0, 0.0), (3.0, 4.0), (0.0, -2.5)]));
+
+        assert_eq!(values, vec![0.0, 1.0, 0.5]);
+        assert_eq!(distance(&dots(&[(0.0, 0.0); 2])), vec![0.0; 2]);
+    }
+
+    #[test]
+    fn iteration_goes_from_zero_to_one() {
+        let gradient = Gradient::new(vec![(0.0, Color32::BLACK), (1.0, Color32::WHITE)]);
+        let mut dots = dots(&[(0.0, 0.0); 5]);
+
+        PointColoring::Iteration.apply(&mut dots, &[0.0; 5], &gradient);
+
+        assert_eq!(dots[0].color, Color32::BLACK);
+        assert_eq!(dots[2].color, gradient.sample(0.5));
+        assert_eq!(dots[4].color, Color32::WHITE);
+    }
+
+    #[test]
+    fn by_system_keeps_colors() {
+        let mut dots = dots(&[(0.0, 0.0), (1.0, 1.0)]);
+        let colors: Vec<Color32> = dots.iter().map(|dot| dot.color).collect();
+
+        PointColoring::BySystem.apply(&mut dots, &[1.0, 1.0], &Gradient::default());
+
+        assert!(dots
+            .iter()
+            .zip(colors)
+            .all(|(dot, color)| dot.color == color));
+    }
+
+    #[test]
+    fn blend_forgets_old_transforms() {
+        // Positions of the three transforms are 0, 0.5 and 1
+        let mut blend = TransformBlend::new(3, 2);
+
+        assert_eq!(blend.push(0), 0.0);
+        assert_eq!(blend.push(2), 0.5);
+        assert_eq!(blend.push(2), 1.0);
+        assert_eq!(blend.push(1), 0.75);
+
+        // Latest transforms first, only two of them count
+        assert_eq!(blend.average([2, 2, 0, 0]), 1.0);
+        assert_eq!(blend.average([]), 0.0);
+    }
+}
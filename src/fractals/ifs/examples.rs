@@ -28,6 +28,9 @@ pub enum Example {
     #[strum(serialize = "Koch-3")]
     Koch3,
 
+    #[strum(serialize = "Recurrent Square")]
+    RecurrentSquare,
+
     #[strum(serialize = "Spiral")]
     Spiral,
 
@@ -54,6 +57,7 @@ impl Example {
             Example::Dragon => PathBuf::from(r"Dragon.json"),
             Example::Floor => PathBuf::from(r"Floor.json"),
             Example::Koch3 => PathBuf::from(r"Koch-3.json"),
+            Example::RecurrentSquare => PathBuf::from(r"Recurrent-Square.json"),
             Example::Spiral => PathBuf::from(r"Spiral.json"),
             Example::Tree => PathBuf::from(r"Tree.json"),
             Example::Triangle => PathBuf::from(r"Triangle.json"),
@@ -82,6 +86,7 @@ impl Example {
             Example::Dragon,
             Example::Floor,
             Example::Koch3,
+            Example::RecurrentSquare,
             Example::Spiral,
             Example::Tree,
             Example::Triangle,
@@ -1,22 +1,86 @@
+use crate::fractals::ifs::coloring;
+use crate::fractals::ifs::coloring::{PointColoring, TransformBlend};
+use crate::fractals::ifs::seed;
+use crate::fractals::ifs::seed::SeedShape;
 use crate::fractals::ifs::system::EquationSystem;
 use crate::geometry::dot::{Dot, DotBuilder};
+use crate::geometry::point2d::Point2D;
+use crate::math::random;
 use crate::ui::styles::colors::ColorScheme;
-use rand::distributions::{Distribution, WeightedIndex};
-use rand::thread_rng;
+use crate::ui::styles::gradient::Gradient;
+use crate::ui::windows::message::MessageWindow;
+use rand::distributions::{Distribution, WeightedError, WeightedIndex};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use thiserror::Error;
 
 pub const DEFAULT_ITERATIONS: u32 = 20000;
 pub const DEFAULT_RADIUS: f32 = 0.025;
+pub const DEFAULT_BURN_IN: u32 = 0;
+pub const DEFAULT_GENERATIONS: u32 = 5;
+
+// Deterministic algorithm grows exponentially, so the picture size is limited
+pub const MAX_DETERMINISTIC_DOTS: usize = 1_000_000;
+
+// Half-size of the square that random start points are picked from, in centimeters
+const RANDOM_START_RANGE: f32 = 1.0;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum StartPoint {
+    #[default]
+    #[strum(serialize = "Origin")]
+    #[serde(rename = "Origin")]
+    Origin,
+
+    #[strum(serialize = "Random")]
+    #[serde(rename = "Random")]
+    Random,
+
+    #[strum(serialize = "Fixed Point of First Map")]
+    #[serde(rename = "Fixed Point")]
+    FixedPoint,
+}
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Algorithm {
+    #[default]
+    #[strum(serialize = "Chaos Game")]
+    #[serde(rename = "Chaos Game")]
+    ChaosGame,
+
+    #[strum(serialize = "Deterministic")]
+    #[serde(rename = "Deterministic")]
+    Deterministic,
+}
 
 pub struct Model {
     systems: Vec<[f32; 7]>,
     color_schemas: Vec<ColorScheme>,
+    // Row of the previous transform holds the probabilities of the next one
+    transitions: Option<Vec<Vec<f32>>>,
+
+    algorithm: Algorithm,
 
     iterations: u32,
+    burn_in: u32,
+    start_point: StartPoint,
+
+    generations: u32,
+    seed_shape: SeedShape,
+    seed_size: f32,
+    bitmap: Vec<Point2D>,
+
+    coloring: PointColoring,
+    gradient: Gradient,
+    history_length: u32,
+
     radius: f32,
+    seed: Option<u64>,
 }
 
 impl Model {
-    pub fn dots(&self) -> Vec<Dot> {
+    pub fn dots(&self) -> Result<Vec<Dot>, ModelError> {
         debug_assert!(self.systems.len() == self.color_schemas.len());
 
         let mut equations: Vec<EquationSystem> = Vec::new();
@@ -27,48 +91,229 @@ impl Model {
             );
         }
 
-        let mut dots: Vec<Dot> = Vec::new();
+        let (mut dots, history) = match self.algorithm {
+            Algorithm::ChaosGame => self.chaos_game(&equations)?,
+            Algorithm::Deterministic => self.deterministic(&equations)?,
+        };
 
-        let start_dot = DotBuilder::default().build();
-        dots.push(start_dot);
+        self.coloring.apply(&mut dots, &history, &self.gradient);
 
+        Ok(dots)
+    }
+
+    // Returns dots with their transform history values
+    fn chaos_game(
+        &self, equations: &[EquationSystem],
+    ) -> Result<(Vec<Dot>, Vec<f32>), ModelError> {
         let probabilities: Vec<f32> = equations
             .iter()
             .map(|equation| equation.probability())
             .collect();
-        let mut rng = thread_rng();
-
-        let dist = match WeightedIndex::new(&probabilities) {
-            Ok(value) => value,
-            Err(err) => {
-                log::error!(
-                    "{}",
-                    format!(
-                        "Error occurred while creating weighted index. Additional Info: {}",
-                        err
-                    )
-                );
-                std::process::exit(1);
-            },
+        let mut rng = random::rng(self.seed);
+
+        let mut picker = TransformPicker::new(&probabilities, &self.transitions)?;
+
+        let mut current_dot = DotBuilder::default()
+            .with_center(self.start_point(equations, &mut rng)?)
+            .with_radius_centimeters(self.radius)
+            .build();
+
+        let mut blend = TransformBlend::new(equations.len(), self.history_length);
+        let mut current_history = 0.0;
+
+        for _ in 0..self.burn_in {
+            let index = picker.next(&mut rng);
+            current_dot = equations[index].next_dot(&current_dot, &mut rng);
+            current_history = blend.push(index);
+        }
+
+        let capacity = self.iterations as usize + 1;
+        let mut dots: Vec<Dot> = Vec::with_capacity(capacity);
+        let mut history: Vec<f32> = Vec::with_capacity(capacity);
+
+        // Without burn-in, the start point is a part of the picture
+        if self.burn_in == 0 {
+            dots.push(current_dot.clone());
+            history.push(current_history);
+        }
+
+        for _ in 0..self.iterations {
+            let index = picker.next(&mut rng);
+            current_dot = equations[index].next_dot(&current_dot, &mut rng);
+            current_history = blend.push(index);
+
+            dots.push(current_dot.clone());
+            history.push(current_history);
+        }
+
+        Ok((dots, history))
+    }
+
+    // Applies every map to every dot of the previous generation (Hutchinson operator)
+    fn deterministic(
+        &self, equations: &[EquationSystem],
+    ) -> Result<(Vec<Dot>, Vec<f32>), ModelError> {
+        let seed_points = self.seed_shape.points(self.seed_size, &self.bitmap);
+        if seed_points.is_empty() {
+            return Err(ModelError::EmptySeedShape);
+        }
+
+        // Counted in integers, so large generations can't wrap around
+        let total = (equations.len() as u64)
+            .checked_pow(self.generations)
+            .and_then(|count| count.checked_mul(seed_points.len() as u64))
+            .filter(|total| *total <= MAX_DETERMINISTIC_DOTS as u64);
+        if total.is_none() {
+            let total = (equations.len() as f64).powf(self.generations as f64)
+                * seed_points.len() as f64;
+            return Err(ModelError::TooManyDots(total));
+        }
+
+        let mut rng = random::rng(self.seed);
+
+        let mut dots: Vec<Dot> = seed_points
+            .into_iter()
+            .map(|point| {
+                DotBuilder::default()
+                    .with_center(point)
+                    .with_radius_centimeters(self.radius)
+                    .build()
+            })
+            .collect();
+        // Transform, that made every dot, the seed dots have none
+        let mut last: Vec<Option<usize>> = vec![None; dots.len()];
+        // Dot of the previous generation and the transform, that made every dot
+        // of every generation, they give the transform history of the last dots
+        let is_history_needed = self.coloring == PointColoring::TransformHistory;
+        let mut ancestry: Vec<Vec<(u32, u32)>> = vec![];
+
+        for _ in 0..self.generations {
+            let capacity = dots.len() * equations.len();
+            let mut next_dots: Vec<Dot> = Vec::with_capacity(capacity);
+            let mut next_last: Vec<Option<usize>> = Vec::with_capacity(capacity);
+            let mut origins: Vec<(u32, u32)> = vec![];
+            for (index, equation) in equations.iter().enumerate() {
+                for (parent, (dot, previous)) in dots.iter().zip(&last).enumerate() {
+                    if !self.may_follow(*previous, index) {
+                        continue;
+                    }
+                    next_dots.push(equation.next_dot(dot, &mut rng));
+                    next_last.push(Some(index));
+                    if is_history_needed {
+                        origins.push((parent as u32, index as u32));
+                    }
+                }
+            }
+            dots = next_dots;
+            last = next_last;
+            ancestry.push(origins);
+        }
+
+        let blend = TransformBlend::new(equations.len(), self.history_length);
+        let history = match is_history_needed {
+            true => (0..dots.len())
+                .map(|index| {
+                    // Walking from the dot back to its seed, the latest transform first
+                    let transforms = ancestry.iter().rev().scan(index, |dot, origins| {
+                        let (parent, transform) = origins[*dot];
+                        *dot = parent as usize;
+                        Some(transform as usize)
+                    });
+                    blend.average(transforms)
+                })
+                .collect(),
+            false => vec![0.0; dots.len()],
         };
 
-        for current_index in 0..self.iterations {
-            let equation = &equations[dist.sample(&mut rng)];
-            let new_dot = equation.next_dot(&dots[current_index as usize]);
+        Ok((dots, history))
+    }
 
-            dots.push(new_dot);
+    // Without the transition matrix, every transform may follow every other
+    fn may_follow(&self, previous: Option<usize>, next: usize) -> bool {
+        match (&self.transitions, previous) {
+            (Some(transitions), Some(previous)) => transitions[previous][next] > 0.0,
+            _ => true,
         }
+    }
+
+    fn start_point(
+        &self, equations: &[EquationSystem], rng: &mut impl Rng,
+    ) -> Result<Point2D, ModelError> {
+        match self.start_point {
+            StartPoint::Origin => Ok(Point2D::new(0.0, 0.0)),
+            StartPoint::Random => Ok(Point2D::new(
+                rng.gen_range(-RANDOM_START_RANGE..=RANDOM_START_RANGE),
+                rng.gen_range(-RANDOM_START_RANGE..=RANDOM_START_RANGE),
+            )),
+            StartPoint::FixedPoint => equations
+                .first()
+                .and_then(|equation| equation.fixed_point())
+                .ok_or(ModelError::NoFixedPoint),
+        }
+    }
+}
+
+// Picks the transforms of the chaos game. With the transition matrix, the next
+// transform depends on the previous one, only the first is picked by the probabilities.
+struct TransformPicker {
+    first: WeightedIndex<f32>,
+    rows: Option<Vec<WeightedIndex<f32>>>,
+    previous: Option<usize>,
+}
+
+impl TransformPicker {
+    fn new(
+        probabilities: &[f32], transitions: &Option<Vec<Vec<f32>>>,
+    ) -> Result<Self, WeightedError> {
+        let rows = match transitions {
+            Some(transitions) => Some(
+                transitions
+                    .iter()
+                    .map(WeightedIndex::new)
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            None => None,
+        };
 
-        dots
+        Ok(Self {
+            first: WeightedIndex::new(probabilities)?,
+            rows,
+            previous: None,
+        })
+    }
+
+    fn next(&mut self, rng: &mut impl Rng) -> usize {
+        let index = match (&self.rows, self.previous) {
+            (Some(rows), Some(previous)) => rows[previous].sample(rng),
+            _ => self.first.sample(rng),
+        };
+        self.previous = Some(index);
+        index
     }
 }
 
 pub struct ModelBuilder {
     systems: Vec<[f32; 7]>,
     color_schemas: Vec<ColorScheme>,
+    transitions: Option<Vec<Vec<f32>>>,
+
+    algorithm: Algorithm,
 
     iterations: u32,
+    burn_in: u32,
+    start_point: StartPoint,
+
+    generations: u32,
+    seed_shape: SeedShape,
+    seed_size: f32,
+    bitmap: Vec<Point2D>,
+
+    coloring: PointColoring,
+    gradient: Gradient,
+    history_length: u32,
+
     radius: f32,
+    seed: Option<u64>,
 }
 
 impl Default for ModelBuilder {
@@ -76,9 +321,25 @@ impl Default for ModelBuilder {
         Self {
             systems: vec![],
             color_schemas: vec![],
+            transitions: None,
+
+            algorithm: Algorithm::default(),
 
             iterations: DEFAULT_ITERATIONS,
+            burn_in: DEFAULT_BURN_IN,
+            start_point: StartPoint::default(),
+
+            generations: DEFAULT_GENERATIONS,
+            seed_shape: SeedShape::default(),
+            seed_size: seed::DEFAULT_SEED_SIZE,
+            bitmap: vec![],
+
+            coloring: PointColoring::default(),
+            gradient: Gradient::default(),
+            history_length: coloring::DEFAULT_HISTORY_LENGTH,
+
             radius: DEFAULT_RADIUS,
+            seed: None,
         }
     }
 }
@@ -94,22 +355,317 @@ impl ModelBuilder {
         self
     }
 
+    pub fn with_transitions(mut self, transitions: Option<Vec<Vec<f32>>>) -> Self {
+        self.transitions = transitions;
+        self
+    }
+
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
     pub fn with_iterations(mut self, iterations: u32) -> Self {
         self.iterations = iterations;
         self
     }
 
+    pub fn with_burn_in(mut self, burn_in: u32) -> Self {
+        self.burn_in = burn_in;
+        self
+    }
+
+    pub fn with_start_point(mut self, start_point: StartPoint) -> Self {
+        self.start_point = start_point;
+        self
+    }
+
+    pub fn with_generations(mut self, generations: u32) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    pub fn with_seed_shape(mut self, seed_shape: SeedShape) -> Self {
+        self.seed_shape = seed_shape;
+        self
+    }
+
+    pub fn with_seed_size(mut self, seed_size: f32) -> Self {
+        self.seed_size = seed_size;
+        self
+    }
+
+    pub fn with_bitmap(mut self, bitmap: Vec<Point2D>) -> Self {
+        self.bitmap = bitmap;
+        self
+    }
+
+    pub fn with_coloring(mut self, coloring: PointColoring) -> Self {
+        self.coloring = coloring;
+        self
+    }
+
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn with_history_length(mut self, history_length: u32) -> Self {
+        self.history_length = history_length;
+        self
+    }
+
     pub fn with_radius(mut self, radius: f32) -> Self {
         self.radius = radius;
         self
     }
 
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
     pub fn build(self) -> Model {
         Model {
             systems: self.systems,
             color_schemas: self.color_schemas,
+            transitions: self.transitions,
+            algorithm: self.algorithm,
             iterations: self.iterations,
+            burn_in: self.burn_in,
+            start_point: self.start_point,
+            generations: self.generations,
+            seed_shape: self.seed_shape,
+            seed_size: self.seed_size,
+            bitmap: self.bitmap,
+            coloring: self.coloring,
+            gradient: self.gradient,
+            history_length: self.history_length,
             radius: self.radius,
+            seed: self.seed,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ModelError {
+    #[error("Transforms cannot be picked by their probabilities.")]
+    BadWeights(#[from] WeightedError),
+
+    #[error("The first map has no single fixed point, so it can't be a start point.")]
+    NoFixedPoint,
+
+    #[error("The initial shape has no points. If it's a bitmap, load a picture first.")]
+    EmptySeedShape,
+
+    #[error("Too many dots for the deterministic algorithm. Lower the generations.")]
+    TooManyDots(f64),
+}
+
+impl ModelError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadWeights(err) => Some(err.to_string()),
+            Self::TooManyDots(total) => Some(format!(
+                "Dots: {:.0}\nLimit: {}",
+                total, MAX_DETERMINISTIC_DOTS
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Drawing Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_dots() {
+        let build = || {
+            ModelBuilder::default()
+                .with_systems(vec![
+                    [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.5],
+                    [0.5, 0.0, 0.0, 0.5, 1.0, 1.0, 0.5],
+                ])
+                .with_color_schemas(vec![ColorScheme::Random, ColorScheme::Random])
+                .with_iterations(1000)
+                .with_seed(Some(42))
+                .build()
+                .dots()
+                .unwrap_or_default()
+        };
+
+        let (first, second) = (build(), build());
+
+        assert!(!first.is_empty());
+        assert_eq!(first.len(), second.len());
+        assert!(first
+            .iter()
+            .zip(second.iter())
+            .all(|(a, b)| a.point == b.point && a.color == b.color));
+    }
+
+    #[test]
+    fn zero_weights_error() {
+        let result = ModelBuilder::default()
+            .with_systems(vec![[0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0]])
+            .with_color_schemas(vec![ColorScheme::Standard])
+            .build()
+            .dots();
+
+        assert!(matches!(result, Err(ModelError::BadWeights(_))));
+    }
+
+    #[test]
+    fn fixed_point_start() {
+        let dots = ModelBuilder::default()
+            .with_systems(vec![[0.5, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0]])
+            .with_color_schemas(vec![ColorScheme::Standard])
+            .with_iterations(10)
+            .with_start_point(StartPoint::FixedPoint)
+            .build()
+            .dots()
+            .unwrap_or_default();
+
+        assert_eq!(dots.len(), 11);
+        assert!(dots
+            .iter()
+            .all(|dot| (dot.point.x - 2.0).abs() < 1e-5
+                && (dot.point.y - 2.0).abs() < 1e-5));
+    }
+
+    #[test]
+    fn burn_in_skips_start_point() {
+        let dots = ModelBuilder::default()
+            .with_systems(vec![[0.5, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0]])
+            .with_color_schemas(vec![ColorScheme::Standard])
+            .with_iterations(10)
+            .with_burn_in(5)
+            .build()
+            .dots()
+            .unwrap_or_default();
+
+        assert_eq!(dots.len(), 10);
+        assert!(dots.iter().all(|dot| dot.point.x > 1.9));
+    }
+
+    #[test]
+    fn deterministic_generations() {
+        let dots = ModelBuilder::default()
+            .with_systems(vec![
+                [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.5],
+                [0.5, 0.0, 0.0, 0.5, 1.0, 1.0, 0.5],
+            ])
+            .with_color_schemas(vec![ColorScheme::Standard, ColorScheme::Standard])
+            .with_algorithm(Algorithm::Deterministic)
+            .with_seed_shape(SeedShape::Point)
+            .with_generations(3)
+            .build()
+            .dots()
+            .unwrap_or_default();
+
+        assert_eq!(dots.len(), 8);
+    }
+
+    #[test]
+    fn deterministic_too_many_dots() {
+        let result = ModelBuilder::default()
+            .with_systems(vec![[0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.5]; 4])
+            .with_color_schemas(vec![ColorScheme::Standard; 4])
+            .with_algorithm(Algorithm::Deterministic)
+            .with_generations(20)
+            .build()
+            .dots();
+
+        assert!(matches!(result, Err(ModelError::TooManyDots(_))));
+    }
+
+    #[test]
+    fn huge_generations_dont_wrap_around() {
+        // 2^31 + 1 generations would be a negative exponent as i32
+        let result = ModelBuilder::default()
+            .with_systems(vec![[0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.5]; 2])
+            .with_color_schemas(vec![ColorScheme::Standard; 2])
+            .with_algorithm(Algorithm::Deterministic)
+            .with_seed_shape(SeedShape::Point)
+            .with_generations(2_147_483_649)
+            .build()
+            .dots();
+
+        assert!(matches!(result, Err(ModelError::TooManyDots(_))));
+    }
+
+    #[test]
+    fn transitions_alternate_transforms() {
+        let build = |algorithm| {
+            ModelBuilder::default()
+                .with_systems(vec![
+                    [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.5],
+                    [0.5, 0.0, 0.0, 0.5, 1.0, 0.0, 0.5],
+                ])
+                .with_color_schemas(vec![ColorScheme::Standard; 2])
+                .with_transitions(Some(vec![vec![0.0, 1.0], vec![1.0, 0.0]]))
+                .with_algorithm(algorithm)
+                .with_seed_shape(SeedShape::Point)
+                .with_generations(4)
+                .with_iterations(100)
+                .with_seed(Some(random::DEFAULT_SEED))
+                .build()
+                .dots()
+                .unwrap_or_default()
+        };
+
+        // Orbit settles on the 2-cycle of the alternating maps
+        let dots = build(Algorithm::ChaosGame);
+        assert_eq!(dots.len(), 101);
+        let x = |index: usize| dots[index].point.x;
+        assert!((x(98) - x(100)).abs() < 1e-5);
+        assert!((x(99) - x(100)).abs() > 0.5);
+
+        // Only the alternating sequences of maps are applied to the seed
+        assert_eq!(build(Algorithm::Deterministic).len(), 2);
+    }
+
+    #[test]
+    fn deterministic_history_of_last_transforms() {
+        let gradient = Gradient::new(vec![
+            (0.0, egui::Color32::BLACK),
+            (1.0, egui::Color32::WHITE),
+        ]);
+        let dots = ModelBuilder::default()
+            .with_systems(vec![
+                [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.5],
+                [0.5, 0.0, 0.0, 0.5, 1.0, 1.0, 0.5],
+            ])
+            .with_color_schemas(vec![ColorScheme::Standard; 2])
+            .with_algorithm(Algorithm::Deterministic)
+            .with_seed_shape(SeedShape::Point)
+            .with_generations(3)
+            .with_coloring(PointColoring::TransformHistory)
+            .with_gradient(gradient.clone())
+            .with_history_length(2)
+            .build()
+            .dots()
+            .unwrap_or_default();
+
+        // Dots go by the last transform, then by the one before it
+        let expected = [0.0, 0.5, 0.5, 1.0].map(|value| gradient.sample(value));
+        assert_eq!(dots.len(), 8);
+        for (index, dot) in dots.iter().enumerate() {
+            assert_eq!(
+                dot.color,
+                expected[index / 2 % 2 + index / 4 * 2],
+                "{}",
+                index
+            );
         }
     }
 }
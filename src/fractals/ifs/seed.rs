@@ -0,0 +1,100 @@
+use crate::geometry::point2d::Point2D;
+use image::ImageError;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_SEED_SIZE: f32 = 1.0;
+
+// Points per side of the sampled square and triangle
+const SHAPE_RESOLUTION: usize = 20;
+// Longest side of a loaded bitmap after downscaling, in pixels
+const BITMAP_MAX_SIDE: u32 = 64;
+// Opaque pixels darker than this luma value become points of the shape
+const BITMAP_LUMA_THRESHOLD: u8 = 128;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum SeedShape {
+    #[strum(serialize = "Point")]
+    #[serde(rename = "Point")]
+    Point,
+
+    #[default]
+    #[strum(serialize = "Square")]
+    #[serde(rename = "Square")]
+    Square,
+
+    #[strum(serialize = "Triangle")]
+    #[serde(rename = "Triangle")]
+    Triangle,
+
+    #[strum(serialize = "Bitmap")]
+    #[serde(rename = "Bitmap")]
+    Bitmap,
+}
+
+impl SeedShape {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            SeedShape::Point,
+            SeedShape::Square,
+            SeedShape::Triangle,
+            SeedShape::Bitmap,
+        ]
+        .into_iter()
+    }
+
+    // Points of the shape with the given side length, bitmap is used only for the bitmap shape
+    pub fn points(&self, size: f32, bitmap: &[Point2D]) -> Vec<Point2D> {
+        let step = size / (SHAPE_RESOLUTION - 1) as f32;
+
+        match self {
+            SeedShape::Point => vec![Point2D::new(0.0, 0.0)],
+            SeedShape::Square => {
+                let mut points = Vec::with_capacity(SHAPE_RESOLUTION * SHAPE_RESOLUTION);
+                for i in 0..SHAPE_RESOLUTION {
+                    for j in 0..SHAPE_RESOLUTION {
+                        points.push(Point2D::new(i as f32 * step, j as f32 * step));
+                    }
+                }
+                points
+            },
+            SeedShape::Triangle => {
+                // Equilateral triangle, sampled row by row from the base to the apex
+                let height = size * f32::sqrt(3.0) / 2.0;
+                let mut points = Vec::new();
+                for row in 0..SHAPE_RESOLUTION {
+                    let count = SHAPE_RESOLUTION - row;
+                    let y = height * row as f32 / (SHAPE_RESOLUTION - 1) as f32;
+                    let x_start = step * row as f32 / 2.0;
+                    for column in 0..count {
+                        points.push(Point2D::new(x_start + column as f32 * step, y));
+                    }
+                }
+                points
+            },
+            SeedShape::Bitmap => bitmap
+                .iter()
+                .map(|point| Point2D::new(point.x * size, point.y * size))
+                .collect(),
+        }
+    }
+}
+
+// Decodes an image into points of unit size, that are scaled later with the shape size
+pub fn bitmap_from_bytes(bytes: &[u8]) -> Result<Vec<Point2D>, ImageError> {
+    let image = image::load_from_memory(bytes)?
+        .thumbnail(BITMAP_MAX_SIDE, BITMAP_MAX_SIDE)
+        .to_luma_alpha8();
+
+    let side = image.width().max(image.height()) as f32;
+
+    Ok(image
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| {
+            pixel.0[0] < BITMAP_LUMA_THRESHOLD && pixel.0[1] >= BITMAP_LUMA_THRESHOLD
+        })
+        .map(|(x, y, _)| {
+            Point2D::new(x as f32 / side, (image.height() - 1 - y) as f32 / side)
+        })
+        .collect())
+}
@@ -1,5 +1,12 @@
+use crate::fractals::ifs::coloring::PointColoring;
+use crate::fractals::ifs::model::{Algorithm, StartPoint};
+use crate::fractals::ifs::seed;
+use crate::fractals::ifs::seed::SeedShape;
 use crate::fractals::ifs::state::IfsState;
 use crate::fractals::ifs::validation::ValidationError;
+use crate::fractals::ifs::{coloring, model};
+use crate::geometry::point2d::Point2D;
+use crate::ui::styles::gradient::GradientStopDto;
 use serde::{Deserialize, Serialize};
 
 pub fn deserialize(json: String) -> serde_json::Result<IfsDto> {
@@ -9,15 +16,114 @@ pub fn deserialize(json: String) -> serde_json::Result<IfsDto> {
 pub fn serialize(state: &IfsState) -> serde_json::Result<String> {
     let dto = IfsDto {
         systems: state.systems.clone(),
+        transitions: state.is_recurrent.then(|| state.transitions.clone()),
+        algorithm: state.algorithm,
+        burn_in: state.burn_in,
+        start_point: state.start_point,
+        generations: state.generations,
+        seed_shape: state.seed_shape,
+        seed_size: state.seed_size,
+        bitmap: match state.seed_shape {
+            SeedShape::Bitmap => state
+                .bitmap
+                .iter()
+                .map(|point| [point.x, point.y])
+                .collect(),
+            _ => vec![],
+        },
+        coloring: state.active_coloring(),
+        gradient: (&state.gradient).into(),
+        history_length: state.history_length,
+        seed: state.seed,
     };
 
     serde_json::to_string_pretty(&dto)
 }
 
+// Plain maps with the default settings, for the modes, that generate an IFS
+pub fn serialize_systems(systems: Vec<[f32; 7]>) -> serde_json::Result<String> {
+    let dto = IfsDto {
+        systems,
+        transitions: None,
+        algorithm: Algorithm::default(),
+        burn_in: model::DEFAULT_BURN_IN,
+        start_point: StartPoint::default(),
+        generations: model::DEFAULT_GENERATIONS,
+        seed_shape: SeedShape::default(),
+        seed_size: seed::DEFAULT_SEED_SIZE,
+        bitmap: vec![],
+        coloring: PointColoring::default(),
+        gradient: vec![],
+        history_length: coloring::DEFAULT_HISTORY_LENGTH,
+        seed: None,
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_generations() -> u32 {
+    model::DEFAULT_GENERATIONS
+}
+
+fn default_seed_size() -> f32 {
+    seed::DEFAULT_SEED_SIZE
+}
+
+fn default_history_length() -> u32 {
+    coloring::DEFAULT_HISTORY_LENGTH
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct IfsDto {
     #[serde(rename = "Systems")]
     systems: Vec<[f32; 7]>,
+
+    // Rows are the previous systems, columns are the next ones
+    #[serde(
+        rename = "Transitions",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    transitions: Option<Vec<Vec<f32>>>,
+
+    #[serde(rename = "Algorithm", default)]
+    algorithm: Algorithm,
+
+    #[serde(rename = "Burn-in", default)]
+    burn_in: u32,
+
+    #[serde(rename = "Start Point", default)]
+    start_point: StartPoint,
+
+    #[serde(rename = "Generations", default = "default_generations")]
+    generations: u32,
+
+    // Shape of the deterministic algorithm, "Seed" is the key of the random seed
+    #[serde(rename = "Initial Shape", default)]
+    seed_shape: SeedShape,
+
+    #[serde(rename = "Initial Size", default = "default_seed_size")]
+    seed_size: f32,
+
+    // Points of the bitmap shape, of unit size
+    #[serde(
+        rename = "Initial Bitmap",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    bitmap: Vec<[f32; 2]>,
+
+    #[serde(rename = "Coloring", default)]
+    coloring: PointColoring,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+
+    #[serde(rename = "History Length", default = "default_history_length")]
+    history_length: u32,
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
 }
 
 impl IfsDto {
@@ -28,6 +134,26 @@ impl IfsDto {
         for system in self.systems {
             state.push_system(system);
         }
+        if let Some(transitions) = self.transitions {
+            state.is_recurrent = true;
+            state.transitions = transitions;
+        }
+        state.algorithm = self.algorithm;
+        state.burn_in = self.burn_in;
+        state.start_point = self.start_point;
+        state.generations = self.generations;
+        state.seed_shape = self.seed_shape;
+        state.seed_size = self.seed_size;
+        state.bitmap = self
+            .bitmap
+            .into_iter()
+            .map(|[x, y]| Point2D::new(x, y))
+            .collect();
+        state.is_coloring_enabled = self.coloring.is_gradient_based();
+        state.coloring = self.coloring;
+        state.gradient = self.gradient.into();
+        state.history_length = self.history_length;
+        state.seed = self.seed;
 
         let result = state.initialize();
         if result.is_err() {
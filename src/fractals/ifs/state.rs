@@ -1,9 +1,13 @@
-use crate::fractals::ifs::model::ModelBuilder;
+use crate::fractals::ifs::coloring::PointColoring;
+use crate::fractals::ifs::model::{Algorithm, ModelBuilder, ModelError, StartPoint};
+use crate::fractals::ifs::seed::SeedShape;
 use crate::fractals::ifs::validation::ValidationError;
-use crate::fractals::ifs::{model, validation};
+use crate::fractals::ifs::{coloring, model, seed, validation};
 use crate::geometry::dot::Dot;
+use crate::geometry::point2d::Point2D;
 use crate::ui::components::canvas::CanvasParams;
 use crate::ui::styles::colors::ColorScheme;
+use crate::ui::styles::gradient::Gradient;
 use egui::Shape;
 
 pub struct IfsState {
@@ -14,11 +18,29 @@ pub struct IfsState {
 
     pub systems: Vec<[f32; 7]>,
 
+    // Recurrent IFS picks the next system by the row of the previous one
+    pub is_recurrent: bool,
+    pub transitions: Vec<Vec<f32>>,
+
     pub is_coloring_enabled: bool,
     pub color_schemas: Vec<ColorScheme>,
+    pub coloring: PointColoring,
+    pub gradient: Gradient,
+    pub history_length: u32,
+
+    pub algorithm: Algorithm,
 
     pub iterations: u32,
+    pub burn_in: u32,
+    pub start_point: StartPoint,
+
+    pub generations: u32,
+    pub seed_shape: SeedShape,
+    pub seed_size: f32,
+    pub bitmap: Vec<Point2D>,
+
     pub radius_cm: f32,
+    pub seed: Option<u64>,
 }
 
 const DEFAULT_SYSTEM: [f32; 7] = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0];
@@ -33,33 +55,72 @@ impl Default for IfsState {
 
             systems: vec![DEFAULT_SYSTEM],
 
+            is_recurrent: false,
+            transitions: vec![vec![1.0]],
+
             is_coloring_enabled: false,
             color_schemas: vec![ColorScheme::Standard],
+            coloring: PointColoring::default(),
+            gradient: Gradient::default(),
+            history_length: coloring::DEFAULT_HISTORY_LENGTH,
+
+            algorithm: Algorithm::default(),
 
             iterations: model::DEFAULT_ITERATIONS,
+            burn_in: model::DEFAULT_BURN_IN,
+            start_point: StartPoint::default(),
+
+            generations: model::DEFAULT_GENERATIONS,
+            seed_shape: SeedShape::default(),
+            seed_size: seed::DEFAULT_SEED_SIZE,
+            bitmap: vec![],
+
             radius_cm: model::DEFAULT_RADIUS,
+            seed: None,
         }
     }
 }
 
 impl IfsState {
-    pub fn shapes(&mut self, params: &CanvasParams) -> Vec<Shape> {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Result<Vec<Shape>, ModelError> {
         if self.is_drawing_requested() {
             self.is_drawing_requested = false;
+            // Previous dots must not stay on canvas if the drawing fails
+            self.dots = vec![];
             self.dots = ModelBuilder::default()
                 .with_systems(self.systems.clone())
                 .with_color_schemas(self.color_schemas.clone())
+                .with_transitions(self.is_recurrent.then(|| self.transitions.clone()))
+                .with_algorithm(self.algorithm)
                 .with_iterations(self.iterations)
+                .with_burn_in(self.burn_in)
+                .with_start_point(self.start_point)
+                .with_generations(self.generations)
+                .with_seed_shape(self.seed_shape)
+                .with_seed_size(self.seed_size)
+                .with_bitmap(self.bitmap.clone())
+                .with_coloring(self.active_coloring())
+                .with_gradient(self.gradient.clone())
+                .with_history_length(self.history_length)
                 .with_radius(self.radius_cm)
+                .with_seed(self.seed)
                 .build()
-                .dots();
+                .dots()?;
         }
 
-        self.dots
+        Ok(self
+            .dots
             .clone()
             .into_iter()
             .map(|dot| dot.to_screen(params).to_shape())
-            .collect()
+            .collect())
+    }
+
+    pub fn active_coloring(&self) -> PointColoring {
+        match self.is_coloring_enabled {
+            true => self.coloring,
+            false => PointColoring::BySystem,
+        }
     }
 
     pub fn is_initialized(&self) -> bool {
@@ -70,6 +131,14 @@ impl IfsState {
         validation::systems_exist(&self.systems)?;
         validation::probability_range(&self.systems)?;
         validation::probability_sum(&self.systems)?;
+        validation::probability_weights(&self.systems)?;
+        validation::generations_range(self.generations)?;
+        validation::seed_size_range(self.seed_size)?;
+        if self.is_recurrent {
+            validation::transitions_size(&self.systems, &self.transitions)?;
+            validation::transitions_stochastic(&self.transitions)?;
+            validation::transitions_irreducible(&self.transitions)?;
+        }
 
         self.is_initialized = true;
 
@@ -91,13 +160,19 @@ impl IfsState {
     pub fn add_empty_system(&mut self) {
         self.reset_initialization();
 
-        self.systems.push(DEFAULT_SYSTEM);
-        self.color_schemas.push(ColorScheme::Standard);
+        self.push_system(DEFAULT_SYSTEM);
     }
 
+    // New system gets a uniform row, but nothing leads to it, until its column is edited
     pub fn push_system(&mut self, system: [f32; 7]) {
         self.systems.push(system);
         self.color_schemas.push(ColorScheme::Standard);
+
+        let count = self.systems.len();
+        for row in &mut self.transitions {
+            row.push(0.0);
+        }
+        self.transitions.push(vec![1.0 / count as f32; count]);
     }
 
     pub fn remove_system(&mut self, index: usize) {
@@ -107,10 +182,49 @@ impl IfsState {
 
         self.systems.remove(index);
         self.color_schemas.remove(index);
+
+        if index < self.transitions.len() {
+            self.transitions.remove(index);
+        }
+        for row in &mut self.transitions {
+            if index < row.len() {
+                row.remove(index);
+            }
+        }
     }
 
     pub fn empty_systems(&mut self) {
         self.systems = vec![];
         self.color_schemas = vec![];
+        self.transitions = vec![];
+    }
+
+    // Every row gets the probabilities of the systems, so the chain picks them
+    // the same way, as the plain IFS does
+    pub fn reset_transitions(&mut self) {
+        self.reset_initialization();
+
+        let sum: f32 = self.systems.iter().map(|system| system[6]).sum();
+        let row: Vec<f32> = self
+            .systems
+            .iter()
+            .map(|system| match sum > 0.0 {
+                true => system[6] / sum,
+                false => 1.0 / self.systems.len() as f32,
+            })
+            .collect();
+        self.transitions = vec![row; self.systems.len()];
+    }
+
+    // Rows with no transitions at all are left as they are
+    pub fn normalize_transitions(&mut self) {
+        self.reset_initialization();
+
+        for row in &mut self.transitions {
+            let sum: f32 = row.iter().sum();
+            if sum > 0.0 {
+                row.iter_mut().for_each(|value| *value /= sum);
+            }
+        }
     }
 }
@@ -1,6 +1,7 @@
 use crate::geometry::dot::{Dot, DotBuilder};
 use crate::geometry::point2d::Point2D;
 use crate::ui::styles::colors::ColorScheme;
+use rand::Rng;
 
 pub struct EquationSystem {
     a: f32,
@@ -40,7 +41,20 @@ impl EquationSystem {
         self.p
     }
 
-    pub fn next_dot(&self, dot: &Dot) -> Dot {
+    pub fn fixed_point(&self) -> Option<Point2D> {
+        // Solving (I - M) * p = t, where M is the linear part and t is the translation
+        let determinant = (1.0 - self.a) * (1.0 - self.e) - self.b * self.d;
+        if determinant.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let x = (self.c * (1.0 - self.e) + self.b * self.f) / determinant;
+        let y = (self.f * (1.0 - self.a) + self.d * self.c) / determinant;
+
+        Some(Point2D::new(x, y))
+    }
+
+    pub fn next_dot(&self, dot: &Dot, rng: &mut impl Rng) -> Dot {
         let point = &dot.point;
 
         let x = self.a * point.x + self.b * point.y + self.c;
@@ -48,7 +62,7 @@ impl EquationSystem {
 
         DotBuilder::default()
             .with_center(Point2D::new(x, y))
-            .with_color(self.color_scheme.get_color())
+            .with_color(self.color_scheme.get_color(rng))
             .with_radius_centimeters(self.radius)
             .build()
     }
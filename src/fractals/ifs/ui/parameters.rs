@@ -1,4 +1,5 @@
 use crate::context::Context;
+use crate::fractals::ifs::coloring::PointColoring;
 use crate::fractals::ifs::serialization;
 use crate::io;
 use crate::io::filter::FileFilter;
@@ -50,6 +51,44 @@ impl Window for IfsParametersWindow {
                     "With colors",
                 );
 
+                if context.ifs_state.is_coloring_enabled {
+                    ui.add_space(5.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Coloring:");
+                        egui::ComboBox::from_id_salt("PointColoring")
+                            .selected_text(format!("{}", context.ifs_state.coloring))
+                            .show_ui(ui, |ui| {
+                                for coloring in PointColoring::iter() {
+                                    ui.selectable_value(
+                                        &mut context.ifs_state.coloring,
+                                        coloring,
+                                        coloring.to_string(),
+                                    );
+                                }
+                            });
+
+                        if context.ifs_state.coloring == PointColoring::TransformHistory {
+                            ui.label("History Length:");
+                            ui.add(
+                                DragValue::new(&mut context.ifs_state.history_length)
+                                    .speed(1)
+                                    .range(1..=100),
+                            )
+                            .on_hover_text("How many last transforms are blended into the color.");
+                        }
+                    });
+
+                    if context.ifs_state.coloring.is_gradient_based() {
+                        ui.collapsing("Gradient", |ui| {
+                            context.ifs_state.gradient.show_editor(ui, "IfsPoints");
+                        });
+                    }
+                }
+
+                let is_coloring_by_system = context.ifs_state.is_coloring_enabled
+                    && !context.ifs_state.coloring.is_gradient_based();
+
                 ui.add_space(10.0);
 
                 egui::ScrollArea::vertical()
@@ -57,7 +96,7 @@ impl Window for IfsParametersWindow {
                     .show(ui, |ui| {
                         let mut rule_removed: (bool, usize) = (false, 0);
 
-                        let grid_columns = 8 + if context.ifs_state.is_coloring_enabled {
+                        let grid_columns = 9 + if is_coloring_by_system {
                             2
                         } else {
                             0
@@ -66,6 +105,7 @@ impl Window for IfsParametersWindow {
                             .num_columns(grid_columns)
                             .striped(true)
                             .show(ui, |ui| {
+                                ui.label(RichText::new("#").strong());
                                 ui.label(RichText::new("A").strong());
                                 ui.label(RichText::new("B").strong());
                                 ui.label(RichText::new("D").strong());
@@ -78,6 +118,8 @@ impl Window for IfsParametersWindow {
                                 for (index_system, system) in
                                     context.ifs_state.systems.iter_mut().enumerate()
                                 {
+                                    ui.label((index_system + 1).to_string());
+
                                     for element in &mut system[0..=5] {
                                         if ui
                                             .add(
@@ -114,7 +156,7 @@ impl Window for IfsParametersWindow {
                                         ColorScheme::Fixed(color) => *color,
                                         _ => colors::BLACK,
                                     };
-                                    if context.ifs_state.is_coloring_enabled {
+                                    if is_coloring_by_system {
                                         egui::ComboBox::from_id_salt(format!("ColorParameter{}", index_system))
                                             .selected_text(format!("{}", &scheme))
                                             .show_ui(ui, |ui| {
@@ -142,7 +184,7 @@ impl Window for IfsParametersWindow {
                                                 egui::color_picker::Alpha::Opaque,
                                             );
                                         }
-                                    } else {
+                                    } else if !context.ifs_state.is_coloring_enabled {
                                         for color in &mut context.ifs_state.color_schemas {
                                             *color = ColorScheme::Standard;
                                         }
@@ -165,6 +207,84 @@ impl Window for IfsParametersWindow {
                     }
                 });
 
+                ui.add_space(10.0);
+
+                if ui
+                    .checkbox(&mut context.ifs_state.is_recurrent, "Recurrent")
+                    .on_hover_text(
+                        "The next system is picked by the row of the previous one\n\
+                         in the transition matrix, P only picks the first system.",
+                    )
+                    .changed()
+                {
+                    if context.ifs_state.is_recurrent {
+                        context.ifs_state.reset_transitions();
+                    }
+                    reset_initialization = true;
+                }
+
+                if context.ifs_state.is_recurrent {
+                    ui.add_space(5.0);
+
+                    egui::ScrollArea::both()
+                        .id_salt("TransitionScroll")
+                        .max_height(self.height - 30.0)
+                        .show(ui, |ui| {
+                            let count = context.ifs_state.transitions.len();
+                            Grid::new("TransitionGrid")
+                                .num_columns(count + 1)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new("From \\ To").strong());
+                                    for index in 1..=count {
+                                        ui.label(RichText::new(index.to_string()).strong());
+                                    }
+                                    ui.end_row();
+
+                                    for (index, row) in
+                                        context.ifs_state.transitions.iter_mut().enumerate()
+                                    {
+                                        ui.label(RichText::new((index + 1).to_string()).strong());
+                                        for value in row.iter_mut() {
+                                            if ui
+                                                .add(
+                                                    DragValue::new(value)
+                                                        .speed(0.01)
+                                                        .range(0.0..=1.0),
+                                                )
+                                                .on_hover_text(
+                                                    "Hint: Every row has to sum to 1.",
+                                                )
+                                                .changed()
+                                            {
+                                                reset_initialization = true;
+                                            }
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+
+                    ui.add_space(5.0);
+
+                    ui.columns(2, |columns| {
+                        columns[0].vertical_centered_justified(|ui| {
+                            if ui.button("Normalize Rows").clicked() {
+                                context.ifs_state.normalize_transitions();
+                            }
+                        });
+                        columns[1].vertical_centered_justified(|ui| {
+                            if ui
+                                .button("Reset to Probabilities")
+                                .on_hover_text("Every row gets the P column.")
+                                .clicked()
+                            {
+                                context.ifs_state.reset_transitions();
+                            }
+                        });
+                    });
+                }
+
                 ui.vertical_centered_justified(|ui| {
                     if ui.button("Save to File...").clicked() {
                         let json = match serialization::serialize(&context.ifs_state) {
@@ -1,10 +1,15 @@
 use crate::context::Context;
 use crate::fractals::ifs::examples::Example;
+use crate::fractals::ifs::model::{Algorithm, StartPoint};
+use crate::fractals::ifs::seed;
+use crate::fractals::ifs::seed::SeedShape;
 use crate::fractals::ifs::serialization;
 use crate::fractals::ifs::state::IfsState;
 use crate::fractals::ifs::ui::parameters::IfsParametersWindow;
+use crate::fractals::ifs::validation;
 use crate::io;
 use crate::io::filter::FileFilter;
+use crate::math::random;
 use crate::ui::styles::colors;
 use crate::ui::windows::message::MessageWindow;
 use crossbeam::channel::{unbounded, Receiver, Sender};
@@ -14,14 +19,21 @@ use indoc::indoc;
 pub struct IfsSettingsBlock {
     json_sender: Sender<String>,
     json_receiver: Receiver<String>,
+
+    bitmap_sender: Sender<Vec<u8>>,
+    bitmap_receiver: Receiver<Vec<u8>>,
 }
 
 impl Default for IfsSettingsBlock {
     fn default() -> Self {
         let (sender, receiver) = unbounded::<String>();
+        let (bitmap_sender, bitmap_receiver) = unbounded::<Vec<u8>>();
         Self {
             json_sender: sender,
             json_receiver: receiver,
+
+            bitmap_sender,
+            bitmap_receiver,
         }
     }
 }
@@ -41,14 +53,106 @@ impl IfsSettingsBlock {
         ui.add_space(10.0);
 
         Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
-            ui.label("Iterations: ");
-            ui.add(
-                DragValue::new(&mut context.ifs_state.iterations)
-                    .speed(1)
-                    .range(0..=u32::MAX),
-            );
+            ui.label("Algorithm: ");
+            egui::ComboBox::from_id_salt("Algorithm")
+                .selected_text(format!("{}", context.ifs_state.algorithm))
+                .show_ui(ui, |ui| {
+                    for algorithm in [Algorithm::ChaosGame, Algorithm::Deterministic] {
+                        ui.selectable_value(
+                            &mut context.ifs_state.algorithm,
+                            algorithm,
+                            algorithm.to_string(),
+                        );
+                    }
+                });
             ui.end_row();
 
+            match context.ifs_state.algorithm {
+                Algorithm::ChaosGame => {
+                    ui.label("Iterations: ");
+                    ui.add(
+                        DragValue::new(&mut context.ifs_state.iterations)
+                            .speed(1)
+                            .range(0..=u32::MAX),
+                    );
+                    ui.end_row();
+
+                    ui.label("Burn-in: ");
+                    ui.add(
+                        DragValue::new(&mut context.ifs_state.burn_in)
+                            .speed(1)
+                            .range(0..=u32::MAX),
+                    )
+                    .on_hover_text("Iterations that are computed, but not drawn.");
+                    ui.end_row();
+
+                    ui.label("Start Point: ");
+                    egui::ComboBox::from_id_salt("StartPoint")
+                        .selected_text(format!("{}", context.ifs_state.start_point))
+                        .show_ui(ui, |ui| {
+                            for start_point in [
+                                StartPoint::Origin,
+                                StartPoint::Random,
+                                StartPoint::FixedPoint,
+                            ] {
+                                ui.selectable_value(
+                                    &mut context.ifs_state.start_point,
+                                    start_point,
+                                    start_point.to_string(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+                },
+                Algorithm::Deterministic => {
+                    ui.label("Generations: ");
+                    ui.add(
+                        DragValue::new(&mut context.ifs_state.generations)
+                            .speed(1)
+                            .range(0..=validation::MAX_GENERATIONS),
+                    )
+                    .on_hover_text("How many times every map is applied to the shape.");
+                    ui.end_row();
+
+                    ui.label("Initial Shape: ");
+                    egui::ComboBox::from_id_salt("SeedShape")
+                        .selected_text(format!("{}", context.ifs_state.seed_shape))
+                        .show_ui(ui, |ui| {
+                            for seed_shape in SeedShape::iter() {
+                                ui.selectable_value(
+                                    &mut context.ifs_state.seed_shape,
+                                    seed_shape,
+                                    seed_shape.to_string(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Initial Size: ");
+                    ui.add(
+                        DragValue::new(&mut context.ifs_state.seed_size)
+                            .speed(0.01)
+                            .range(0.01..=f32::MAX)
+                            .suffix(" cm."),
+                    );
+                    ui.end_row();
+
+                    if context.ifs_state.seed_shape == SeedShape::Bitmap {
+                        ui.label("Bitmap: ");
+                        if ui
+                            .button("Open Image...")
+                            .on_hover_text(
+                                "Dark pixels of the picture become the initial shape.",
+                            )
+                            .clicked()
+                        {
+                            self.pick_bitmap(context);
+                        }
+                        ui.end_row();
+                    }
+                },
+            }
+
             ui.label("Dot Radius: ");
             ui.add(
                 DragValue::new(&mut context.ifs_state.radius_cm)
@@ -57,6 +161,22 @@ impl IfsSettingsBlock {
                     .suffix(" cm."),
             );
             ui.end_row();
+
+            ui.label("Seed: ");
+            ui.horizontal(|ui| {
+                let mut is_seeded = context.ifs_state.seed.is_some();
+                if ui
+                    .checkbox(&mut is_seeded, "")
+                    .on_hover_text("With a fixed seed, every drawing is identical.")
+                    .changed()
+                {
+                    context.ifs_state.seed = is_seeded.then_some(random::DEFAULT_SEED);
+                }
+                if let Some(seed) = &mut context.ifs_state.seed {
+                    ui.add(DragValue::new(seed).speed(1));
+                }
+            });
+            ui.end_row();
         });
 
         ui.add_space(10.0);
@@ -87,6 +207,19 @@ impl IfsSettingsBlock {
 
         ui.add_space(10.0);
 
+        // Decoding the seed bitmap, if needed
+        if let Ok(bytes) = self.bitmap_receiver.try_recv() {
+            match seed::bitmap_from_bytes(&bytes) {
+                Ok(bitmap) => context.ifs_state.bitmap = bitmap,
+                Err(err) => {
+                    let message = format!("Image Error: {}", err);
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::error(&message)));
+                },
+            }
+        }
+
         // Deserializing from Json, if needed
         if let Ok(json) = self.json_receiver.try_recv() {
             let result = self.deserialize_state(&mut context.ifs_state, json);
@@ -168,9 +301,27 @@ impl IfsSettingsBlock {
                                     [0.85, 0.04, -0.04, 0.85, 0, 1.6, 0.85],
                                     [0.2, -0.26, 0.23, 0.22, 0, 1.6, 0.07],
                                     [-0.15, 0.28, 0.26, 0.24, 0, 0.44, 0.07]
-                                ]
+                                ],
+                                \"Algorithm\": \"Chaos Game\",
+                                \"Burn-in\": 20,
+                                \"Start Point\": \"Fixed Point\",
+                                \"Seed\": 42
                             }
 
+                            Other fields besides \"Systems\" are optional.
+                            Algorithm: \"Chaos Game\" or \"Deterministic\".
+                            Start Point: \"Origin\", \"Random\" or \"Fixed Point\".
+                            Deterministic algorithm also reads \"Generations\",
+                            \"Initial Shape\" (\"Point\", \"Square\", \"Triangle\",
+                            \"Bitmap\") and \"Initial Size\". A bitmap shape is saved
+                            as \"Initial Bitmap\", the points of the picture.
+                            Without a seed, every drawing differs.
+
+                            \"Transitions\" makes the IFS recurrent: a square matrix,
+                            row i holds the probabilities of the systems, that may
+                            follow system i. Every row has to sum to 1, and every
+                            system has to be able to follow every other.
+
                             You can find other examples in the 'assets/fractals/ifs' folder.
                         "};
                     let _ = context.windows_sender.send(Box::new(MessageWindow::help(message)));
@@ -179,6 +330,49 @@ impl IfsSettingsBlock {
         });
     }
 
+    fn pick_bitmap(&self, context: &Context) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let bytes = match io::ops_native::load_bytes_with_file_pick(FileFilter::png())
+            {
+                Some(Ok(bytes)) => bytes,
+                Some(Err(err)) => {
+                    let message = format!("File Error: {}", err);
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::error(&message)));
+                    return;
+                },
+                None => return,
+            };
+
+            let _ = self.bitmap_sender.send(bytes);
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let bitmap_sender = self.bitmap_sender.clone();
+            let windows_sender = context.windows_sender.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let bytes = match io::ops_wasm::load_bytes_with_file_pick(
+                    FileFilter::png(),
+                )
+                .await
+                {
+                    Some(Ok(bytes)) => bytes,
+                    Some(Err(err)) => {
+                        let message = format!("File Error: {}", err);
+                        let _ =
+                            windows_sender.send(Box::new(MessageWindow::error(&message)));
+                        return;
+                    },
+                    None => return,
+                };
+                let _ = bitmap_sender.send(bytes);
+            });
+        }
+    }
+
     fn deserialize_state(
         &mut self, state: &mut IfsState, json: String,
     ) -> Result<(), String> {
@@ -3,6 +3,11 @@ use thiserror::Error;
 
 const EPSILON: f32 = 1e-6;
 
+// Rows of the transition matrix are typed by hand, like 0.33, 0.33, 0.34
+const ROW_SUM_TOLERANCE: f32 = 1e-3;
+
+pub const MAX_GENERATIONS: u32 = 30;
+
 pub fn systems_exist(systems: &[[f32; 7]]) -> Result<(), ValidationError> {
     match !systems.is_empty() {
         true => Ok(()),
@@ -37,6 +42,118 @@ pub fn probability_sum(systems: &[[f32; 7]]) -> Result<(), ValidationError> {
     Ok(())
 }
 
+pub fn probability_weights(systems: &[[f32; 7]]) -> Result<(), ValidationError> {
+    let sum: f32 = systems.iter().map(|row| row[6]).sum();
+
+    if sum < EPSILON {
+        return Err(ValidationError::ZeroProbabilities);
+    }
+    Ok(())
+}
+
+pub fn generations_range(generations: u32) -> Result<(), ValidationError> {
+    if generations > MAX_GENERATIONS {
+        return Err(ValidationError::TooManyGenerations(format!(
+            "Generations: {}, maximum: {}",
+            generations, MAX_GENERATIONS
+        )));
+    }
+    Ok(())
+}
+
+pub fn seed_size_range(seed_size: f32) -> Result<(), ValidationError> {
+    if !(seed_size > 0.0 && seed_size.is_finite()) {
+        return Err(ValidationError::BadSeedSize);
+    }
+    Ok(())
+}
+
+pub fn transitions_size(
+    systems: &[[f32; 7]], transitions: &[Vec<f32>],
+) -> Result<(), ValidationError> {
+    let count = systems.len();
+
+    if transitions.len() != count || transitions.iter().any(|row| row.len() != count) {
+        return Err(ValidationError::BadTransitionsSize(format!(
+            "Systems: {}\nRows: {}",
+            count,
+            transitions.len()
+        )));
+    }
+    Ok(())
+}
+
+pub fn transitions_stochastic(transitions: &[Vec<f32>]) -> Result<(), ValidationError> {
+    for (index, row) in transitions.iter().enumerate() {
+        if let Some(value) = row
+            .iter()
+            .find(|value| !(0.0..=(1.0 + EPSILON)).contains(*value))
+        {
+            return Err(ValidationError::BadTransition(format!(
+                "Row {}, value: {:.2}",
+                index + 1,
+                value
+            )));
+        }
+
+        let sum: f32 = row.iter().sum();
+        if (sum - 1.0).abs() > ROW_SUM_TOLERANCE {
+            return Err(ValidationError::BadTransitionSum(format!(
+                "Row {} sums to {:.3}",
+                index + 1,
+                sum
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+// Every system has to be reachable from the first one, and the first one from every
+// system, then any system can follow any other after a few steps
+pub fn transitions_irreducible(transitions: &[Vec<f32>]) -> Result<(), ValidationError> {
+    if transitions.is_empty() {
+        return Ok(());
+    }
+
+    let is_edge = |from: usize, to: usize| transitions[from][to] > 0.0;
+    let forward = reachable_from_first(transitions.len(), is_edge);
+    let backward = reachable_from_first(transitions.len(), |from, to| is_edge(to, from));
+
+    if let Some(index) = forward.iter().position(|is_reached| !is_reached) {
+        return Err(ValidationError::ReducibleChain(format!(
+            "System {} never follows system 1",
+            index + 1
+        )));
+    }
+    if let Some(index) = backward.iter().position(|is_reached| !is_reached) {
+        return Err(ValidationError::ReducibleChain(format!(
+            "System 1 never follows system {}",
+            index + 1
+        )));
+    }
+    Ok(())
+}
+
+fn reachable_from_first(
+    count: usize, is_edge: impl Fn(usize, usize) -> bool,
+) -> Vec<bool> {
+    let mut is_reached = vec![false; count];
+    is_reached[0] = true;
+    let mut stack = vec![0];
+
+    while let Some(from) = stack.pop() {
+        for (to, is_reached) in is_reached.iter_mut().enumerate() {
+            if !*is_reached && is_edge(from, to) {
+                *is_reached = true;
+                stack.push(to);
+            }
+        }
+    }
+
+    is_reached
+}
+
 #[derive(Error, Debug)]
 pub enum ValidationError {
     #[error("Probability have to be in range 0..=1")]
@@ -47,14 +164,39 @@ pub enum ValidationError {
 
     #[error("The provided list of systems is empty. At least one system is required.")]
     NoSystems,
+
+    #[error("All probabilities are zero. At least one system has to be picked.")]
+    ZeroProbabilities,
+
+    #[error("Too many generations for the deterministic algorithm.")]
+    TooManyGenerations(String),
+
+    #[error("The initial size has to be greater than 0.")]
+    BadSeedSize,
+
+    #[error("The transition matrix has to have a row and a column for every system.")]
+    BadTransitionsSize(String),
+
+    #[error("Transition probabilities have to be in range 0..=1")]
+    BadTransition(String),
+
+    #[error("Every row of the transition matrix has to sum to 1")]
+    BadTransitionSum(String),
+
+    #[error("Every system has to be able to follow every other through the transition matrix.")]
+    ReducibleChain(String),
 }
 
 impl ValidationError {
     pub fn additional_info(&self) -> Option<String> {
         match self {
-            Self::BadProbability(value) | Self::BadProbabilitySum(value) => {
-                Some(value.clone())
-            },
+            Self::BadProbability(value)
+            | Self::BadProbabilitySum(value)
+            | Self::TooManyGenerations(value)
+            | Self::BadTransitionsSize(value)
+            | Self::BadTransition(value)
+            | Self::BadTransitionSum(value)
+            | Self::ReducibleChain(value) => Some(value.clone()),
             _ => None,
         }
     }
@@ -71,7 +213,11 @@ impl ValidationError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fractals::ifs::examples::Example;
+    use crate::fractals::ifs::seed::SeedShape;
+    use crate::fractals::ifs::serialization;
     use crate::fractals::ifs::state::IfsState;
+    use crate::geometry::point2d::Point2D;
 
     #[test]
     fn valid_probability_range() {
@@ -155,4 +301,156 @@ mod tests {
 
         assert!(matches!(result, Err(ValidationError::BadProbabilitySum(_))));
     }
+
+    #[test]
+    fn zero_probabilities() {
+        let mut state = IfsState::default();
+        state.systems = vec![
+            [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0],
+            [0.5, 0.0, 0.0, 0.5, 1.0, 1.0, 0.0],
+        ];
+
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::ZeroProbabilities)));
+    }
+
+    fn recurrent_state(transitions: Vec<Vec<f32>>) -> IfsState {
+        let mut state = IfsState::default();
+        state.systems = vec![
+            [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.33],
+            [0.5, 0.0, 0.0, 0.5, 0.0, 1.0, 0.33],
+            [0.5, 0.0, 0.0, 0.5, 1.0, 1.0, 0.34],
+        ];
+        state.is_recurrent = true;
+        state.transitions = transitions;
+        state
+    }
+
+    #[test]
+    fn valid_transitions() {
+        let mut state = recurrent_state(vec![
+            vec![0.0, 0.5, 0.5],
+            vec![0.5, 0.0, 0.5],
+            vec![0.33, 0.33, 0.34],
+        ]);
+
+        let result = state.initialize();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn transitions_size_mismatch() {
+        let mut state = recurrent_state(vec![vec![0.5, 0.5], vec![0.5, 0.5]]);
+
+        let result = state.initialize();
+
+        assert!(matches!(
+            result,
+            Err(ValidationError::BadTransitionsSize(_))
+        ));
+    }
+
+    #[test]
+    fn transitions_row_not_stochastic() {
+        let mut state = recurrent_state(vec![
+            vec![0.0, 0.5, 0.5],
+            vec![0.5, 0.0, 0.2],
+            vec![0.33, 0.33, 0.34],
+        ]);
+
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::BadTransitionSum(_))));
+    }
+
+    #[test]
+    fn transitions_chain_reducible() {
+        // Third system never leads back to the others
+        let mut state = recurrent_state(vec![
+            vec![0.0, 0.5, 0.5],
+            vec![0.5, 0.0, 0.5],
+            vec![0.0, 0.0, 1.0],
+        ]);
+
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::ReducibleChain(_))));
+    }
+
+    #[test]
+    fn recurrent_example_is_valid() {
+        let mut state = IfsState::default();
+        let json = Example::RecurrentSquare.contents().unwrap_or_default();
+
+        let result = serialization::deserialize(json)
+            .map(|dto| dto.load(&mut state).is_ok())
+            .unwrap_or_default();
+
+        assert!(result);
+        assert!(state.is_recurrent);
+        assert_eq!(state.transitions.len(), 4);
+    }
+
+    #[test]
+    fn generations_limit_is_inclusive() {
+        assert!(generations_range(0).is_ok());
+        assert!(generations_range(MAX_GENERATIONS).is_ok());
+        assert_eq!(
+            generations_range(u32::MAX)
+                .err()
+                .and_then(|error| error.additional_info()),
+            Some("Generations: 4294967295, maximum: 30".to_string())
+        );
+    }
+
+    #[test]
+    fn loaded_generations_are_checked() {
+        let json = r#"{
+            "Systems": [[0.5, 0, 0, 0.5, 0, 0, 1]],
+            "Algorithm": "Deterministic",
+            "Generations": 2147483649
+        }"#;
+        let mut state = IfsState::default();
+
+        let result = serialization::deserialize(json.to_string())
+            .map(|dto| dto.load(&mut state))
+            .unwrap_or(Ok(()));
+
+        assert!(matches!(
+            result,
+            Err(ValidationError::TooManyGenerations(_))
+        ));
+        assert!(!state.is_initialized());
+    }
+
+    #[test]
+    fn initial_size_is_positive() {
+        assert!(seed_size_range(0.01).is_ok());
+        for seed_size in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                seed_size_range(seed_size),
+                Err(ValidationError::BadSeedSize)
+            ));
+        }
+    }
+
+    #[test]
+    fn saved_bitmap_shape_is_loaded() {
+        let mut state = IfsState::default();
+        state.systems = vec![[0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 1.0]];
+        state.seed_shape = SeedShape::Bitmap;
+        state.bitmap = vec![Point2D::new(0.0, 0.0), Point2D::new(0.5, 0.25)];
+        let json = serialization::serialize(&state).unwrap_or_default();
+
+        let mut loaded = IfsState::default();
+        let result = serialization::deserialize(json)
+            .map(|dto| dto.load(&mut loaded).is_ok())
+            .unwrap_or_default();
+
+        assert!(result);
+        assert_eq!(loaded.seed_shape, SeedShape::Bitmap);
+        assert_eq!(loaded.bitmap, state.bitmap);
+    }
 }
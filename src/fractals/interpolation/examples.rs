@@ -0,0 +1,63 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static INTERPOLATION_EXAMPLES_DIR: Dir<'_> =
+    include_dir!("./assets/fractals/interpolation/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Mountain Ridge")]
+    MountainRidge,
+
+    #[strum(serialize = "Plateau")]
+    Plateau,
+
+    #[strum(serialize = "Sawtooth")]
+    Sawtooth,
+
+    #[strum(serialize = "Stock Chart")]
+    StockChart,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::MountainRidge => PathBuf::from(r"Mountain-Ridge.json"),
+            Example::Plateau => PathBuf::from(r"Plateau.json"),
+            Example::Sawtooth => PathBuf::from(r"Sawtooth.json"),
+            Example::StockChart => PathBuf::from(r"Stock-Chart.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = INTERPOLATION_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::MountainRidge,
+            Example::Plateau,
+            Example::Sawtooth,
+            Example::StockChart,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
@@ -0,0 +1,177 @@
+use egui::Color32;
+
+pub const DEFAULT_SCALE: f32 = 0.3;
+pub const DEFAULT_DEPTH: u32 = 6;
+pub const DEFAULT_STROKE_WIDTH: f32 = 1.5;
+pub const DEFAULT_COLOR: Color32 = Color32::from_rgb(90, 200, 255);
+
+// Graph of the attractor is a polyline, so its size grows exponentially with depth
+pub const MAX_GRAPH_POINTS: usize = 1_000_000;
+
+pub fn default_points() -> Vec<[f32; 2]> {
+    vec![[-8.0, -2.0], [-3.0, 4.0], [2.0, 0.0], [8.0, 3.0]]
+}
+
+pub fn default_scales() -> Vec<f32> {
+    vec![DEFAULT_SCALE; default_points().len() - 1]
+}
+
+// Number of the graph points after the given depth
+pub fn graph_size(points_count: usize, depth: u32) -> f64 {
+    let intervals = points_count.saturating_sub(1) as f64;
+    (0..depth).fold(points_count as f64, |size, _| {
+        intervals * (size - 1.0) + 1.0
+    })
+}
+
+// Shear map of one interval: (x, y) -> (a x + e, c x + d y + f)
+#[derive(Copy, Clone, Debug)]
+pub struct Map {
+    a: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+}
+
+impl Map {
+    pub fn apply(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        [self.a * x + self.e, self.c * x + self.d * y + self.f]
+    }
+
+    // Coefficients in the order of the IFS mode: [a, b, d, e, c, f, p],
+    // where x' = a x + b y + c and y' = d x + e y + f
+    pub fn to_system(self, probability: f64) -> [f32; 7] {
+        [
+            self.a as f32,
+            0.0,
+            self.c as f32,
+            self.d as f32,
+            self.e as f32,
+            self.f as f32,
+            probability as f32,
+        ]
+    }
+}
+
+pub struct Model {
+    points: Vec<[f64; 2]>,
+    maps: Vec<Map>,
+}
+
+impl Model {
+    // Every map sends the whole interval [x0, xN] to its own interval,
+    // and the ends of the graph to the interpolation points of that interval
+    pub fn new(points: &[[f32; 2]], scales: &[f32]) -> Self {
+        debug_assert!(points.len() >= 2 && scales.len() + 1 == points.len());
+
+        let points: Vec<[f64; 2]> =
+            points.iter().map(|[x, y]| [*x as f64, *y as f64]).collect();
+        let ([x0, y0], [xn, yn]) = (points[0], points[points.len() - 1]);
+        let length = xn - x0;
+
+        let maps = points
+            .windows(2)
+            .zip(scales)
+            .map(|(pair, scale)| {
+                let ([x1, y1], [x2, y2]) = (pair[0], pair[1]);
+                let d = *scale as f64;
+
+                Map {
+                    a: (x2 - x1) / length,
+                    c: (y2 - y1 - d * (yn - y0)) / length,
+                    d,
+                    e: (xn * x1 - x0 * x2) / length,
+                    f: (xn * y1 - x0 * y2 - d * (xn * y0 - x0 * yn)) / length,
+                }
+            })
+            .collect();
+
+        Self { points, maps }
+    }
+
+    // Maps with probabilities proportional to the widths of their intervals
+    pub fn systems(&self) -> Vec<[f32; 7]> {
+        self.maps.iter().map(|map| map.to_system(map.a)).collect()
+    }
+
+    // Images of the interpolation polyline under all the maps, depth times.
+    // The maps keep the order of the arguments, so the result stays a polyline.
+    pub fn graph(&self, depth: u32) -> Vec<[f64; 2]> {
+        let mut graph = self.points.clone();
+
+        for _ in 0..depth {
+            let mut next = Vec::with_capacity(self.maps.len() * graph.len());
+            for (index, map) in self.maps.iter().enumerate() {
+                // Neighboring images share the end point
+                let skip = usize::from(index > 0);
+                next.extend(graph.iter().skip(skip).map(|point| map.apply(*point)));
+            }
+            graph = next;
+        }
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_passes_through_points() {
+        let points = default_points();
+        let model = Model::new(&points, &default_scales());
+
+        let graph = model.graph(3);
+
+        assert_eq!(graph.len() as f64, graph_size(points.len(), 3));
+        for [x, y] in points {
+            let found = graph.iter().any(|point| {
+                (point[0] - x as f64).abs() < 1e-9 && (point[1] - y as f64).abs() < 1e-9
+            });
+            assert!(found, "({}, {})", x, y);
+        }
+        assert!(graph.windows(2).all(|pair| pair[0][0] < pair[1][0]));
+    }
+
+    #[test]
+    fn zero_scales_give_polyline() {
+        let points = vec![[0.0, 0.0], [1.0, 2.0], [3.0, 1.0]];
+        let model = Model::new(&points, &[0.0, 0.0]);
+
+        // Without the vertical scaling, the attractor is the interpolation polyline
+        for [x, y] in model.graph(4) {
+            let expected = match x <= 1.0 {
+                true => 2.0 * x,
+                false => 2.0 - (x - 1.0) / 2.0,
+            };
+            assert!((y - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn maps_send_ends_to_interval_points() {
+        let points = vec![[-2.0, 1.0], [0.5, -3.0], [1.0, 2.0], [4.0, 0.0]];
+        let model = Model::new(&points, &[-0.9, 0.5, -0.2]);
+        let (first, last) = (model.points[0], model.points[3]);
+
+        for (index, map) in model.maps.iter().enumerate() {
+            let (start, end) = (map.apply(first), map.apply(last));
+            for (image, point) in [(start, points[index]), (end, points[index + 1])] {
+                assert!((image[0] - point[0] as f64).abs() < 1e-9);
+                assert!((image[1] - point[1] as f64).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn probabilities_follow_interval_widths() {
+        let model = Model::new(&[[0.0, 0.0], [1.0, 1.0], [4.0, 0.0]], &[0.3, 0.3]);
+
+        let probabilities: Vec<f32> =
+            model.systems().iter().map(|system| system[6]).collect();
+
+        assert_eq!(probabilities, vec![0.25, 0.75]);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::fractals::interpolation::model;
+use crate::fractals::interpolation::state::InterpolationState;
+use crate::fractals::interpolation::validation::ValidationError;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<InterpolationDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &InterpolationState) -> serde_json::Result<String> {
+    let dto = InterpolationDto {
+        points: state.points.clone(),
+        scales: state.scales.clone(),
+        depth: state.depth,
+        stroke_width: state.stroke_width,
+        color: [state.color.r(), state.color.g(), state.color.b()],
+        is_points_shown: state.is_points_shown,
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_depth() -> u32 {
+    model::DEFAULT_DEPTH
+}
+
+fn default_stroke_width() -> f32 {
+    model::DEFAULT_STROKE_WIDTH
+}
+
+fn default_color() -> [u8; 3] {
+    let color = model::DEFAULT_COLOR;
+    [color.r(), color.g(), color.b()]
+}
+
+fn default_is_points_shown() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct InterpolationDto {
+    #[serde(rename = "Points")]
+    points: Vec<[f32; 2]>,
+
+    // Default scale for every interval if empty
+    #[serde(rename = "Vertical Scales", default)]
+    scales: Vec<f32>,
+
+    #[serde(rename = "Depth", default = "default_depth")]
+    depth: u32,
+
+    #[serde(rename = "Stroke Width", default = "default_stroke_width")]
+    stroke_width: f32,
+
+    #[serde(rename = "Color", default = "default_color")]
+    color: [u8; 3],
+
+    #[serde(rename = "Show Points", default = "default_is_points_shown")]
+    is_points_shown: bool,
+}
+
+impl InterpolationDto {
+    pub fn load(self, state: &mut InterpolationState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.scales = match self.scales.is_empty() {
+            true => vec![model::DEFAULT_SCALE; self.points.len().saturating_sub(1)],
+            false => self.scales,
+        };
+        state.points = self.points;
+        state.depth = self.depth;
+        state.stroke_width = self.stroke_width;
+        state.is_points_shown = self.is_points_shown;
+
+        let [r, g, b] = self.color;
+        state.color = Color32::from_rgb(r, g, b);
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
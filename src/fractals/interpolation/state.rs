@@ -0,0 +1,236 @@
+use crate::fractals::interpolation::model::Model;
+use crate::fractals::interpolation::validation::ValidationError;
+use crate::fractals::interpolation::{model, validation};
+use crate::geometry::dot::DotBuilder;
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::colors;
+use egui::{Color32, Pos2, Response, Shape, Stroke};
+
+const PREVIEW_STROKE_WIDTH: f32 = 2.0;
+const POINT_RADIUS_CM: f32 = 0.15;
+
+pub struct InterpolationState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub points: Vec<[f32; 2]>,
+    pub scales: Vec<f32>,
+    pub depth: u32,
+
+    pub stroke_width: f32,
+    pub color: Color32,
+    pub is_points_shown: bool,
+
+    pub is_snapped: bool,
+    is_picking: bool,
+    picked_points: Vec<[f32; 2]>,
+
+    model: Option<Model>,
+    graph: Vec<Point2D>,
+}
+
+impl Default for InterpolationState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            points: model::default_points(),
+            scales: model::default_scales(),
+            depth: model::DEFAULT_DEPTH,
+
+            stroke_width: model::DEFAULT_STROKE_WIDTH,
+            color: model::DEFAULT_COLOR,
+            is_points_shown: true,
+
+            is_snapped: false,
+            is_picking: false,
+            picked_points: vec![],
+
+            model: None,
+            graph: vec![],
+        }
+    }
+}
+
+impl InterpolationState {
+    pub fn shapes(&mut self, params: &CanvasParams, response: &Response) -> Vec<Shape> {
+        if self.is_picking {
+            self.handle_click(params, response);
+            return self.picking_shapes(params);
+        }
+
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+            if let Some(model) = &self.model {
+                self.graph = model
+                    .graph(self.depth)
+                    .into_iter()
+                    .map(|[x, y]| Point2D::new(x as f32, y as f32))
+                    .collect();
+            }
+        }
+
+        let points: Vec<Pos2> = self
+            .graph
+            .iter()
+            .map(|point| point.to_screen(params).to_pos2())
+            .collect();
+        let mut shapes = vec![Shape::line(
+            points,
+            Stroke::new(self.stroke_width, self.color),
+        )];
+
+        if self.is_points_shown && !self.graph.is_empty() {
+            shapes.extend(self.point_shapes(&self.points, params));
+        }
+
+        shapes
+    }
+
+    // Every click on the canvas adds an interpolation point
+    fn handle_click(&mut self, params: &CanvasParams, response: &Response) {
+        if !response.clicked() {
+            return;
+        }
+        let Some(position) = response.interact_pointer_pos() else {
+            return;
+        };
+
+        let mut point = params.point_px_to_cm(Point2D::from_pos2(position));
+        if self.is_snapped {
+            let unit = params.unit_length;
+            point = Point2D::new(
+                (point.x / unit).round() * unit,
+                (point.y / unit).round() * unit,
+            );
+        }
+        self.picked_points.push([point.x, point.y]);
+    }
+
+    fn picking_shapes(&self, params: &CanvasParams) -> Vec<Shape> {
+        let points = sorted_by_x(&self.picked_points);
+
+        let line: Vec<Pos2> = points
+            .iter()
+            .map(|[x, y]| Point2D::new(*x, *y).to_screen(params).to_pos2())
+            .collect();
+        let stroke = Stroke::new(PREVIEW_STROKE_WIDTH, colors::BLACK);
+
+        let mut shapes = vec![Shape::line(line, stroke)];
+        shapes.extend(self.point_shapes(&points, params));
+        shapes
+    }
+
+    fn point_shapes<'a>(
+        &self, points: &'a [[f32; 2]], params: &'a CanvasParams,
+    ) -> impl Iterator<Item = Shape> + 'a {
+        points.iter().map(|[x, y]| {
+            DotBuilder::default()
+                .with_center(Point2D::new(*x, *y))
+                .with_color(colors::RED)
+                .with_radius_centimeters(POINT_RADIUS_CM)
+                .build()
+                .to_screen(params)
+                .to_shape()
+        })
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::points_are_valid(&self.points)?;
+        validation::scales_are_valid(&self.points, &self.scales)?;
+        validation::depth_range(self.points.len(), self.depth)?;
+        validation::stroke_width_range(self.stroke_width)?;
+
+        self.model = Some(Model::new(&self.points, &self.scales));
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    // Shear maps in the format of the IFS mode, empty until initialized
+    pub fn systems(&self) -> Vec<[f32; 7]> {
+        self.model.as_ref().map_or(vec![], |model| model.systems())
+    }
+
+    pub fn graph_size(&self) -> usize {
+        self.graph.len()
+    }
+
+    pub fn is_picking(&self) -> bool {
+        self.is_picking
+    }
+
+    pub fn picked_points_count(&self) -> usize {
+        self.picked_points.len()
+    }
+
+    pub fn start_picking(&mut self) {
+        self.is_picking = true;
+        self.picked_points = vec![];
+    }
+
+    pub fn undo_point(&mut self) {
+        self.picked_points.pop();
+    }
+
+    pub fn cancel_picking(&mut self) {
+        self.is_picking = false;
+        self.picked_points = vec![];
+    }
+
+    // Replaces the interpolation points, every interval gets the default scale
+    pub fn finish_picking(&mut self) -> Result<(), ValidationError> {
+        let points = sorted_by_x(&self.picked_points);
+        validation::points_are_valid(&points)?;
+
+        self.is_picking = false;
+        self.picked_points = vec![];
+        self.scales = vec![model::DEFAULT_SCALE; points.len() - 1];
+        self.points = points;
+
+        self.reset_initialization();
+
+        Ok(())
+    }
+
+    pub fn insert_point(&mut self, index: usize) {
+        debug_assert!(index > 0 && index < self.points.len());
+
+        // New point halves the interval, both halves keep its scale
+        let ([x1, y1], [x2, y2]) = (self.points[index - 1], self.points[index]);
+        self.points
+            .insert(index, [(x1 + x2) / 2.0, (y1 + y2) / 2.0]);
+        self.scales.insert(index, self.scales[index - 1]);
+
+        self.reset_initialization();
+    }
+
+    pub fn remove_point(&mut self, index: usize) {
+        debug_assert!(self.points.len() > 3);
+
+        self.points.remove(index);
+        self.scales.remove(index.min(self.scales.len() - 1));
+
+        self.reset_initialization();
+    }
+}
+
+fn sorted_by_x(points: &[[f32; 2]]) -> Vec<[f32; 2]> {
+    let mut points = points.to_vec();
+    points.sort_by(|first, second| first[0].total_cmp(&second[0]));
+    points
+}
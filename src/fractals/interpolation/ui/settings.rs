@@ -0,0 +1,349 @@
+use crate::context::Context;
+use crate::fractals::ifs;
+use crate::fractals::interpolation::examples::Example;
+use crate::fractals::interpolation::serialization;
+use crate::fractals::interpolation::state::InterpolationState;
+use crate::fractals::interpolation::validation::MAX_DEPTH;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct InterpolationSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for InterpolationSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl InterpolationSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.interpolation_state.is_picking() {
+                ui.label(RichText::new("Picking points...").color(colors::LIME));
+            } else if context.interpolation_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Graph Points: ");
+            ui.label(context.interpolation_state.graph_size().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        if context.interpolation_state.is_picking() {
+            self.show_picking(ui, context);
+            return;
+        }
+
+        let state = &mut context.interpolation_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Depth:");
+            changed |= ui
+                .add(Slider::new(&mut state.depth, 0..=MAX_DEPTH))
+                .on_hover_text("How many times the maps are applied to the points.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Stroke Width:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.stroke_width)
+                        .speed(0.1)
+                        .range(0.1..=10.0),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+
+            ui.label("Show Points:");
+            ui.checkbox(&mut state.is_points_shown, "");
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Interpolation Points", |ui| {
+            let mut inserted: Option<usize> = None;
+            let mut removed: Option<usize> = None;
+            Grid::new("InterpolationPointsGrid")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    let can_remove = state.points.len() > 3;
+                    for (index, point) in state.points.iter_mut().enumerate() {
+                        ui.label(format!("{}.", index + 1));
+                        ui.horizontal(|ui| {
+                            changed |= ui
+                                .add(DragValue::new(&mut point[0]).speed(0.1))
+                                .changed();
+                            changed |= ui
+                                .add(DragValue::new(&mut point[1]).speed(0.1))
+                                .changed();
+                        });
+                        if ui.add_enabled(can_remove, Button::new("Remove")).clicked() {
+                            removed = Some(index);
+                        }
+                        ui.end_row();
+
+                        // Scale of the interval, that ends at the next point
+                        if let Some(scale) = state.scales.get_mut(index) {
+                            ui.label("");
+                            changed |= ui
+                                .add(Slider::new(scale, -0.99..=0.99).text("d"))
+                                .on_hover_text("Vertical scaling of the interval.")
+                                .changed();
+                            if ui.button("Split").clicked() {
+                                inserted = Some(index + 1);
+                            }
+                            ui.end_row();
+                        }
+                    }
+                });
+            if let Some(index) = inserted {
+                state.insert_point(index);
+            }
+            if let Some(index) = removed {
+                state.remove_point(index);
+            }
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .button("Pick Points on Canvas")
+                .on_hover_text("Every click on the canvas adds a point.")
+                .clicked()
+            {
+                context.interpolation_state.start_picking();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.interpolation_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.interpolation_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    context.interpolation_state.is_initialized(),
+                    Button::new("Draw"),
+                )
+                .clicked()
+            {
+                context.interpolation_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.interpolation_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.interpolation_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.interpolation_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui
+                    .add_enabled(
+                        context.interpolation_state.is_initialized(),
+                        Button::new("Export as IFS"),
+                    )
+                    .on_hover_text("Saves the maps, so they open in the IFS mode.")
+                    .clicked()
+                {
+                    io::json::save_with_file_pick(
+                        ifs::serialization::serialize_systems(
+                            context.interpolation_state.systems(),
+                        ),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Points\": [[-8.0, -2.0], [-3.0, 4.0], [2.0, 0.0], [8.0, 3.0]],
+                                \"Vertical Scales\": [0.3, -0.4, 0.3],
+                                \"Depth\": 6
+                            }
+
+                            Points: interpolation points in centimeters, the x
+                            coordinates have to increase.
+                            Vertical Scales: one value in range (-1, 1) for every
+                            interval between the points, 0 gives a straight line.
+                            Every interval gets a map (x, y) -> (a x + e, c x + d y + f),
+                            that sends the whole graph into the interval.
+
+                            Other fields: \"Stroke Width\", \"Color\" ([r, g, b]),
+                            \"Show Points\".
+
+                            Export as IFS saves the maps with probabilities
+                            proportional to the interval widths.
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn show_picking(&mut self, ui: &mut Ui, context: &mut Context) {
+        let state = &mut context.interpolation_state;
+
+        Grid::new("PickingGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Points:");
+            ui.label(state.picked_points_count().to_string());
+            ui.end_row();
+
+            ui.label("Snap to Grid:");
+            ui.checkbox(&mut state.is_snapped, "")
+                .on_hover_text("Points are rounded to the grid units.");
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+        ui.label("Click on the canvas to add points.");
+        ui.label("The points are ordered from left to right.");
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Finish").clicked() {
+                if let Err(err) = state.finish_picking() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(state.picked_points_count() > 0, Button::new("Undo Point"))
+                .clicked()
+            {
+                state.undo_point();
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Cancel").clicked() {
+                state.cancel_picking();
+            }
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut InterpolationState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
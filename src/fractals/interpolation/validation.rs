@@ -0,0 +1,211 @@
+use crate::fractals::interpolation::model;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub const MAX_DEPTH: u32 = 20;
+
+pub fn points_are_valid(points: &[[f32; 2]]) -> Result<(), ValidationError> {
+    if points.len() < 3 {
+        return Err(ValidationError::NotEnoughPoints);
+    }
+
+    if let Some(index) = points.windows(2).position(|pair| pair[0][0] >= pair[1][0]) {
+        return Err(ValidationError::UnorderedPoints(format!(
+            "Points {} and {}: x = {} and x = {}.",
+            index + 1,
+            index + 2,
+            points[index][0],
+            points[index + 1][0]
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn scales_are_valid(
+    points: &[[f32; 2]], scales: &[f32],
+) -> Result<(), ValidationError> {
+    if scales.len() + 1 != points.len() {
+        return Err(ValidationError::BadScalesCount(format!(
+            "Intervals: {}, scales: {}.",
+            points.len().saturating_sub(1),
+            scales.len()
+        )));
+    }
+
+    if let Some(index) = scales
+        .iter()
+        .position(|scale| !(*scale > -1.0 && *scale < 1.0))
+    {
+        return Err(ValidationError::BadScaleValue(format!(
+            "Interval {}: {}.",
+            index + 1,
+            scales[index]
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn depth_range(points_count: usize, depth: u32) -> Result<(), ValidationError> {
+    if depth > MAX_DEPTH {
+        return Err(ValidationError::BadDepth);
+    }
+
+    let size = model::graph_size(points_count, depth);
+    if size > model::MAX_GRAPH_POINTS as f64 {
+        return Err(ValidationError::TooManyGraphPoints(format!(
+            "Points: {}, maximum: {}.",
+            size,
+            model::MAX_GRAPH_POINTS
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn stroke_width_range(stroke_width: f32) -> Result<(), ValidationError> {
+    if !(stroke_width > 0.0 && stroke_width.is_finite()) {
+        return Err(ValidationError::BadStrokeWidth);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("At least 3 interpolation points are required.")]
+    NotEnoughPoints,
+
+    #[error("The x coordinates of the points have to increase.")]
+    UnorderedPoints(String),
+
+    #[error("Every interval needs one vertical scale.")]
+    BadScalesCount(String),
+
+    #[error("Vertical scales have to be in range (-1, 1).")]
+    BadScaleValue(String),
+
+    #[error("Depth has to be in range 0..=20.")]
+    BadDepth,
+
+    #[error("The graph is too large, lower the depth or the number of points.")]
+    TooManyGraphPoints(String),
+
+    #[error("Stroke width has to be greater than 0.")]
+    BadStrokeWidth,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::UnorderedPoints(value)
+            | Self::BadScalesCount(value)
+            | Self::BadScaleValue(value)
+            | Self::TooManyGraphPoints(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::ifs;
+    use crate::fractals::ifs::state::IfsState;
+    use crate::fractals::interpolation::examples::Example;
+    use crate::fractals::interpolation::serialization;
+    use crate::fractals::interpolation::state::InterpolationState;
+
+    #[test]
+    fn equal_arguments_are_unordered() {
+        assert!(matches!(
+            points_are_valid(&[[0.0, 0.0], [1.0, 1.0]]),
+            Err(ValidationError::NotEnoughPoints)
+        ));
+
+        let result = points_are_valid(&[[0.0, 0.0], [1.0, 1.0], [1.0, 2.0]]);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Points 2 and 3: x = 1 and x = 1.".to_string())
+        );
+    }
+
+    #[test]
+    fn scales_are_contractions() {
+        let points = [[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]];
+        assert!(scales_are_valid(&points, &[-0.99, 0.99]).is_ok());
+
+        for scale in [-1.0, 1.0, f32::NAN] {
+            assert!(matches!(
+                scales_are_valid(&points, &[0.0, scale]),
+                Err(ValidationError::BadScaleValue(_))
+            ));
+        }
+
+        let result = scales_are_valid(&points, &[0.5]);
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Intervals: 2, scales: 1.".to_string())
+        );
+    }
+
+    #[test]
+    fn graph_size_limit() {
+        // Three points give 2^(depth + 1) + 1 graph points
+        assert!(depth_range(3, 18).is_ok());
+        assert_eq!(
+            depth_range(3, 19)
+                .err()
+                .and_then(|error| error.additional_info()),
+            Some("Points: 1048577, maximum: 1000000.".to_string())
+        );
+        assert!(matches!(
+            depth_range(2, MAX_DEPTH + 1),
+            Err(ValidationError::BadDepth)
+        ));
+    }
+
+    #[test]
+    fn exported_ifs_is_valid() {
+        let mut state = InterpolationState::default();
+        let _ = state.initialize();
+
+        let result = ifs::serialization::serialize_systems(state.systems())
+            .and_then(ifs::serialization::deserialize)
+            .map_err(|err| err.to_string())
+            .and_then(|dto| {
+                dto.load(&mut IfsState::default())
+                    .map_err(|err| err.to_string())
+            });
+
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = InterpolationState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static JULIA_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/julia/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Cantor Dust")]
+    CantorDust,
+
+    #[strum(serialize = "Dendrite")]
+    Dendrite,
+
+    #[strum(serialize = "Douady Rabbit")]
+    DouadyRabbit,
+
+    #[strum(serialize = "Rabbit (Random Orbit)")]
+    RabbitRandomOrbit,
+
+    #[strum(serialize = "San Marco")]
+    SanMarco,
+
+    #[strum(serialize = "Siegel Disk")]
+    SiegelDisk,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::CantorDust => PathBuf::from(r"Cantor-Dust.json"),
+            Example::Dendrite => PathBuf::from(r"Dendrite.json"),
+            Example::DouadyRabbit => PathBuf::from(r"Douady-Rabbit.json"),
+            Example::RabbitRandomOrbit => PathBuf::from(r"Rabbit-Random-Orbit.json"),
+            Example::SanMarco => PathBuf::from(r"San-Marco.json"),
+            Example::SiegelDisk => PathBuf::from(r"Siegel-Disk.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = JULIA_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::CantorDust,
+            Example::Dendrite,
+            Example::DouadyRabbit,
+            Example::RabbitRandomOrbit,
+            Example::SanMarco,
+            Example::SiegelDisk,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
@@ -0,0 +1,265 @@
+use crate::math::complex::Complex;
+use crate::math::random;
+use egui::Color32;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum_macros::Display;
+
+pub const DEFAULT_C: [f64; 2] = [-0.123, 0.745];
+pub const DEFAULT_POINTS: u32 = 100_000;
+pub const DEFAULT_MAX_HITS: u32 = 2;
+pub const DEFAULT_SCALE_CM: f32 = 5.0;
+pub const DEFAULT_RADIUS: f32 = 0.02;
+pub const DEFAULT_COLOR: Color32 = Color32::from_rgb(40, 40, 40);
+
+pub const MAX_POINTS: u32 = 2_000_000;
+
+// Random preimages, that are taken before the orbit comes close to the set
+const BURN_IN: u32 = 50;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Method {
+    // Random backward orbit, it visits the set with its natural measure,
+    // so the thin parts are almost never reached
+    #[strum(serialize = "Inverse Iteration (IIM)")]
+    #[serde(rename = "IIM")]
+    Inverse,
+
+    // Whole tree of preimages, a branch is cut when its cell is hit often enough
+    #[default]
+    #[strum(serialize = "Modified Inverse Iteration (MIIM)")]
+    #[serde(rename = "MIIM")]
+    ModifiedInverse,
+}
+
+impl Method {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Method::Inverse, Method::ModifiedInverse].into_iter()
+    }
+}
+
+pub struct Model {
+    c: Complex,
+    method: Method,
+    points: u32,
+    max_hits: u32,
+    cell_size: f64,
+    seed: Option<u64>,
+}
+
+impl Model {
+    pub fn points(&self) -> Vec<[f64; 2]> {
+        match self.method {
+            Method::Inverse => self.inverse(),
+            Method::ModifiedInverse => self.modified_inverse(),
+        }
+    }
+
+    fn inverse(&self) -> Vec<[f64; 2]> {
+        let mut rng = random::rng(self.seed);
+        let mut z = Complex::ONE;
+        let mut points = Vec::with_capacity(self.points as usize);
+
+        for index in 0..BURN_IN + self.points {
+            z = preimage(z, self.c, rng.gen_bool(0.5));
+            if index >= BURN_IN {
+                points.push([z.re, z.im]);
+            }
+        }
+
+        points
+    }
+
+    // Depth-first walk over the preimages of the repelling fixed point,
+    // that already lies on the set, so no burn-in is needed
+    fn modified_inverse(&self) -> Vec<[f64; 2]> {
+        let mut hits: HashMap<(i64, i64), u32> = HashMap::new();
+        let mut points = Vec::new();
+        let mut stack = vec![fixed_point(self.c)];
+
+        while let Some(z) = stack.pop() {
+            if points.len() >= self.points as usize {
+                break;
+            }
+
+            let cell = (
+                (z.re / self.cell_size).floor() as i64,
+                (z.im / self.cell_size).floor() as i64,
+            );
+            let count = hits.entry(cell).or_insert(0);
+            if *count >= self.max_hits {
+                continue;
+            }
+            *count += 1;
+
+            points.push([z.re, z.im]);
+            stack.push(preimage(z, self.c, false));
+            stack.push(preimage(z, self.c, true));
+        }
+
+        points
+    }
+}
+
+// One of the two solutions of w² + c = z
+fn preimage(z: Complex, c: Complex, is_negative: bool) -> Complex {
+    let root = (z - c).sqrt();
+    match is_negative {
+        true => -root,
+        false => root,
+    }
+}
+
+// Fixed point (1 + √(1 - 4c)) / 2, that is repelling for every c except 1/4
+fn fixed_point(c: Complex) -> Complex {
+    (Complex::ONE + (Complex::ONE - c.scale(4.0)).sqrt()).scale(0.5)
+}
+
+pub struct ModelBuilder {
+    c: [f64; 2],
+    method: Method,
+    points: u32,
+    max_hits: u32,
+    cell_size: f64,
+    seed: Option<u64>,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            c: DEFAULT_C,
+            method: Method::default(),
+            points: DEFAULT_POINTS,
+            max_hits: DEFAULT_MAX_HITS,
+            cell_size: 0.01,
+            seed: None,
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_c(mut self, c: [f64; 2]) -> Self {
+        self.c = c;
+        self
+    }
+
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    pub fn with_points(mut self, points: u32) -> Self {
+        self.points = points;
+        self
+    }
+
+    // Hits are counted in square cells of the given size, usually one pixel
+    pub fn with_max_hits(mut self, max_hits: u32, cell_size: f64) -> Self {
+        self.max_hits = max_hits;
+        self.cell_size = cell_size;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            c: Complex::new(self.c[0], self.c[1]),
+            method: self.method,
+            points: self.points,
+            max_hits: self.max_hits,
+            cell_size: self.cell_size,
+            seed: self.seed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_circle_for_zero() {
+        for method in Method::iter() {
+            let points = ModelBuilder::default()
+                .with_c([0.0, 0.0])
+                .with_method(method)
+                .with_points(1000)
+                .with_seed(Some(random::DEFAULT_SEED))
+                .build()
+                .points();
+
+            assert_eq!(points.len(), 1000);
+            for [x, y] in points {
+                assert!((x.hypot(y) - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn segment_for_minus_two() {
+        let points = ModelBuilder::default()
+            .with_c([-2.0, 0.0])
+            .with_method(Method::Inverse)
+            .with_points(1000)
+            .with_seed(Some(random::DEFAULT_SEED))
+            .build()
+            .points();
+
+        assert!(points.iter().all(|[x, y]| x.abs() <= 2.0 && *y == 0.0));
+    }
+
+    #[test]
+    fn hits_are_capped() {
+        let cell_size = 0.05;
+        let points = ModelBuilder::default()
+            .with_max_hits(3, cell_size)
+            .with_points(MAX_POINTS)
+            .build()
+            .points();
+
+        let mut hits: HashMap<(i64, i64), u32> = HashMap::new();
+        for [x, y] in &points {
+            let cell = (
+                (x / cell_size).floor() as i64,
+                (y / cell_size).floor() as i64,
+            );
+            *hits.entry(cell).or_insert(0) += 1;
+        }
+
+        // The walk ends by itself, when every cell on the set is full
+        assert!(points.len() < MAX_POINTS as usize);
+        assert!(hits.values().all(|count| *count <= 3));
+    }
+
+    #[test]
+    fn points_stay_within_escape_radius() {
+        // Julia set lies in the disk |z| <= 1/2 + √(1/4 + |c|), even for the dust
+        // at the largest valid parameter and at the parabolic c = 1/4
+        for c in [[4.0, 0.0], [0.0, -4.0], [0.25, 0.0], DEFAULT_C] {
+            let radius = 0.5 + (0.25 + c[0].hypot(c[1])).sqrt();
+
+            for method in Method::iter() {
+                let points = ModelBuilder::default()
+                    .with_c(c)
+                    .with_method(method)
+                    .with_points(2000)
+                    .with_seed(Some(random::DEFAULT_SEED))
+                    .build()
+                    .points();
+
+                assert!(!points.is_empty());
+                assert!(
+                    points.iter().all(|[x, y]| x.hypot(*y) <= radius + 1e-9),
+                    "c: {:?}, {}",
+                    c,
+                    method
+                );
+            }
+        }
+    }
+}
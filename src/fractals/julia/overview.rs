@@ -0,0 +1,57 @@
+use egui::Color32;
+
+// Part of the parameter plane, that holds the whole Mandelbrot set
+pub const RE_RANGE: [f64; 2] = [-2.25, 0.75];
+pub const IM_RANGE: [f64; 2] = [-1.25, 1.25];
+
+pub const WIDTH: usize = 240;
+pub const HEIGHT: usize = 200;
+
+const MAX_ITERATIONS: u32 = 64;
+
+// Escape-time picture of the Mandelbrot set, where the Julia sets are connected
+pub fn pixels() -> Vec<Color32> {
+    (0..WIDTH * HEIGHT)
+        .map(|index| {
+            let [re, im] =
+                pixel_to_c((index % WIDTH) as f32 + 0.5, (index / WIDTH) as f32 + 0.5);
+            color(escape_time(re, im))
+        })
+        .collect()
+}
+
+pub fn pixel_to_c(x: f32, y: f32) -> [f64; 2] {
+    [
+        RE_RANGE[0] + (RE_RANGE[1] - RE_RANGE[0]) * x as f64 / WIDTH as f64,
+        IM_RANGE[1] - (IM_RANGE[1] - IM_RANGE[0]) * y as f64 / HEIGHT as f64,
+    ]
+}
+
+pub fn c_to_pixel([re, im]: [f64; 2]) -> [f32; 2] {
+    [
+        ((re - RE_RANGE[0]) / (RE_RANGE[1] - RE_RANGE[0]) * WIDTH as f64) as f32,
+        ((IM_RANGE[1] - im) / (IM_RANGE[1] - IM_RANGE[0]) * HEIGHT as f64) as f32,
+    ]
+}
+
+fn escape_time(re: f64, im: f64) -> Option<u32> {
+    let (mut x, mut y) = (0.0f64, 0.0f64);
+    for iteration in 0..MAX_ITERATIONS {
+        if x * x + y * y > 4.0 {
+            return Some(iteration);
+        }
+        (x, y) = (x * x - y * y + re, 2.0 * x * y + im);
+    }
+
+    None
+}
+
+fn color(escape_time: Option<u32>) -> Color32 {
+    match escape_time {
+        None => Color32::BLACK,
+        Some(iteration) => {
+            let value = 255 - (iteration * 200 / MAX_ITERATIONS) as u8;
+            Color32::from_gray(value)
+        },
+    }
+}
@@ -0,0 +1,100 @@
+use crate::fractals::julia::model;
+use crate::fractals::julia::model::Method;
+use crate::fractals::julia::state::JuliaState;
+use crate::fractals::julia::validation::ValidationError;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<JuliaDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &JuliaState) -> serde_json::Result<String> {
+    let dto = JuliaDto {
+        c: state.c,
+        method: state.method,
+        points: state.points,
+        max_hits: state.max_hits,
+        scale_cm: state.scale_cm,
+        radius_cm: state.radius_cm,
+        color: [state.color.r(), state.color.g(), state.color.b()],
+        seed: state.seed,
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_c() -> [f64; 2] {
+    model::DEFAULT_C
+}
+
+fn default_points() -> u32 {
+    model::DEFAULT_POINTS
+}
+
+fn default_max_hits() -> u32 {
+    model::DEFAULT_MAX_HITS
+}
+
+fn default_scale() -> f32 {
+    model::DEFAULT_SCALE_CM
+}
+
+fn default_radius() -> f32 {
+    model::DEFAULT_RADIUS
+}
+
+fn default_color() -> [u8; 3] {
+    let color = model::DEFAULT_COLOR;
+    [color.r(), color.g(), color.b()]
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JuliaDto {
+    #[serde(rename = "C", default = "default_c")]
+    c: [f64; 2],
+
+    #[serde(rename = "Method", default)]
+    method: Method,
+
+    #[serde(rename = "Points", default = "default_points")]
+    points: u32,
+
+    #[serde(rename = "Max Hits", default = "default_max_hits")]
+    max_hits: u32,
+
+    #[serde(rename = "Scale", default = "default_scale")]
+    scale_cm: f32,
+
+    #[serde(rename = "Radius", default = "default_radius")]
+    radius_cm: f32,
+
+    #[serde(rename = "Color", default = "default_color")]
+    color: [u8; 3],
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+}
+
+impl JuliaDto {
+    pub fn load(self, state: &mut JuliaState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.c = self.c;
+        state.method = self.method;
+        state.points = self.points;
+        state.max_hits = self.max_hits;
+        state.scale_cm = self.scale_cm;
+        state.radius_cm = self.radius_cm;
+        state.seed = self.seed;
+
+        let [r, g, b] = self.color;
+        state.color = Color32::from_rgb(r, g, b);
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,108 @@
+use crate::fractals::julia::model::{Method, ModelBuilder};
+use crate::fractals::julia::validation::ValidationError;
+use crate::fractals::julia::{model, validation};
+use crate::geometry::dot::{Dot, DotBuilder};
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use egui::{Color32, Shape};
+
+pub struct JuliaState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub c: [f64; 2],
+    pub method: Method,
+    pub points: u32,
+    pub max_hits: u32,
+
+    pub scale_cm: f32,
+    pub radius_cm: f32,
+    pub color: Color32,
+    pub seed: Option<u64>,
+
+    dots: Vec<Dot>,
+}
+
+impl Default for JuliaState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            c: model::DEFAULT_C,
+            method: Method::default(),
+            points: model::DEFAULT_POINTS,
+            max_hits: model::DEFAULT_MAX_HITS,
+
+            scale_cm: model::DEFAULT_SCALE_CM,
+            radius_cm: model::DEFAULT_RADIUS,
+            color: model::DEFAULT_COLOR,
+            seed: None,
+
+            dots: vec![],
+        }
+    }
+}
+
+impl JuliaState {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Vec<Shape> {
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+
+            // Hits are capped per pixel of the canvas at the current zoom
+            let cell_size = params.value_px_to_cm(1.0) as f64 / self.scale_cm as f64;
+            let scale = self.scale_cm;
+
+            self.dots = ModelBuilder::default()
+                .with_c(self.c)
+                .with_method(self.method)
+                .with_points(self.points)
+                .with_max_hits(self.max_hits, cell_size)
+                .with_seed(self.seed)
+                .build()
+                .points()
+                .into_iter()
+                .map(|[x, y]| {
+                    DotBuilder::default()
+                        .with_center(Point2D::new(x as f32 * scale, y as f32 * scale))
+                        .with_color(self.color)
+                        .with_radius_centimeters(self.radius_cm)
+                        .build()
+                })
+                .collect();
+        }
+
+        self.dots
+            .iter()
+            .map(|dot| dot.to_screen(params).to_shape())
+            .collect()
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::c_range(self.c)?;
+        validation::points_range(self.points)?;
+        validation::max_hits_range(self.max_hits)?;
+        validation::scale_range(self.scale_cm)?;
+        validation::radius_range(self.radius_cm)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn dots_count(&self) -> usize {
+        self.dots.len()
+    }
+}
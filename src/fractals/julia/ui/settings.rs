@@ -0,0 +1,338 @@
+use crate::context::Context;
+use crate::fractals::julia::examples::Example;
+use crate::fractals::julia::model::{Method, MAX_POINTS};
+use crate::fractals::julia::serialization;
+use crate::fractals::julia::state::JuliaState;
+use crate::fractals::julia::{overview, validation};
+use crate::io;
+use crate::math::random;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{
+    pos2, vec2, Button, Color32, ColorImage, DragValue, Grid, Rect, RichText, Sense,
+    Stroke, TextureHandle, TextureOptions, Ui,
+};
+use indoc::indoc;
+
+const MARKER_RADIUS: f32 = 4.0;
+
+pub struct JuliaSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+
+    // Mandelbrot set for picking c, rendered on the first showing
+    overview: Option<TextureHandle>,
+}
+
+impl Default for JuliaSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+
+            overview: None,
+        }
+    }
+}
+
+impl JuliaSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.julia_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Dots: ");
+            ui.label(context.julia_state.dots_count().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.julia_state;
+        let mut changed = self.show_overview(ui, state);
+
+        ui.add_space(5.0);
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("c:");
+            ui.horizontal(|ui| {
+                let re = ui.add(DragValue::new(&mut state.c[0]).speed(0.001));
+                let im = ui.add(DragValue::new(&mut state.c[1]).speed(0.001).suffix("i"));
+                changed |= re.changed() || im.changed();
+            });
+            ui.end_row();
+
+            ui.label("Method:");
+            egui::ComboBox::from_id_salt("JuliaMethod")
+                .selected_text(format!("{}", state.method))
+                .show_ui(ui, |ui| {
+                    for method in Method::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.method,
+                                method,
+                                method.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Points:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.points)
+                        .speed(1000)
+                        .range(1..=MAX_POINTS),
+                )
+                .changed();
+            ui.end_row();
+
+            match state.method {
+                Method::Inverse => {
+                    ui.label("Seed: ");
+                    ui.horizontal(|ui| {
+                        let mut is_seeded = state.seed.is_some();
+                        if ui
+                            .checkbox(&mut is_seeded, "")
+                            .on_hover_text("With a fixed seed, every orbit is identical.")
+                            .changed()
+                        {
+                            state.seed = is_seeded.then_some(random::DEFAULT_SEED);
+                            changed = true;
+                        }
+                        if let Some(seed) = &mut state.seed {
+                            changed |= ui.add(DragValue::new(seed).speed(1)).changed();
+                        }
+                    });
+                    ui.end_row();
+                },
+                Method::ModifiedInverse => {
+                    ui.label("Max Hits:");
+                    changed |= ui
+                        .add(DragValue::new(&mut state.max_hits).speed(1).range(1..=100))
+                        .on_hover_text(
+                            "Preimages are not followed further from the pixels,\n\
+                             that were hit this many times.",
+                        )
+                        .changed();
+                    ui.end_row();
+                },
+            }
+
+            ui.label("Scale:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.scale_cm)
+                        .speed(0.1)
+                        .range(0.1..=1000.0)
+                        .suffix(" cm"),
+                )
+                .on_hover_text("Length of the unit on the canvas.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Radius:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.radius_cm)
+                        .speed(0.001)
+                        .range(0.001..=1.0)
+                        .suffix(" cm"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.julia_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.julia_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.julia_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.julia_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.julia_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.julia_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.julia_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"C\": [-0.123, 0.745],
+                                \"Method\": \"MIIM\",
+                                \"Points\": 200000,
+                                \"Max Hits\": 2
+                            }
+
+                            C: parameter of the map z -> z² + c, [re, im].
+                            Method: \"IIM\" follows one random backward orbit
+                            z -> ±√(z - c), \"MIIM\" follows all the preimages,
+                            but stops in the pixels, that were hit \"Max Hits\" times,
+                            so the thin parts of the set show up as well.
+                            Points: maximum number of the dots.
+
+                            Other fields: \"Scale\" (cm per unit), \"Radius\" (cm),
+                            \"Color\" ([r, g, b]), \"Seed\" (IIM only).
+
+                            The hits are counted in the pixels of the current zoom.
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    // Clicking or dragging on the picture of the Mandelbrot set picks c
+    fn show_overview(&mut self, ui: &mut Ui, state: &mut JuliaState) -> bool {
+        let size = vec2(overview::WIDTH as f32, overview::HEIGHT as f32);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+
+        let texture = self.overview.get_or_insert_with(|| {
+            let image = ColorImage {
+                size: [overview::WIDTH, overview::HEIGHT],
+                pixels: overview::pixels(),
+            };
+            ui.ctx()
+                .load_texture("JuliaOverview", image, TextureOptions::LINEAR)
+        });
+        let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+        ui.painter().image(texture.id(), rect, uv, Color32::WHITE);
+
+        let mut changed = false;
+        if response.clicked() || response.dragged() {
+            if let Some(position) = response.interact_pointer_pos() {
+                let offset = position - rect.min;
+                let [re, im] = overview::pixel_to_c(offset.x, offset.y);
+                state.c = [(re * 1e4).round() / 1e4, (im * 1e4).round() / 1e4];
+                changed = validation::c_range(state.c).is_ok();
+            }
+        }
+
+        let [x, y] = overview::c_to_pixel(state.c);
+        if rect.contains(rect.min + vec2(x, y)) {
+            ui.painter().circle_stroke(
+                rect.min + vec2(x, y),
+                MARKER_RADIUS,
+                Stroke::new(1.5, colors::RED),
+            );
+        }
+
+        changed
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut JuliaState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
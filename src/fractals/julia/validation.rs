@@ -0,0 +1,145 @@
+use crate::fractals::julia::model;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+// Beyond this the set is a sparse dust far away from the origin
+pub const MAX_C_MODULUS: f64 = 4.0;
+
+pub fn c_range(c: [f64; 2]) -> Result<(), ValidationError> {
+    let modulus = c[0].hypot(c[1]);
+    if modulus.is_nan() || modulus > MAX_C_MODULUS {
+        return Err(ValidationError::BadParameter(format!(
+            "c = {} + {}i.",
+            c[0], c[1]
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn points_range(points: u32) -> Result<(), ValidationError> {
+    if !(1..=model::MAX_POINTS).contains(&points) {
+        return Err(ValidationError::BadPointsCount);
+    }
+
+    Ok(())
+}
+
+pub fn max_hits_range(max_hits: u32) -> Result<(), ValidationError> {
+    if max_hits == 0 {
+        return Err(ValidationError::ZeroMaxHits);
+    }
+
+    Ok(())
+}
+
+pub fn scale_range(scale_cm: f32) -> Result<(), ValidationError> {
+    if !(scale_cm > 0.0 && scale_cm.is_finite()) {
+        return Err(ValidationError::BadScaleValue);
+    }
+
+    Ok(())
+}
+
+pub fn radius_range(radius_cm: f32) -> Result<(), ValidationError> {
+    if !(radius_cm > 0.0 && radius_cm.is_finite()) {
+        return Err(ValidationError::BadRadius);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("Modulus of the parameter c has to be at most 4.")]
+    BadParameter(String),
+
+    #[error("The number of points has to be in range 1..=2000000.")]
+    BadPointsCount,
+
+    #[error("Maximum hits per pixel has to be at least 1.")]
+    ZeroMaxHits,
+
+    #[error("Scale has to be greater than 0.")]
+    BadScaleValue,
+
+    #[error("Radius of the dots has to be greater than 0.")]
+    BadRadius,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadParameter(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::julia::examples::Example;
+    use crate::fractals::julia::serialization;
+    use crate::fractals::julia::state::JuliaState;
+
+    #[test]
+    fn parameter_modulus_limit_is_inclusive() {
+        assert!(c_range([0.0, -MAX_C_MODULUS]).is_ok());
+        assert!(c_range([f64::NAN, 0.0]).is_err());
+        assert!(c_range([f64::INFINITY, 0.0]).is_err());
+
+        let mut state = JuliaState::default();
+        state.c = [3.0, 3.0];
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("c = 3 + 3i.".to_string())
+        );
+    }
+
+    #[test]
+    fn points_and_hits_limits() {
+        assert!(points_range(model::MAX_POINTS).is_ok());
+        assert!(matches!(
+            points_range(model::MAX_POINTS + 1),
+            Err(ValidationError::BadPointsCount)
+        ));
+        assert!(matches!(
+            points_range(0),
+            Err(ValidationError::BadPointsCount)
+        ));
+        assert!(max_hits_range(1).is_ok());
+        assert!(matches!(
+            max_hits_range(0),
+            Err(ValidationError::ZeroMaxHits)
+        ));
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = JuliaState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
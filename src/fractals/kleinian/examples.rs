@@ -0,0 +1,62 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static KLEINIAN_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/kleinian/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Apollonian Gasket")]
+    ApollonianGasket,
+
+    #[strum(serialize = "Quasi-Fuchsian Group")]
+    QuasiFuchsian,
+
+    #[strum(serialize = "Schottky Group")]
+    SchottkyGroup,
+
+    #[strum(serialize = "Spirals")]
+    Spirals,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::ApollonianGasket => PathBuf::from(r"Apollonian-Gasket.json"),
+            Example::QuasiFuchsian => PathBuf::from(r"Quasi-Fuchsian.json"),
+            Example::SchottkyGroup => PathBuf::from(r"Schottky-Group.json"),
+            Example::Spirals => PathBuf::from(r"Spirals.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = KLEINIAN_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::ApollonianGasket,
+            Example::QuasiFuchsian,
+            Example::SchottkyGroup,
+            Example::Spirals,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
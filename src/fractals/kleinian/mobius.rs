@@ -0,0 +1,151 @@
+use crate::math::complex::Complex;
+use std::ops::Mul;
+
+// z -> (a * z + b) / (c * z + d), as the matrix [a b; c d]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mobius {
+    pub a: Complex,
+    pub b: Complex,
+    pub c: Complex,
+    pub d: Complex,
+}
+
+impl Mobius {
+    pub const IDENTITY: Mobius =
+        Mobius::new(Complex::ONE, Complex::ZERO, Complex::ZERO, Complex::ONE);
+
+    pub const fn new(a: Complex, b: Complex, c: Complex, d: Complex) -> Self {
+        Self { a, b, c, d }
+    }
+
+    pub fn determinant(&self) -> Complex {
+        self.a * self.d - self.b * self.c
+    }
+
+    // Same transformation with the determinant equal to 1
+    pub fn normalized(&self) -> Self {
+        let root = self.determinant().sqrt();
+        Self::new(self.a / root, self.b / root, self.c / root, self.d / root)
+    }
+
+    // Inverse of the normalized matrix
+    pub fn inverse(&self) -> Self {
+        Self::new(self.d, -self.b, -self.c, self.a)
+    }
+
+    // None for the point at infinity
+    pub fn apply(&self, z: Complex) -> Option<Complex> {
+        let denominator = self.c * z + self.d;
+        if denominator.norm_sqr() <= f64::EPSILON * f64::EPSILON {
+            return None;
+        }
+
+        let result = (self.a * z + self.b) / denominator;
+        result.is_finite().then_some(result)
+    }
+
+    // Fixed point, that attracts the orbits, the only one for parabolic transformations
+    pub fn attracting_fixed_point(&self) -> Option<Complex> {
+        if self.c.norm_sqr() <= f64::EPSILON * f64::EPSILON {
+            // The other fixed point is at infinity
+            let difference = self.d - self.a;
+            if difference.norm_sqr() <= f64::EPSILON * f64::EPSILON {
+                return None;
+            }
+            return Some(self.b / difference);
+        }
+
+        let difference = self.a - self.d;
+        let root = (difference * difference + (self.b * self.c).scale(4.0)).sqrt();
+        let twice_c = self.c.scale(2.0);
+        let first = (difference + root) / twice_c;
+        let second = (difference - root) / twice_c;
+
+        // Derivative at a fixed point z is 1 / (c * z + d)^2
+        match (self.c * first + self.d).norm_sqr()
+            >= (self.c * second + self.d).norm_sqr()
+        {
+            true => Some(first),
+            false => Some(second),
+        }
+    }
+}
+
+impl Mul for Mobius {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.a * other.a + self.b * other.c,
+            self.a * other.b + self.b * other.d,
+            self.c * other.a + self.d * other.c,
+            self.c * other.b + self.d * other.d,
+        )
+    }
+}
+
+// Two generators with the parabolic commutator, from traces of a and b,
+// as in "Indra's Pearls" by Mumford, Series and Wright
+pub fn grandmas_recipe(ta: Complex, tb: Complex) -> Option<(Mobius, Mobius)> {
+    let two = Complex::new(2.0, 0.0);
+    let four = Complex::new(4.0, 0.0);
+    let i = Complex::I;
+
+    let product = ta * tb;
+    let discriminant = product * product - (ta * ta + tb * tb).scale(4.0);
+    let tab = (product - discriminant.sqrt()).scale(0.5);
+
+    let z0 = ((tab - two) * tb) / (tb * tab - ta.scale(2.0) + (i * tab).scale(2.0));
+
+    let a = Mobius::new(
+        ta.scale(0.5),
+        (ta * tab - tb.scale(2.0) + i.scale(4.0)) / ((tab.scale(2.0) + four) * z0),
+        ((ta * tab - tb.scale(2.0) - i.scale(4.0)) * z0) / (tab.scale(2.0) - four),
+        ta.scale(0.5),
+    );
+    let b = Mobius::new(
+        (tb - i.scale(2.0)).scale(0.5),
+        tb.scale(0.5),
+        tb.scale(0.5),
+        (tb + i.scale(2.0)).scale(0.5),
+    );
+
+    let is_finite = [a.a, a.b, a.c, a.d, b.a, b.b, b.c, b.d]
+        .iter()
+        .all(|value| value.is_finite());
+    is_finite.then_some((a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recipe_gives_parabolic_commutator() {
+        let (a, b) = grandmas_recipe(Complex::new(1.91, 0.05), Complex::new(1.91, 0.05))
+            .unwrap_or((Mobius::IDENTITY, Mobius::IDENTITY));
+
+        let commutator = a * b * a.inverse() * b.inverse();
+
+        assert!(((a.a + a.d) - Complex::new(1.91, 0.05)).norm() < 1e-9);
+        assert!(((b.a + b.d) - Complex::new(1.91, 0.05)).norm() < 1e-9);
+        assert!((a.determinant() - Complex::ONE).norm() < 1e-9);
+        assert!(((commutator.a + commutator.d) + Complex::new(2.0, 0.0)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn attracting_fixed_point() {
+        // z -> 2z + 1 has fixed points -1 and infinity, the first one is repelling
+        let transformation = Mobius::new(
+            Complex::new(2.0, 0.0),
+            Complex::ONE,
+            Complex::ZERO,
+            Complex::ONE,
+        );
+        let inverse = transformation.normalized().inverse();
+
+        let fixed_point = inverse.attracting_fixed_point().unwrap_or_default();
+
+        assert!((fixed_point + Complex::ONE).norm() < 1e-9);
+    }
+}
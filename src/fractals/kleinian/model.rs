@@ -0,0 +1,229 @@
+use crate::fractals::kleinian::mobius::{grandmas_recipe, Mobius};
+use crate::geometry::line2d::Line2D;
+use crate::geometry::point2d::Point2D;
+use crate::math::complex::Complex;
+use crate::ui::styles::gradient::Gradient;
+use egui::{Color32, Stroke};
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+// Traces of the Apollonian gasket group
+pub const DEFAULT_TA: [f64; 2] = [2.0, 0.0];
+pub const DEFAULT_TB: [f64; 2] = [2.0, 0.0];
+pub const DEFAULT_SCALE: f32 = 8.0;
+pub const DEFAULT_EPSILON_CM: f32 = 0.02;
+pub const DEFAULT_MAX_DEPTH: u32 = 40;
+pub const DEFAULT_STROKE_WIDTH: f32 = 1.0;
+pub const MAX_DEPTH: u32 = 200;
+pub const MAX_LINES: usize = 2_000_000;
+
+// Points further from the origin don't fit the canvas anyway
+const FAR_AWAY: f64 = 1e6;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Construction {
+    #[default]
+    #[strum(serialize = "Grandma's Recipe")]
+    #[serde(rename = "Grandma's Recipe")]
+    Recipe,
+
+    #[strum(serialize = "Generators")]
+    #[serde(rename = "Generators")]
+    Generators,
+}
+
+impl Construction {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Construction::Recipe, Construction::Generators].into_iter()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Coloring {
+    #[default]
+    #[strum(serialize = "Solid (Black)")]
+    #[serde(rename = "Solid")]
+    Solid,
+
+    // Words starting with a, b, A and B get their own color
+    #[strum(serialize = "First Letter")]
+    #[serde(rename = "First Letter")]
+    FirstLetter,
+}
+
+impl Coloring {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Coloring::Solid, Coloring::FirstLetter].into_iter()
+    }
+}
+
+// Matrix entries a, b, c and d as [re, im]
+pub type MatrixDto = [[f64; 2]; 4];
+
+pub fn default_generators() -> (MatrixDto, MatrixDto) {
+    let to_dto = |mobius: Mobius| {
+        [
+            mobius.a.into(),
+            mobius.b.into(),
+            mobius.c.into(),
+            mobius.d.into(),
+        ]
+    };
+    let (a, b) = grandmas_recipe(DEFAULT_TA.into(), DEFAULT_TB.into())
+        .unwrap_or((Mobius::IDENTITY, Mobius::IDENTITY));
+
+    (to_dto(a), to_dto(b))
+}
+
+pub fn to_mobius(matrix: &MatrixDto) -> Mobius {
+    Mobius::new(
+        matrix[0].into(),
+        matrix[1].into(),
+        matrix[2].into(),
+        matrix[3].into(),
+    )
+}
+
+pub struct Model {
+    // a, b, A and B, inverse of the generator i is (i + 2) % 4
+    generators: [Mobius; 4],
+    // Fixed points of the cyclic words, that end with the generator
+    fixed_points: [[Option<Complex>; 3]; 4],
+
+    scale: f64,
+    epsilon: f64,
+    max_depth: u32,
+
+    stroke_width: f32,
+    coloring: Coloring,
+    gradient: Gradient,
+}
+
+impl Model {
+    pub fn new(a: Mobius, b: Mobius) -> Self {
+        let (a, b) = (a.normalized(), b.normalized());
+        let generators = [a, b, a.inverse(), b.inverse()];
+
+        let fixed_points = std::array::from_fn(|i| {
+            let word = |indices: [usize; 4]| {
+                indices
+                    .iter()
+                    .fold(Mobius::IDENTITY, |word, index| word * generators[index % 4])
+            };
+            [
+                word([i + 1, i + 2, i + 3, i]).attracting_fixed_point(),
+                generators[i].attracting_fixed_point(),
+                word([i + 3, i + 2, i + 1, i]).attracting_fixed_point(),
+            ]
+        });
+
+        Self {
+            generators,
+            fixed_points,
+
+            scale: DEFAULT_SCALE as f64,
+            epsilon: DEFAULT_EPSILON_CM as f64 / DEFAULT_SCALE as f64,
+            max_depth: DEFAULT_MAX_DEPTH,
+
+            stroke_width: DEFAULT_STROKE_WIDTH,
+            coloring: Coloring::default(),
+            gradient: Gradient::default(),
+        }
+    }
+
+    // Scale is in centimeters per unit, epsilon is in centimeters
+    pub fn with_view(mut self, scale: f32, epsilon_cm: f32) -> Self {
+        self.scale = scale as f64;
+        self.epsilon = epsilon_cm as f64 / scale as f64;
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_style(
+        mut self, stroke_width: f32, coloring: Coloring, gradient: Gradient,
+    ) -> Self {
+        self.stroke_width = stroke_width;
+        self.coloring = coloring;
+        self.gradient = gradient;
+        self
+    }
+
+    // Depth-first search over the reduced words, a branch ends,
+    // when images of the fixed points are closer than epsilon
+    pub fn lines(&self) -> Vec<Line2D> {
+        let mut lines: Vec<Line2D> = Vec::new();
+
+        for first in 0..4 {
+            let color = match self.coloring {
+                Coloring::Solid => Color32::BLACK,
+                Coloring::FirstLetter => self.gradient.sample(first as f32 / 3.0),
+            };
+            let stroke = Stroke::new(self.stroke_width, color);
+            self.search(self.generators[first], first, 1, stroke, &mut lines);
+        }
+
+        lines
+    }
+
+    fn search(
+        &self, word: Mobius, last: usize, depth: u32, stroke: Stroke,
+        lines: &mut Vec<Line2D>,
+    ) {
+        if lines.len() >= MAX_LINES {
+            return;
+        }
+
+        let points: Vec<Complex> = self.fixed_points[last]
+            .iter()
+            .filter_map(|point| word.apply((*point)?))
+            .filter(|point| point.norm() < FAR_AWAY)
+            .collect();
+
+        let is_small = points
+            .windows(2)
+            .all(|pair| (pair[0] - pair[1]).norm() <= self.epsilon);
+        if is_small || depth >= self.max_depth {
+            for pair in points.windows(2) {
+                lines.push(Line2D::new(
+                    self.to_point(pair[0]),
+                    self.to_point(pair[1]),
+                    stroke,
+                ));
+            }
+            return;
+        }
+
+        // Children go from the right to the left, so the curve is traced in order
+        for next in [last + 1, last, last + 3] {
+            let next = next % 4;
+            self.search(word * self.generators[next], next, depth + 1, stroke, lines);
+        }
+    }
+
+    fn to_point(&self, z: Complex) -> Point2D {
+        Point2D::new((z.re * self.scale) as f32, (z.im * self.scale) as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gasket_limit_set_is_in_unit_disk() {
+        let (a, b) = grandmas_recipe(DEFAULT_TA.into(), DEFAULT_TB.into())
+            .unwrap_or((Mobius::IDENTITY, Mobius::IDENTITY));
+
+        let lines = Model::new(a, b).with_view(1.0, 0.01).lines();
+
+        assert!(lines.len() > 100);
+        // This gasket is inscribed in the unit circle
+        for line in lines {
+            assert!(line.start.x.hypot(line.start.y) <= 1.0 + 1e-3);
+        }
+    }
+}
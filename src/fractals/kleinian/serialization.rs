@@ -0,0 +1,119 @@
+use crate::fractals::kleinian::model;
+use crate::fractals::kleinian::model::{Coloring, Construction, MatrixDto};
+use crate::fractals::kleinian::state::KleinianState;
+use crate::fractals::kleinian::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<KleinianDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &KleinianState) -> serde_json::Result<String> {
+    let (ta, tb, generators) = match state.construction {
+        Construction::Recipe => (Some(state.ta), Some(state.tb), None),
+        Construction::Generators => {
+            (None, None, Some([state.generator_a, state.generator_b]))
+        },
+    };
+
+    let dto = KleinianDto {
+        construction: state.construction,
+        ta,
+        tb,
+        generators,
+        scale: state.scale,
+        epsilon_cm: state.epsilon_cm,
+        max_depth: state.max_depth,
+        stroke_width: state.stroke_width,
+        coloring: state.coloring,
+        gradient: (&state.gradient).into(),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_scale() -> f32 {
+    model::DEFAULT_SCALE
+}
+
+fn default_epsilon() -> f32 {
+    model::DEFAULT_EPSILON_CM
+}
+
+fn default_max_depth() -> u32 {
+    model::DEFAULT_MAX_DEPTH
+}
+
+fn default_stroke_width() -> f32 {
+    model::DEFAULT_STROKE_WIDTH
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct KleinianDto {
+    #[serde(rename = "Construction", default)]
+    construction: Construction,
+
+    // Traces for Grandma's recipe, as [re, im]
+    #[serde(rename = "Ta", default, skip_serializing_if = "Option::is_none")]
+    ta: Option<[f64; 2]>,
+
+    #[serde(rename = "Tb", default, skip_serializing_if = "Option::is_none")]
+    tb: Option<[f64; 2]>,
+
+    // Matrices [a, b, c, d] of both generators
+    #[serde(
+        rename = "Generators",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    generators: Option<[MatrixDto; 2]>,
+
+    #[serde(rename = "Scale", default = "default_scale")]
+    scale: f32,
+
+    #[serde(rename = "Epsilon", default = "default_epsilon")]
+    epsilon_cm: f32,
+
+    #[serde(rename = "Max Depth", default = "default_max_depth")]
+    max_depth: u32,
+
+    #[serde(rename = "Stroke Width", default = "default_stroke_width")]
+    stroke_width: f32,
+
+    #[serde(rename = "Coloring", default)]
+    coloring: Coloring,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+}
+
+impl KleinianDto {
+    pub fn load(self, state: &mut KleinianState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.construction = self.construction;
+        if let Some(ta) = self.ta {
+            state.ta = ta;
+        }
+        if let Some(tb) = self.tb {
+            state.tb = tb;
+        }
+        if let Some([generator_a, generator_b]) = self.generators {
+            state.generator_a = generator_a;
+            state.generator_b = generator_b;
+        }
+        state.scale = self.scale;
+        state.epsilon_cm = self.epsilon_cm;
+        state.max_depth = self.max_depth;
+        state.stroke_width = self.stroke_width;
+        state.coloring = self.coloring;
+        state.gradient = self.gradient.into();
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,112 @@
+use crate::fractals::kleinian::mobius::Mobius;
+use crate::fractals::kleinian::model::{Coloring, Construction, MatrixDto, Model};
+use crate::fractals::kleinian::validation::ValidationError;
+use crate::fractals::kleinian::{model, validation};
+use crate::geometry::line2d::Line2D;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::gradient::Gradient;
+use egui::Shape;
+
+pub struct KleinianState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub construction: Construction,
+    pub ta: [f64; 2],
+    pub tb: [f64; 2],
+    pub generator_a: MatrixDto,
+    pub generator_b: MatrixDto,
+
+    pub scale: f32,
+    pub epsilon_cm: f32,
+    pub max_depth: u32,
+
+    pub stroke_width: f32,
+    pub coloring: Coloring,
+    pub gradient: Gradient,
+
+    generators: Option<(Mobius, Mobius)>,
+    lines: Vec<Line2D>,
+}
+
+impl Default for KleinianState {
+    fn default() -> Self {
+        let (generator_a, generator_b) = model::default_generators();
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            construction: Construction::default(),
+            ta: model::DEFAULT_TA,
+            tb: model::DEFAULT_TB,
+            generator_a,
+            generator_b,
+
+            scale: model::DEFAULT_SCALE,
+            epsilon_cm: model::DEFAULT_EPSILON_CM,
+            max_depth: model::DEFAULT_MAX_DEPTH,
+
+            stroke_width: model::DEFAULT_STROKE_WIDTH,
+            coloring: Coloring::default(),
+            gradient: Gradient::default(),
+
+            generators: None,
+            lines: vec![],
+        }
+    }
+}
+
+impl KleinianState {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Vec<Shape> {
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+            if let Some((a, b)) = self.generators {
+                self.lines = Model::new(a, b)
+                    .with_view(self.scale, self.epsilon_cm)
+                    .with_max_depth(self.max_depth)
+                    .with_style(self.stroke_width, self.coloring, self.gradient.clone())
+                    .lines();
+            }
+        }
+
+        self.lines
+            .iter()
+            .map(|line| line.to_screen(params).to_shape())
+            .collect()
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        let generators = match self.construction {
+            Construction::Recipe => validation::recipe_is_valid(&self.ta, &self.tb)?,
+            Construction::Generators => (
+                validation::generator_is_valid(&self.generator_a, "a")?,
+                validation::generator_is_valid(&self.generator_b, "b")?,
+            ),
+        };
+        validation::view_is_valid(self.scale, self.epsilon_cm)?;
+        validation::max_depth_range(self.max_depth)?;
+        validation::stroke_width_range(self.stroke_width)?;
+
+        self.generators = Some(generators);
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.generators = None;
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn lines_count(&self) -> usize {
+        self.lines.len()
+    }
+}
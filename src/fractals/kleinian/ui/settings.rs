@@ -0,0 +1,302 @@
+use crate::context::Context;
+use crate::fractals::kleinian::examples::Example;
+use crate::fractals::kleinian::model::{Coloring, Construction, MAX_DEPTH};
+use crate::fractals::kleinian::serialization;
+use crate::fractals::kleinian::state::KleinianState;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct KleinianSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for KleinianSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+// Two drag values for the real and the imaginary parts
+fn complex_edit(ui: &mut Ui, value: &mut [f64; 2]) -> bool {
+    ui.horizontal(|ui| {
+        let re = ui.add(DragValue::new(&mut value[0]).speed(0.001));
+        ui.label("+");
+        let im = ui.add(DragValue::new(&mut value[1]).speed(0.001).suffix("i"));
+        re.changed() || im.changed()
+    })
+    .inner
+}
+
+impl KleinianSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.kleinian_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Lines: ");
+            ui.label(context.kleinian_state.lines_count().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.kleinian_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Construction:");
+            egui::ComboBox::from_id_salt("KleinianConstruction")
+                .selected_text(format!("{}", state.construction))
+                .show_ui(ui, |ui| {
+                    for construction in Construction::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.construction,
+                                construction,
+                                construction.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            match state.construction {
+                Construction::Recipe => {
+                    ui.label("Trace of a:");
+                    changed |= complex_edit(ui, &mut state.ta);
+                    ui.end_row();
+
+                    ui.label("Trace of b:");
+                    changed |= complex_edit(ui, &mut state.tb);
+                    ui.end_row();
+                },
+                Construction::Generators => {
+                    for (name, matrix) in
+                        [("a", &mut state.generator_a), ("b", &mut state.generator_b)]
+                    {
+                        for (entry, value) in
+                            ["a", "b", "c", "d"].iter().zip(matrix.iter_mut())
+                        {
+                            ui.label(format!("{}.{}:", name, entry));
+                            changed |= complex_edit(ui, value);
+                            ui.end_row();
+                        }
+                    }
+                },
+            }
+
+            ui.label("Scale:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.scale)
+                        .speed(0.1)
+                        .range(0.01..=1000.0)
+                        .suffix(" cm"),
+                )
+                .on_hover_text("Centimeters per unit of the complex plane.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Epsilon:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.epsilon_cm)
+                        .speed(0.001)
+                        .range(0.001..=10.0)
+                        .suffix(" cm"),
+                )
+                .on_hover_text(
+                    "Branches of the search end on pieces of the curve this small.",
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Max Depth:");
+            changed |= ui
+                .add(Slider::new(&mut state.max_depth, 1..=MAX_DEPTH))
+                .on_hover_text("Longest word of the search.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Stroke Width:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.stroke_width)
+                        .speed(0.1)
+                        .range(0.1..=10.0),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Coloring:");
+            egui::ComboBox::from_id_salt("KleinianColoring")
+                .selected_text(format!("{}", state.coloring))
+                .show_ui(ui, |ui| {
+                    for coloring in Coloring::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.coloring,
+                                coloring,
+                                coloring.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+        });
+
+        if state.coloring == Coloring::FirstLetter {
+            ui.add_space(5.0);
+            ui.collapsing("Gradient", |ui| {
+                changed |= state.gradient.show_editor(ui, "Kleinian");
+            });
+        }
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.kleinian_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.kleinian_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.kleinian_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.kleinian_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.kleinian_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.kleinian_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.kleinian_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Construction\": \"Grandma's Recipe\",
+                                \"Ta\": [1.87, 0.1],
+                                \"Tb\": [1.87, -0.1],
+                                \"Max Depth\": 80
+                            }
+
+                            Construction: \"Grandma's Recipe\" or \"Generators\".
+                            Ta, Tb: traces of the generators as [re, im].
+                            Generators: two matrices [a, b, c, d] of complex entries,
+                            for the transformations z -> (az + b) / (cz + d).
+
+                            Other fields: \"Scale\" (cm per unit), \"Epsilon\" (cm),
+                            \"Stroke Width\", \"Coloring\", \"Gradient\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut KleinianState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use crate::fractals::kleinian::mobius;
+use crate::fractals::kleinian::mobius::Mobius;
+use crate::fractals::kleinian::model;
+use crate::fractals::kleinian::model::MatrixDto;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub fn recipe_is_valid(
+    ta: &[f64; 2], tb: &[f64; 2],
+) -> Result<(Mobius, Mobius), ValidationError> {
+    if ta.iter().chain(tb).any(|value| !value.is_finite()) {
+        return Err(ValidationError::BadNumberValue);
+    }
+
+    mobius::grandmas_recipe((*ta).into(), (*tb).into()).ok_or(
+        ValidationError::RecipeFailed(format!(
+            "ta: {} + {}i\ntb: {} + {}i",
+            ta[0], ta[1], tb[0], tb[1]
+        )),
+    )
+}
+
+pub fn generator_is_valid(
+    matrix: &MatrixDto, name: &str,
+) -> Result<Mobius, ValidationError> {
+    if matrix.iter().flatten().any(|value| !value.is_finite()) {
+        return Err(ValidationError::BadNumberValue);
+    }
+
+    // Scaling the matrix doesn't change the transformation, so the determinant
+    // is compared to the products of the entries, not to a fixed number
+    let mobius = model::to_mobius(matrix);
+    let scale = mobius.a.norm() * mobius.d.norm() + mobius.b.norm() * mobius.c.norm();
+    if mobius.determinant().norm() <= f64::EPSILON * scale {
+        return Err(ValidationError::SingularGenerator(format!(
+            "Generator: {}",
+            name
+        )));
+    }
+
+    Ok(mobius)
+}
+
+pub fn view_is_valid(scale: f32, epsilon_cm: f32) -> Result<(), ValidationError> {
+    if !(scale > 0.0 && scale.is_finite()) {
+        return Err(ValidationError::BadScaleValue);
+    }
+    if !(epsilon_cm > 0.0 && epsilon_cm.is_finite()) {
+        return Err(ValidationError::BadEpsilonValue);
+    }
+
+    Ok(())
+}
+
+pub fn max_depth_range(depth: u32) -> Result<(), ValidationError> {
+    if !(1..=model::MAX_DEPTH).contains(&depth) {
+        return Err(ValidationError::BadMaxDepthValue);
+    }
+
+    Ok(())
+}
+
+pub fn stroke_width_range(width: f32) -> Result<(), ValidationError> {
+    if !(width > 0.0 && width.is_finite()) {
+        return Err(ValidationError::BadStrokeWidth);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("All numbers have to be finite.")]
+    BadNumberValue,
+
+    #[error("Grandma's recipe doesn't work for these traces.")]
+    RecipeFailed(String),
+
+    #[error("The determinant of the generator matrix is zero.")]
+    SingularGenerator(String),
+
+    #[error("The scale has to be greater than 0.")]
+    BadScaleValue,
+
+    #[error("Epsilon has to be greater than 0.")]
+    BadEpsilonValue,
+
+    #[error("The maximal depth has to be in range 1..200.")]
+    BadMaxDepthValue,
+
+    #[error("The stroke width has to be greater than 0.")]
+    BadStrokeWidth,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::RecipeFailed(value) => Some(value.clone()),
+            Self::SingularGenerator(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::kleinian::examples::Example;
+    use crate::fractals::kleinian::model::Construction;
+    use crate::fractals::kleinian::serialization;
+    use crate::fractals::kleinian::state::KleinianState;
+
+    #[test]
+    fn default_generators_are_valid() {
+        let mut state = KleinianState::default();
+        state.construction = Construction::Generators;
+
+        let result = state.initialize();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn singular_generator_is_named() {
+        let mut state = KleinianState::default();
+        state.construction = Construction::Generators;
+        state.generator_b = [[1.0, 0.0], [2.0, 0.0], [2.0, 0.0], [4.0, 0.0]];
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Generator: b".to_string())
+        );
+    }
+
+    #[test]
+    fn scaled_generator_is_not_singular() {
+        let tiny_identity = [[1e-9, 0.0], [0.0, 0.0], [0.0, 0.0], [1e-9, 0.0]];
+
+        assert!(generator_is_valid(&tiny_identity, "a").is_ok());
+        assert!(matches!(
+            generator_is_valid(&[[0.0, 0.0]; 4], "a"),
+            Err(ValidationError::SingularGenerator(_))
+        ));
+    }
+
+    #[test]
+    fn not_finite_generator() {
+        let matrix = [[1.0, 0.0], [f64::NAN, 0.0], [0.0, 0.0], [1.0, 0.0]];
+
+        assert!(matches!(
+            generator_is_valid(&matrix, "a"),
+            Err(ValidationError::BadNumberValue)
+        ));
+    }
+
+    #[test]
+    fn zero_traces_break_recipe() {
+        // The recipe divides by zero, when both traces are zero
+        let result = recipe_is_valid(&[0.0, 0.0], &[0.0, 0.0]);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("ta: 0 + 0i\ntb: 0 + 0i".to_string())
+        );
+    }
+
+    #[test]
+    fn max_depth_bounds_are_inclusive() {
+        assert!(max_depth_range(1).is_ok());
+        assert!(max_depth_range(model::MAX_DEPTH).is_ok());
+        assert!(max_depth_range(0).is_err());
+        assert!(max_depth_range(model::MAX_DEPTH + 1).is_err());
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = KleinianState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::geometry::line2d::Line2D;
 use crate::geometry::point2d::Point2D;
 use crate::math::angle::Angle;
+use crate::math::random;
 use crate::ui::styles::colors::ColorScheme;
 use egui::Stroke;
 use std::collections::HashMap;
@@ -19,6 +20,7 @@ pub struct Model {
     pub length: f32,
 
     pub color_scheme: ColorScheme,
+    pub seed: Option<u64>,
 }
 
 impl Model {
@@ -26,6 +28,7 @@ impl Model {
         let mut lines: Vec<Line2D> = Vec::new();
 
         let path = self.create_path();
+        let mut rng = random::rng(self.seed);
 
         let mut current_x = 0.0;
         let mut current_y = 0.0;
@@ -52,7 +55,10 @@ impl Model {
                     lines.push(Line2D::new(
                         start,
                         end,
-                        Stroke::new(FRACTAL_STROKE_WIDTH, self.color_scheme.get_color()),
+                        Stroke::new(
+                            FRACTAL_STROKE_WIDTH,
+                            self.color_scheme.get_color(&mut rng),
+                        ),
                     ));
                 },
                 '+' => {
@@ -112,6 +118,7 @@ pub struct ModelBuilder {
     pub length: f32,
 
     pub color_scheme: ColorScheme,
+    pub seed: Option<u64>,
 }
 
 impl ModelBuilder {
@@ -150,6 +157,11 @@ impl ModelBuilder {
         self
     }
 
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
     pub fn build(self) -> Model {
         Model {
             angle: Angle::from_degree(self.angle),
@@ -159,6 +171,7 @@ impl ModelBuilder {
             iterations: self.iterations,
             length: self.length,
             color_scheme: self.color_scheme,
+            seed: self.seed,
         }
     }
 }
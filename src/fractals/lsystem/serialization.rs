@@ -13,6 +13,7 @@ pub fn serialize(state: &LSystemState) -> serde_json::Result<String> {
         initial_angle: state.initial_angle,
         iterations: state.iterations,
         rules: state.rules.clone(),
+        seed: state.seed,
     };
 
     serde_json::to_string_pretty(&dto)
@@ -34,6 +35,9 @@ pub struct LSystemDto {
 
     #[serde(rename = "Rules")]
     pub rules: Vec<String>,
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 impl LSystemDto {
@@ -45,6 +49,7 @@ impl LSystemDto {
         state.initial_angle = self.initial_angle;
         state.iterations = self.iterations;
         state.rules = self.rules;
+        state.seed = self.seed;
 
         let result = state.initialize();
         if result.is_err() {
@@ -19,6 +19,7 @@ pub struct LSystemState {
     pub length: f32,
 
     pub color_scheme: ColorScheme,
+    pub seed: Option<u64>,
 
     lines: Vec<Line2D>,
 
@@ -39,6 +40,7 @@ impl Default for LSystemState {
             length: 0.5,
 
             color_scheme: ColorScheme::Standard,
+            seed: None,
 
             rules_set: HashMap::new(),
 
@@ -59,6 +61,7 @@ impl LSystemState {
                 .with_iterations(self.iterations)
                 .with_length(self.length)
                 .with_color_scheme(self.color_scheme)
+                .with_seed(self.seed)
                 .build()
                 .lines()
         }
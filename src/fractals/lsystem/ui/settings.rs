@@ -4,6 +4,7 @@ use crate::fractals::lsystem::serialization;
 use crate::fractals::lsystem::state::LSystemState;
 use crate::io;
 use crate::io::filter::FileFilter;
+use crate::math::random;
 use crate::ui::styles::colors;
 use crate::ui::styles::colors::ColorScheme;
 use crate::ui::windows::message::MessageWindow;
@@ -144,6 +145,23 @@ impl LSystemSettingsBlock {
                 };
             }
             ui.end_row();
+
+            ui.label("Seed:");
+            ui.horizontal(|ui| {
+                let mut is_seeded = context.lsystem_state.seed.is_some();
+                if ui
+                    .checkbox(&mut is_seeded, "")
+                    .on_hover_text("With a fixed seed, every drawing is identical.")
+                    .changed()
+                {
+                    context.lsystem_state.seed =
+                        is_seeded.then_some(random::DEFAULT_SEED);
+                }
+                if let Some(seed) = &mut context.lsystem_state.seed {
+                    ui.add(DragValue::new(seed).speed(1));
+                }
+            });
+            ui.end_row();
         });
 
         ui.add_space(10.0);
@@ -330,9 +348,12 @@ impl LSystemSettingsBlock {
                                 \"Rules\": [
                                     \"X -> X+YF+\",
                                     \"Y -> -FX-Y\"
-                                ]
+                                ],
+                                \"Seed\": 42
                             }
 
+                            \"Seed\" is optional. Without it, random colors differ on every drawing.
+
                             You can find other examples in the 'assets/fractals/ifs' folder.
                         "};
                     let _ = context.windows_sender.send(Box::new(MessageWindow::help(message)));
@@ -0,0 +1,254 @@
+use crate::ui::styles::gradient::Gradient;
+use egui::Color32;
+
+pub const DEFAULT_SEQUENCE: &str = "AABAB";
+pub const DEFAULT_A_RANGE: [f64; 2] = [2.5, 4.0];
+pub const DEFAULT_B_RANGE: [f64; 2] = [2.5, 4.0];
+pub const DEFAULT_WARMUP: u32 = 200;
+pub const DEFAULT_ITERATIONS: u32 = 400;
+pub const DEFAULT_START: f64 = 0.5;
+pub const DEFAULT_CONTRAST: f64 = 1.0;
+
+// Both ranges are stretched over the square of this size, centered at the origin
+pub const REGION_SIZE_CM: f64 = 20.0;
+
+// Stable regions are golden, as on the "Zircon Zity" pictures
+pub fn default_stable_gradient() -> Gradient {
+    Gradient::new(vec![
+        (0.0, Color32::from_rgb(0, 0, 0)),
+        (0.4, Color32::from_rgb(130, 90, 0)),
+        (1.0, Color32::from_rgb(255, 230, 80)),
+    ])
+}
+
+pub fn default_chaotic_gradient() -> Gradient {
+    Gradient::new(vec![
+        (0.0, Color32::from_rgb(0, 0, 0)),
+        (0.5, Color32::from_rgb(0, 40, 140)),
+        (1.0, Color32::from_rgb(120, 190, 255)),
+    ])
+}
+
+pub struct Model {
+    // True for 'A', false for 'B'
+    sequence: Vec<bool>,
+    a_range: [f64; 2],
+    b_range: [f64; 2],
+
+    warmup: u32,
+    iterations: u32,
+    start: f64,
+    contrast: f64,
+
+    stable_gradient: Gradient,
+    chaotic_gradient: Gradient,
+}
+
+impl Model {
+    // Point is in centimeters
+    pub fn color(&self, x: f64, y: f64) -> Color32 {
+        let a = lerp(self.a_range, x / REGION_SIZE_CM + 0.5);
+        let b = lerp(self.b_range, y / REGION_SIZE_CM + 0.5);
+
+        let Some(exponent) = self.exponent(a, b) else {
+            return Color32::TRANSPARENT;
+        };
+
+        // Magnitude of the exponent picks the shade, its sign picks the gradient
+        let t = (exponent.abs() / self.contrast).min(1.0) as f32;
+        match exponent < 0.0 {
+            true => self.stable_gradient.sample(t),
+            false => self.chaotic_gradient.sample(t),
+        }
+    }
+
+    // Lyapunov exponent of the logistic map x -> r * x * (1 - x),
+    // where r follows the sequence, taking values a and b
+    pub fn exponent(&self, a: f64, b: f64) -> Option<f64> {
+        if self.sequence.is_empty() {
+            return None;
+        }
+
+        let rates = self.sequence.iter().cycle().map(|is_a| match is_a {
+            true => a,
+            false => b,
+        });
+
+        let mut x = self.start;
+        let mut sum = 0.0;
+        for (index, r) in rates
+            .take((self.warmup + self.iterations) as usize)
+            .enumerate()
+        {
+            if index >= self.warmup as usize {
+                let derivative = (r * (1.0 - 2.0 * x)).abs();
+                // Superstable point, the exponent goes to minus infinity
+                if derivative == 0.0 {
+                    return Some(f64::NEG_INFINITY);
+                }
+                sum += derivative.ln();
+            }
+            x = r * x * (1.0 - x);
+
+            if !x.is_finite() {
+                return None;
+            }
+        }
+
+        Some(sum / self.iterations as f64)
+    }
+}
+
+fn lerp(range: [f64; 2], t: f64) -> f64 {
+    range[0] + (range[1] - range[0]) * t
+}
+
+pub struct ModelBuilder {
+    sequence: Vec<bool>,
+    a_range: [f64; 2],
+    b_range: [f64; 2],
+
+    warmup: u32,
+    iterations: u32,
+    start: f64,
+    contrast: f64,
+
+    stable_gradient: Gradient,
+    chaotic_gradient: Gradient,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            sequence: parse_sequence(DEFAULT_SEQUENCE),
+            a_range: DEFAULT_A_RANGE,
+            b_range: DEFAULT_B_RANGE,
+
+            warmup: DEFAULT_WARMUP,
+            iterations: DEFAULT_ITERATIONS,
+            start: DEFAULT_START,
+            contrast: DEFAULT_CONTRAST,
+
+            stable_gradient: default_stable_gradient(),
+            chaotic_gradient: default_chaotic_gradient(),
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_sequence(mut self, sequence: &str) -> Self {
+        self.sequence = parse_sequence(sequence);
+        self
+    }
+
+    pub fn with_ranges(mut self, a_range: [f64; 2], b_range: [f64; 2]) -> Self {
+        self.a_range = a_range;
+        self.b_range = b_range;
+        self
+    }
+
+    pub fn with_warmup(mut self, warmup: u32) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_start(mut self, start: f64) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn with_contrast(mut self, contrast: f64) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    pub fn with_gradients(mut self, stable: Gradient, chaotic: Gradient) -> Self {
+        self.stable_gradient = stable;
+        self.chaotic_gradient = chaotic;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            sequence: self.sequence,
+            a_range: self.a_range,
+            b_range: self.b_range,
+
+            warmup: self.warmup,
+            iterations: self.iterations,
+            start: self.start,
+            contrast: self.contrast,
+
+            stable_gradient: self.stable_gradient,
+            chaotic_gradient: self.chaotic_gradient,
+        }
+    }
+}
+
+// Letters other than 'A' and 'B' are rejected by validation
+fn parse_sequence(sequence: &str) -> Vec<bool> {
+    sequence
+        .chars()
+        .filter(|letter| !letter.is_whitespace())
+        .map(|letter| letter.eq_ignore_ascii_case(&'A'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_and_chaotic_exponents() {
+        let model = ModelBuilder::default()
+            .with_sequence("AB")
+            .with_start(0.3)
+            .build();
+
+        let stable = model.exponent(2.5, 2.5).unwrap_or(f64::NAN);
+        let chaotic = model.exponent(4.0, 4.0).unwrap_or(f64::NAN);
+
+        assert!(stable < 0.0);
+        // The fully chaotic logistic map has the exponent ln(2)
+        assert!((chaotic - f64::ln(2.0)).abs() < 0.1);
+    }
+
+    #[test]
+    fn superstable_point_is_most_stable() {
+        // x = 0.5 is the fixed point of r = 2, where the derivative is zero
+        let model = ModelBuilder::default()
+            .with_sequence("A")
+            .with_ranges([2.0, 2.0], [2.0, 2.0])
+            .with_start(0.5)
+            .build();
+
+        assert_eq!(model.exponent(2.0, 2.0), Some(f64::NEG_INFINITY));
+        assert_eq!(model.color(0.0, 0.0), default_stable_gradient().sample(1.0));
+    }
+
+    #[test]
+    fn escaping_orbit_is_transparent() {
+        // Rates above 4 throw the orbit out of the unit interval, it goes to infinity
+        let model = ModelBuilder::default()
+            .with_sequence("A")
+            .with_ranges([5.0, 5.0], [5.0, 5.0])
+            .build();
+
+        assert_eq!(model.exponent(5.0, 5.0), None);
+        assert_eq!(model.color(0.0, 0.0), Color32::TRANSPARENT);
+    }
+
+    #[test]
+    fn lowercase_letters_and_spaces() {
+        let spaced = ModelBuilder::default().with_sequence("a b B").build();
+        let plain = ModelBuilder::default().with_sequence("ABB").build();
+
+        assert_eq!(spaced.exponent(3.2, 3.8), plain.exponent(3.2, 3.8));
+        assert_ne!(spaced.exponent(3.2, 3.8), spaced.exponent(3.8, 3.2));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::fractals::lyapunov::model;
+use crate::fractals::lyapunov::state::LyapunovState;
+use crate::fractals::lyapunov::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<LyapunovDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &LyapunovState) -> serde_json::Result<String> {
+    let dto = LyapunovDto {
+        sequence: state.sequence.clone(),
+        a_range: state.a_range,
+        b_range: state.b_range,
+        warmup: state.warmup,
+        iterations: state.iterations,
+        start: state.start,
+        contrast: state.contrast,
+        stable_gradient: (&state.stable_gradient).into(),
+        chaotic_gradient: (&state.chaotic_gradient).into(),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_a_range() -> [f64; 2] {
+    model::DEFAULT_A_RANGE
+}
+
+fn default_b_range() -> [f64; 2] {
+    model::DEFAULT_B_RANGE
+}
+
+fn default_warmup() -> u32 {
+    model::DEFAULT_WARMUP
+}
+
+fn default_iterations() -> u32 {
+    model::DEFAULT_ITERATIONS
+}
+
+fn default_start() -> f64 {
+    model::DEFAULT_START
+}
+
+fn default_contrast() -> f64 {
+    model::DEFAULT_CONTRAST
+}
+
+fn default_stable_gradient() -> Vec<GradientStopDto> {
+    (&model::default_stable_gradient()).into()
+}
+
+fn default_chaotic_gradient() -> Vec<GradientStopDto> {
+    (&model::default_chaotic_gradient()).into()
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LyapunovDto {
+    #[serde(rename = "Sequence")]
+    sequence: String,
+
+    #[serde(rename = "A Range", default = "default_a_range")]
+    a_range: [f64; 2],
+
+    #[serde(rename = "B Range", default = "default_b_range")]
+    b_range: [f64; 2],
+
+    #[serde(rename = "Warm-up", default = "default_warmup")]
+    warmup: u32,
+
+    #[serde(rename = "Iterations", default = "default_iterations")]
+    iterations: u32,
+
+    #[serde(rename = "Start", default = "default_start")]
+    start: f64,
+
+    #[serde(rename = "Contrast", default = "default_contrast")]
+    contrast: f64,
+
+    #[serde(rename = "Stable Gradient", default = "default_stable_gradient")]
+    stable_gradient: Vec<GradientStopDto>,
+
+    #[serde(rename = "Chaotic Gradient", default = "default_chaotic_gradient")]
+    chaotic_gradient: Vec<GradientStopDto>,
+}
+
+impl LyapunovDto {
+    pub fn load(self, state: &mut LyapunovState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.sequence = self.sequence;
+        state.a_range = self.a_range;
+        state.b_range = self.b_range;
+        state.warmup = self.warmup;
+        state.iterations = self.iterations;
+        state.start = self.start;
+        state.contrast = self.contrast;
+        state.stable_gradient = self.stable_gradient.into();
+        state.chaotic_gradient = self.chaotic_gradient.into();
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,106 @@
+use crate::fractals::lyapunov::model::{Model, ModelBuilder};
+use crate::fractals::lyapunov::validation::ValidationError;
+use crate::fractals::lyapunov::{model, validation};
+use crate::graphics::raster::Raster;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::gradient::Gradient;
+use egui::Shape;
+
+pub struct LyapunovState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub sequence: String,
+    pub a_range: [f64; 2],
+    pub b_range: [f64; 2],
+
+    pub warmup: u32,
+    pub iterations: u32,
+    pub start: f64,
+    pub contrast: f64,
+
+    pub stable_gradient: Gradient,
+    pub chaotic_gradient: Gradient,
+
+    model: Option<Model>,
+    raster: Raster,
+}
+
+impl Default for LyapunovState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            sequence: model::DEFAULT_SEQUENCE.to_string(),
+            a_range: model::DEFAULT_A_RANGE,
+            b_range: model::DEFAULT_B_RANGE,
+
+            warmup: model::DEFAULT_WARMUP,
+            iterations: model::DEFAULT_ITERATIONS,
+            start: model::DEFAULT_START,
+            contrast: model::DEFAULT_CONTRAST,
+
+            stable_gradient: model::default_stable_gradient(),
+            chaotic_gradient: model::default_chaotic_gradient(),
+
+            model: None,
+            raster: Raster::new("LyapunovFractal"),
+        }
+    }
+}
+
+impl LyapunovState {
+    pub fn shapes(&mut self, ctx: &egui::Context, params: &CanvasParams) -> Vec<Shape> {
+        let Some(model) = &self.model else {
+            return vec![];
+        };
+
+        let is_requested = std::mem::take(&mut self.is_drawing_requested);
+        self.raster.render_on_change(params, is_requested, |point| {
+            model.color(point.x as f64, point.y as f64)
+        });
+
+        vec![self.raster.shape(ctx, params.screen_rect())]
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::sequence_is_valid(&self.sequence)?;
+        validation::range_is_valid(&self.a_range, "a")?;
+        validation::range_is_valid(&self.b_range, "b")?;
+        validation::iterations_range(self.iterations)?;
+        validation::start_range(self.start)?;
+        validation::contrast_range(self.contrast)?;
+
+        self.model = Some(
+            ModelBuilder::default()
+                .with_sequence(&self.sequence)
+                .with_ranges(self.a_range, self.b_range)
+                .with_warmup(self.warmup)
+                .with_iterations(self.iterations)
+                .with_start(self.start)
+                .with_contrast(self.contrast)
+                .with_gradients(
+                    self.stable_gradient.clone(),
+                    self.chaotic_gradient.clone(),
+                )
+                .build(),
+        );
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+}
@@ -0,0 +1,222 @@
+use crate::context::Context;
+use crate::fractals::lyapunov::serialization;
+use crate::fractals::lyapunov::state::LyapunovState;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, TextEdit, Ui};
+use indoc::indoc;
+
+pub struct LyapunovSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for LyapunovSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl LyapunovSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.lyapunov_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.lyapunov_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Sequence:");
+            changed |= ui
+                .add(
+                    TextEdit::singleline(&mut state.sequence)
+                        .desired_width(120.0)
+                        .hint_text("AABAB"),
+                )
+                .changed();
+            ui.end_row();
+
+            for (name, range) in [("a", &mut state.a_range), ("b", &mut state.b_range)] {
+                ui.label(format!("Range of {}:", name));
+                ui.horizontal(|ui| {
+                    let from = ui.add(DragValue::new(&mut range[0]).speed(0.01));
+                    ui.label("..");
+                    let to = ui.add(DragValue::new(&mut range[1]).speed(0.01));
+                    changed |= from.changed() || to.changed();
+                });
+                ui.end_row();
+            }
+
+            ui.label("Warm-up:");
+            changed |= ui
+                .add(DragValue::new(&mut state.warmup).speed(1).range(0..=100000))
+                .on_hover_text("Iterations skipped before the exponent is measured.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Iterations:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.iterations)
+                        .speed(1)
+                        .range(1..=100000),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Start Value:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.start)
+                        .speed(0.01)
+                        .range(0.0..=1.0),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Contrast:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.contrast)
+                        .speed(0.01)
+                        .range(0.001..=f64::MAX),
+                )
+                .on_hover_text("Magnitude of the exponent that gets the last color.")
+                .changed();
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Stable Gradient", |ui| {
+            changed |= state.stable_gradient.show_editor(ui, "LyapunovStable");
+        });
+        ui.collapsing("Chaotic Gradient", |ui| {
+            changed |= state.chaotic_gradient.show_editor(ui, "LyapunovChaotic");
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.lyapunov_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.lyapunov_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.lyapunov_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.lyapunov_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.lyapunov_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.lyapunov_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.lyapunov_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Sequence\": \"BBBBBBAAAAAA\",
+                                \"A Range\": [3.4, 4.0],
+                                \"B Range\": [2.5, 3.4],
+                                \"Iterations\": 400
+                            }
+
+                            Sequence: letters 'A' and 'B', the rate of the logistic map
+                            takes values a and b in this order.
+                            Ranges are stretched over the 20 cm square in the center.
+
+                            Other fields: \"Warm-up\", \"Start\", \"Contrast\",
+                            \"Stable Gradient\", \"Chaotic Gradient\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut LyapunovState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub fn sequence_is_valid(sequence: &str) -> Result<(), ValidationError> {
+    let letters: Vec<char> = sequence
+        .chars()
+        .filter(|letter| !letter.is_whitespace())
+        .collect();
+
+    if letters.is_empty() {
+        return Err(ValidationError::EmptySequence);
+    }
+
+    if let Some(letter) = letters
+        .iter()
+        .find(|letter| !matches!(letter, 'A' | 'B' | 'a' | 'b'))
+    {
+        return Err(ValidationError::UnknownLetter(format!(
+            "Letter: {}",
+            letter
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn range_is_valid(range: &[f64; 2], name: &str) -> Result<(), ValidationError> {
+    if !range.iter().all(|value| value.is_finite()) || range[0] == range[1] {
+        return Err(ValidationError::BadRange(format!("Parameter: {}", name)));
+    }
+
+    Ok(())
+}
+
+pub fn iterations_range(iterations: u32) -> Result<(), ValidationError> {
+    if iterations < 1 {
+        return Err(ValidationError::BadIterationsValue);
+    }
+
+    Ok(())
+}
+
+pub fn start_range(start: f64) -> Result<(), ValidationError> {
+    if !(start > 0.0 && start < 1.0) {
+        return Err(ValidationError::BadStartValue);
+    }
+
+    Ok(())
+}
+
+pub fn contrast_range(contrast: f64) -> Result<(), ValidationError> {
+    if !(contrast > 0.0 && contrast.is_finite()) {
+        return Err(ValidationError::BadContrastValue);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The sequence is empty.")]
+    EmptySequence,
+
+    #[error("The sequence can contain only letters 'A' and 'B'.")]
+    UnknownLetter(String),
+
+    #[error("The range has to have finite and different bounds.")]
+    BadRange(String),
+
+    #[error("The 'iterations' value is lower than 1.")]
+    BadIterationsValue,
+
+    #[error("The start value has to be in range 0..1 (exclusive).")]
+    BadStartValue,
+
+    #[error("The contrast has to be greater than 0.")]
+    BadContrastValue,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::UnknownLetter(value) | Self::BadRange(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::lyapunov::state::LyapunovState;
+
+    #[test]
+    fn sequence_ignores_case_and_spaces() {
+        assert!(sequence_is_valid("aB bA\tb").is_ok());
+        assert!(matches!(
+            sequence_is_valid(" \n "),
+            Err(ValidationError::EmptySequence)
+        ));
+    }
+
+    #[test]
+    fn unknown_letter_is_reported() {
+        let result = sequence_is_valid("AABC");
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Letter: C".to_string())
+        );
+    }
+
+    #[test]
+    fn reversed_range_is_valid() {
+        assert!(range_is_valid(&[4.0, 2.0], "a").is_ok());
+
+        for range in [[3.0, 3.0], [2.0, f64::INFINITY], [f64::NAN, 4.0]] {
+            assert!(matches!(
+                range_is_valid(&range, "b"),
+                Err(ValidationError::BadRange(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn bad_range_names_parameter() {
+        let mut state = LyapunovState::default();
+        state.b_range = [3.0, 3.0];
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Parameter: b".to_string())
+        );
+    }
+
+    #[test]
+    fn start_at_fixed_points() {
+        // Orbits of 0 and 1 are stuck at 0 for every rate
+        assert!(matches!(
+            start_range(0.0),
+            Err(ValidationError::BadStartValue)
+        ));
+        assert!(matches!(
+            start_range(1.0),
+            Err(ValidationError::BadStartValue)
+        ));
+        assert!(start_range(0.5).is_ok());
+    }
+}
@@ -0,0 +1,62 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static MANDELBROT_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/mandelbrot/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Elephant Valley")]
+    ElephantValley,
+
+    #[strum(serialize = "Misiurewicz Point (1e-100)")]
+    MisiurewiczPoint,
+
+    #[strum(serialize = "Seahorse Valley (Deep)")]
+    SeahorseValley,
+
+    #[strum(serialize = "Whole Set")]
+    WholeSet,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::ElephantValley => PathBuf::from(r"Elephant-Valley.json"),
+            Example::MisiurewiczPoint => PathBuf::from(r"Misiurewicz-Point.json"),
+            Example::SeahorseValley => PathBuf::from(r"Seahorse-Valley.json"),
+            Example::WholeSet => PathBuf::from(r"Whole-Set.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = MANDELBROT_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::ElephantValley,
+            Example::MisiurewiczPoint,
+            Example::SeahorseValley,
+            Example::WholeSet,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
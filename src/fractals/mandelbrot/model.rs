@@ -0,0 +1,363 @@
+use crate::graphics::tiles::PixelModel;
+use crate::math::complex::Complex;
+use crate::math::fixed::Fixed;
+use crate::ui::styles::gradient::Gradient;
+use egui::Color32;
+use std::sync::{Mutex, OnceLock};
+
+pub const DEFAULT_CENTER_RE: &str = "-0.5";
+pub const DEFAULT_CENTER_IM: &str = "0";
+pub const DEFAULT_RADIUS: f64 = 1.5;
+pub const DEFAULT_MAX_ITERATIONS: u32 = 1000;
+pub const DEFAULT_COLOR_PERIOD: f64 = 64.0;
+pub const DEFAULT_INSIDE_COLOR: Color32 = Color32::BLACK;
+
+// Pixels escape beyond this radius, it's large for the smooth coloring
+const ESCAPE_RADIUS_SQR: f64 = 65536.0;
+
+// Reference orbit stops, when it leaves the disk, that holds the whole set
+const REFERENCE_ESCAPE_RADIUS_SQR: f64 = 4.0;
+
+// Iterations of the reference orbit between the checks, whether the rendering is stopped
+const REFERENCE_STEPS: u32 = 64;
+
+// Bits of the reference orbit beyond the radius of the view.
+// The view is never that many pixels high, so pixels get distinct centers.
+const GUARD_BITS: f64 = 64.0;
+
+// Precision of the center, that is enough for the view of the given radius
+pub fn fraction_limbs(radius: f64) -> usize {
+    let bits = -radius.log2().min(0.0) + GUARD_BITS;
+    (bits / 32.0).ceil() as usize
+}
+
+// Decimal digits of the center, that keep its position on the view of the given radius
+pub fn decimal_digits(radius: f64) -> usize {
+    (-radius.log10()).max(0.0).ceil() as usize + 6
+}
+
+pub struct Model {
+    // Orbit of the center, computed with the arbitrary precision in the background,
+    // every pixel is iterated as a small difference from it
+    reference: Mutex<ReferenceOrbit>,
+    orbit: OnceLock<Vec<Complex>>,
+
+    radius: f64,
+    max_iterations: u32,
+
+    color_period: f64,
+    gradient: Gradient,
+    inside_color: Color32,
+}
+
+impl PixelModel for Model {
+    fn color(&self, x: f64, y: f64, width: usize, height: usize) -> Color32 {
+        let pixel_size = 2.0 * self.radius / height as f64;
+        let delta_c = Complex::new(
+            (x - width as f64 / 2.0) * pixel_size,
+            (height as f64 / 2.0 - y) * pixel_size,
+        );
+
+        match self.escape_time(delta_c) {
+            // Colors go back and forth along the gradient, so there are no seams
+            Some(time) => {
+                let phase = (time / self.color_period).fract();
+                self.gradient.sample(1.0 - (2.0 * phase as f32 - 1.0).abs())
+            },
+            None => self.inside_color,
+        }
+    }
+
+    // Deep zoom needs thousands of iterations with long numbers, it takes seconds
+    fn prepare(&self, is_stopped: &dyn Fn() -> bool) -> bool {
+        if self.orbit.get().is_some() {
+            return true;
+        }
+        let Ok(mut reference) = self.reference.lock() else {
+            return false;
+        };
+
+        while !reference.advance(REFERENCE_STEPS) {
+            if is_stopped() {
+                return false;
+            }
+        }
+        let _ = self.orbit.set(std::mem::take(&mut reference.orbit));
+
+        true
+    }
+}
+
+impl Model {
+    // Smooth iteration count of the point, that is shifted from the center by delta_c,
+    // None if it doesn't escape
+    pub fn escape_time(&self, delta_c: Complex) -> Option<f64> {
+        let orbit = self.orbit.get()?;
+
+        // Point is z = Z + delta, where Z is the reference orbit.
+        // From z -> z^2 + c follows delta -> (2Z + delta) * delta + delta_c.
+        let mut delta = Complex::ZERO;
+        let mut index = 0;
+
+        for iteration in 1..=self.max_iterations {
+            delta = (orbit[index].scale(2.0) + delta) * delta + delta_c;
+            index += 1;
+
+            let z = orbit[index] + delta;
+            let norm_sqr = z.norm_sqr();
+            if norm_sqr > ESCAPE_RADIUS_SQR {
+                return Some(iteration as f64 + 1.0 - (norm_sqr.ln() / 2.0).log2());
+            }
+
+            // Glitch: the point is closer to zero than to the reference, so the delta
+            // is not small anymore, and its digits are lost on the next steps.
+            // Rebasing moves the point to the start of the orbit, where Z = 0, so z
+            // stays the same. It's done also, when the reference orbit has escaped.
+            if norm_sqr < delta.norm_sqr() || index == orbit.len() - 1 {
+                delta = z;
+                index = 0;
+            }
+        }
+
+        None
+    }
+}
+
+// Iterates the center with the arbitrary precision, until it escapes.
+// It's made in steps, so the rendering can be stopped in the middle.
+struct ReferenceOrbit {
+    re: Fixed,
+    im: Fixed,
+    z_re: Fixed,
+    z_im: Fixed,
+    max_iterations: u32,
+
+    orbit: Vec<Complex>,
+    is_complete: bool,
+}
+
+impl ReferenceOrbit {
+    fn new(re: Fixed, im: Fixed, max_iterations: u32) -> Self {
+        Self {
+            z_re: re.clone(),
+            z_im: im.clone(),
+            re,
+            im,
+            max_iterations,
+
+            orbit: vec![Complex::ZERO],
+            is_complete: false,
+        }
+    }
+
+    // Returns whether the orbit is complete.
+    // Orbit has at least two points, so the pixels always have a step to make.
+    fn advance(&mut self, steps: u32) -> bool {
+        for _ in 0..steps {
+            if self.is_complete {
+                break;
+            }
+
+            let z = Complex::new(self.z_re.to_f64(), self.z_im.to_f64());
+            self.orbit.push(z);
+            if z.norm_sqr() > REFERENCE_ESCAPE_RADIUS_SQR
+                || self.orbit.len() > self.max_iterations as usize
+            {
+                self.is_complete = true;
+                break;
+            }
+
+            let re_im = &self.z_re * &self.z_im;
+            let next_re =
+                &(&(&self.z_re * &self.z_re) - &(&self.z_im * &self.z_im)) + &self.re;
+            self.z_im = &(&re_im + &re_im) + &self.im;
+            self.z_re = next_re;
+        }
+
+        self.is_complete
+    }
+}
+
+pub struct ModelBuilder {
+    center_re: Fixed,
+    center_im: Fixed,
+    radius: f64,
+    max_iterations: u32,
+
+    color_period: f64,
+    gradient: Gradient,
+    inside_color: Color32,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        let limbs = fraction_limbs(DEFAULT_RADIUS);
+
+        Self {
+            center_re: Fixed::parse(DEFAULT_CENTER_RE, limbs)
+                .unwrap_or_else(|| Fixed::zero(limbs)),
+            center_im: Fixed::parse(DEFAULT_CENTER_IM, limbs)
+                .unwrap_or_else(|| Fixed::zero(limbs)),
+            radius: DEFAULT_RADIUS,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+
+            color_period: DEFAULT_COLOR_PERIOD,
+            gradient: Gradient::default(),
+            inside_color: DEFAULT_INSIDE_COLOR,
+        }
+    }
+}
+
+impl ModelBuilder {
+    // Both parts are brought to the same precision
+    pub fn with_center(mut self, re: Fixed, im: Fixed) -> Self {
+        let limbs = re.fraction_limbs().max(im.fraction_limbs());
+        self.center_re = re.with_precision(limbs);
+        self.center_im = im.with_precision(limbs);
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn with_coloring(
+        mut self, color_period: f64, gradient: Gradient, inside_color: Color32,
+    ) -> Self {
+        self.color_period = color_period;
+        self.gradient = gradient;
+        self.inside_color = inside_color;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            reference: Mutex::new(ReferenceOrbit::new(
+                self.center_re,
+                self.center_im,
+                self.max_iterations,
+            )),
+            orbit: OnceLock::new(),
+
+            radius: self.radius,
+            max_iterations: self.max_iterations,
+
+            color_period: self.color_period,
+            gradient: self.gradient,
+            inside_color: self.inside_color,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn direct_escape_time(c: Complex, max_iterations: u32) -> Option<f64> {
+        let mut z = Complex::ZERO;
+        for iteration in 1..=max_iterations {
+            z = z * z + c;
+            let norm_sqr = z.norm_sqr();
+            if norm_sqr > ESCAPE_RADIUS_SQR {
+                return Some(iteration as f64 + 1.0 - (norm_sqr.ln() / 2.0).log2());
+            }
+        }
+
+        None
+    }
+
+    fn center(re: &str, im: &str, radius: f64) -> (Fixed, Fixed) {
+        let limbs = fraction_limbs(radius);
+        (
+            Fixed::parse(re, limbs).unwrap_or_else(|| Fixed::zero(limbs)),
+            Fixed::parse(im, limbs).unwrap_or_else(|| Fixed::zero(limbs)),
+        )
+    }
+
+    #[test]
+    fn precision_follows_radius() {
+        // Wide views need only the guard bits, both limbs of them
+        assert_eq!(fraction_limbs(4.0), 2);
+        assert_eq!(fraction_limbs(1.0), 2);
+        assert!(fraction_limbs(1e-100) > fraction_limbs(1e-10));
+        assert_eq!(fraction_limbs(1e-280), 32);
+
+        assert_eq!(decimal_digits(4.0), 6);
+        assert_eq!(decimal_digits(1e-10), 16);
+    }
+
+    #[test]
+    fn perturbation_matches_direct_iteration() {
+        let (re, im) = center("-0.75", "0.1", 0.5);
+        let model = ModelBuilder::default()
+            .with_center(re, im)
+            .with_max_iterations(100)
+            .build();
+        assert!(model.prepare(&|| false));
+
+        for step in 0..400 {
+            let delta_c = Complex::new(
+                (step % 20) as f64 / 20.0 - 0.5,
+                (step / 20) as f64 / 20.0 - 0.5,
+            );
+            let c = Complex::new(-0.75, 0.1) + delta_c;
+
+            let perturbed = model.escape_time(delta_c);
+            let direct = direct_escape_time(c, 100);
+            match (perturbed, direct) {
+                (Some(a), Some(b)) => assert!((a - b).abs() < 1e-6, "{:?}", c),
+                (a, b) => assert_eq!(a, b, "{:?}", c),
+            }
+        }
+    }
+
+    #[test]
+    fn deep_zoom_keeps_details() {
+        // Misiurewicz point, the set has spirals around it at every scale
+        let radius = 1e-120;
+        let (re, im) = center("0", "1", radius);
+        let model = ModelBuilder::default()
+            .with_center(re, im)
+            .with_radius(radius)
+            .with_max_iterations(5000)
+            .build();
+        assert!(model.prepare(&|| false));
+
+        let times: Vec<Option<f64>> = (0..16)
+            .map(|step| {
+                let angle = step as f64 / 16.0 * std::f64::consts::TAU;
+                model.escape_time(Complex::from_polar(radius, angle))
+            })
+            .collect();
+
+        assert!(times.iter().all(|time| time.is_some_and(f64::is_finite)));
+        assert!(times.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn stopped_preparation_is_resumed() {
+        // Center inside the set, its orbit takes all the iterations
+        let (re, im) = center("-0.5", "0", 0.5);
+        let model = ModelBuilder::default()
+            .with_center(re, im)
+            .with_max_iterations(1000)
+            .build();
+
+        assert!(!model.prepare(&|| true));
+        assert_eq!(model.escape_time(Complex::ZERO), None);
+
+        assert!(model.prepare(&|| false));
+        let perturbed = model.escape_time(Complex::new(1.0, 0.5));
+        let direct = direct_escape_time(Complex::new(0.5, 0.5), 1000);
+        assert!(perturbed.is_some());
+        assert!(perturbed
+            .zip(direct)
+            .is_some_and(|(a, b)| (a - b).abs() < 1e-6));
+    }
+}
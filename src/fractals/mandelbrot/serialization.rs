@@ -0,0 +1,104 @@
+use crate::fractals::mandelbrot::model;
+use crate::fractals::mandelbrot::state::MandelbrotState;
+use crate::fractals::mandelbrot::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<MandelbrotDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &MandelbrotState) -> serde_json::Result<String> {
+    let dto = MandelbrotDto {
+        center_re: state.center_re.clone(),
+        center_im: state.center_im.clone(),
+        radius: format!("{:e}", state.radius),
+        max_iterations: state.max_iterations,
+        color_period: state.color_period,
+        gradient: (&state.gradient).into(),
+        inside_color: [
+            state.inside_color.r(),
+            state.inside_color.g(),
+            state.inside_color.b(),
+        ],
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_center_re() -> String {
+    model::DEFAULT_CENTER_RE.to_string()
+}
+
+fn default_center_im() -> String {
+    model::DEFAULT_CENTER_IM.to_string()
+}
+
+fn default_radius() -> String {
+    model::DEFAULT_RADIUS.to_string()
+}
+
+fn default_max_iterations() -> u32 {
+    model::DEFAULT_MAX_ITERATIONS
+}
+
+fn default_color_period() -> f64 {
+    model::DEFAULT_COLOR_PERIOD
+}
+
+fn default_inside_color() -> [u8; 3] {
+    let color = model::DEFAULT_INSIDE_COLOR;
+    [color.r(), color.g(), color.b()]
+}
+
+// Center and radius are decimal strings, so no digits are lost to f64
+#[derive(Serialize, Deserialize)]
+pub struct MandelbrotDto {
+    #[serde(rename = "Center Re", default = "default_center_re")]
+    center_re: String,
+
+    #[serde(rename = "Center Im", default = "default_center_im")]
+    center_im: String,
+
+    #[serde(rename = "Radius", default = "default_radius")]
+    radius: String,
+
+    #[serde(rename = "Max Iterations", default = "default_max_iterations")]
+    max_iterations: u32,
+
+    #[serde(rename = "Color Period", default = "default_color_period")]
+    color_period: f64,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+
+    #[serde(rename = "Inside Color", default = "default_inside_color")]
+    inside_color: [u8; 3],
+}
+
+impl MandelbrotDto {
+    pub fn load(self, state: &mut MandelbrotState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.radius = self
+            .radius
+            .trim()
+            .parse()
+            .map_err(|_| ValidationError::BadRadius(self.radius.clone()))?;
+        state.center_re = self.center_re.trim().to_string();
+        state.center_im = self.center_im.trim().to_string();
+        state.max_iterations = self.max_iterations;
+        state.color_period = self.color_period;
+        state.gradient = self.gradient.into();
+
+        let [r, g, b] = self.inside_color;
+        state.inside_color = Color32::from_rgb(r, g, b);
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
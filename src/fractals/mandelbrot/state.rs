@@ -0,0 +1,218 @@
+use crate::fractals::mandelbrot::model::{Model, ModelBuilder};
+use crate::fractals::mandelbrot::validation::ValidationError;
+use crate::fractals::mandelbrot::{model, validation};
+use crate::graphics::raster::Raster;
+use crate::graphics::tiles::TiledImage;
+use crate::math::fixed::Fixed;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::gradient::Gradient;
+use egui::{Color32, Response, Shape};
+
+// Radius is divided by it on every click, and multiplied on every right click
+const CLICK_ZOOM: f64 = 4.0;
+
+pub struct MandelbrotState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    // Decimal strings, f64 can't hold enough digits for the deep zoom
+    pub center_re: String,
+    pub center_im: String,
+    // Half of the view height on the complex plane
+    pub radius: f64,
+    pub max_iterations: u32,
+
+    pub color_period: f64,
+    pub gradient: Gradient,
+    pub inside_color: Color32,
+
+    drawn_params: Option<CanvasParams>,
+
+    image: TiledImage<Model>,
+    raster: Raster,
+}
+
+impl Default for MandelbrotState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            center_re: model::DEFAULT_CENTER_RE.to_string(),
+            center_im: model::DEFAULT_CENTER_IM.to_string(),
+            radius: model::DEFAULT_RADIUS,
+            max_iterations: model::DEFAULT_MAX_ITERATIONS,
+
+            color_period: model::DEFAULT_COLOR_PERIOD,
+            gradient: Gradient::default(),
+            inside_color: model::DEFAULT_INSIDE_COLOR,
+
+            drawn_params: None,
+
+            image: TiledImage::default(),
+            raster: Raster::new("Mandelbrot"),
+        }
+    }
+}
+
+impl MandelbrotState {
+    pub fn shapes(
+        &mut self, ctx: &egui::Context, params: &CanvasParams, response: &Response,
+    ) -> Vec<Shape> {
+        if self.is_initialized {
+            self.handle_click(params, response);
+        }
+
+        // Canvas offset and scale can't reach the deep zoom, so their changes are moved
+        // into the center and the radius, when the dragging is over
+        let is_params_changed = self
+            .drawn_params
+            .as_ref()
+            .is_some_and(|drawn_params| drawn_params != params);
+
+        if self.is_drawing_requested
+            || (is_params_changed && self.is_initialized && !response.dragged())
+        {
+            self.is_drawing_requested = false;
+            if let Some(drawn_params) = self.drawn_params.take() {
+                let (x, y) = (
+                    drawn_params.offset.0 - params.offset.0,
+                    drawn_params.offset.1 - params.offset.1,
+                );
+                let zoom = (params.px_per_cm / drawn_params.px_per_cm) as f64;
+                self.move_view(x as f64, y as f64, zoom, params);
+            }
+            self.drawn_params = Some(params.clone());
+            self.start(params);
+        }
+
+        if self.image.is_rendering() {
+            ctx.request_repaint();
+        }
+        if self.image.update() {
+            let (width, height) = self.image.size();
+            self.raster
+                .set_pixels(width, height, self.image.pixels().to_vec());
+        }
+
+        // Picture follows the drag, until it's rendered again
+        let mut rect = params.screen_rect();
+        if let Some(drawn_params) = &self.drawn_params {
+            rect = rect.translate(egui::vec2(
+                params.offset.0 - drawn_params.offset.0,
+                params.offset.1 - drawn_params.offset.1,
+            ));
+        }
+
+        vec![self.raster.shape(ctx, rect)]
+    }
+
+    // Click zooms into the point, right click zooms out
+    fn handle_click(&mut self, params: &CanvasParams, response: &Response) {
+        let zoom = if response.clicked() {
+            CLICK_ZOOM
+        } else if response.secondary_clicked() {
+            1.0 / CLICK_ZOOM
+        } else {
+            return;
+        };
+        let Some(position) = response.interact_pointer_pos() else {
+            return;
+        };
+
+        let center = params.screen_rect().center();
+        let (x, y) = (position.x - center.x, position.y - center.y);
+        if zoom > 1.0 {
+            self.move_view(x as f64, y as f64, zoom, params);
+        } else {
+            self.move_view(0.0, 0.0, zoom, params);
+        }
+        self.request_drawing();
+    }
+
+    // Moves the center by the offset in pixels of the canvas, then divides the radius
+    // by the zoom. The center is rounded to the digits, that the new radius needs.
+    fn move_view(&mut self, x: f64, y: f64, zoom: f64, params: &CanvasParams) {
+        let height = params.screen_rect().height() as f64;
+        if height <= 0.0 {
+            return;
+        }
+
+        let pixel_size = 2.0 * self.radius / height;
+        let radius =
+            (self.radius / zoom).clamp(validation::MIN_RADIUS, validation::MAX_RADIUS);
+        let limbs = model::fraction_limbs(radius.min(self.radius));
+        let digits = model::decimal_digits(radius);
+
+        let shift = |part: &str, delta: f64| {
+            Fixed::parse(part, limbs)
+                .map(|value| (&value + &Fixed::from_f64(delta, limbs)).to_decimal(digits))
+        };
+        if let (Some(re), Some(im)) = (
+            shift(&self.center_re, x * pixel_size),
+            shift(&self.center_im, -y * pixel_size),
+        ) {
+            self.center_re = re;
+            self.center_im = im;
+        }
+        self.radius = radius;
+    }
+
+    fn start(&mut self, params: &CanvasParams) {
+        let limbs = model::fraction_limbs(self.radius);
+        let (Some(re), Some(im)) = (
+            Fixed::parse(&self.center_re, limbs),
+            Fixed::parse(&self.center_im, limbs),
+        ) else {
+            return;
+        };
+
+        let model = ModelBuilder::default()
+            .with_center(re, im)
+            .with_radius(self.radius)
+            .with_max_iterations(self.max_iterations)
+            .with_coloring(self.color_period, self.gradient.clone(), self.inside_color)
+            .build();
+
+        let rect = params.screen_rect();
+        let (width, height) = (
+            rect.width().max(0.0) as usize,
+            rect.height().max(0.0) as usize,
+        );
+
+        self.image.start(model, width, height, self.inside_color);
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::center_is_valid(&self.center_re, &self.center_im)?;
+        validation::radius_range(self.radius)?;
+        validation::iterations_range(self.max_iterations)?;
+        validation::color_period_range(self.color_period)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    // Rendering with the old settings is useless, so it stops
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+        self.image.stop();
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn is_rendering(&self) -> bool {
+        self.image.is_rendering()
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.image.progress()
+    }
+}
@@ -0,0 +1,275 @@
+use crate::context::Context;
+use crate::fractals::mandelbrot::examples::Example;
+use crate::fractals::mandelbrot::model;
+use crate::fractals::mandelbrot::serialization;
+use crate::fractals::mandelbrot::state::MandelbrotState;
+use crate::fractals::mandelbrot::validation::{MAX_ITERATIONS, MAX_RADIUS, MIN_RADIUS};
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, TextEdit, Ui};
+use indoc::indoc;
+
+pub struct MandelbrotSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for MandelbrotSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl MandelbrotSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            let state = &context.mandelbrot_state;
+
+            ui.label("Status: ");
+            if state.is_rendering() {
+                ui.label(
+                    RichText::new(format!(
+                        "Rendering {:.0}%...",
+                        state.progress() * 100.0
+                    ))
+                    .color(colors::LIME),
+                );
+            } else if state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.mandelbrot_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Center Re:");
+            changed |= ui
+                .add(TextEdit::singleline(&mut state.center_re).desired_width(150.0))
+                .changed();
+            ui.end_row();
+
+            ui.label("Center Im:");
+            changed |= ui
+                .add(TextEdit::singleline(&mut state.center_im).desired_width(150.0))
+                .changed();
+            ui.end_row();
+
+            ui.label("Radius:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.radius)
+                        .speed(0.0)
+                        .range(MIN_RADIUS..=MAX_RADIUS)
+                        .custom_formatter(|value, _| format!("{:e}", value))
+                        .custom_parser(|text| text.trim().parse().ok()),
+                )
+                .on_hover_text("Half of the view height on the complex plane.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Max Iterations:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.max_iterations)
+                        .speed(10)
+                        .range(1..=MAX_ITERATIONS),
+                )
+                .on_hover_text("Deeper views usually need more iterations.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Color Period:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.color_period)
+                        .speed(1)
+                        .range(1.0..=100000.0),
+                )
+                .on_hover_text("Iterations between the ends of the gradient.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Inside Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.inside_color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Gradient", |ui| {
+            changed |= state.gradient.show_editor(ui, "Mandelbrot");
+        });
+
+        ui.add_space(5.0);
+
+        ui.label("Click to zoom in, right click to zoom out, drag to move.");
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset View").clicked() {
+                state.center_re = model::DEFAULT_CENTER_RE.to_string();
+                state.center_im = model::DEFAULT_CENTER_IM.to_string();
+                state.radius = model::DEFAULT_RADIUS;
+                changed = true;
+            }
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.mandelbrot_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.mandelbrot_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    context.mandelbrot_state.is_initialized(),
+                    Button::new("Draw"),
+                )
+                .clicked()
+            {
+                context.mandelbrot_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.mandelbrot_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.mandelbrot_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.mandelbrot_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Center Re\": \"-0.743643887037158704752191506114774\",
+                                \"Center Im\": \"0.131825904205311970493132056385139\",
+                                \"Radius\": \"1e-25\",
+                                \"Max Iterations\": 20000,
+                                \"Color Period\": 200.0
+                            }
+
+                            Center Re, Center Im: decimal strings of any length.
+                            Radius: half of the view height on the complex plane,
+                            down to 1e-280, as a string.
+                            Other fields: \"Gradient\", \"Inside Color\" ([r, g, b]).
+
+                            Only the center is computed with the arbitrary precision,
+                            other pixels are computed as small differences from it,
+                            so even deep views are rendered fast.
+
+                            Click on the canvas zooms into the point, right click
+                            zooms out, dragging and scrolling move the view.
+                            The saved center and radius are the current view.
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut MandelbrotState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::math::fixed::Fixed;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+// Beyond this the plane holds no points of the set
+pub const MAX_CENTER_MODULUS: f64 = 4.0;
+
+// Pixel deltas are f64 numbers, they have to stay far from its smallest values
+pub const MIN_RADIUS: f64 = 1e-280;
+pub const MAX_RADIUS: f64 = 4.0;
+
+pub const MAX_ITERATIONS: u32 = 1_000_000;
+
+pub fn center_is_valid(re: &str, im: &str) -> Result<(), ValidationError> {
+    let parts = Fixed::parse(re, 2).zip(Fixed::parse(im, 2));
+    let modulus = parts.map(|(re, im)| re.to_f64().hypot(im.to_f64()));
+    if !modulus.is_some_and(|modulus| modulus <= MAX_CENTER_MODULUS) {
+        return Err(ValidationError::BadCenter(format!("{} + {}i.", re, im)));
+    }
+
+    Ok(())
+}
+
+pub fn radius_range(radius: f64) -> Result<(), ValidationError> {
+    if !(MIN_RADIUS..=MAX_RADIUS).contains(&radius) {
+        // Tiny radii would be written with hundreds of zeros otherwise
+        return Err(ValidationError::BadRadius(format!("{:e}", radius)));
+    }
+
+    Ok(())
+}
+
+pub fn iterations_range(iterations: u32) -> Result<(), ValidationError> {
+    if !(1..=MAX_ITERATIONS).contains(&iterations) {
+        return Err(ValidationError::ZeroOrTooManyIterations);
+    }
+
+    Ok(())
+}
+
+pub fn color_period_range(color_period: f64) -> Result<(), ValidationError> {
+    if !(color_period > 0.0 && color_period.is_finite()) {
+        return Err(ValidationError::BadColorPeriod);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error(
+        "Center has to be written as decimal numbers, its modulus has to be at most 4."
+    )]
+    BadCenter(String),
+
+    #[error("Radius has to be in range 1e-280..=4.")]
+    BadRadius(String),
+
+    #[error("Max iterations have to be in range 1..=1000000.")]
+    ZeroOrTooManyIterations,
+
+    #[error("Color period has to be greater than 0.")]
+    BadColorPeriod,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadCenter(value) | Self::BadRadius(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::mandelbrot::examples::Example;
+    use crate::fractals::mandelbrot::serialization;
+    use crate::fractals::mandelbrot::state::MandelbrotState;
+
+    #[test]
+    fn center_beyond_f64_precision() {
+        // Deep zoom centers have more digits, than f64 holds, and may use exponents
+        let re = format!("-1.{}", "7".repeat(200));
+        assert!(center_is_valid(&re, "1e-150").is_ok());
+        assert!(center_is_valid(" +0.25 ", "-0").is_ok());
+    }
+
+    #[test]
+    fn center_modulus_limit_is_inclusive() {
+        assert!(center_is_valid("-4", "0").is_ok());
+        assert!(center_is_valid("0", "4e0").is_ok());
+        assert!(center_is_valid("-4.000001", "0").is_err());
+    }
+
+    #[test]
+    fn malformed_center_is_reported() {
+        for (re, im) in [("-0.5", "1..2"), ("abc", "0"), ("", "0"), ("1e", "0")] {
+            assert!(matches!(
+                center_is_valid(re, im),
+                Err(ValidationError::BadCenter(_))
+            ));
+        }
+
+        let mut state = MandelbrotState::default();
+        state.center_re = "3".to_string();
+        state.center_im = "-3".to_string();
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("3 + -3i.".to_string())
+        );
+    }
+
+    #[test]
+    fn radius_bounds_are_inclusive() {
+        assert!(radius_range(MIN_RADIUS).is_ok());
+        assert!(radius_range(MAX_RADIUS).is_ok());
+        for radius in [0.0, -1.0, 4.5, f64::NAN] {
+            assert!(radius_range(radius).is_err(), "{}", radius);
+        }
+    }
+
+    #[test]
+    fn tiny_radius_is_reported_with_exponent() {
+        let mut state = MandelbrotState::default();
+        state.radius = 1e-300;
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("1e-300".to_string())
+        );
+    }
+
+    #[test]
+    fn iterations_and_color_period() {
+        assert!(iterations_range(MAX_ITERATIONS).is_ok());
+        assert!(iterations_range(0).is_err());
+        assert!(iterations_range(MAX_ITERATIONS + 1).is_err());
+        for period in [0.0, f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                color_period_range(period),
+                Err(ValidationError::BadColorPeriod)
+            ));
+        }
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = MandelbrotState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
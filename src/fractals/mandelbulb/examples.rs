@@ -0,0 +1,64 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static MANDELBULB_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/mandelbulb/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Mandelbox")]
+    Mandelbox,
+
+    #[strum(serialize = "Mandelbox (Negative Scale)")]
+    MandelboxNegativeScale,
+
+    #[strum(serialize = "Mandelbulb")]
+    Mandelbulb,
+
+    #[strum(serialize = "Mandelbulb (Power 4)")]
+    MandelbulbPower4,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::Mandelbox => PathBuf::from(r"Mandelbox.json"),
+            Example::MandelboxNegativeScale => {
+                PathBuf::from(r"Mandelbox-Negative-Scale.json")
+            },
+            Example::Mandelbulb => PathBuf::from(r"Mandelbulb.json"),
+            Example::MandelbulbPower4 => PathBuf::from(r"Mandelbulb-Power-4.json"),
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = MANDELBULB_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::Mandelbox,
+            Example::MandelboxNegativeScale,
+            Example::Mandelbulb,
+            Example::MandelbulbPower4,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
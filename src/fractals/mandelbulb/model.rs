@@ -0,0 +1,478 @@
+use crate::graphics::tiles::PixelModel;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_POWER: f64 = 8.0;
+pub const DEFAULT_SCALE: f64 = 2.0;
+pub const DEFAULT_ITERATIONS: u32 = 10;
+pub const DEFAULT_MAX_STEPS: u32 = 160;
+pub const DEFAULT_DETAIL: f64 = 1.0;
+pub const DEFAULT_AMBIENT_OCCLUSION: f64 = 0.8;
+pub const DEFAULT_SURFACE_COLOR: Color32 = Color32::from_rgb(230, 170, 110);
+pub const DEFAULT_BACKGROUND_COLOR: Color32 = Color32::from_rgb(25, 30, 45);
+
+const BAILOUT: f64 = 2.0;
+// Radii of the sphere fold of the Mandelbox
+const MIN_RADIUS_SQR: f64 = 0.25;
+const FIXED_RADIUS_SQR: f64 = 1.0;
+// Samples along the normal for the ambient occlusion
+const OCCLUSION_SAMPLES: u32 = 5;
+// Light comes from the upper left behind the camera
+const LIGHT_DIRECTION: [f64; 3] = [-0.45, 0.65, 0.6];
+const AMBIENT_LIGHT: f64 = 0.3;
+const SHININESS: i32 = 24;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Kind {
+    #[default]
+    #[strum(serialize = "Mandelbulb")]
+    #[serde(rename = "Mandelbulb")]
+    Mandelbulb,
+
+    #[strum(serialize = "Mandelbox")]
+    #[serde(rename = "Mandelbox")]
+    Mandelbox,
+}
+
+impl Kind {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Kind::Mandelbulb, Kind::Mandelbox].into_iter()
+    }
+
+    // Camera, that sees the whole fractal
+    pub fn default_camera(&self) -> Camera {
+        match self {
+            Kind::Mandelbulb => Camera {
+                yaw: 30.0,
+                pitch: 25.0,
+                distance: 3.2,
+                fov: 45.0,
+            },
+            Kind::Mandelbox => Camera {
+                yaw: 35.0,
+                pitch: 30.0,
+                distance: 14.0,
+                fov: 45.0,
+            },
+        }
+    }
+}
+
+// Orbit camera, that looks at the origin. Angles are in degrees.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Camera {
+    #[serde(rename = "Yaw")]
+    pub yaw: f64,
+
+    #[serde(rename = "Pitch")]
+    pub pitch: f64,
+
+    #[serde(rename = "Distance")]
+    pub distance: f64,
+
+    #[serde(rename = "Field of View")]
+    pub fov: f64,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Kind::default().default_camera()
+    }
+}
+
+impl Camera {
+    pub const MAX_PITCH: f64 = 89.0;
+
+    pub fn orbited(&self, yaw: f64, pitch: f64, zoom: f64) -> Self {
+        Self {
+            yaw: (self.yaw + yaw).rem_euclid(360.0),
+            pitch: (self.pitch + pitch).clamp(-Self::MAX_PITCH, Self::MAX_PITCH),
+            distance: self.distance / zoom,
+            fov: self.fov,
+        }
+    }
+
+    pub fn position(&self) -> [f64; 3] {
+        let (yaw, pitch) = (self.yaw.to_radians(), self.pitch.to_radians());
+        [
+            self.distance * pitch.cos() * yaw.sin(),
+            self.distance * pitch.sin(),
+            self.distance * pitch.cos() * yaw.cos(),
+        ]
+    }
+}
+
+pub struct Model {
+    kind: Kind,
+    power: f64,
+    scale: f64,
+    iterations: u32,
+    max_steps: u32,
+    detail: f64,
+    ambient_occlusion: f64,
+
+    camera: Camera,
+    surface_color: Color32,
+    background_color: Color32,
+}
+
+impl PixelModel for Model {
+    fn color(&self, x: f64, y: f64, width: usize, height: usize) -> Color32 {
+        let position = self.camera.position();
+        let forward = normalize(scale(position, -1.0));
+        let right = normalize(cross(forward, [0.0, 1.0, 0.0]));
+        let up = cross(right, forward);
+
+        let tangent = (self.camera.fov.to_radians() / 2.0).tan();
+        let u = (2.0 * x / width as f64 - 1.0) * tangent * width as f64 / height as f64;
+        let v = (1.0 - 2.0 * y / height as f64) * tangent;
+        let direction = normalize(add(forward, add(scale(right, u), scale(up, v))));
+
+        // Surface is hit, when it's closer than the size of a pixel at that distance
+        let pixel_angle = 2.0 * tangent / height as f64 * self.detail;
+
+        match self.march(position, direction, pixel_angle) {
+            Some(distance) => {
+                let point = add(position, scale(direction, distance));
+                self.shade(point, direction, distance * pixel_angle)
+            },
+            None => self.background_color,
+        }
+    }
+}
+
+impl Model {
+    // Distance along the ray to the surface, if it's hit
+    fn march(
+        &self, origin: [f64; 3], direction: [f64; 3], pixel_angle: f64,
+    ) -> Option<f64> {
+        let far = 2.0 * self.camera.distance + self.bounding_radius();
+        let mut distance = 0.0;
+
+        for _ in 0..self.max_steps {
+            let step = self.distance_estimate(add(origin, scale(direction, distance)));
+            if step < pixel_angle * distance {
+                return Some(distance);
+            }
+            distance += step;
+            if distance > far {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    fn shade(&self, point: [f64; 3], direction: [f64; 3], epsilon: f64) -> Color32 {
+        let normal = self.normal(point, epsilon);
+        let light = normalize(self.camera_light());
+
+        let diffuse = dot(normal, light).max(0.0);
+        let half = normalize(sub(light, direction));
+        let specular = dot(normal, half).max(0.0).powi(SHININESS);
+        let occlusion = self.occlusion(point, normal, epsilon);
+
+        let intensity = (AMBIENT_LIGHT + (1.0 - AMBIENT_LIGHT) * diffuse) * occlusion;
+        let channel = |value: u8| {
+            let value = value as f64 * intensity + 255.0 * specular * occlusion * 0.5;
+            value.clamp(0.0, 255.0) as u8
+        };
+
+        let color = self.surface_color;
+        Color32::from_rgb(channel(color.r()), channel(color.g()), channel(color.b()))
+    }
+
+    // Light direction turns with the camera, so the visible side is always lit
+    fn camera_light(&self) -> [f64; 3] {
+        let yaw = self.camera.yaw.to_radians();
+        let [x, y, z] = LIGHT_DIRECTION;
+        [
+            x * yaw.cos() + z * yaw.sin(),
+            y,
+            -x * yaw.sin() + z * yaw.cos(),
+        ]
+    }
+
+    fn normal(&self, point: [f64; 3], epsilon: f64) -> [f64; 3] {
+        let gradient = [0, 1, 2].map(|axis| {
+            let mut offset = [0.0; 3];
+            offset[axis] = epsilon;
+            self.distance_estimate(add(point, offset))
+                - self.distance_estimate(sub(point, offset))
+        });
+
+        normalize(gradient)
+    }
+
+    // Compares the distances to the surface along the normal with the expected ones,
+    // points in creases are closer to the surface than they should be
+    fn occlusion(&self, point: [f64; 3], normal: [f64; 3], epsilon: f64) -> f64 {
+        let step = 4.0 * epsilon.max(1e-4);
+        let mut occlusion = 0.0;
+        let mut weight = 1.0;
+
+        for sample in 1..=OCCLUSION_SAMPLES {
+            let offset = step * sample as f64;
+            let distance = self.distance_estimate(add(point, scale(normal, offset)));
+            occlusion += weight * (offset - distance).max(0.0) / offset;
+            weight /= 2.0;
+        }
+
+        (1.0 - self.ambient_occlusion * occlusion / 2.0).clamp(0.0, 1.0)
+    }
+
+    fn bounding_radius(&self) -> f64 {
+        match self.kind {
+            Kind::Mandelbulb => BAILOUT,
+            // Corner of the box, that holds the whole set
+            Kind::Mandelbox => {
+                3f64.sqrt() * 2.0 * (self.scale.abs() + 1.0) / (self.scale.abs() - 1.0)
+            },
+        }
+    }
+
+    pub fn distance_estimate(&self, point: [f64; 3]) -> f64 {
+        // Far from the fractal the estimates are not reliable, but the sphere is
+        let radius = length(point);
+        let bound = self.bounding_radius();
+        if radius > bound + 1.0 {
+            return radius - bound;
+        }
+
+        match self.kind {
+            Kind::Mandelbulb => self.mandelbulb(point),
+            Kind::Mandelbox => self.mandelbox(point),
+        }
+    }
+
+    // Spherical coordinates of the point are raised to the power on every iteration
+    fn mandelbulb(&self, point: [f64; 3]) -> f64 {
+        let mut z = point;
+        let mut derivative = 1.0;
+        let mut radius = length(z);
+
+        for _ in 0..self.iterations {
+            if radius > BAILOUT {
+                break;
+            }
+            // The orbit got stuck in the origin, so the point is inside
+            if radius == 0.0 {
+                return 0.0;
+            }
+
+            let theta = (z[2] / radius).clamp(-1.0, 1.0).acos() * self.power;
+            let phi = z[1].atan2(z[0]) * self.power;
+            derivative = self.power * radius.powf(self.power - 1.0) * derivative + 1.0;
+
+            let scaled = radius.powf(self.power);
+            z = add(
+                scale(
+                    [
+                        theta.sin() * phi.cos(),
+                        theta.sin() * phi.sin(),
+                        theta.cos(),
+                    ],
+                    scaled,
+                ),
+                point,
+            );
+            radius = length(z);
+        }
+
+        0.5 * radius.ln() * radius / derivative
+    }
+
+    // Box fold and sphere fold, then scaling and translation back by the point
+    fn mandelbox(&self, point: [f64; 3]) -> f64 {
+        let mut z = point;
+        let mut derivative = 1.0;
+
+        for _ in 0..self.iterations {
+            z = z.map(|value| value.clamp(-1.0, 1.0) * 2.0 - value);
+
+            let radius_sqr = dot(z, z);
+            let factor = match radius_sqr {
+                value if value < MIN_RADIUS_SQR => FIXED_RADIUS_SQR / MIN_RADIUS_SQR,
+                value if value < FIXED_RADIUS_SQR => FIXED_RADIUS_SQR / value,
+                _ => 1.0,
+            };
+            z = add(scale(z, factor * self.scale), point);
+            derivative = derivative * factor * self.scale.abs() + 1.0;
+        }
+
+        length(z) / derivative.abs()
+    }
+}
+
+fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f64; 3], factor: f64) -> [f64; 3] {
+    a.map(|value| value * factor)
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f64; 3]) -> [f64; 3] {
+    let length = length(a);
+    match length > 0.0 {
+        true => scale(a, 1.0 / length),
+        false => a,
+    }
+}
+
+pub struct ModelBuilder {
+    kind: Kind,
+    power: f64,
+    scale: f64,
+    iterations: u32,
+    max_steps: u32,
+    detail: f64,
+    ambient_occlusion: f64,
+
+    camera: Camera,
+    surface_color: Color32,
+    background_color: Color32,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            kind: Kind::default(),
+            power: DEFAULT_POWER,
+            scale: DEFAULT_SCALE,
+            iterations: DEFAULT_ITERATIONS,
+            max_steps: DEFAULT_MAX_STEPS,
+            detail: DEFAULT_DETAIL,
+            ambient_occlusion: DEFAULT_AMBIENT_OCCLUSION,
+
+            camera: Camera::default(),
+            surface_color: DEFAULT_SURFACE_COLOR,
+            background_color: DEFAULT_BACKGROUND_COLOR,
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_kind(mut self, kind: Kind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_power(mut self, power: f64) -> Self {
+        self.power = power;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_marching(mut self, max_steps: u32, detail: f64) -> Self {
+        self.max_steps = max_steps;
+        self.detail = detail;
+        self
+    }
+
+    pub fn with_ambient_occlusion(mut self, ambient_occlusion: f64) -> Self {
+        self.ambient_occlusion = ambient_occlusion;
+        self
+    }
+
+    pub fn with_camera(mut self, camera: Camera) -> Self {
+        self.camera = camera;
+        self
+    }
+
+    pub fn with_colors(mut self, surface: Color32, background: Color32) -> Self {
+        self.surface_color = surface;
+        self.background_color = background;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            kind: self.kind,
+            power: self.power,
+            scale: self.scale,
+            iterations: self.iterations,
+            max_steps: self.max_steps,
+            detail: self.detail,
+            ambient_occlusion: self.ambient_occlusion,
+
+            camera: self.camera,
+            surface_color: self.surface_color,
+            background_color: self.background_color,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_estimates_are_bounds() {
+        for kind in Kind::iter() {
+            let model = ModelBuilder::default().with_kind(kind).build();
+
+            // Far away the estimate can't exceed the distance to the origin
+            let far = model.distance_estimate([0.0, 0.0, 40.0]);
+            assert!(far > 0.0 && far <= 40.0, "{}: {}", kind, far);
+
+            // The origin is inside both fractals
+            assert!(model.distance_estimate([0.0, 0.0, 0.0]) < 1e-3, "{}", kind);
+        }
+    }
+
+    #[test]
+    fn center_is_hit_and_corner_is_not() {
+        let model = ModelBuilder::default()
+            .with_camera(Camera {
+                yaw: 0.0,
+                pitch: 0.0,
+                distance: 3.0,
+                fov: 45.0,
+            })
+            .build();
+
+        assert_ne!(model.color(20.0, 20.0, 40, 40), DEFAULT_BACKGROUND_COLOR);
+        assert_eq!(model.color(0.5, 0.5, 40, 40), DEFAULT_BACKGROUND_COLOR);
+    }
+
+    #[test]
+    fn orbit_keeps_distance() {
+        let camera = Camera::default().orbited(120.0, 100.0, 2.0);
+
+        let [x, y, z] = camera.position();
+        assert!(((x * x + y * y + z * z).sqrt() - camera.distance).abs() < 1e-9);
+        assert_eq!(camera.pitch, Camera::MAX_PITCH);
+        assert_eq!(camera.distance, Camera::default().distance / 2.0);
+    }
+}
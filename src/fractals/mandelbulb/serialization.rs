@@ -0,0 +1,123 @@
+use crate::fractals::mandelbulb::model;
+use crate::fractals::mandelbulb::model::{Camera, Kind};
+use crate::fractals::mandelbulb::state::MandelbulbState;
+use crate::fractals::mandelbulb::validation::ValidationError;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<MandelbulbDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &MandelbulbState) -> serde_json::Result<String> {
+    let dto = MandelbulbDto {
+        kind: state.kind,
+        power: state.power,
+        scale: state.scale,
+        iterations: state.iterations,
+        max_steps: state.max_steps,
+        detail: state.detail,
+        ambient_occlusion: state.ambient_occlusion,
+        camera: Some(state.camera),
+        surface_color: to_rgb(state.surface_color),
+        background_color: to_rgb(state.background_color),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn to_rgb(color: Color32) -> [u8; 3] {
+    [color.r(), color.g(), color.b()]
+}
+
+fn default_power() -> f64 {
+    model::DEFAULT_POWER
+}
+
+fn default_scale() -> f64 {
+    model::DEFAULT_SCALE
+}
+
+fn default_iterations() -> u32 {
+    model::DEFAULT_ITERATIONS
+}
+
+fn default_max_steps() -> u32 {
+    model::DEFAULT_MAX_STEPS
+}
+
+fn default_detail() -> f64 {
+    model::DEFAULT_DETAIL
+}
+
+fn default_ambient_occlusion() -> f64 {
+    model::DEFAULT_AMBIENT_OCCLUSION
+}
+
+fn default_surface_color() -> [u8; 3] {
+    to_rgb(model::DEFAULT_SURFACE_COLOR)
+}
+
+fn default_background_color() -> [u8; 3] {
+    to_rgb(model::DEFAULT_BACKGROUND_COLOR)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MandelbulbDto {
+    #[serde(rename = "Kind", default)]
+    kind: Kind,
+
+    #[serde(rename = "Power", default = "default_power")]
+    power: f64,
+
+    #[serde(rename = "Scale", default = "default_scale")]
+    scale: f64,
+
+    #[serde(rename = "Iterations", default = "default_iterations")]
+    iterations: u32,
+
+    #[serde(rename = "Max Steps", default = "default_max_steps")]
+    max_steps: u32,
+
+    #[serde(rename = "Detail", default = "default_detail")]
+    detail: f64,
+
+    #[serde(rename = "Ambient Occlusion", default = "default_ambient_occlusion")]
+    ambient_occlusion: f64,
+
+    // Default camera of the kind if missing
+    #[serde(rename = "Camera", default)]
+    camera: Option<Camera>,
+
+    #[serde(rename = "Surface Color", default = "default_surface_color")]
+    surface_color: [u8; 3],
+
+    #[serde(rename = "Background Color", default = "default_background_color")]
+    background_color: [u8; 3],
+}
+
+impl MandelbulbDto {
+    pub fn load(self, state: &mut MandelbulbState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.kind = self.kind;
+        state.power = self.power;
+        state.scale = self.scale;
+        state.iterations = self.iterations;
+        state.max_steps = self.max_steps;
+        state.detail = self.detail;
+        state.ambient_occlusion = self.ambient_occlusion;
+        state.camera = self.camera.unwrap_or(self.kind.default_camera());
+
+        let [r, g, b] = self.surface_color;
+        state.surface_color = Color32::from_rgb(r, g, b);
+        let [r, g, b] = self.background_color;
+        state.background_color = Color32::from_rgb(r, g, b);
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,157 @@
+use crate::fractals::mandelbulb::model::{Camera, Kind, Model, ModelBuilder};
+use crate::fractals::mandelbulb::validation::ValidationError;
+use crate::fractals::mandelbulb::{model, validation};
+use crate::graphics::raster::Raster;
+use crate::graphics::tiles::TiledImage;
+use crate::ui::components::canvas::CanvasParams;
+use egui::{Color32, Shape};
+
+// Camera turn for every pixel of the canvas drag, in degrees
+const DEGREES_PER_PX: f64 = 0.3;
+
+pub struct MandelbulbState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub kind: Kind,
+    pub power: f64,
+    pub scale: f64,
+    pub iterations: u32,
+    pub max_steps: u32,
+    pub detail: f64,
+    pub ambient_occlusion: f64,
+
+    pub camera: Camera,
+    pub surface_color: Color32,
+    pub background_color: Color32,
+
+    drawn_params: Option<CanvasParams>,
+
+    image: TiledImage<Model>,
+
+    raster: Raster,
+}
+
+impl Default for MandelbulbState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            kind: Kind::default(),
+            power: model::DEFAULT_POWER,
+            scale: model::DEFAULT_SCALE,
+            iterations: model::DEFAULT_ITERATIONS,
+            max_steps: model::DEFAULT_MAX_STEPS,
+            detail: model::DEFAULT_DETAIL,
+            ambient_occlusion: model::DEFAULT_AMBIENT_OCCLUSION,
+
+            camera: Camera::default(),
+            surface_color: model::DEFAULT_SURFACE_COLOR,
+            background_color: model::DEFAULT_BACKGROUND_COLOR,
+
+            drawn_params: None,
+
+            image: TiledImage::default(),
+
+            raster: Raster::new("Mandelbulb"),
+        }
+    }
+}
+
+impl MandelbulbState {
+    pub fn shapes(&mut self, ctx: &egui::Context, params: &CanvasParams) -> Vec<Shape> {
+        // Dragging the canvas orbits the camera, zooming moves it closer
+        let is_params_changed = self
+            .drawn_params
+            .as_ref()
+            .is_some_and(|drawn_params| drawn_params != params);
+
+        if self.is_drawing_requested || (is_params_changed && self.is_initialized) {
+            self.is_drawing_requested = false;
+            if let Some(drawn_params) = &self.drawn_params {
+                self.camera = orbited(&self.camera, drawn_params, params);
+            }
+            self.drawn_params = Some(params.clone());
+            self.start(params);
+        }
+
+        if self.image.is_rendering() {
+            ctx.request_repaint();
+        }
+        if self.image.update() {
+            let (width, height) = self.image.size();
+            self.raster
+                .set_pixels(width, height, self.image.pixels().to_vec());
+        }
+
+        vec![self.raster.shape(ctx, params.screen_rect())]
+    }
+
+    fn start(&mut self, params: &CanvasParams) {
+        let model = ModelBuilder::default()
+            .with_kind(self.kind)
+            .with_power(self.power)
+            .with_scale(self.scale)
+            .with_iterations(self.iterations)
+            .with_marching(self.max_steps, self.detail)
+            .with_ambient_occlusion(self.ambient_occlusion)
+            .with_camera(self.camera)
+            .with_colors(self.surface_color, self.background_color)
+            .build();
+
+        let rect = params.screen_rect();
+        let (width, height) = (
+            rect.width().max(0.0) as usize,
+            rect.height().max(0.0) as usize,
+        );
+
+        self.image
+            .start(model, width, height, self.background_color);
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::power_range(self.power)?;
+        validation::scale_range(self.scale)?;
+        validation::iterations_range(self.iterations)?;
+        validation::marching_range(self.max_steps, self.detail)?;
+        validation::ambient_occlusion_range(self.ambient_occlusion)?;
+        validation::camera_is_valid(&self.camera)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    // Rendering with the old settings is useless, so it stops
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+        self.image.stop();
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn is_rendering(&self) -> bool {
+        self.image.is_rendering()
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.image.progress()
+    }
+}
+
+// Camera moved by the drag and the zoom since the previous drawing,
+// so the camera settings always show the current view
+fn orbited(camera: &Camera, from: &CanvasParams, to: &CanvasParams) -> Camera {
+    camera.orbited(
+        (to.offset.0 - from.offset.0) as f64 * DEGREES_PER_PX,
+        (to.offset.1 - from.offset.1) as f64 * DEGREES_PER_PX,
+        (to.px_per_cm / from.px_per_cm) as f64,
+    )
+}
@@ -0,0 +1,352 @@
+use crate::context::Context;
+use crate::fractals::mandelbulb::examples::Example;
+use crate::fractals::mandelbulb::model::{Camera, Kind};
+use crate::fractals::mandelbulb::serialization;
+use crate::fractals::mandelbulb::state::MandelbulbState;
+use crate::fractals::mandelbulb::validation::{
+    MAX_FOV, MAX_ITERATIONS, MAX_POWER, MAX_SCALE, MAX_STEPS, MIN_POWER,
+};
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct MandelbulbSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for MandelbulbSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl MandelbulbSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            let state = &context.mandelbulb_state;
+
+            ui.label("Status: ");
+            if state.is_rendering() {
+                ui.label(
+                    RichText::new(format!(
+                        "Rendering {:.0}%...",
+                        state.progress() * 100.0
+                    ))
+                    .color(colors::LIME),
+                );
+            } else if state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.mandelbulb_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Kind:");
+            egui::ComboBox::from_id_salt("MandelbulbKind")
+                .selected_text(format!("{}", state.kind))
+                .show_ui(ui, |ui| {
+                    for kind in Kind::iter() {
+                        if ui
+                            .selectable_value(&mut state.kind, kind, kind.to_string())
+                            .changed()
+                        {
+                            state.camera = kind.default_camera();
+                            changed = true;
+                        }
+                    }
+                });
+            ui.end_row();
+
+            match state.kind {
+                Kind::Mandelbulb => {
+                    ui.label("Power:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.power, MIN_POWER..=MAX_POWER))
+                        .changed();
+                    ui.end_row();
+                },
+                Kind::Mandelbox => {
+                    ui.label("Scale:");
+                    changed |= ui
+                        .add(
+                            DragValue::new(&mut state.scale)
+                                .speed(0.01)
+                                .range(-MAX_SCALE..=MAX_SCALE),
+                        )
+                        .on_hover_text("Absolute value has to be at least 1.1.")
+                        .changed();
+                    ui.end_row();
+                },
+            }
+
+            ui.label("Iterations:");
+            changed |= ui
+                .add(Slider::new(&mut state.iterations, 1..=MAX_ITERATIONS))
+                .changed();
+            ui.end_row();
+
+            ui.label("Max Steps:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.max_steps)
+                        .speed(1)
+                        .range(1..=MAX_STEPS),
+                )
+                .on_hover_text("Steps of every ray before it's considered a miss.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Detail:");
+            changed |= ui
+                .add(Slider::new(&mut state.detail, 0.1..=10.0).logarithmic(true))
+                .on_hover_text(
+                    "Distance to the surface, that counts as a hit, in pixels.\n\
+                     Lower values show finer details, but take longer.",
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Ambient Occlusion:");
+            changed |= ui
+                .add(Slider::new(&mut state.ambient_occlusion, 0.0..=1.0))
+                .on_hover_text("Darkening of the creases.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Surface Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.surface_color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+
+            ui.label("Background Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.background_color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Camera", |ui| {
+            Grid::new("MandelbulbCameraGrid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let camera = &mut state.camera;
+
+                    ui.label("Yaw:");
+                    changed |= ui
+                        .add(DragValue::new(&mut camera.yaw).speed(1).suffix("°"))
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Pitch:");
+                    changed |= ui
+                        .add(
+                            DragValue::new(&mut camera.pitch)
+                                .speed(1)
+                                .range(-Camera::MAX_PITCH..=Camera::MAX_PITCH)
+                                .suffix("°"),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Distance:");
+                    changed |= ui
+                        .add(
+                            DragValue::new(&mut camera.distance)
+                                .speed(0.01)
+                                .range(0.01..=1000.0),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Field of View:");
+                    changed |= ui
+                        .add(
+                            DragValue::new(&mut camera.fov)
+                                .speed(1)
+                                .range(1.0..=MAX_FOV)
+                                .suffix("°"),
+                        )
+                        .changed();
+                    ui.end_row();
+                });
+
+            ui.label("Drag the canvas to orbit, scroll to move closer.");
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Reset Camera").clicked() {
+                    state.camera = state.kind.default_camera();
+                    changed = true;
+                }
+            });
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.mandelbulb_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.mandelbulb_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    context.mandelbulb_state.is_initialized(),
+                    Button::new("Draw"),
+                )
+                .clicked()
+            {
+                context.mandelbulb_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.mandelbulb_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.mandelbulb_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.mandelbulb_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Kind\": \"Mandelbulb\",
+                                \"Power\": 8.0,
+                                \"Iterations\": 10,
+                                \"Camera\": {
+                                    \"Yaw\": 30.0,
+                                    \"Pitch\": 25.0,
+                                    \"Distance\": 3.2,
+                                    \"Field of View\": 45.0
+                                }
+                            }
+
+                            Kind: \"Mandelbulb\" (uses \"Power\") or \"Mandelbox\"
+                            (uses \"Scale\").
+                            Camera: angles in degrees, it looks at the origin.
+                            Other fields: \"Max Steps\", \"Detail\" (pixels),
+                            \"Ambient Occlusion\" (0..1), \"Surface Color\",
+                            \"Background Color\" ([r, g, b]).
+
+                            The picture is rendered on the processor, first with
+                            large blocks, that are refined on every pass.
+                            Dragging the canvas orbits the camera, scrolling moves it
+                            closer, the saved camera is the current view.
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut MandelbulbState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
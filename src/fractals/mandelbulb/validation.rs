@@ -0,0 +1,228 @@
+use crate::fractals::mandelbulb::model::Camera;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub const MIN_POWER: f64 = 2.0;
+pub const MAX_POWER: f64 = 16.0;
+pub const MAX_SCALE: f64 = 4.0;
+pub const MAX_ITERATIONS: u32 = 64;
+pub const MAX_STEPS: u32 = 1000;
+pub const MAX_FOV: f64 = 150.0;
+
+pub fn power_range(power: f64) -> Result<(), ValidationError> {
+    if !(MIN_POWER..=MAX_POWER).contains(&power) {
+        return Err(ValidationError::BadPowerValue);
+    }
+
+    Ok(())
+}
+
+// Scales close to 1 and -1 don't shrink the box, so the estimate is useless
+pub fn scale_range(scale: f64) -> Result<(), ValidationError> {
+    if !(scale.abs() >= 1.1 && scale.abs() <= MAX_SCALE) {
+        return Err(ValidationError::BadScaleValue);
+    }
+
+    Ok(())
+}
+
+pub fn iterations_range(iterations: u32) -> Result<(), ValidationError> {
+    if !(1..=MAX_ITERATIONS).contains(&iterations) {
+        return Err(ValidationError::BadIterationsCount);
+    }
+
+    Ok(())
+}
+
+pub fn marching_range(max_steps: u32, detail: f64) -> Result<(), ValidationError> {
+    if !(1..=MAX_STEPS).contains(&max_steps) {
+        return Err(ValidationError::ZeroOrTooManySteps);
+    }
+    if !(detail > 0.0 && detail <= 10.0) {
+        return Err(ValidationError::BadDetailValue);
+    }
+
+    Ok(())
+}
+
+pub fn ambient_occlusion_range(ambient_occlusion: f64) -> Result<(), ValidationError> {
+    if !(0.0..=1.0).contains(&ambient_occlusion) {
+        return Err(ValidationError::BadAmbientOcclusion);
+    }
+
+    Ok(())
+}
+
+pub fn camera_is_valid(camera: &Camera) -> Result<(), ValidationError> {
+    let is_valid = camera.yaw.is_finite()
+        && camera.pitch.abs() <= Camera::MAX_PITCH
+        && camera.distance > 0.0
+        && camera.distance.is_finite()
+        && camera.fov > 0.0
+        && camera.fov <= MAX_FOV;
+
+    if !is_valid {
+        return Err(ValidationError::BadCamera(format!(
+            "Yaw: {}, pitch: {}, distance: {}, field of view: {}.",
+            camera.yaw, camera.pitch, camera.distance, camera.fov
+        )));
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("Power has to be in range 2..=16.")]
+    BadPowerValue,
+
+    #[error("Absolute value of the scale has to be in range 1.1..=4.")]
+    BadScaleValue,
+
+    #[error("Iterations have to be in range 1..=64.")]
+    BadIterationsCount,
+
+    #[error("Maximum steps of the ray have to be in range 1..=1000.")]
+    ZeroOrTooManySteps,
+
+    #[error("Detail has to be in range (0, 10].")]
+    BadDetailValue,
+
+    #[error("Ambient occlusion has to be in range 0..=1.")]
+    BadAmbientOcclusion,
+
+    #[error(
+        "Camera pitch has to be in range -89..=89, distance greater than 0 \
+         and field of view in range (0, 150]."
+    )]
+    BadCamera(String),
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadCamera(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::mandelbulb::examples::Example;
+    use crate::fractals::mandelbulb::serialization;
+    use crate::fractals::mandelbulb::state::MandelbulbState;
+
+    #[test]
+    fn power_bounds_are_inclusive() {
+        assert!(power_range(MIN_POWER).is_ok());
+        assert!(power_range(MAX_POWER).is_ok());
+        for power in [1.9, 16.1, f64::NAN] {
+            assert!(matches!(
+                power_range(power),
+                Err(ValidationError::BadPowerValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn negative_scale_is_valid() {
+        for scale in [-MAX_SCALE, -1.5, -1.1, 1.1, MAX_SCALE] {
+            assert!(scale_range(scale).is_ok(), "{}", scale);
+        }
+        for scale in [-1.0, 0.0, 1.0, 4.1, f64::NAN] {
+            assert!(matches!(
+                scale_range(scale),
+                Err(ValidationError::BadScaleValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn marching_limits() {
+        assert!(marching_range(MAX_STEPS, 10.0).is_ok());
+        assert!(matches!(
+            marching_range(0, 1.0),
+            Err(ValidationError::ZeroOrTooManySteps)
+        ));
+        for detail in [0.0, 10.1, f64::NAN] {
+            assert!(matches!(
+                marching_range(1, detail),
+                Err(ValidationError::BadDetailValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn camera_pitch_stops_at_poles() {
+        let mut camera = Camera::default();
+        for pitch in [-Camera::MAX_PITCH, Camera::MAX_PITCH] {
+            camera.pitch = pitch;
+            assert!(camera_is_valid(&camera).is_ok());
+        }
+
+        // Looking straight down, the view has no up direction
+        camera.pitch = 90.0;
+        assert!(camera_is_valid(&camera).is_err());
+    }
+
+    #[test]
+    fn camera_is_reported() {
+        let mut state = MandelbulbState::default();
+        state.camera = Camera {
+            yaw: f64::NAN,
+            pitch: 0.0,
+            distance: 3.0,
+            fov: MAX_FOV,
+        };
+
+        let result = state.initialize();
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Yaw: NaN, pitch: 0, distance: 3, field of view: 150.".to_string())
+        );
+    }
+
+    #[test]
+    fn camera_inside_out() {
+        let mut camera = Camera::default();
+        for (distance, fov) in
+            [(0.0, 60.0), (f64::INFINITY, 60.0), (3.0, 0.0), (3.0, 151.0)]
+        {
+            camera.distance = distance;
+            camera.fov = fov;
+            assert!(matches!(
+                camera_is_valid(&camera),
+                Err(ValidationError::BadCamera(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = MandelbulbState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
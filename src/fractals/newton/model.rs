@@ -0,0 +1,279 @@
+use crate::fractals::newton::polynomial::Polynomial;
+use crate::math::complex::Complex;
+use crate::ui::styles::colors;
+use crate::ui::styles::gradient::Gradient;
+use egui::ecolor::Hsva;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_MAX_ITERATIONS: u32 = 50;
+pub const DEFAULT_TOLERANCE: f64 = 1e-6;
+pub const DEFAULT_SHADING: f32 = 0.7;
+pub const DEFAULT_UNITS_PER_CM: f64 = 0.1;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Variant {
+    #[default]
+    #[strum(serialize = "Newton")]
+    #[serde(rename = "Newton")]
+    Newton,
+
+    // z -> z - a * p(z) / p'(z) + c, where c is the pixel
+    #[strum(serialize = "Nova")]
+    #[serde(rename = "Nova")]
+    Nova,
+}
+
+pub struct Model {
+    polynomial: Polynomial,
+    roots: Vec<Complex>,
+    root_colors: Vec<Color32>,
+
+    variant: Variant,
+    relaxation: Complex,
+    nova_start: Complex,
+
+    max_iterations: u32,
+    tolerance: f64,
+    shading: f32,
+
+    gradient: Gradient,
+}
+
+impl Model {
+    pub fn color(&self, point: Complex) -> Color32 {
+        match self.variant {
+            Variant::Newton => self.newton_color(point),
+            Variant::Nova => self.nova_color(point),
+        }
+    }
+
+    pub fn roots(&self) -> &[Complex] {
+        &self.roots
+    }
+
+    // Color of the root that the point converges to, darker with more iterations
+    fn newton_color(&self, point: Complex) -> Color32 {
+        let tolerance_sqr = self.tolerance * self.tolerance;
+        let mut z = point;
+
+        for iteration in 0..self.max_iterations {
+            for (index, root) in self.roots.iter().enumerate() {
+                if (z - *root).norm_sqr() < tolerance_sqr {
+                    return self.shade(self.root_colors[index], iteration);
+                }
+            }
+
+            z = match self.step(z) {
+                Some(value) => value,
+                None => return colors::BLACK,
+            };
+        }
+
+        colors::BLACK
+    }
+
+    // Nova fixed points are not roots, so the color depends on the iterations only
+    fn nova_color(&self, point: Complex) -> Color32 {
+        let tolerance_sqr = self.tolerance * self.tolerance;
+        let mut z = self.nova_start;
+
+        for iteration in 0..self.max_iterations {
+            let next = match self.step(z) {
+                Some(value) => value + point,
+                None => return colors::BLACK,
+            };
+
+            if (next - z).norm_sqr() < tolerance_sqr {
+                return self
+                    .gradient
+                    .sample(iteration as f32 / self.max_iterations as f32);
+            }
+            z = next;
+        }
+
+        colors::BLACK
+    }
+
+    fn step(&self, z: Complex) -> Option<Complex> {
+        let (value, derivative) = self.polynomial.evaluate(z);
+        let next = z - self.relaxation * (value / derivative);
+
+        next.is_finite().then_some(next)
+    }
+
+    fn shade(&self, color: Color32, iteration: u32) -> Color32 {
+        let ratio = (iteration as f32 / self.max_iterations as f32).sqrt();
+        let factor = 1.0 - self.shading * ratio;
+
+        Color32::from_rgb(
+            (color.r() as f32 * factor) as u8,
+            (color.g() as f32 * factor) as u8,
+            (color.b() as f32 * factor) as u8,
+        )
+    }
+}
+
+pub struct ModelBuilder {
+    polynomial: Polynomial,
+    roots: Option<Vec<Complex>>,
+
+    variant: Variant,
+    relaxation: Complex,
+    nova_start: Complex,
+
+    max_iterations: u32,
+    tolerance: f64,
+    shading: f32,
+
+    gradient: Gradient,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            polynomial: Polynomial::from_roots(&[]),
+            roots: None,
+
+            variant: Variant::default(),
+            relaxation: Complex::ONE,
+            nova_start: Complex::ONE,
+
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            tolerance: DEFAULT_TOLERANCE,
+            shading: DEFAULT_SHADING,
+
+            gradient: Gradient::default(),
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_roots(mut self, roots: Vec<Complex>) -> Self {
+        self.polynomial = Polynomial::from_roots(&roots);
+        self.roots = Some(roots);
+        self
+    }
+
+    // Coefficients from the highest power to the lowest, roots are found numerically
+    pub fn with_coefficients(mut self, coefficients: &[Complex]) -> Self {
+        self.polynomial = Polynomial::from_coefficients(coefficients);
+        self.roots = None;
+        self
+    }
+
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn with_relaxation(mut self, relaxation: Complex) -> Self {
+        self.relaxation = relaxation;
+        self
+    }
+
+    pub fn with_nova_start(mut self, nova_start: Complex) -> Self {
+        self.nova_start = nova_start;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_shading(mut self, shading: f32) -> Self {
+        self.shading = shading;
+        self
+    }
+
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        let roots = self.roots.unwrap_or_else(|| self.polynomial.roots());
+        let root_colors = root_colors(roots.len());
+
+        Model {
+            polynomial: self.polynomial,
+            roots,
+            root_colors,
+            variant: self.variant,
+            relaxation: self.relaxation,
+            nova_start: self.nova_start,
+            max_iterations: self.max_iterations,
+            tolerance: self.tolerance,
+            shading: self.shading,
+            gradient: self.gradient,
+        }
+    }
+}
+
+// Evenly spread hues, one per root
+pub fn root_colors(count: usize) -> Vec<Color32> {
+    (0..count)
+        .map(|index| Hsva::new(index as f32 / count as f32, 0.75, 0.95, 1.0).into())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_near_roots_get_their_colors() {
+        let roots = vec![
+            Complex::ONE,
+            Complex::from_polar(1.0, std::f64::consts::TAU / 3.0),
+            Complex::from_polar(1.0, -std::f64::consts::TAU / 3.0),
+        ];
+        // Without shading, the basin color doesn't depend on the iterations
+        let model = ModelBuilder::default()
+            .with_roots(roots.clone())
+            .with_shading(0.0)
+            .build();
+        let colors = root_colors(3);
+
+        for (root, color) in roots.iter().zip(&colors) {
+            let point = *root + Complex::new(0.05, -0.03);
+            assert_eq!(model.color(point), *color);
+        }
+    }
+
+    #[test]
+    fn shading_darkens_slow_points() {
+        let model = ModelBuilder::default()
+            .with_coefficients(&[Complex::ONE, Complex::ZERO, Complex::new(-1.0, 0.0)])
+            .build();
+        let index = model
+            .roots()
+            .iter()
+            .position(|root| (*root - Complex::ONE).norm() < 1e-9)
+            .unwrap_or_default();
+        let color = root_colors(2)[index];
+
+        // Root itself converges at once, a far point takes many steps
+        assert_eq!(model.color(Complex::ONE), color);
+        let far = model.color(Complex::new(100.0, 1.0));
+        assert!(far.r() <= color.r() && far.g() <= color.g() && far.b() <= color.b());
+        assert_ne!(far, color);
+    }
+
+    #[test]
+    fn zero_derivative_is_black() {
+        // p'(0) = 0 for p(z) = z^2 - 1, Newton's step is undefined there
+        let model = ModelBuilder::default()
+            .with_roots(vec![Complex::ONE, Complex::new(-1.0, 0.0)])
+            .build();
+
+        assert_eq!(model.color(Complex::ZERO), colors::BLACK);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::math::complex::Complex;
+
+const ROOT_FINDING_ITERATIONS: usize = 500;
+const ROOT_FINDING_TOLERANCE: f64 = 1e-12;
+
+// Coefficients are stored from the lowest power to the highest
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<Complex>,
+}
+
+impl Polynomial {
+    // Coefficients are given from the highest power to the lowest, as they are written
+    pub fn from_coefficients(coefficients: &[Complex]) -> Self {
+        let mut coefficients: Vec<Complex> = coefficients.iter().rev().copied().collect();
+        while coefficients.len() > 1
+            && coefficients
+                .last()
+                .is_some_and(|value| *value == Complex::ZERO)
+        {
+            coefficients.pop();
+        }
+
+        Self { coefficients }
+    }
+
+    pub fn from_roots(roots: &[Complex]) -> Self {
+        let mut coefficients = vec![Complex::ONE];
+
+        // Multiplying by (z - root) one by one
+        for root in roots {
+            let mut next = vec![Complex::ZERO; coefficients.len() + 1];
+            for (power, coefficient) in coefficients.iter().enumerate() {
+                next[power + 1] += *coefficient;
+                next[power] -= *coefficient * *root;
+            }
+            coefficients = next;
+        }
+
+        Self { coefficients }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    // Value of the polynomial and its derivative, by Horner's method
+    pub fn evaluate(&self, z: Complex) -> (Complex, Complex) {
+        let mut value = Complex::ZERO;
+        let mut derivative = Complex::ZERO;
+
+        for coefficient in self.coefficients.iter().rev() {
+            derivative = derivative * z + value;
+            value = value * z + *coefficient;
+        }
+
+        (value, derivative)
+    }
+
+    // All complex roots by the Durand-Kerner method
+    pub fn roots(&self) -> Vec<Complex> {
+        let degree = self.degree();
+        let Some(leading) = self.coefficients.last().copied() else {
+            return vec![];
+        };
+        if degree == 0 || leading == Complex::ZERO {
+            return vec![];
+        }
+
+        let monic: Vec<Complex> =
+            self.coefficients.iter().map(|c| *c / leading).collect();
+        let monic = Polynomial {
+            coefficients: monic,
+        };
+
+        // Standard initial guesses: powers of a number that is neither real nor a root of unity
+        let seed = Complex::new(0.4, 0.9);
+        let mut roots: Vec<Complex> = (0..degree).map(|k| seed.powi(k as i32)).collect();
+
+        for _ in 0..ROOT_FINDING_ITERATIONS {
+            let mut max_change: f64 = 0.0;
+
+            for i in 0..degree {
+                let mut denominator = Complex::ONE;
+                for j in 0..degree {
+                    if i != j {
+                        denominator *= roots[i] - roots[j];
+                    }
+                }
+
+                let (value, _) = monic.evaluate(roots[i]);
+                let change = value / denominator;
+                if change.is_finite() {
+                    roots[i] -= change;
+                    max_change = max_change.max(change.norm());
+                }
+            }
+
+            if max_change < ROOT_FINDING_TOLERANCE {
+                break;
+            }
+        }
+
+        roots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn real(values: &[f64]) -> Vec<Complex> {
+        values
+            .iter()
+            .map(|value| Complex::new(*value, 0.0))
+            .collect()
+    }
+
+    // Every expected root has a found one next to it
+    fn assert_same_roots(found: &[Complex], expected: &[Complex]) {
+        assert_eq!(found.len(), expected.len());
+        for root in expected {
+            assert!(
+                found.iter().any(|value| (*value - *root).norm() < 1e-9),
+                "{:?} not in {:?}",
+                root,
+                found
+            );
+        }
+    }
+
+    #[test]
+    fn cube_roots_of_unity() {
+        let polynomial = Polynomial::from_coefficients(&real(&[1.0, 0.0, 0.0, -1.0]));
+        let expected: Vec<Complex> = (0..3)
+            .map(|k| Complex::from_polar(1.0, std::f64::consts::TAU * k as f64 / 3.0))
+            .collect();
+
+        assert_eq!(polynomial.degree(), 3);
+        assert_same_roots(&polynomial.roots(), &expected);
+    }
+
+    #[test]
+    fn roots_and_coefficients_round_trip() {
+        // (z - 1)(z + 1)(z - 2) = z^3 - 2z^2 - z + 2
+        let roots = real(&[1.0, -1.0, 2.0]);
+        let from_roots = Polynomial::from_roots(&roots);
+
+        assert_eq!(
+            from_roots,
+            Polynomial::from_coefficients(&real(&[1.0, -2.0, -1.0, 2.0]))
+        );
+        assert_same_roots(&from_roots.roots(), &roots);
+    }
+
+    #[test]
+    fn complex_and_repeated_roots() {
+        let roots = vec![
+            Complex::new(0.0, 1.0),
+            Complex::new(0.0, -1.0),
+            Complex::ONE,
+        ];
+        assert_same_roots(&Polynomial::from_roots(&roots).roots(), &roots);
+
+        // Double root converges slowly, so it's found less precisely
+        let double = Polynomial::from_roots(&real(&[0.5, 0.5]));
+        assert!(double
+            .roots()
+            .iter()
+            .all(|root| (*root - Complex::new(0.5, 0.0)).norm() < 1e-4));
+    }
+
+    #[test]
+    fn leading_zeros_are_dropped() {
+        let polynomial = Polynomial::from_coefficients(&real(&[0.0, 0.0, 2.0, -4.0]));
+
+        assert_eq!(polynomial.degree(), 1);
+        assert_same_roots(&polynomial.roots(), &real(&[2.0]));
+        assert!(Polynomial::from_coefficients(&real(&[5.0]))
+            .roots()
+            .is_empty());
+    }
+
+    #[test]
+    fn value_and_derivative() {
+        // p(z) = z^3 - 1, p'(z) = 3z^2
+        let polynomial = Polynomial::from_coefficients(&real(&[1.0, 0.0, 0.0, -1.0]));
+
+        let (value, derivative) = polynomial.evaluate(Complex::new(2.0, 0.0));
+        assert_eq!(value, Complex::new(7.0, 0.0));
+        assert_eq!(derivative, Complex::new(12.0, 0.0));
+
+        let (value, derivative) = polynomial.evaluate(Complex::new(0.0, 1.0));
+        assert_eq!(value, Complex::new(-1.0, -1.0));
+        assert_eq!(derivative, Complex::new(-3.0, 0.0));
+    }
+}
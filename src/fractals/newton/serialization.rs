@@ -0,0 +1,113 @@
+use crate::fractals::newton::model;
+use crate::fractals::newton::model::Variant;
+use crate::fractals::newton::state::{InputMode, NewtonState};
+use crate::fractals::newton::validation::ValidationError;
+use crate::math::complex::Complex;
+use crate::ui::styles::gradient::GradientStopDto;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<NewtonDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &NewtonState) -> serde_json::Result<String> {
+    let dto = NewtonDto {
+        input_mode: state.input_mode,
+        roots: state.roots.iter().map(|root| (*root).into()).collect(),
+        coefficients: state
+            .coefficients
+            .iter()
+            .map(|coefficient| (*coefficient).into())
+            .collect(),
+        variant: state.variant,
+        relaxation: state.relaxation.into(),
+        nova_start: state.nova_start.into(),
+        max_iterations: state.max_iterations,
+        tolerance: state.tolerance,
+        shading: state.shading,
+        units_per_cm: state.units_per_cm,
+        gradient: (&state.gradient).into(),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_one() -> [f64; 2] {
+    [1.0, 0.0]
+}
+
+fn default_max_iterations() -> u32 {
+    model::DEFAULT_MAX_ITERATIONS
+}
+
+fn default_tolerance() -> f64 {
+    model::DEFAULT_TOLERANCE
+}
+
+fn default_shading() -> f32 {
+    model::DEFAULT_SHADING
+}
+
+fn default_units_per_cm() -> f64 {
+    model::DEFAULT_UNITS_PER_CM
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NewtonDto {
+    #[serde(rename = "Input", default)]
+    input_mode: InputMode,
+
+    #[serde(rename = "Roots", default)]
+    roots: Vec<[f64; 2]>,
+
+    #[serde(rename = "Coefficients", default)]
+    coefficients: Vec<[f64; 2]>,
+
+    #[serde(rename = "Variant", default)]
+    variant: Variant,
+
+    #[serde(rename = "Relaxation", default = "default_one")]
+    relaxation: [f64; 2],
+
+    #[serde(rename = "Nova Start", default = "default_one")]
+    nova_start: [f64; 2],
+
+    #[serde(rename = "Max Iterations", default = "default_max_iterations")]
+    max_iterations: u32,
+
+    #[serde(rename = "Tolerance", default = "default_tolerance")]
+    tolerance: f64,
+
+    #[serde(rename = "Shading", default = "default_shading")]
+    shading: f32,
+
+    #[serde(rename = "Units per cm", default = "default_units_per_cm")]
+    units_per_cm: f64,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+}
+
+impl NewtonDto {
+    pub fn load(self, state: &mut NewtonState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.input_mode = self.input_mode;
+        state.roots = self.roots.into_iter().map(Complex::from).collect();
+        state.coefficients = self.coefficients.into_iter().map(Complex::from).collect();
+        state.variant = self.variant;
+        state.relaxation = self.relaxation.into();
+        state.nova_start = self.nova_start.into();
+        state.max_iterations = self.max_iterations;
+        state.tolerance = self.tolerance;
+        state.shading = self.shading;
+        state.units_per_cm = self.units_per_cm;
+        state.gradient = self.gradient.into();
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
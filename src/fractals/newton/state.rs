@@ -0,0 +1,178 @@
+use crate::fractals::newton::model::{Model, ModelBuilder, Variant};
+use crate::fractals::newton::validation::ValidationError;
+use crate::fractals::newton::{model, validation};
+use crate::graphics::raster::Raster;
+use crate::math::complex::Complex;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::gradient::Gradient;
+use egui::Shape;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum InputMode {
+    #[default]
+    #[strum(serialize = "Roots")]
+    #[serde(rename = "Roots")]
+    Roots,
+
+    #[strum(serialize = "Coefficients")]
+    #[serde(rename = "Coefficients")]
+    Coefficients,
+}
+
+pub struct NewtonState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub input_mode: InputMode,
+    pub roots: Vec<Complex>,
+    // From the highest power to the lowest
+    pub coefficients: Vec<Complex>,
+
+    pub variant: Variant,
+    pub relaxation: Complex,
+    pub nova_start: Complex,
+
+    pub max_iterations: u32,
+    pub tolerance: f64,
+    pub shading: f32,
+    pub units_per_cm: f64,
+
+    pub gradient: Gradient,
+
+    model: Option<Model>,
+    raster: Raster,
+}
+
+impl Default for NewtonState {
+    fn default() -> Self {
+        // Roots of z^3 - 1
+        let half_sqrt_3 = f64::sqrt(3.0) / 2.0;
+
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            input_mode: InputMode::default(),
+            roots: vec![
+                Complex::new(1.0, 0.0),
+                Complex::new(-0.5, half_sqrt_3),
+                Complex::new(-0.5, -half_sqrt_3),
+            ],
+            coefficients: vec![
+                Complex::ONE,
+                Complex::ZERO,
+                Complex::ZERO,
+                Complex::new(-1.0, 0.0),
+            ],
+
+            variant: Variant::default(),
+            relaxation: Complex::ONE,
+            nova_start: Complex::ONE,
+
+            max_iterations: model::DEFAULT_MAX_ITERATIONS,
+            tolerance: model::DEFAULT_TOLERANCE,
+            shading: model::DEFAULT_SHADING,
+            units_per_cm: model::DEFAULT_UNITS_PER_CM,
+
+            gradient: Gradient::default(),
+
+            model: None,
+            raster: Raster::new("NewtonFractal"),
+        }
+    }
+}
+
+impl NewtonState {
+    pub fn shapes(&mut self, ctx: &egui::Context, params: &CanvasParams) -> Vec<Shape> {
+        let Some(model) = &self.model else {
+            return vec![];
+        };
+
+        let scale = self.units_per_cm;
+        let is_requested = std::mem::take(&mut self.is_drawing_requested);
+        self.raster.render_on_change(params, is_requested, |point| {
+            model.color(Complex::new(point.x as f64 * scale, point.y as f64 * scale))
+        });
+
+        vec![self.raster.shape(ctx, params.screen_rect())]
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        let builder = match self.input_mode {
+            InputMode::Roots => {
+                validation::numbers_are_finite(&self.roots)?;
+                validation::roots_degree(&self.roots)?;
+                ModelBuilder::default().with_roots(self.roots.clone())
+            },
+            InputMode::Coefficients => {
+                validation::numbers_are_finite(&self.coefficients)?;
+                validation::coefficients_degree(&self.coefficients)?;
+                ModelBuilder::default().with_coefficients(&self.coefficients)
+            },
+        };
+        validation::iterations_range(self.max_iterations)?;
+        validation::tolerance_range(self.tolerance)?;
+        validation::relaxation_is_not_zero(&self.relaxation)?;
+        validation::numbers_are_finite(&[self.nova_start])?;
+        validation::scale_range(self.units_per_cm)?;
+
+        self.model = Some(
+            builder
+                .with_variant(self.variant)
+                .with_relaxation(self.relaxation)
+                .with_nova_start(self.nova_start)
+                .with_max_iterations(self.max_iterations)
+                .with_tolerance(self.tolerance)
+                .with_shading(self.shading)
+                .with_gradient(self.gradient.clone())
+                .build(),
+        );
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    // Roots of the initialized polynomial, found numerically for coefficients
+    pub fn found_roots(&self) -> Option<&[Complex]> {
+        self.model.as_ref().map(|model| model.roots())
+    }
+
+    pub fn push_empty_number(&mut self) {
+        self.reset_initialization();
+
+        match self.input_mode {
+            InputMode::Roots => self.roots.push(Complex::ZERO),
+            InputMode::Coefficients => self.coefficients.push(Complex::ZERO),
+        }
+    }
+
+    pub fn remove_number(&mut self, index: usize) {
+        self.reset_initialization();
+
+        match self.input_mode {
+            InputMode::Roots => {
+                debug_assert!(index < self.roots.len());
+                self.roots.remove(index);
+            },
+            InputMode::Coefficients => {
+                debug_assert!(index < self.coefficients.len());
+                self.coefficients.remove(index);
+            },
+        }
+    }
+}
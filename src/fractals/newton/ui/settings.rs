@@ -0,0 +1,301 @@
+use crate::context::Context;
+use crate::fractals::newton::model::Variant;
+use crate::fractals::newton::serialization;
+use crate::fractals::newton::state::{InputMode, NewtonState};
+use crate::io;
+use crate::math::complex::Complex;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct NewtonSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for NewtonSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl NewtonSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.newton_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.newton_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Variant:");
+            egui::ComboBox::from_id_salt("NewtonVariant")
+                .selected_text(format!("{}", state.variant))
+                .show_ui(ui, |ui| {
+                    for variant in [Variant::Newton, Variant::Nova] {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.variant,
+                                variant,
+                                variant.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Max Iterations:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.max_iterations)
+                        .speed(1)
+                        .range(1..=10000),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Tolerance:");
+            changed |= ui
+                .add(Slider::new(&mut state.tolerance, 1e-12..=1e-1).logarithmic(true))
+                .changed();
+            ui.end_row();
+
+            ui.label("Relaxation:");
+            changed |= complex_edit(ui, &mut state.relaxation);
+            ui.end_row();
+
+            match state.variant {
+                Variant::Newton => {
+                    ui.label("Shading:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.shading, 0.0..=1.0))
+                        .on_hover_text(
+                            "How much slow convergence darkens the root color.",
+                        )
+                        .changed();
+                    ui.end_row();
+                },
+                Variant::Nova => {
+                    ui.label("Start Value:");
+                    changed |= complex_edit(ui, &mut state.nova_start);
+                    ui.end_row();
+                },
+            }
+
+            ui.label("Scale:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.units_per_cm)
+                        .speed(0.001)
+                        .range(1e-12..=f64::MAX)
+                        .prefix("1 cm = ")
+                        .suffix(" units"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Input:");
+            egui::ComboBox::from_id_salt("NewtonInputMode")
+                .selected_text(format!("{}", state.input_mode))
+                .show_ui(ui, |ui| {
+                    for input_mode in [InputMode::Roots, InputMode::Coefficients] {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.input_mode,
+                                input_mode,
+                                input_mode.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let mut removed_number: Option<usize> = None;
+        let numbers = match state.input_mode {
+            InputMode::Roots => &mut state.roots,
+            InputMode::Coefficients => &mut state.coefficients,
+        };
+        let degree = numbers.len().saturating_sub(1);
+        Grid::new("NumbersGrid").num_columns(3).show(ui, |ui| {
+            for (index, number) in numbers.iter_mut().enumerate() {
+                let label = match state.input_mode {
+                    InputMode::Roots => format!("Root {}:", index + 1),
+                    InputMode::Coefficients => format!("z^{}:", degree - index),
+                };
+                ui.label(label);
+                changed |= complex_edit(ui, number);
+                if ui.button("Remove").clicked() {
+                    removed_number = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = removed_number {
+            state.remove_number(index);
+        }
+        ui.vertical_centered_justified(|ui| {
+            let text = match state.input_mode {
+                InputMode::Roots => "Add Root",
+                InputMode::Coefficients => "Add Coefficient",
+            };
+            if ui.button(text).clicked() {
+                state.push_empty_number();
+            }
+        });
+
+        if state.input_mode == InputMode::Coefficients && state.is_initialized() {
+            if let Some(roots) = state.found_roots() {
+                ui.add_space(5.0);
+                ui.label(RichText::new("Found Roots:").strong());
+                for root in roots {
+                    ui.label(format!("{:.4} {:+.4}i", root.re, root.im));
+                }
+            }
+        }
+
+        if state.variant == Variant::Nova {
+            ui.add_space(5.0);
+            ui.collapsing("Gradient", |ui| {
+                changed |= state.gradient.show_editor(ui, "Newton");
+            });
+        }
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.newton_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.newton_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.newton_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.newton_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.newton_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.newton_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.newton_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+                            Complex numbers are pairs: [Re, Im].
+
+                            Example:
+
+                            {
+                                \"Input\": \"Coefficients\",
+                                \"Coefficients\": [[1, 0], [0, 0], [0, 0], [-1, 0]],
+                                \"Variant\": \"Newton\",
+                                \"Relaxation\": [1, 0],
+                                \"Max Iterations\": 50
+                            }
+
+                            Input: \"Roots\" or \"Coefficients\".
+                            Coefficients go from the highest power to the lowest.
+                            Variant: \"Newton\" or \"Nova\".
+                            Other fields: \"Roots\", \"Nova Start\", \"Tolerance\",
+                            \"Shading\", \"Units per cm\", \"Gradient\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut NewtonState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
+
+// Returns true, if the number was changed
+fn complex_edit(ui: &mut Ui, number: &mut Complex) -> bool {
+    ui.horizontal(|ui| {
+        let re = ui.add(DragValue::new(&mut number.re).speed(0.01));
+        let im = ui.add(DragValue::new(&mut number.im).speed(0.01).prefix("i: "));
+        re.changed() || im.changed()
+    })
+    .inner
+}
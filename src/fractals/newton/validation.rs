@@ -0,0 +1,210 @@
+use crate::math::complex::Complex;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub fn numbers_are_finite(numbers: &[Complex]) -> Result<(), ValidationError> {
+    for (index, number) in numbers.iter().enumerate() {
+        if !number.is_finite() {
+            return Err(ValidationError::NotFiniteNumber(format!(
+                "Row: {}",
+                index + 1
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn roots_degree(roots: &[Complex]) -> Result<(), ValidationError> {
+    if roots.len() < 2 {
+        return Err(ValidationError::TooLowDegree);
+    }
+
+    Ok(())
+}
+
+pub fn coefficients_degree(coefficients: &[Complex]) -> Result<(), ValidationError> {
+    let first_non_zero = coefficients
+        .iter()
+        .position(|coefficient| *coefficient != Complex::ZERO);
+
+    match first_non_zero {
+        // Coefficients go from the highest power, so the degree is the number of the rest
+        Some(index) if coefficients.len() - index > 2 => Ok(()),
+        _ => Err(ValidationError::TooLowDegree),
+    }
+}
+
+pub fn iterations_range(iterations: u32) -> Result<(), ValidationError> {
+    if iterations < 1 {
+        return Err(ValidationError::BadIterationsValue);
+    }
+
+    Ok(())
+}
+
+pub fn tolerance_range(tolerance: f64) -> Result<(), ValidationError> {
+    if !(tolerance > 0.0 && tolerance < 1.0) {
+        return Err(ValidationError::BadToleranceValue);
+    }
+
+    Ok(())
+}
+
+pub fn relaxation_is_not_zero(relaxation: &Complex) -> Result<(), ValidationError> {
+    if *relaxation == Complex::ZERO || !relaxation.is_finite() {
+        return Err(ValidationError::BadRelaxationValue);
+    }
+
+    Ok(())
+}
+
+pub fn scale_range(units_per_cm: f64) -> Result<(), ValidationError> {
+    if !(units_per_cm > 0.0 && units_per_cm.is_finite()) {
+        return Err(ValidationError::BadScaleValue);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("There's a number that is not finite.")]
+    NotFiniteNumber(String),
+
+    #[error("The polynomial degree has to be at least 2.")]
+    TooLowDegree,
+
+    #[error("The 'iterations' value is lower than 1.")]
+    BadIterationsValue,
+
+    #[error("The tolerance has to be in range 0..1 (exclusive).")]
+    BadToleranceValue,
+
+    #[error("The relaxation factor can't be zero.")]
+    BadRelaxationValue,
+
+    #[error("The scale has to be greater than 0.")]
+    BadScaleValue,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::NotFiniteNumber(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::newton::state::{InputMode, NewtonState};
+
+    fn real(values: &[f64]) -> Vec<Complex> {
+        values
+            .iter()
+            .map(|value| Complex::new(*value, 0.0))
+            .collect()
+    }
+
+    #[test]
+    fn degree_counts_from_first_non_zero_coefficient() {
+        assert!(coefficients_degree(&real(&[1.0, 0.0, -1.0])).is_ok());
+        assert!(coefficients_degree(&real(&[0.0, 1.0, 0.0, -1.0])).is_ok());
+
+        // Leading zeros leave a linear polynomial, it has a single root
+        assert!(matches!(
+            coefficients_degree(&real(&[0.0, 0.0, 1.0, -1.0])),
+            Err(ValidationError::TooLowDegree)
+        ));
+        assert!(matches!(
+            coefficients_degree(&real(&[0.0, 0.0, 0.0])),
+            Err(ValidationError::TooLowDegree)
+        ));
+        assert!(matches!(
+            coefficients_degree(&[]),
+            Err(ValidationError::TooLowDegree)
+        ));
+    }
+
+    #[test]
+    fn at_least_two_roots() {
+        assert!(roots_degree(&real(&[1.0, 1.0])).is_ok());
+        assert!(matches!(
+            roots_degree(&real(&[1.0])),
+            Err(ValidationError::TooLowDegree)
+        ));
+    }
+
+    #[test]
+    fn not_finite_number_row_is_reported() {
+        let numbers = vec![
+            Complex::ONE,
+            Complex::ZERO,
+            Complex::new(0.0, f64::INFINITY),
+        ];
+
+        let result = numbers_are_finite(&numbers);
+
+        assert!(matches!(result, Err(ValidationError::NotFiniteNumber(_))));
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Row: 3".to_string())
+        );
+    }
+
+    #[test]
+    fn tolerance_bounds_are_exclusive() {
+        assert!(tolerance_range(f64::MIN_POSITIVE).is_ok());
+        for tolerance in [0.0, 1.0, -1e-6, f64::NAN] {
+            assert!(matches!(
+                tolerance_range(tolerance),
+                Err(ValidationError::BadToleranceValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn relaxation_may_be_negative_or_complex() {
+        assert!(relaxation_is_not_zero(&Complex::new(-1.0, 0.5)).is_ok());
+        for relaxation in [Complex::ZERO, Complex::new(f64::NAN, 1.0)] {
+            assert!(matches!(
+                relaxation_is_not_zero(&relaxation),
+                Err(ValidationError::BadRelaxationValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn inactive_input_is_not_validated() {
+        let mut state = NewtonState::default();
+        state.input_mode = InputMode::Coefficients;
+        state.coefficients = real(&[1.0, 0.0, 0.0, -1.0]);
+        state.roots = real(&[f64::NAN]);
+
+        let result = state.initialize();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn not_finite_nova_start() {
+        let mut state = NewtonState::default();
+        state.nova_start = Complex::new(f64::NAN, 0.0);
+
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::NotFiniteNumber(_))));
+    }
+}
@@ -0,0 +1,415 @@
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_GRAINS: u64 = 100_000;
+pub const DEFAULT_CELL_SIZE_CM: f32 = 0.05;
+pub const MAX_GRAINS: u64 = 5_000_000;
+
+// All grains may be on one site, its height has to hold them
+const _: () = assert!(MAX_GRAINS <= u32::MAX as u64);
+
+// Half of the side of the first grid, even to keep the parity of hexagonal rows
+const INITIAL_EXTENT: usize = 32;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Lattice {
+    // Every site has 4 neighbors and topples with 4 grains
+    #[default]
+    #[strum(serialize = "Square")]
+    #[serde(rename = "Square")]
+    Square,
+
+    // Every site has 6 neighbors and topples with 6 grains.
+    // Rows are stored in a square grid, odd rows are shifted by a half of a site.
+    #[strum(serialize = "Hexagonal")]
+    #[serde(rename = "Hexagonal")]
+    Hexagonal,
+}
+
+impl Lattice {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Lattice::Square, Lattice::Hexagonal].into_iter()
+    }
+
+    pub fn threshold(&self) -> u32 {
+        match self {
+            Lattice::Square => 4,
+            Lattice::Hexagonal => 6,
+        }
+    }
+
+    // One color for every stable number of grains
+    pub fn default_colors(&self) -> Vec<Color32> {
+        match self {
+            Lattice::Square => vec![
+                Color32::from_rgb(24, 24, 48),
+                Color32::from_rgb(60, 120, 216),
+                Color32::from_rgb(250, 200, 60),
+                Color32::from_rgb(200, 40, 40),
+            ],
+            Lattice::Hexagonal => vec![
+                Color32::from_rgb(24, 24, 48),
+                Color32::from_rgb(60, 120, 216),
+                Color32::from_rgb(40, 170, 160),
+                Color32::from_rgb(250, 200, 60),
+                Color32::from_rgb(240, 130, 40),
+                Color32::from_rgb(200, 40, 40),
+            ],
+        }
+    }
+
+    fn neighbors(&self, row: usize) -> &'static [(isize, isize)] {
+        match (self, row % 2) {
+            (Lattice::Square, _) => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            (Lattice::Hexagonal, 0) => {
+                &[(-1, -1), (0, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)]
+            },
+            (Lattice::Hexagonal, _) => {
+                &[(0, -1), (1, -1), (-1, 0), (1, 0), (0, 1), (1, 1)]
+            },
+        }
+    }
+}
+
+// Grains dropped on the site at the offset from the center, y goes up
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Source {
+    #[serde(rename = "X", default)]
+    pub x: i32,
+
+    #[serde(rename = "Y", default)]
+    pub y: i32,
+
+    #[serde(rename = "Grains")]
+    pub grains: u64,
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            grains: DEFAULT_GRAINS,
+        }
+    }
+}
+
+// Square grid of sites, that grows while the grains spread.
+// The center is always in the middle, and the row of the center is even.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pile {
+    lattice: Lattice,
+    side: usize,
+    heights: Vec<u32>,
+
+    // Left, top, right and bottom sites of the region, that holds all grains
+    bounds: [usize; 4],
+    is_stable: bool,
+    topplings: u64,
+}
+
+impl Pile {
+    pub fn new(lattice: Lattice, sources: &[Source]) -> Self {
+        let extent = sources
+            .iter()
+            .map(|source| source.x.unsigned_abs().max(source.y.unsigned_abs()) as usize)
+            .max()
+            .unwrap_or(0);
+        let extent = (extent + INITIAL_EXTENT).next_multiple_of(2);
+
+        let mut pile = Self::empty(lattice, 2 * extent + 1);
+        pile.bounds = [extent, extent, extent, extent];
+        pile.is_stable = false;
+        for source in sources {
+            let x = (extent as isize + source.x as isize) as usize;
+            let y = (extent as isize - source.y as isize) as usize;
+            // Validation keeps the total within MAX_GRAINS, so it never saturates
+            let grains = u32::try_from(source.grains).unwrap_or(u32::MAX);
+            let height = &mut pile.heights[y * pile.side + x];
+            *height = height.saturating_add(grains);
+            pile.include(x, y);
+        }
+
+        pile
+    }
+
+    // Stable configuration by rows of digits, the top row goes first
+    pub fn from_rows(lattice: Lattice, rows: &[String]) -> Self {
+        let mut pile = Self::empty(lattice, rows.len());
+        for (row, heights) in rows.iter().zip(pile.heights.chunks_mut(rows.len().max(1)))
+        {
+            for (letter, height) in row.chars().zip(heights.iter_mut()) {
+                *height = letter.to_digit(10).unwrap_or(0);
+            }
+        }
+
+        pile
+    }
+
+    fn empty(lattice: Lattice, side: usize) -> Self {
+        Self {
+            lattice,
+            side,
+            heights: vec![0; side * side],
+
+            bounds: [0, 0, side.saturating_sub(1), side.saturating_sub(1)],
+            is_stable: true,
+            topplings: 0,
+        }
+    }
+
+    fn include(&mut self, x: usize, y: usize) {
+        let [left, top, right, bottom] = &mut self.bounds;
+        *left = (*left).min(x);
+        *top = (*top).min(y);
+        *right = (*right).max(x);
+        *bottom = (*bottom).max(y);
+    }
+
+    pub fn lattice(&self) -> Lattice {
+        self.lattice
+    }
+
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    pub fn is_stable(&self) -> bool {
+        self.is_stable
+    }
+
+    pub fn topplings(&self) -> u64 {
+        self.topplings
+    }
+
+    pub fn to_rows(&self) -> Vec<String> {
+        self.heights
+            .chunks(self.side.max(1))
+            .map(|row| {
+                row.iter()
+                    .map(|height| char::from_digit(*height, 10).unwrap_or('0'))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Sweeps over the region with grains up to the given number of times,
+    // returns true, when the pile is stable. Every unstable site gives away
+    // all of its excess at once, so millions of grains don't take millions of steps.
+    pub fn topple(&mut self, max_sweeps: usize) -> bool {
+        let threshold = self.lattice.threshold();
+
+        for _ in 0..max_sweeps {
+            if self.is_stable {
+                break;
+            }
+
+            let [left, top, right, bottom] = self.bounds;
+            if left == 0 || top == 0 || right == self.side - 1 || bottom == self.side - 1
+            {
+                self.grow();
+                continue;
+            }
+
+            let mut is_toppled = false;
+            for y in top..=bottom {
+                let neighbors = self.lattice.neighbors(y);
+                for x in left..=right {
+                    let index = y * self.side + x;
+                    let count = self.heights[index] / threshold;
+                    if count == 0 {
+                        continue;
+                    }
+
+                    self.heights[index] %= threshold;
+                    self.topplings += count as u64;
+                    for (dx, dy) in neighbors {
+                        let neighbor = (y as isize + dy) as usize * self.side
+                            + (x as isize + dx) as usize;
+                        self.heights[neighbor] += count;
+                    }
+
+                    is_toppled = true;
+                    self.include(x - 1, y - 1);
+                    self.include(x + 1, y + 1);
+                }
+            }
+            self.is_stable = !is_toppled;
+        }
+
+        self.is_stable
+    }
+
+    // Adds the margin of at least a half of the side around the grid
+    fn grow(&mut self) {
+        let margin = (self.side / 2 + 1).next_multiple_of(2);
+        let side = self.side + 2 * margin;
+
+        let mut heights = vec![0; side * side];
+        for (y, row) in self.heights.chunks(self.side).enumerate() {
+            let start = (y + margin) * side + margin;
+            heights[start..start + self.side].copy_from_slice(row);
+        }
+
+        self.side = side;
+        self.heights = heights;
+        for bound in self.bounds.iter_mut() {
+            *bound += margin;
+        }
+    }
+
+    // Copy without the empty margin, the center stays in the middle
+    pub fn cropped(&self) -> Self {
+        let center = self.side / 2;
+        let [left, top, right, bottom] = self.bounds;
+        let extent = [center - left, center - top, right - center, bottom - center]
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+            .next_multiple_of(2)
+            .min(center);
+
+        let margin = center - extent;
+        let side = 2 * extent + 1;
+        let mut heights = Vec::with_capacity(side * side);
+        for y in margin..margin + side {
+            let start = y * self.side + margin;
+            heights.extend_from_slice(&self.heights[start..start + side]);
+        }
+
+        Self {
+            lattice: self.lattice,
+            side,
+            heights,
+
+            bounds: self.bounds.map(|bound| bound - margin),
+            is_stable: self.is_stable,
+            topplings: self.topplings,
+        }
+    }
+
+    // Every site is 2 texels wide, so odd hexagonal rows can be shifted by a half
+    pub fn pixels(&self, colors: &[Color32]) -> (usize, usize, Vec<Color32>) {
+        let color = |height: &u32| {
+            colors
+                .get(*height as usize)
+                .copied()
+                .unwrap_or(Color32::WHITE)
+        };
+
+        match self.lattice {
+            Lattice::Square => (
+                self.side,
+                self.side,
+                self.heights.iter().map(color).collect(),
+            ),
+            Lattice::Hexagonal => {
+                let width = 2 * self.side + 1;
+                let mut pixels = vec![Color32::TRANSPARENT; width * self.side];
+                for (y, row) in self.heights.chunks(self.side).enumerate() {
+                    for (x, height) in row.iter().enumerate() {
+                        let start = y * width + 2 * x + y % 2;
+                        pixels[start..start + 2].fill(color(height));
+                    }
+                }
+                (width, self.side, pixels)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stable_pile(lattice: Lattice, sources: &[Source]) -> Pile {
+        let mut pile = Pile::new(lattice, sources);
+        while !pile.topple(100) {}
+        pile.cropped()
+    }
+
+    #[test]
+    fn pile_is_symmetric() {
+        let source = Source {
+            grains: 10_000,
+            ..Default::default()
+        };
+        let pile = stable_pile(Lattice::Square, &[source]);
+
+        let side = pile.side();
+        for y in 0..side {
+            for x in 0..side {
+                let height = pile.heights[y * side + x];
+                assert!(height < 4);
+                assert_eq!(height, pile.heights[x * side + y]);
+                assert_eq!(height, pile.heights[y * side + side - 1 - x]);
+            }
+        }
+    }
+
+    #[test]
+    fn toppling_is_abelian() {
+        // The stable configuration doesn't depend on the order of the topplings,
+        // so dropping grains in parts gives the same result
+        let sources = [
+            Source {
+                x: 3,
+                y: -2,
+                grains: 3_000,
+            },
+            Source {
+                x: -4,
+                y: 1,
+                grains: 2_000,
+            },
+        ];
+        let pile = stable_pile(Lattice::Hexagonal, &sources);
+
+        let mut parts = stable_pile(Lattice::Hexagonal, &sources[..1]);
+        let center = parts.side / 2;
+        parts.heights[(center - 1) * parts.side + center - 4] += 2_000;
+        parts.include(center - 4, center - 1);
+        parts.is_stable = false;
+        while !parts.topple(100) {}
+        let parts = parts.cropped();
+
+        assert_eq!(pile.heights, parts.heights);
+        assert_eq!(pile.topplings(), parts.topplings());
+    }
+
+    #[test]
+    fn rows_keep_configuration() {
+        let source = Source {
+            grains: 5_000,
+            ..Default::default()
+        };
+        let pile = stable_pile(Lattice::Hexagonal, &[source]);
+
+        let loaded = Pile::from_rows(Lattice::Hexagonal, &pile.to_rows());
+
+        assert_eq!(pile.heights, loaded.heights);
+        assert_eq!(
+            loaded
+                .heights
+                .iter()
+                .map(|height| *height as u64)
+                .sum::<u64>(),
+            source.grains
+        );
+    }
+
+    #[test]
+    fn grains_below_threshold_stay_in_place() {
+        let source = Source {
+            x: 2,
+            y: 1,
+            grains: 3,
+        };
+        let pile = stable_pile(Lattice::Square, &[source]);
+
+        assert_eq!(pile.topplings(), 0);
+        assert_eq!(pile.heights.iter().filter(|height| **height > 0).count(), 1);
+        assert_eq!(pile.heights.iter().sum::<u32>(), 3);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::fractals::sandpile::model;
+use crate::fractals::sandpile::model::{Lattice, Source};
+use crate::fractals::sandpile::state::SandpileState;
+use crate::fractals::sandpile::validation::ValidationError;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<SandpileDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &SandpileState) -> serde_json::Result<String> {
+    let dto = SandpileDto {
+        lattice: state.lattice,
+        sources: state.sources.clone(),
+        colors: state
+            .colors
+            .iter()
+            .map(|color| [color.r(), color.g(), color.b()])
+            .collect(),
+        cell_size_cm: state.cell_size_cm,
+        heights: state.pile_rows().unwrap_or_default(),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_sources() -> Vec<Source> {
+    vec![Source::default()]
+}
+
+fn default_cell_size() -> f32 {
+    model::DEFAULT_CELL_SIZE_CM
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SandpileDto {
+    #[serde(rename = "Lattice", default)]
+    lattice: Lattice,
+
+    #[serde(rename = "Sources", default = "default_sources")]
+    sources: Vec<Source>,
+
+    // Empty for the default colors of the lattice
+    #[serde(rename = "Colors", default)]
+    colors: Vec<[u8; 3]>,
+
+    #[serde(rename = "Cell Size", default = "default_cell_size")]
+    cell_size_cm: f32,
+
+    // Stable configuration by rows of digits, so it doesn't have to be toppled again
+    #[serde(rename = "Heights", default, skip_serializing_if = "Vec::is_empty")]
+    heights: Vec<String>,
+}
+
+impl SandpileDto {
+    pub fn load(self, state: &mut SandpileState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.lattice = self.lattice;
+        state.sources = self.sources;
+        state.colors = match self.colors.is_empty() {
+            true => self.lattice.default_colors(),
+            false => self
+                .colors
+                .into_iter()
+                .map(|[r, g, b]| Color32::from_rgb(r, g, b))
+                .collect(),
+        };
+        state.cell_size_cm = self.cell_size_cm;
+
+        let result = state
+            .initialize()
+            .and_then(|_| match self.heights.is_empty() {
+                true => Ok(()),
+                false => state.load_pile(&self.heights),
+            });
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,166 @@
+use crate::fractals::sandpile::model::{Lattice, Pile, Source};
+use crate::fractals::sandpile::validation::ValidationError;
+use crate::fractals::sandpile::worker::Worker;
+use crate::fractals::sandpile::{model, validation};
+use crate::geometry::point2d::Point2D;
+use crate::graphics::raster::Raster;
+use crate::ui::components::canvas::CanvasParams;
+use egui::{Color32, Rect, Shape};
+
+pub struct SandpileState {
+    is_initialized: bool,
+    is_refresh_requested: bool,
+
+    pub lattice: Lattice,
+    pub sources: Vec<Source>,
+    pub colors: Vec<Color32>,
+    pub cell_size_cm: f32,
+
+    pile: Option<Pile>,
+    // Settings, that the pile was toppled with
+    pile_config: Option<(Lattice, Vec<Source>)>,
+    worker: Option<Worker>,
+
+    raster: Raster,
+}
+
+impl Default for SandpileState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_refresh_requested: false,
+
+            lattice: Lattice::default(),
+            sources: vec![Source::default()],
+            colors: Lattice::default().default_colors(),
+            cell_size_cm: model::DEFAULT_CELL_SIZE_CM,
+
+            pile: None,
+            pile_config: None,
+            worker: None,
+
+            raster: Raster::new("Sandpile"),
+        }
+    }
+}
+
+impl SandpileState {
+    pub fn shapes(&mut self, ctx: &egui::Context, params: &CanvasParams) -> Vec<Shape> {
+        if let Some(worker) = &mut self.worker {
+            worker.step();
+            ctx.request_repaint();
+
+            // Progressive toppling: the picture is updated while the grains spread
+            if let Some(pile) = worker.snapshot() {
+                if pile.is_stable() {
+                    self.worker = None;
+                }
+                self.pile = Some(pile);
+                self.is_refresh_requested = true;
+            }
+        }
+
+        let Some(pile) = &self.pile else {
+            return vec![];
+        };
+
+        if self.is_refresh_requested {
+            self.is_refresh_requested = false;
+
+            let (width, height, pixels) = pile.pixels(&self.colors);
+            self.raster.set_pixels(width, height, pixels);
+        }
+
+        vec![self.raster.shape(ctx, self.screen_rect(pile, params))]
+    }
+
+    // Pile is centered at the origin of the canvas, hexagonal rows are denser
+    fn screen_rect(&self, pile: &Pile, params: &CanvasParams) -> Rect {
+        let side = pile.side() as f32 * self.cell_size_cm;
+        let (half_width, half_height) = match pile.lattice() {
+            Lattice::Square => (side / 2.0, side / 2.0),
+            Lattice::Hexagonal => (
+                (side + self.cell_size_cm / 2.0) / 2.0,
+                side * 3f32.sqrt() / 4.0,
+            ),
+        };
+        let top_left = Point2D::new(-half_width, half_height).to_screen(params);
+        let bottom_right = Point2D::new(half_width, -half_height).to_screen(params);
+
+        Rect::from_two_pos(top_left.to_pos2(), bottom_right.to_pos2())
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::sources_are_valid(&self.sources)?;
+        validation::colors_count(self.lattice, self.colors.len())?;
+        validation::cell_size_range(self.cell_size_cm)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    // Toppling with the old settings is useless, so it stops
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+        self.worker = None;
+    }
+
+    // Colors and the cell size don't change the pile, only the picture
+    pub fn request_refresh(&mut self) {
+        self.is_refresh_requested = true;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.worker.is_some()
+    }
+
+    pub fn start(&mut self) {
+        debug_assert!(self.is_initialized);
+
+        self.pile = None;
+        self.pile_config = Some((self.lattice, self.sources.clone()));
+        self.worker = Some(Worker::start(Pile::new(self.lattice, &self.sources)));
+    }
+
+    pub fn stop(&mut self) {
+        self.worker = None;
+    }
+
+    pub fn grains(&self) -> u64 {
+        self.sources.iter().map(|source| source.grains).sum()
+    }
+
+    pub fn topplings(&self) -> u64 {
+        self.pile.as_ref().map_or(0, |pile| pile.topplings())
+    }
+
+    // Stable configuration for saving, if it matches current settings
+    pub fn pile_rows(&self) -> Option<Vec<String>> {
+        if self.pile_config.as_ref() != Some(&(self.lattice, self.sources.clone())) {
+            return None;
+        }
+
+        self.pile
+            .as_ref()
+            .filter(|pile| pile.is_stable())
+            .map(|pile| pile.to_rows())
+    }
+
+    pub fn load_pile(&mut self, rows: &[String]) -> Result<(), ValidationError> {
+        debug_assert!(self.is_initialized);
+
+        validation::rows_are_valid(self.lattice, rows)?;
+
+        self.stop();
+        self.pile = Some(Pile::from_rows(self.lattice, rows));
+        self.pile_config = Some((self.lattice, self.sources.clone()));
+        self.is_refresh_requested = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use crate::context::Context;
+use crate::fractals::sandpile::model;
+use crate::fractals::sandpile::model::{Lattice, Source};
+use crate::fractals::sandpile::serialization;
+use crate::fractals::sandpile::state::SandpileState;
+use crate::fractals::sandpile::validation::MAX_OFFSET;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Ui};
+use indoc::indoc;
+
+pub struct SandpileSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for SandpileSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl SandpileSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        let state = &mut context.sandpile_state;
+
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if state.is_running() {
+                ui.label(RichText::new("Toppling...").color(colors::LIME));
+            } else if state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Grains: ");
+            ui.label(state.grains().to_string());
+            ui.end_row();
+
+            ui.label("Topplings: ");
+            ui.label(state.topplings().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let mut changed = false;
+        let mut display_changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Lattice:");
+            egui::ComboBox::from_id_salt("SandpileLattice")
+                .selected_text(format!("{}", state.lattice))
+                .show_ui(ui, |ui| {
+                    for lattice in Lattice::iter() {
+                        if ui
+                            .selectable_value(
+                                &mut state.lattice,
+                                lattice,
+                                lattice.to_string(),
+                            )
+                            .changed()
+                        {
+                            state.colors = lattice.default_colors();
+                            changed = true;
+                        }
+                    }
+                });
+            ui.end_row();
+
+            for (index, source) in state.sources.iter_mut().enumerate() {
+                ui.label(format!("Source {}:", index + 1));
+                ui.horizontal(|ui| {
+                    let x = ui.add(
+                        DragValue::new(&mut source.x)
+                            .speed(1)
+                            .range(-MAX_OFFSET..=MAX_OFFSET)
+                            .prefix("x: "),
+                    );
+                    let y = ui.add(
+                        DragValue::new(&mut source.y)
+                            .speed(1)
+                            .range(-MAX_OFFSET..=MAX_OFFSET)
+                            .prefix("y: "),
+                    );
+                    let grains = ui
+                        .add(
+                            DragValue::new(&mut source.grains)
+                                .speed(100)
+                                .range(0..=model::MAX_GRAINS),
+                        )
+                        .on_hover_text("Grains dropped on the site.");
+                    changed |= x.changed() || y.changed() || grains.changed();
+                });
+                ui.end_row();
+            }
+
+            ui.label("");
+            ui.horizontal(|ui| {
+                if ui.button("Add Source").clicked() {
+                    state.sources.push(Source {
+                        grains: 0,
+                        ..Default::default()
+                    });
+                    changed = true;
+                }
+                if ui
+                    .add_enabled(state.sources.len() > 1, Button::new("Remove"))
+                    .clicked()
+                {
+                    state.sources.pop();
+                    changed = true;
+                }
+            });
+            ui.end_row();
+
+            ui.label("Cell Size:");
+            display_changed |= ui
+                .add(
+                    DragValue::new(&mut state.cell_size_cm)
+                        .speed(0.01)
+                        .range(0.01..=10.0)
+                        .suffix(" cm"),
+                )
+                .changed();
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Colors", |ui| {
+            Grid::new("SandpileColorsGrid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (grains, color) in state.colors.iter_mut().enumerate() {
+                        ui.label(format!("{} Grains:", grains));
+                        display_changed |= egui::color_picker::color_edit_button_srgba(
+                            ui,
+                            color,
+                            egui::color_picker::Alpha::Opaque,
+                        )
+                        .changed();
+                        ui.end_row();
+                    }
+                });
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+        if display_changed {
+            state.request_refresh();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(!state.is_initialized(), Button::new("Initialize"))
+                .clicked()
+            {
+                if let Err(err) = state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if state.is_running() {
+                if ui.button("Stop").clicked() {
+                    state.stop();
+                }
+            } else if ui
+                .add_enabled(state.is_initialized(), Button::new("Topple"))
+                .on_hover_text("Drops the grains and topples the sites until stable.")
+                .clicked()
+            {
+                state.start();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.sandpile_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.sandpile_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.sandpile_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Lattice\": \"Square\",
+                                \"Sources\": [
+                                    { \"X\": 0, \"Y\": 0, \"Grains\": 1000000 }
+                                ]
+                            }
+
+                            Lattice: \"Square\" (4 neighbors) or \"Hexagonal\"
+                            (6 neighbors), a site topples, when it has as many grains
+                            as neighbors.
+                            Sources: offsets from the center and numbers of grains.
+                            Other fields: \"Colors\" (RGB for every number of grains),
+                            \"Cell Size\" (cm).
+
+                            Millions of grains take minutes to topple, the picture
+                            is updated, while the grains spread.
+
+                            Saved files also keep the stable configuration
+                            in \"Heights\", so it's shown without toppling again.
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut SandpileState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use crate::fractals::sandpile::model;
+use crate::fractals::sandpile::model::{Lattice, Source};
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub const MAX_OFFSET: i32 = 1000;
+
+pub fn sources_are_valid(sources: &[Source]) -> Result<(), ValidationError> {
+    if sources.is_empty() {
+        return Err(ValidationError::NoSources);
+    }
+
+    for source in sources {
+        // Absolute value of i32::MIN doesn't fit into i32
+        let limit = MAX_OFFSET as u32;
+        if source.x.unsigned_abs() > limit || source.y.unsigned_abs() > limit {
+            return Err(ValidationError::BadSourceOffset(format!(
+                "Source: ({}, {}).",
+                source.x, source.y
+            )));
+        }
+    }
+
+    let grains = sources
+        .iter()
+        .fold(0u64, |sum, source| sum.saturating_add(source.grains));
+    if !(1..=model::MAX_GRAINS).contains(&grains) {
+        return Err(ValidationError::BadGrainsCount(format!(
+            "Grains: {}, maximum: {}.",
+            grains,
+            model::MAX_GRAINS
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn colors_count(lattice: Lattice, count: usize) -> Result<(), ValidationError> {
+    if count != lattice.threshold() as usize {
+        return Err(ValidationError::BadColorsCount(format!(
+            "The {} lattice needs {} colors.",
+            lattice,
+            lattice.threshold()
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn cell_size_range(cell_size_cm: f32) -> Result<(), ValidationError> {
+    if !(cell_size_cm > 0.0 && cell_size_cm.is_finite()) {
+        return Err(ValidationError::BadCellSize);
+    }
+
+    Ok(())
+}
+
+// Saved stable configuration: a square of digits with the center in the middle
+pub fn rows_are_valid(lattice: Lattice, rows: &[String]) -> Result<(), ValidationError> {
+    let side = rows.len();
+    if side % 2 == 0 || (side / 2) % 2 != 0 {
+        return Err(ValidationError::BadConfiguration(format!(
+            "The number of rows has to be 4k + 1, got {}.",
+            side
+        )));
+    }
+
+    for (index, row) in rows.iter().enumerate() {
+        if row.chars().count() != side {
+            return Err(ValidationError::BadConfiguration(format!(
+                "Row {} has {} sites, expected {}.",
+                index + 1,
+                row.chars().count(),
+                side
+            )));
+        }
+        if let Some(letter) = row.chars().find(|letter| {
+            letter
+                .to_digit(10)
+                .is_none_or(|height| height >= lattice.threshold())
+        }) {
+            return Err(ValidationError::BadConfiguration(format!(
+                "Row {} has the unstable site '{}'.",
+                index + 1,
+                letter
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("At least one source of grains is required.")]
+    NoSources,
+
+    #[error("Offsets of the sources have to be in range -1000..1000.")]
+    BadSourceOffset(String),
+
+    #[error("The total number of grains has to be in range 1..5000000.")]
+    BadGrainsCount(String),
+
+    #[error("The number of colors has to match the toppling threshold.")]
+    BadColorsCount(String),
+
+    #[error("The cell size has to be greater than 0.")]
+    BadCellSize,
+
+    #[error("The saved configuration is not a stable square of sites.")]
+    BadConfiguration(String),
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadSourceOffset(value)
+            | Self::BadGrainsCount(value)
+            | Self::BadColorsCount(value)
+            | Self::BadConfiguration(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::sandpile::state::SandpileState;
+
+    fn rows(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn source_offset_bounds_are_inclusive() {
+        let source = |x, y| Source { x, y, grains: 1 };
+
+        assert!(sources_are_valid(&[source(MAX_OFFSET, -MAX_OFFSET)]).is_ok());
+        let result = sources_are_valid(&[source(0, 0), source(0, -MAX_OFFSET - 1)]);
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Source: (0, -1001).".to_string())
+        );
+    }
+
+    #[test]
+    fn smallest_offset_is_reported() {
+        let source = Source {
+            x: i32::MIN,
+            y: 0,
+            grains: 1,
+        };
+
+        let result = sources_are_valid(&[source]);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Source: (-2147483648, 0).".to_string())
+        );
+    }
+
+    #[test]
+    fn sources_need_grains() {
+        assert!(matches!(
+            sources_are_valid(&[]),
+            Err(ValidationError::NoSources)
+        ));
+        assert!(matches!(
+            sources_are_valid(&[Source {
+                grains: 0,
+                ..Default::default()
+            }]),
+            Err(ValidationError::BadGrainsCount(_))
+        ));
+    }
+
+    #[test]
+    fn too_many_grains() {
+        let mut state = SandpileState::default();
+        state.sources[0].grains = model::MAX_GRAINS;
+        state.sources.push(Source::default());
+
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::BadGrainsCount(_))));
+    }
+
+    #[test]
+    fn stacked_sources_beyond_height_limit() {
+        // Every source alone fits a u32 height, all of them on one site don't
+        let mut state = SandpileState::default();
+        state.sources = vec![
+            Source {
+                grains: u32::MAX as u64,
+                ..Default::default()
+            };
+            2
+        ];
+
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::BadGrainsCount(_))));
+    }
+
+    #[test]
+    fn wrong_colors_count() {
+        let mut state = SandpileState::default();
+        state.lattice = Lattice::Hexagonal;
+
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::BadColorsCount(_))));
+    }
+
+    #[test]
+    fn unstable_configuration() {
+        let rows = rows(&["00000", "01230", "02420", "01210", "00000"]);
+
+        let result = rows_are_valid(Lattice::Square, &rows);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Row 3 has the unstable site '4'.".to_string())
+        );
+        assert!(rows_are_valid(Lattice::Hexagonal, &rows).is_ok());
+    }
+
+    #[test]
+    fn rows_form_centered_square() {
+        // Piles are cropped to an even radius around the center, so the side is 4k + 1
+        assert!(rows_are_valid(Lattice::Square, &rows(&["3"])).is_ok());
+        for side in [0, 2, 3, 7] {
+            let rows = vec!["0".repeat(side); side];
+            assert!(matches!(
+                rows_are_valid(Lattice::Square, &rows),
+                Err(ValidationError::BadConfiguration(_))
+            ));
+        }
+
+        let result = rows_are_valid(
+            Lattice::Square,
+            &rows(&["00000", "0000", "00000", "00000", "00000"]),
+        );
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Row 2 has 4 sites, expected 5.".to_string())
+        );
+
+        let result = rows_are_valid(Lattice::Square, &rows(&["x"]));
+        assert!(matches!(result, Err(ValidationError::BadConfiguration(_))));
+    }
+}
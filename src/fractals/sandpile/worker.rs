@@ -0,0 +1,100 @@
+use crate::fractals::sandpile::model::Pile;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crossbeam::channel::{unbounded, Receiver};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread::JoinHandle;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+// Sweeps between the checks for stopping
+const BATCH_SWEEPS: usize = 4;
+
+// Time between the snapshots of the pile, that is being toppled
+#[cfg(not(target_arch = "wasm32"))]
+const SNAPSHOT_INTERVAL: Duration = Duration::from_millis(500);
+
+// Topples the pile until it's stable, giving back cropped snapshots on the way.
+// On native platforms the pile is toppled on a background thread,
+// on the web there are no threads, so a batch is toppled on every frame.
+pub struct Worker {
+    #[cfg(not(target_arch = "wasm32"))]
+    is_stopped: Arc<AtomicBool>,
+    #[cfg(not(target_arch = "wasm32"))]
+    receiver: Receiver<Pile>,
+    #[cfg(not(target_arch = "wasm32"))]
+    handle: Option<JoinHandle<()>>,
+
+    #[cfg(target_arch = "wasm32")]
+    pile: Pile,
+}
+
+impl Worker {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start(mut pile: Pile) -> Self {
+        let is_stopped = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = unbounded();
+
+        let handle = {
+            let is_stopped = Arc::clone(&is_stopped);
+
+            std::thread::spawn(move || {
+                let mut last_snapshot = Instant::now();
+                while !is_stopped.load(Ordering::Relaxed) {
+                    let is_stable = pile.topple(BATCH_SWEEPS);
+
+                    if is_stable || last_snapshot.elapsed() >= SNAPSHOT_INTERVAL {
+                        last_snapshot = Instant::now();
+                        if sender.send(pile.cropped()).is_err() || is_stable {
+                            return;
+                        }
+                    }
+                }
+            })
+        };
+
+        Self {
+            is_stopped,
+            receiver,
+            handle: Some(handle),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn start(pile: Pile) -> Self {
+        Self { pile }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn step(&mut self) {}
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn step(&mut self) {
+        self.pile.topple(BATCH_SWEEPS);
+    }
+
+    // The latest snapshot since the previous call, the last one is stable
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn snapshot(&mut self) -> Option<Pile> {
+        self.receiver.try_iter().last()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn snapshot(&mut self) -> Option<Pile> {
+        Some(self.pile.cropped())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.is_stopped.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
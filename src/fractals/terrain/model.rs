@@ -0,0 +1,429 @@
+use crate::math::random;
+use crate::ui::styles::gradient::Gradient;
+use egui::Color32;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_DETAIL: u32 = 8;
+pub const MAX_DETAIL: u32 = 11;
+pub const DEFAULT_HURST: f64 = 0.8;
+pub const DEFAULT_SIZE_CM: f32 = 20.0;
+pub const DEFAULT_BANDS: u32 = 12;
+pub const DEFAULT_CONTOURS: u32 = 10;
+
+// Direction of the light for hillshading, as in most GIS tools: from the north-west
+const LIGHT_AZIMUTH: f32 = 315.0;
+const LIGHT_ALTITUDE: f32 = 45.0;
+// Height of the highest peak relative to the width of the map
+const RELIEF: f32 = 0.25;
+const AMBIENT: f32 = 0.3;
+const CONTOUR_DARKENING: f32 = 0.45;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Algorithm {
+    // New points are averaged only from the corners of their square or edge
+    #[strum(serialize = "Midpoint Displacement")]
+    #[serde(rename = "Midpoint Displacement")]
+    MidpointDisplacement,
+
+    // Edge points are also averaged from the centers of the neighboring squares
+    #[default]
+    #[strum(serialize = "Diamond-Square")]
+    #[serde(rename = "Diamond-Square")]
+    DiamondSquare,
+}
+
+impl Algorithm {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Algorithm::MidpointDisplacement, Algorithm::DiamondSquare].into_iter()
+    }
+}
+
+// Water, shore, lowlands, mountains and snow
+pub fn default_gradient() -> Gradient {
+    Gradient::new(vec![
+        (0.0, Color32::from_rgb(20, 50, 120)),
+        (0.3, Color32::from_rgb(60, 120, 190)),
+        (0.35, Color32::from_rgb(220, 205, 150)),
+        (0.45, Color32::from_rgb(80, 150, 60)),
+        (0.65, Color32::from_rgb(40, 95, 40)),
+        (0.8, Color32::from_rgb(120, 100, 80)),
+        (1.0, Color32::from_rgb(250, 250, 250)),
+    ])
+}
+
+// Square grid of (2^detail + 1) x (2^detail + 1) heights, normalized to 0..1
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heightmap {
+    size: usize,
+    heights: Vec<f32>,
+}
+
+impl Heightmap {
+    pub fn generate(
+        algorithm: Algorithm, detail: u32, hurst: f64, seed: Option<u64>,
+    ) -> Self {
+        let size = (1 << detail) + 1;
+        let mut rng = random::rng(seed);
+        let mut heights = vec![0.0f32; size * size];
+
+        let last = size - 1;
+        for (x, y) in [(0, 0), (last, 0), (0, last), (last, last)] {
+            heights[y * size + x] = rng.gen_range(-1.0..1.0);
+        }
+
+        // Displacements shrink by 2^-H on every halving of the step
+        let ratio = 0.5f64.powf(hurst) as f32;
+        let mut scale = 1.0f32;
+        let mut step = last;
+        while step > 1 {
+            let half = step / 2;
+            scale *= ratio;
+
+            // Centers of the squares
+            for y in (half..size).step_by(step) {
+                for x in (half..size).step_by(step) {
+                    let average = (heights[(y - half) * size + x - half]
+                        + heights[(y - half) * size + x + half]
+                        + heights[(y + half) * size + x - half]
+                        + heights[(y + half) * size + x + half])
+                        / 4.0;
+                    heights[y * size + x] = average + scale * rng.gen_range(-1.0..1.0);
+                }
+            }
+
+            // Midpoints of the edges
+            for y in (0..size).step_by(half) {
+                let first = match (y / half) % 2 {
+                    0 => half,
+                    _ => 0,
+                };
+                for x in (first..size).step_by(step) {
+                    let neighbors: Vec<(usize, usize)> = match algorithm {
+                        Algorithm::MidpointDisplacement => match first {
+                            0 => vec![(x, y - half), (x, y + half)],
+                            _ => vec![(x - half, y), (x + half, y)],
+                        },
+                        Algorithm::DiamondSquare => [
+                            (x.checked_sub(half), Some(y)),
+                            (Some(x + half), Some(y)),
+                            (Some(x), y.checked_sub(half)),
+                            (Some(x), Some(y + half)),
+                        ]
+                        .into_iter()
+                        .filter_map(|(x, y)| Some((x?, y?)))
+                        .filter(|(x, y)| *x < size && *y < size)
+                        .collect(),
+                    };
+
+                    let sum: f32 =
+                        neighbors.iter().map(|(x, y)| heights[y * size + x]).sum();
+                    let average = sum / neighbors.len() as f32;
+                    heights[y * size + x] = average + scale * rng.gen_range(-1.0..1.0);
+                }
+            }
+
+            step = half;
+        }
+
+        // Normalization
+        let min = heights.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = heights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let range = (max - min).max(f32::EPSILON);
+        for height in &mut heights {
+            *height = (*height - min) / range;
+        }
+
+        Self { size, heights }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn height(&self, x: usize, y: usize) -> f32 {
+        self.heights[y.min(self.size - 1) * self.size + x.min(self.size - 1)]
+    }
+
+    // Full range of 16-bit grayscale, row by row from the north
+    pub fn to_u16(&self) -> Vec<u16> {
+        self.heights
+            .iter()
+            .map(|height| (height * u16::MAX as f32).round() as u16)
+            .collect()
+    }
+}
+
+// Colored map of the heightmap, one color per grid point
+pub struct Model {
+    heightmap: Heightmap,
+    size_cm: f32,
+    colors: Vec<Color32>,
+}
+
+impl Model {
+    pub fn heightmap(&self) -> &Heightmap {
+        &self.heightmap
+    }
+
+    // Point is in centimeters, the map is centered at the origin
+    pub fn color(&self, x: f32, y: f32) -> Color32 {
+        let size = self.heightmap.size();
+        let cells = (size - 1) as f32;
+        let column = ((x / self.size_cm + 0.5) * cells).round();
+        let row = ((0.5 - y / self.size_cm) * cells).round();
+
+        if !(0.0..=cells).contains(&column) || !(0.0..=cells).contains(&row) {
+            return Color32::TRANSPARENT;
+        }
+
+        self.colors[row as usize * size + column as usize]
+    }
+}
+
+pub struct ModelBuilder {
+    algorithm: Algorithm,
+    detail: u32,
+    hurst: f64,
+    seed: Option<u64>,
+
+    size_cm: f32,
+    bands: u32,
+    contours: Option<u32>,
+    is_hillshaded: bool,
+    gradient: Gradient,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            algorithm: Algorithm::default(),
+            detail: DEFAULT_DETAIL,
+            hurst: DEFAULT_HURST,
+            seed: None,
+
+            size_cm: DEFAULT_SIZE_CM,
+            bands: DEFAULT_BANDS,
+            contours: Some(DEFAULT_CONTOURS),
+            is_hillshaded: true,
+            gradient: default_gradient(),
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn with_detail(mut self, detail: u32) -> Self {
+        self.detail = detail;
+        self
+    }
+
+    pub fn with_hurst(mut self, hurst: f64) -> Self {
+        self.hurst = hurst;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_size(mut self, size_cm: f32) -> Self {
+        self.size_cm = size_cm;
+        self
+    }
+
+    // Zero bands means a smooth gradient
+    pub fn with_bands(mut self, bands: u32) -> Self {
+        self.bands = bands;
+        self
+    }
+
+    pub fn with_contours(mut self, contours: Option<u32>) -> Self {
+        self.contours = contours;
+        self
+    }
+
+    pub fn with_hillshade(mut self, is_hillshaded: bool) -> Self {
+        self.is_hillshaded = is_hillshaded;
+        self
+    }
+
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        let heightmap =
+            Heightmap::generate(self.algorithm, self.detail, self.hurst, self.seed);
+        let colors = self.colors(&heightmap);
+
+        Model {
+            heightmap,
+            size_cm: self.size_cm,
+            colors,
+        }
+    }
+
+    fn colors(&self, heightmap: &Heightmap) -> Vec<Color32> {
+        let size = heightmap.size();
+        let light = light_direction();
+        // Heights are scaled, so that the slopes don't depend on the detail level
+        let relief = RELIEF * (size - 1) as f32;
+
+        let mut colors = Vec::with_capacity(size * size);
+        for y in 0..size {
+            for x in 0..size {
+                let height = heightmap.height(x, y);
+
+                let t = match self.bands {
+                    0 => height,
+                    bands => (level(height, bands) as f32 + 0.5) / bands as f32,
+                };
+                let mut color = self.gradient.sample(t.min(1.0));
+
+                if self.is_hillshaded {
+                    let dx = (heightmap.height(x + 1, y)
+                        - heightmap.height(x.saturating_sub(1), y))
+                        / 2.0
+                        * relief;
+                    let dy = (heightmap.height(x, y + 1)
+                        - heightmap.height(x, y.saturating_sub(1)))
+                        / 2.0
+                        * relief;
+                    // Rows go to the south, so the y axis of the normal is flipped
+                    let normal = [-dx, dy, 1.0];
+                    let length = (normal[0].powi(2) + normal[1].powi(2) + 1.0).sqrt();
+                    let lambert = (normal[0] * light[0]
+                        + normal[1] * light[1]
+                        + normal[2] * light[2])
+                        / length;
+                    color = darken(color, AMBIENT + (1.0 - AMBIENT) * lambert.max(0.0));
+                }
+
+                if let Some(contours) = self.contours {
+                    let current = level(height, contours);
+                    let is_contour = level(heightmap.height(x + 1, y), contours)
+                        != current
+                        || level(heightmap.height(x, y + 1), contours) != current;
+                    if is_contour {
+                        color = darken(color, CONTOUR_DARKENING);
+                    }
+                }
+
+                colors.push(color);
+            }
+        }
+        colors
+    }
+}
+
+// Index of the equal part of 0..1, that holds the height.
+// The highest point belongs to the last part, not to a part of its own.
+fn level(height: f32, levels: u32) -> u32 {
+    ((height * levels as f32) as u32).min(levels.saturating_sub(1))
+}
+
+// Unit vector pointing to the light, x to the east, y to the north, z up
+fn light_direction() -> [f32; 3] {
+    let azimuth = LIGHT_AZIMUTH.to_radians();
+    let altitude = LIGHT_ALTITUDE.to_radians();
+    [
+        altitude.cos() * azimuth.sin(),
+        altitude.cos() * azimuth.cos(),
+        altitude.sin(),
+    ]
+}
+
+fn darken(color: Color32, factor: f32) -> Color32 {
+    let factor = factor.clamp(0.0, 1.0);
+    Color32::from_rgba_unmultiplied(
+        (color.r() as f32 * factor) as u8,
+        (color.g() as f32 * factor) as u8,
+        (color.b() as f32 * factor) as u8,
+        color.a(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heightmap_is_normalized() {
+        for algorithm in Algorithm::iter() {
+            let heightmap = Heightmap::generate(algorithm, 5, 0.8, Some(1));
+
+            let heights = heightmap.to_u16();
+
+            assert_eq!(heightmap.size(), 33);
+            assert_eq!(heights.len(), 33 * 33);
+            assert_eq!(heights.iter().min(), Some(&0));
+            assert_eq!(heights.iter().max(), Some(&u16::MAX));
+        }
+    }
+
+    #[test]
+    fn seed_gives_same_heightmap() {
+        let first = Heightmap::generate(Algorithm::DiamondSquare, 4, 0.5, Some(7));
+        let second = Heightmap::generate(Algorithm::DiamondSquare, 4, 0.5, Some(7));
+        let other = Heightmap::generate(Algorithm::MidpointDisplacement, 4, 0.5, Some(7));
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    fn plain_model(bands: u32, contours: Option<u32>) -> Model {
+        ModelBuilder::default()
+            .with_detail(4)
+            .with_seed(Some(3))
+            .with_bands(bands)
+            .with_contours(contours)
+            .with_hillshade(false)
+            .build()
+    }
+
+    #[test]
+    fn highest_point_stays_in_top_band() {
+        let model = plain_model(1, None);
+
+        let band = default_gradient().sample(0.5);
+        assert!(model.colors.iter().all(|color| *color == band));
+    }
+
+    #[test]
+    fn single_contour_level_draws_no_lines() {
+        let with_contours = plain_model(0, Some(1));
+        let without_contours = plain_model(0, None);
+
+        assert_eq!(with_contours.colors, without_contours.colors);
+    }
+
+    #[test]
+    fn map_is_centered_at_origin() {
+        let model = plain_model(0, None);
+        let half = DEFAULT_SIZE_CM / 2.0;
+        let heightmap = model.heightmap();
+        let last = heightmap.size() - 1;
+
+        // North-west and south-east corners
+        let gradient = default_gradient();
+        assert_eq!(
+            model.color(-half, half),
+            gradient.sample(heightmap.height(0, 0))
+        );
+        assert_eq!(
+            model.color(half, -half),
+            gradient.sample(heightmap.height(last, last))
+        );
+
+        assert_eq!(model.color(half + 1.0, 0.0), Color32::TRANSPARENT);
+        assert_eq!(model.color(0.0, -half - 1.0), Color32::TRANSPARENT);
+    }
+}
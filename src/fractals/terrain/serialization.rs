@@ -0,0 +1,101 @@
+use crate::fractals::terrain::model;
+use crate::fractals::terrain::model::Algorithm;
+use crate::fractals::terrain::state::TerrainState;
+use crate::fractals::terrain::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<TerrainDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &TerrainState) -> serde_json::Result<String> {
+    let dto = TerrainDto {
+        algorithm: state.algorithm,
+        detail: state.detail,
+        hurst: state.hurst,
+        seed: state.seed,
+        size_cm: state.size_cm,
+        bands: state.bands,
+        contours: state.contours,
+        is_hillshaded: state.is_hillshaded,
+        gradient: (&state.gradient).into(),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_detail() -> u32 {
+    model::DEFAULT_DETAIL
+}
+
+fn default_hurst() -> f64 {
+    model::DEFAULT_HURST
+}
+
+fn default_size() -> f32 {
+    model::DEFAULT_SIZE_CM
+}
+
+fn default_bands() -> u32 {
+    model::DEFAULT_BANDS
+}
+
+fn default_hillshade() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TerrainDto {
+    #[serde(rename = "Algorithm", default)]
+    algorithm: Algorithm,
+
+    #[serde(rename = "Detail", default = "default_detail")]
+    detail: u32,
+
+    #[serde(rename = "Hurst Exponent", default = "default_hurst")]
+    hurst: f64,
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+
+    #[serde(rename = "Size", default = "default_size")]
+    size_cm: f32,
+
+    #[serde(rename = "Color Bands", default = "default_bands")]
+    bands: u32,
+
+    #[serde(rename = "Contours", default, skip_serializing_if = "Option::is_none")]
+    contours: Option<u32>,
+
+    #[serde(rename = "Hillshade", default = "default_hillshade")]
+    is_hillshaded: bool,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+}
+
+impl TerrainDto {
+    pub fn load(self, state: &mut TerrainState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.algorithm = self.algorithm;
+        state.detail = self.detail;
+        state.hurst = self.hurst;
+        state.seed = self.seed;
+        state.size_cm = self.size_cm;
+        state.bands = self.bands;
+        state.contours = self.contours;
+        state.is_hillshaded = self.is_hillshaded;
+        state.gradient = match self.gradient.is_empty() {
+            true => model::default_gradient(),
+            false => self.gradient.into(),
+        };
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
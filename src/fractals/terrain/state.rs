@@ -0,0 +1,121 @@
+use crate::fractals::terrain::model::{Algorithm, Model, ModelBuilder};
+use crate::fractals::terrain::validation::ValidationError;
+use crate::fractals::terrain::{model, validation};
+use crate::graphics::raster::Raster;
+use crate::io::filter::FileFilter;
+use crate::io::heightmap::HeightmapImage;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::gradient::Gradient;
+use egui::Shape;
+
+pub struct TerrainState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub algorithm: Algorithm,
+    pub detail: u32,
+    pub hurst: f64,
+    pub seed: Option<u64>,
+
+    pub size_cm: f32,
+    pub bands: u32,
+    pub contours: Option<u32>,
+    pub is_hillshaded: bool,
+    pub gradient: Gradient,
+
+    model: Option<Model>,
+    raster: Raster,
+}
+
+impl Default for TerrainState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            algorithm: Algorithm::default(),
+            detail: model::DEFAULT_DETAIL,
+            hurst: model::DEFAULT_HURST,
+            seed: None,
+
+            size_cm: model::DEFAULT_SIZE_CM,
+            bands: model::DEFAULT_BANDS,
+            contours: Some(model::DEFAULT_CONTOURS),
+            is_hillshaded: true,
+            gradient: model::default_gradient(),
+
+            model: None,
+            raster: Raster::new("Terrain"),
+        }
+    }
+}
+
+impl TerrainState {
+    pub fn shapes(&mut self, ctx: &egui::Context, params: &CanvasParams) -> Vec<Shape> {
+        let Some(model) = &self.model else {
+            return vec![];
+        };
+
+        let is_requested = std::mem::take(&mut self.is_drawing_requested);
+        self.raster.render_on_change(params, is_requested, |point| {
+            model.color(point.x, point.y)
+        });
+
+        vec![self.raster.shape(ctx, params.screen_rect())]
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    // Generates a new heightmap, so it takes a while on the finest detail levels
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::detail_range(self.detail)?;
+        validation::hurst_range(self.hurst)?;
+        validation::size_range(self.size_cm)?;
+        validation::bands_range(self.bands)?;
+        validation::contours_range(self.contours)?;
+
+        self.model = Some(
+            ModelBuilder::default()
+                .with_algorithm(self.algorithm)
+                .with_detail(self.detail)
+                .with_hurst(self.hurst)
+                .with_seed(self.seed)
+                .with_size(self.size_cm)
+                .with_bands(self.bands)
+                .with_contours(self.contours)
+                .with_hillshade(self.is_hillshaded)
+                .with_gradient(self.gradient.clone())
+                .build(),
+        );
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn is_generated(&self) -> bool {
+        self.model.is_some()
+    }
+
+    // Heights of the last generated map as a 16-bit grayscale PNG
+    pub fn heightmap_image(&self) -> Option<HeightmapImage> {
+        let heightmap = self.model.as_ref()?.heightmap();
+        let size = heightmap.size() as u32;
+
+        Some(
+            HeightmapImage::default()
+                .with_file_filter(FileFilter::png())
+                .with_samples(size, size, heightmap.to_u16()),
+        )
+    }
+}
@@ -0,0 +1,296 @@
+use crate::context::Context;
+use crate::fractals::terrain::model::{Algorithm, DEFAULT_CONTOURS, MAX_DETAIL};
+use crate::fractals::terrain::serialization;
+use crate::fractals::terrain::state::TerrainState;
+use crate::io;
+use crate::math::random;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct TerrainSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for TerrainSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl TerrainSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.terrain_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.terrain_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Algorithm:");
+            egui::ComboBox::from_id_salt("TerrainAlgorithm")
+                .selected_text(format!("{}", state.algorithm))
+                .show_ui(ui, |ui| {
+                    for algorithm in Algorithm::iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut state.algorithm,
+                                algorithm,
+                                algorithm.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Detail:");
+            changed |= ui
+                .add(Slider::new(&mut state.detail, 1..=MAX_DETAIL))
+                .on_hover_text(format!(
+                    "The heightmap has 2^detail + 1 points per side, {} now.",
+                    (1 << state.detail) + 1
+                ))
+                .changed();
+            ui.end_row();
+
+            ui.label("Hurst Exponent:");
+            changed |= ui
+                .add(Slider::new(&mut state.hurst, 0.01..=1.0))
+                .on_hover_text("Lower values give rougher terrain.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Seed: ");
+            ui.horizontal(|ui| {
+                let mut is_seeded = state.seed.is_some();
+                if ui
+                    .checkbox(&mut is_seeded, "")
+                    .on_hover_text("With a fixed seed, every heightmap is identical.")
+                    .changed()
+                {
+                    state.seed = is_seeded.then_some(random::DEFAULT_SEED);
+                    changed = true;
+                }
+                if let Some(seed) = &mut state.seed {
+                    changed |= ui.add(DragValue::new(seed).speed(1)).changed();
+                }
+            });
+            ui.end_row();
+
+            ui.label("Size:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.size_cm)
+                        .speed(0.1)
+                        .range(0.1..=1000.0)
+                        .suffix(" cm"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Color Bands:");
+            changed |= ui
+                .add(DragValue::new(&mut state.bands).speed(1).range(0..=256))
+                .on_hover_text("0 gives a smooth gradient.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Hillshade:");
+            changed |= ui.checkbox(&mut state.is_hillshaded, "").changed();
+            ui.end_row();
+
+            ui.label("Contours: ");
+            ui.horizontal(|ui| {
+                let mut has_contours = state.contours.is_some();
+                if ui
+                    .checkbox(&mut has_contours, "")
+                    .on_hover_text("Number of contour levels between the lowest and the highest point.")
+                    .changed()
+                {
+                    state.contours = has_contours.then_some(DEFAULT_CONTOURS);
+                    changed = true;
+                }
+                if let Some(contours) = &mut state.contours {
+                    changed |= ui
+                        .add(DragValue::new(contours).speed(1).range(1..=256))
+                        .changed();
+                }
+            });
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Gradient", |ui| {
+            changed |= state.gradient.show_editor(ui, "Terrain");
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.terrain_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.terrain_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.terrain_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.terrain_state.request_drawing();
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    context.terrain_state.is_generated(),
+                    Button::new("Export Heightmap"),
+                )
+                .on_hover_text("Saves the heights as a 16-bit grayscale .png file.")
+                .clicked()
+            {
+                self.export_heightmap(context);
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.terrain_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.terrain_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.terrain_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Algorithm\": \"Diamond-Square\",
+                                \"Detail\": 8,
+                                \"Hurst Exponent\": 0.8,
+                                \"Seed\": 42
+                            }
+
+                            Algorithm: \"Midpoint Displacement\" or \"Diamond-Square\".
+                            Detail: the heightmap has 2^Detail + 1 points per side.
+                            Hurst Exponent: in range 0..1, lower values give rougher terrain.
+
+                            Other fields: \"Size\" (cm), \"Color Bands\", \"Contours\",
+                            \"Hillshade\", \"Gradient\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn export_heightmap(&self, context: &Context) {
+        let Some(image) = context.terrain_state.heightmap_image() else {
+            return;
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Err(err) = image.save_dialog() {
+                let message = format!("Error occurred while saving heightmap: {}", err);
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&message)));
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let sender = context.windows_sender.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(err) = image.save_dialog().await {
+                    let message =
+                        format!("Error occurred while saving heightmap: {}", err);
+                    let _ = sender.send(Box::new(MessageWindow::error(&message)));
+                }
+            });
+        }
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut TerrainState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use crate::fractals::terrain::model;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+const MIN_DETAIL: u32 = 1;
+const MAX_BANDS: u32 = 256;
+const MAX_CONTOURS: u32 = 256;
+
+pub fn detail_range(detail: u32) -> Result<(), ValidationError> {
+    if !(MIN_DETAIL..=model::MAX_DETAIL).contains(&detail) {
+        return Err(ValidationError::BadDetailValue);
+    }
+
+    Ok(())
+}
+
+pub fn hurst_range(hurst: f64) -> Result<(), ValidationError> {
+    if !(hurst > 0.0 && hurst <= 1.0) {
+        return Err(ValidationError::BadHurstExponent);
+    }
+
+    Ok(())
+}
+
+pub fn size_range(size_cm: f32) -> Result<(), ValidationError> {
+    if !(size_cm > 0.0 && size_cm.is_finite()) {
+        return Err(ValidationError::BadSizeValue);
+    }
+
+    Ok(())
+}
+
+pub fn bands_range(bands: u32) -> Result<(), ValidationError> {
+    if bands > MAX_BANDS {
+        return Err(ValidationError::BadBandsValue);
+    }
+
+    Ok(())
+}
+
+pub fn contours_range(contours: Option<u32>) -> Result<(), ValidationError> {
+    if contours.is_some_and(|contours| !(1..=MAX_CONTOURS).contains(&contours)) {
+        return Err(ValidationError::ContoursOutOfRange);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The detail level has to be in range 1..11.")]
+    BadDetailValue,
+
+    #[error("The Hurst exponent has to be in range 0..1 (0 is exclusive).")]
+    BadHurstExponent,
+
+    #[error("The size of the map has to be greater than 0.")]
+    BadSizeValue,
+
+    #[error("The number of color bands has to be in range 0..256.")]
+    BadBandsValue,
+
+    #[error("The number of contour levels has to be in range 1..256.")]
+    ContoursOutOfRange,
+}
+
+impl ValidationError {
+    pub fn window(&self) -> MessageWindow {
+        let message = format!("Validation Error: {}", self);
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::terrain::state::TerrainState;
+
+    #[test]
+    fn detail_bounds_are_inclusive() {
+        assert!(detail_range(MIN_DETAIL).is_ok());
+        assert!(detail_range(model::MAX_DETAIL).is_ok());
+        for detail in [0, model::MAX_DETAIL + 1] {
+            assert!(matches!(
+                detail_range(detail),
+                Err(ValidationError::BadDetailValue)
+            ));
+        }
+    }
+
+    #[test]
+    fn hurst_exponent_of_one_is_valid() {
+        assert!(hurst_range(1.0).is_ok());
+        for hurst in [0.0, 1.0 + f64::EPSILON, f64::NAN] {
+            assert!(matches!(
+                hurst_range(hurst),
+                Err(ValidationError::BadHurstExponent)
+            ));
+        }
+    }
+
+    #[test]
+    fn zero_bands_and_no_contours_are_valid() {
+        assert!(bands_range(0).is_ok());
+        assert!(bands_range(MAX_BANDS).is_ok());
+        assert!(matches!(
+            bands_range(MAX_BANDS + 1),
+            Err(ValidationError::BadBandsValue)
+        ));
+
+        assert!(contours_range(None).is_ok());
+        assert!(contours_range(Some(MAX_CONTOURS)).is_ok());
+        for contours in [0, MAX_CONTOURS + 1] {
+            assert!(matches!(
+                contours_range(Some(contours)),
+                Err(ValidationError::ContoursOutOfRange)
+            ));
+        }
+    }
+
+    #[test]
+    fn heightmap_is_exported_after_initialization() {
+        let mut state = TerrainState::default();
+        assert!(state.heightmap_image().is_none());
+
+        state.detail = MIN_DETAIL;
+        let result = state.initialize();
+
+        assert!(result.is_ok());
+        assert!(state.heightmap_image().is_some());
+    }
+}
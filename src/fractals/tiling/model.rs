@@ -0,0 +1,789 @@
+use crate::geometry::point2d::Point2D;
+use crate::geometry::polygon2d::Polygon2D;
+use crate::math::complex::Complex;
+use egui::{Color32, Stroke};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::{FRAC_1_SQRT_2, PI, SQRT_2};
+use strum_macros::Display;
+
+pub const DEFAULT_GENERATIONS: u32 = 5;
+pub const DEFAULT_SIZE_CM: f32 = 20.0;
+pub const DEFAULT_STROKE_WIDTH: f32 = 1.0;
+pub const DEFAULT_STROKE_COLOR: Color32 = Color32::from_rgb(40, 40, 40);
+pub const MAX_TILES: usize = 500_000;
+
+const GOLDEN_RATIO: f64 = 1.618_033_988_749_895;
+// Inflation factor of the Ammann-Beenker tiling
+const SILVER_RATIO: f64 = 1.0 + SQRT_2;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Tiling {
+    #[default]
+    #[strum(serialize = "Penrose P2 (Kites and Darts)")]
+    #[serde(rename = "Penrose P2")]
+    PenroseKites,
+
+    #[strum(serialize = "Penrose P3 (Rhombs)")]
+    #[serde(rename = "Penrose P3")]
+    PenroseRhombs,
+
+    #[strum(serialize = "Pinwheel")]
+    #[serde(rename = "Pinwheel")]
+    Pinwheel,
+
+    #[strum(serialize = "Chair")]
+    #[serde(rename = "Chair")]
+    Chair,
+
+    #[strum(serialize = "Sphinx")]
+    #[serde(rename = "Sphinx")]
+    Sphinx,
+
+    #[strum(serialize = "Ammann-Beenker")]
+    #[serde(rename = "Ammann-Beenker")]
+    AmmannBeenker,
+}
+
+impl Tiling {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Tiling::PenroseKites,
+            Tiling::PenroseRhombs,
+            Tiling::Pinwheel,
+            Tiling::Chair,
+            Tiling::Sphinx,
+            Tiling::AmmannBeenker,
+        ]
+        .into_iter()
+    }
+
+    pub fn prototiles(&self) -> Vec<Prototile> {
+        match self {
+            Tiling::PenroseKites => penrose_kites(),
+            Tiling::PenroseRhombs => penrose_rhombs(),
+            Tiling::Pinwheel => {
+                with_mirror(pinwheel(), "Left-Handed", Color32::from_rgb(60, 120, 90))
+            },
+            Tiling::Chair => chair(),
+            Tiling::Sphinx => {
+                with_mirror(sphinx(), "Mirrored Sphinx", Color32::from_rgb(150, 90, 50))
+            },
+            Tiling::AmmannBeenker => ammann_beenker(),
+        }
+    }
+
+    pub fn default_colors(&self) -> Vec<Color32> {
+        self.prototiles()
+            .iter()
+            .map(|prototile| prototile.default_color)
+            .collect()
+    }
+
+    fn initial_patch(&self) -> Vec<Tile> {
+        match self {
+            Tiling::PenroseKites | Tiling::PenroseRhombs => {
+                // Sun of ten Robinson triangles with the 36 degrees angle in the center
+                (0..10)
+                    .map(|index| {
+                        let mut b =
+                            Complex::from_polar(1.0, (2 * index - 1) as f64 * PI / 10.0);
+                        let mut c =
+                            Complex::from_polar(1.0, (2 * index + 1) as f64 * PI / 10.0);
+                        if index % 2 == 0 {
+                            (b, c) = (c, b);
+                        }
+                        let vertices = match self {
+                            Tiling::PenroseKites => vec![b, Complex::ZERO, c],
+                            _ => vec![Complex::ZERO, b, c],
+                        };
+                        Tile::new(0, vertices)
+                    })
+                    .collect()
+            },
+            Tiling::Pinwheel => vec![
+                Tile::new(0, points(&[(0.0, 0.0), (2.0, 0.0), (0.0, 1.0)])),
+                Tile::new(0, points(&[(2.0, 1.0), (0.0, 1.0), (2.0, 0.0)])),
+            ],
+            Tiling::Chair | Tiling::Sphinx => {
+                let prototiles = self.prototiles();
+                prototiles
+                    .first()
+                    .map(|prototile| vec![Tile::new(0, prototile.vertices.clone())])
+                    .unwrap_or_default()
+            },
+            Tiling::AmmannBeenker => {
+                // Star of eight rhombs
+                (0..8)
+                    .map(|index| {
+                        let a = Complex::from_polar(1.0, index as f64 * PI / 4.0);
+                        let b = Complex::from_polar(1.0, (index + 1) as f64 * PI / 4.0);
+                        Tile::new(0, vec![Complex::ZERO, a, a + b, b])
+                    })
+                    .collect()
+            },
+        }
+    }
+}
+
+// Polygon with the inflation rule. Children are given in coordinates of the vertices,
+// every child is similar to its prototile with the same order of the vertices.
+#[derive(Clone, Debug)]
+pub struct Prototile {
+    pub name: String,
+    pub default_color: Color32,
+
+    pub vertices: Vec<Complex>,
+    pub children: Vec<(usize, Vec<Complex>)>,
+
+    // Robinson triangles and halves of the squares are drawn in pairs,
+    // glued along the edge opposite to this vertex
+    pub glued_opposite: Option<usize>,
+}
+
+impl Prototile {
+    fn new(name: &str, default_color: Color32, vertices: Vec<Complex>) -> Self {
+        Self {
+            name: name.to_string(),
+            default_color,
+            vertices,
+            children: vec![],
+            glued_opposite: None,
+        }
+    }
+
+    fn with_child(mut self, prototile: usize, vertices: Vec<Complex>) -> Self {
+        self.children.push((prototile, vertices));
+        self
+    }
+
+    fn with_glued_opposite(mut self, vertex: usize) -> Self {
+        self.glued_opposite = Some(vertex);
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tile {
+    pub prototile: usize,
+    pub vertices: Vec<Complex>,
+}
+
+impl Tile {
+    pub fn new(prototile: usize, vertices: Vec<Complex>) -> Self {
+        Self {
+            prototile,
+            vertices,
+        }
+    }
+}
+
+fn points(coordinates: &[(f64, f64)]) -> Vec<Complex> {
+    coordinates
+        .iter()
+        .map(|(x, y)| Complex::new(*x, *y))
+        .collect()
+}
+
+fn lerp(a: Complex, b: Complex, t: f64) -> Complex {
+    a + (b - a).scale(t)
+}
+
+// Affine map, that takes the first three points of `from` to the first three of `to`
+fn affine_map(from: &[Complex], to: &[Complex], point: Complex) -> Complex {
+    let (e1, e2) = (from[1] - from[0], from[2] - from[0]);
+    let determinant = e1.re * e2.im - e1.im * e2.re;
+    let offset = point - from[0];
+    let x = (offset.re * e2.im - offset.im * e2.re) / determinant;
+    let y = (e1.re * offset.im - e1.im * offset.re) / determinant;
+
+    to[0] + (to[1] - to[0]).scale(x) + (to[2] - to[0]).scale(y)
+}
+
+// Second prototile is the mirror image of the first one. Children of the first one
+// with index 1 are mirrored.
+fn with_mirror(
+    prototile: Prototile, mirrored_name: &str, color: Color32,
+) -> Vec<Prototile> {
+    let mirror = Prototile {
+        name: mirrored_name.to_string(),
+        default_color: color,
+        vertices: prototile.vertices.iter().map(Complex::conj).collect(),
+        children: prototile
+            .children
+            .iter()
+            .map(|(index, vertices)| {
+                (1 - index, vertices.iter().map(Complex::conj).collect())
+            })
+            .collect(),
+        glued_opposite: prototile.glued_opposite,
+    };
+
+    vec![prototile, mirror]
+}
+
+// Robinson triangles with the apex B, halves of the kite and the dart are glued along BC
+fn penrose_kites() -> Vec<Prototile> {
+    let (a, b) = (Complex::ONE, Complex::ZERO);
+
+    let c = Complex::from_polar(1.0, PI / 5.0);
+    let q = lerp(a, b, 1.0 / GOLDEN_RATIO);
+    let r = lerp(b, c, 1.0 / GOLDEN_RATIO);
+    let kite = Prototile::new("Kite", Color32::from_rgb(230, 140, 60), vec![a, b, c])
+        .with_child(1, vec![r, q, b])
+        .with_child(0, vec![q, a, r])
+        .with_child(0, vec![c, a, r])
+        .with_glued_opposite(0);
+
+    let c = Complex::from_polar(1.0, 3.0 * PI / 5.0);
+    let p = lerp(c, a, 1.0 / GOLDEN_RATIO);
+    let dart = Prototile::new("Dart", Color32::from_rgb(60, 110, 180), vec![a, b, c])
+        .with_child(1, vec![b, p, a])
+        .with_child(0, vec![p, c, b])
+        .with_glued_opposite(0);
+
+    vec![kite, dart]
+}
+
+// Robinson triangles with the apex A, halves of the rhombs are glued along BC
+fn penrose_rhombs() -> Vec<Prototile> {
+    let (a, b) = (Complex::ZERO, Complex::ONE);
+
+    let c = Complex::from_polar(1.0, PI / 5.0);
+    let p = lerp(a, b, 1.0 / GOLDEN_RATIO);
+    let thin =
+        Prototile::new("Thin Rhomb", Color32::from_rgb(200, 70, 70), vec![a, b, c])
+            .with_child(0, vec![c, p, b])
+            .with_child(1, vec![p, c, a])
+            .with_glued_opposite(0);
+
+    let c = Complex::from_polar(1.0, 3.0 * PI / 5.0);
+    let q = lerp(b, a, 1.0 / GOLDEN_RATIO);
+    let r = lerp(b, c, 1.0 / GOLDEN_RATIO);
+    let thick = Prototile::new(
+        "Thick Rhomb",
+        Color32::from_rgb(70, 110, 200),
+        vec![a, b, c],
+    )
+    .with_child(1, vec![r, c, a])
+    .with_child(1, vec![q, r, b])
+    .with_child(0, vec![r, q, a])
+    .with_glued_opposite(0);
+
+    vec![thin, thick]
+}
+
+// Right triangle with the legs 2 and 1, the first vertex has the right angle.
+// It's split by the altitude, and the larger part is split into four triangles.
+fn pinwheel() -> Prototile {
+    let [right, long, short] = [(0.0, 0.0), (2.0, 0.0), (0.0, 1.0)];
+    let [foot, long_middle, hypotenuse_middle, short_middle] =
+        [(0.4, 0.8), (1.2, 0.4), (1.0, 0.0), (0.2, 0.4)];
+
+    Prototile::new(
+        "Right-Handed",
+        Color32::from_rgb(130, 200, 140),
+        points(&[right, long, short]),
+    )
+    .with_child(1, points(&[foot, right, short]))
+    .with_child(1, points(&[long_middle, long, hypotenuse_middle]))
+    .with_child(1, points(&[short_middle, hypotenuse_middle, right]))
+    .with_child(0, points(&[long_middle, foot, hypotenuse_middle]))
+    .with_child(0, points(&[short_middle, hypotenuse_middle, foot]))
+}
+
+// L-tromino in four orientations, every chair is made of four chairs
+fn chair() -> Vec<Prototile> {
+    let chair = points(&[
+        (0.0, 0.0),
+        (2.0, 0.0),
+        (2.0, 1.0),
+        (1.0, 1.0),
+        (1.0, 2.0),
+        (0.0, 2.0),
+    ]);
+    let children = [
+        (
+            0,
+            points(&[
+                (0.0, 0.0),
+                (1.0, 0.0),
+                (1.0, 0.5),
+                (0.5, 0.5),
+                (0.5, 1.0),
+                (0.0, 1.0),
+            ]),
+        ),
+        (
+            0,
+            points(&[
+                (0.5, 0.5),
+                (1.5, 0.5),
+                (1.5, 1.0),
+                (1.0, 1.0),
+                (1.0, 1.5),
+                (0.5, 1.5),
+            ]),
+        ),
+        (
+            1,
+            points(&[
+                (2.0, 0.0),
+                (2.0, 1.0),
+                (1.5, 1.0),
+                (1.5, 0.5),
+                (1.0, 0.5),
+                (1.0, 0.0),
+            ]),
+        ),
+        (
+            3,
+            points(&[
+                (0.0, 2.0),
+                (0.0, 1.0),
+                (0.5, 1.0),
+                (0.5, 1.5),
+                (1.0, 1.5),
+                (1.0, 2.0),
+            ]),
+        ),
+    ];
+    let colors = [
+        Color32::from_rgb(230, 180, 60),
+        Color32::from_rgb(200, 90, 70),
+        Color32::from_rgb(70, 140, 190),
+        Color32::from_rgb(110, 170, 90),
+    ];
+
+    (0..4)
+        .map(|orientation| {
+            let rotation = Complex::from_polar(1.0, orientation as f64 * PI / 2.0);
+            let rotate = |vertices: &[Complex]| {
+                vertices.iter().map(|point| *point * rotation).collect()
+            };
+
+            children.iter().fold(
+                Prototile::new(
+                    &format!("Chair ({}°)", orientation * 90),
+                    colors[orientation],
+                    rotate(&chair),
+                ),
+                |prototile, (index, vertices)| {
+                    prototile.with_child((index + orientation) % 4, rotate(vertices))
+                },
+            )
+        })
+        .collect()
+}
+
+// Hexiamond, that is made of four sphinxes, three of them are mirrored
+fn sphinx() -> Prototile {
+    let h = 3f64.sqrt() / 2.0;
+
+    Prototile::new(
+        "Sphinx",
+        Color32::from_rgb(220, 170, 90),
+        points(&[(0.0, 0.0), (3.0, 0.0), (2.5, h), (1.5, h), (1.0, 2.0 * h)]),
+    )
+    .with_child(
+        1,
+        points(&[
+            (1.5, 0.0),
+            (0.0, 0.0),
+            (0.25, h / 2.0),
+            (0.75, h / 2.0),
+            (1.0, h),
+        ]),
+    )
+    .with_child(
+        0,
+        points(&[
+            (1.0, 2.0 * h),
+            (0.25, h / 2.0),
+            (0.75, h / 2.0),
+            (1.0, h),
+            (1.5, h),
+        ]),
+    )
+    .with_child(
+        1,
+        points(&[
+            (1.0, h),
+            (2.5, h),
+            (2.25, h / 2.0),
+            (1.75, h / 2.0),
+            (1.5, 0.0),
+        ]),
+    )
+    .with_child(
+        1,
+        points(&[
+            (3.0, 0.0),
+            (1.5, 0.0),
+            (1.75, h / 2.0),
+            (2.25, h / 2.0),
+            (2.5, h),
+        ]),
+    )
+}
+
+// Rhomb with the angle of 45 degrees and the half of the square,
+// the second vertex of the triangle has the right angle
+fn ammann_beenker() -> Vec<Prototile> {
+    let (s, d) = (FRAC_1_SQRT_2, SILVER_RATIO);
+
+    let rhomb = Prototile::new(
+        "Rhomb",
+        Color32::from_rgb(90, 140, 200),
+        points(&[
+            (0.0, 0.0),
+            (d, 0.0),
+            (d + 1.0 + s, 1.0 + s),
+            (1.0 + s, 1.0 + s),
+        ]),
+    )
+    .with_child(0, points(&[(0.0, 0.0), (1.0, 0.0), (1.0 + s, s), (s, s)]))
+    .with_child(
+        0,
+        points(&[
+            (d + 1.0 + s, 1.0 + s),
+            (d + s, 1.0 + s),
+            (d, 1.0),
+            (d + 1.0, 1.0),
+        ]),
+    )
+    .with_child(
+        0,
+        points(&[(d, 0.0), (d, 1.0), (1.0 + s, 1.0 + s), (1.0 + s, s)]),
+    )
+    .with_child(1, points(&[(d, 0.0), (1.0 + s, s), (1.0, 0.0)]))
+    .with_child(1, points(&[(d, 0.0), (d, 1.0), (d + 1.0, 1.0)]))
+    .with_child(1, points(&[(1.0 + s, 1.0 + s), (d, 1.0), (d + s, 1.0 + s)]))
+    .with_child(1, points(&[(1.0 + s, 1.0 + s), (1.0 + s, s), (s, s)]));
+
+    let triangle = Prototile::new(
+        "Square",
+        Color32::from_rgb(240, 200, 80),
+        points(&[(0.0, 0.0), (d, 0.0), (d, d)]),
+    )
+    .with_child(0, points(&[(0.0, 0.0), (1.0, 0.0), (1.0 + s, s), (s, s)]))
+    .with_child(
+        0,
+        points(&[(d, 0.0), (d, 1.0), (1.0 + s, 1.0 + s), (1.0 + s, s)]),
+    )
+    .with_child(1, points(&[(d, 0.0), (1.0 + s, s), (1.0, 0.0)]))
+    .with_child(1, points(&[(d, d), (1.0 + s, 1.0 + s), (d, 1.0)]))
+    .with_child(1, points(&[(1.0 + s, 1.0 + s), (1.0 + s, s), (s, s)]))
+    .with_glued_opposite(1);
+
+    vec![rhomb, triangle]
+}
+
+// Tiles after the given number of inflations, the halves aren't glued yet
+pub fn tiles_count(tiling: Tiling, generations: u32) -> usize {
+    let prototiles = tiling.prototiles();
+    let mut counts = vec![0usize; prototiles.len()];
+    for tile in tiling.initial_patch() {
+        counts[tile.prototile] += 1;
+    }
+
+    for _ in 0..generations {
+        let mut next = vec![0usize; prototiles.len()];
+        for (prototile, count) in prototiles.iter().zip(&counts) {
+            for (index, _) in &prototile.children {
+                next[*index] = next[*index].saturating_add(*count);
+            }
+        }
+        counts = next;
+    }
+
+    counts
+        .iter()
+        .fold(0, |sum, count| sum.saturating_add(*count))
+}
+
+pub fn inflate(prototiles: &[Prototile], tiles: &[Tile]) -> Vec<Tile> {
+    let mut children = Vec::with_capacity(tiles.len() * 4);
+    for tile in tiles {
+        let prototile = &prototiles[tile.prototile];
+        for (index, vertices) in &prototile.children {
+            children.push(Tile::new(
+                *index,
+                vertices
+                    .iter()
+                    .map(|point| affine_map(&prototile.vertices, &tile.vertices, *point))
+                    .collect(),
+            ));
+        }
+    }
+    children
+}
+
+pub struct Model {
+    tiling: Tiling,
+    generations: u32,
+    size_cm: f32,
+    colors: Vec<Color32>,
+    stroke: Stroke,
+}
+
+impl Model {
+    // Tiles in centimeters, the halves are glued together
+    pub fn tiles(&self) -> Vec<Tile> {
+        let prototiles = self.tiling.prototiles();
+
+        let mut tiles = self.tiling.initial_patch();
+        normalize(&mut tiles, self.size_cm as f64);
+        for _ in 0..self.generations {
+            tiles = inflate(&prototiles, &tiles);
+        }
+
+        glue_halves(&prototiles, tiles)
+    }
+
+    pub fn polygons(&self) -> Vec<Polygon2D> {
+        self.tiles()
+            .iter()
+            .map(|tile| {
+                Polygon2D::new(
+                    tile.vertices
+                        .iter()
+                        .map(|point| Point2D::new(point.re as f32, point.im as f32))
+                        .collect(),
+                    self.colors
+                        .get(tile.prototile)
+                        .copied()
+                        .unwrap_or(Color32::GRAY),
+                    self.stroke,
+                )
+            })
+            .collect()
+    }
+}
+
+// Centered patch with the given diameter
+fn normalize(tiles: &mut [Tile], size: f64) {
+    let count = tiles.iter().map(|tile| tile.vertices.len()).sum::<usize>();
+    if count == 0 {
+        return;
+    }
+
+    let center = tiles
+        .iter()
+        .flat_map(|tile| tile.vertices.iter())
+        .fold(Complex::ZERO, |sum, point| sum + *point)
+        .scale(1.0 / count as f64);
+    let radius = tiles
+        .iter()
+        .flat_map(|tile| tile.vertices.iter())
+        .map(|point| (*point - center).norm())
+        .fold(0.0, f64::max);
+    if radius <= 0.0 {
+        return;
+    }
+
+    for point in tiles.iter_mut().flat_map(|tile| tile.vertices.iter_mut()) {
+        *point = (*point - center).scale(size / 2.0 / radius);
+    }
+}
+
+fn glue_halves(prototiles: &[Prototile], tiles: Vec<Tile>) -> Vec<Tile> {
+    // Vertices are compared with the precision, that is much finer than the tiles
+    let precision = 1e6
+        / tiles
+            .first()
+            .map(|tile| (tile.vertices[1] - tile.vertices[0]).norm())
+            .unwrap_or(1.0);
+    let key = |point: Complex| {
+        (
+            (point.re * precision).round() as i64,
+            (point.im * precision).round() as i64,
+        )
+    };
+
+    let mut glued = Vec::with_capacity(tiles.len());
+    let mut halves: HashMap<(usize, [(i64, i64); 2]), Tile> = HashMap::new();
+    for tile in tiles {
+        let Some(apex) = prototiles[tile.prototile].glued_opposite else {
+            glued.push(tile);
+            continue;
+        };
+
+        let (first, second) =
+            (tile.vertices[(apex + 1) % 3], tile.vertices[(apex + 2) % 3]);
+        let mut edge = [key(first), key(second)];
+        edge.sort();
+
+        match halves.remove(&(tile.prototile, edge)) {
+            Some(other) => {
+                let other_apex = other.vertices[apex];
+                glued.push(Tile::new(
+                    tile.prototile,
+                    vec![tile.vertices[apex], first, other_apex, second],
+                ));
+            },
+            None => {
+                halves.insert((tile.prototile, edge), tile);
+            },
+        }
+    }
+
+    // Halves on the border of the patch stay alone
+    glued.extend(halves.into_values());
+    glued
+}
+
+pub struct ModelBuilder {
+    tiling: Tiling,
+    generations: u32,
+    size_cm: f32,
+    colors: Vec<Color32>,
+    stroke: Stroke,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            tiling: Tiling::default(),
+            generations: DEFAULT_GENERATIONS,
+            size_cm: DEFAULT_SIZE_CM,
+            colors: Tiling::default().default_colors(),
+            stroke: Stroke::new(DEFAULT_STROKE_WIDTH, DEFAULT_STROKE_COLOR),
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_tiling(mut self, tiling: Tiling) -> Self {
+        self.tiling = tiling;
+        self
+    }
+
+    pub fn with_generations(mut self, generations: u32) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    pub fn with_size(mut self, size_cm: f32) -> Self {
+        self.size_cm = size_cm;
+        self
+    }
+
+    pub fn with_colors(mut self, colors: Vec<Color32>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn with_stroke(mut self, width: f32, color: Color32) -> Self {
+        self.stroke = Stroke::new(width, color);
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            tiling: self.tiling,
+            generations: self.generations,
+            size_cm: self.size_cm,
+            colors: self.colors,
+            stroke: self.stroke,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(vertices: &[Complex]) -> f64 {
+        let count = vertices.len();
+        (0..count)
+            .map(|index| {
+                let (a, b) = (vertices[index], vertices[(index + 1) % count]);
+                a.re * b.im - b.re * a.im
+            })
+            .sum::<f64>()
+            .abs()
+            / 2.0
+    }
+
+    fn are_similar(a: &[Complex], b: &[Complex]) -> bool {
+        let ratio = (b[1] - b[0]).norm() / (a[1] - a[0]).norm();
+        a.len() == b.len()
+            && (0..a.len()).all(|i| {
+                (0..a.len()).all(|j| {
+                    let (from, to) = ((a[j] - a[i]).norm(), (b[j] - b[i]).norm());
+                    (from * ratio - to).abs() < 1e-9
+                })
+            })
+    }
+
+    #[test]
+    fn children_fill_their_parents() {
+        for tiling in Tiling::iter() {
+            for prototile in tiling.prototiles() {
+                let children_area: f64 = prototile
+                    .children
+                    .iter()
+                    .map(|(_, vertices)| area(vertices))
+                    .sum();
+                assert!(
+                    (children_area - area(&prototile.vertices)).abs() < 1e-9,
+                    "{}: {}",
+                    tiling,
+                    prototile.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn children_are_similar_to_prototiles() {
+        for tiling in Tiling::iter() {
+            let prototiles = tiling.prototiles();
+            for prototile in &prototiles {
+                for (index, vertices) in &prototile.children {
+                    assert!(
+                        are_similar(&prototiles[*index].vertices, vertices),
+                        "{}: child of {}",
+                        tiling,
+                        prototile.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn robinson_triangles_are_glued_into_kites_and_darts() {
+        let model = ModelBuilder::default()
+            .with_tiling(Tiling::PenroseKites)
+            .with_generations(5)
+            .build();
+
+        let tiles = model.tiles();
+        let quadrilaterals = tiles.iter().filter(|tile| tile.vertices.len() == 4).count();
+
+        // Only the halves on the border of the patch are left
+        assert_eq!(tiles_count(Tiling::PenroseKites, 5), 1440);
+        assert!(quadrilaterals * 2 > tiles_count(Tiling::PenroseKites, 5) * 9 / 10);
+    }
+
+    #[test]
+    fn tiles_count_matches_inflation() {
+        for tiling in Tiling::iter() {
+            let prototiles = tiling.prototiles();
+            let mut tiles = tiling.initial_patch();
+
+            for generation in 0..4 {
+                assert_eq!(
+                    tiles.len(),
+                    tiles_count(tiling, generation),
+                    "{}: generation {}",
+                    tiling,
+                    generation
+                );
+                tiles = inflate(&prototiles, &tiles);
+            }
+        }
+    }
+}
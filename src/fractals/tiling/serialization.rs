@@ -0,0 +1,96 @@
+use crate::fractals::tiling::model;
+use crate::fractals::tiling::model::Tiling;
+use crate::fractals::tiling::state::TilingState;
+use crate::fractals::tiling::validation::ValidationError;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<TilingDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &TilingState) -> serde_json::Result<String> {
+    let dto = TilingDto {
+        tiling: state.tiling,
+        generations: state.generations,
+        size_cm: state.size_cm,
+        colors: state
+            .colors
+            .iter()
+            .map(|color| [color.r(), color.g(), color.b()])
+            .collect(),
+        stroke_width: state.stroke_width,
+        stroke_color: [
+            state.stroke_color.r(),
+            state.stroke_color.g(),
+            state.stroke_color.b(),
+        ],
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_generations() -> u32 {
+    model::DEFAULT_GENERATIONS
+}
+
+fn default_size() -> f32 {
+    model::DEFAULT_SIZE_CM
+}
+
+fn default_stroke_width() -> f32 {
+    model::DEFAULT_STROKE_WIDTH
+}
+
+fn default_stroke_color() -> [u8; 3] {
+    let color = model::DEFAULT_STROKE_COLOR;
+    [color.r(), color.g(), color.b()]
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TilingDto {
+    #[serde(rename = "Tiling", default)]
+    tiling: Tiling,
+
+    #[serde(rename = "Generations", default = "default_generations")]
+    generations: u32,
+
+    #[serde(rename = "Size", default = "default_size")]
+    size_cm: f32,
+
+    #[serde(rename = "Colors", default)]
+    colors: Vec<[u8; 3]>,
+
+    #[serde(rename = "Stroke Width", default = "default_stroke_width")]
+    stroke_width: f32,
+
+    #[serde(rename = "Stroke Color", default = "default_stroke_color")]
+    stroke_color: [u8; 3],
+}
+
+impl TilingDto {
+    pub fn load(self, state: &mut TilingState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.tiling = self.tiling;
+        state.generations = self.generations;
+        state.size_cm = self.size_cm;
+        state.colors = match self.colors.is_empty() {
+            true => self.tiling.default_colors(),
+            false => self
+                .colors
+                .into_iter()
+                .map(|[r, g, b]| Color32::from_rgb(r, g, b))
+                .collect(),
+        };
+        state.stroke_width = self.stroke_width;
+        let [r, g, b] = self.stroke_color;
+        state.stroke_color = Color32::from_rgb(r, g, b);
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,86 @@
+use crate::fractals::tiling::model::{ModelBuilder, Tiling};
+use crate::fractals::tiling::validation::ValidationError;
+use crate::fractals::tiling::{model, validation};
+use crate::geometry::polygon2d::Polygon2D;
+use crate::ui::components::canvas::CanvasParams;
+use egui::{Color32, Shape};
+
+pub struct TilingState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub tiling: Tiling,
+    pub generations: u32,
+    pub size_cm: f32,
+    pub colors: Vec<Color32>,
+    pub stroke_width: f32,
+    pub stroke_color: Color32,
+
+    polygons: Vec<Polygon2D>,
+}
+
+impl Default for TilingState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            tiling: Tiling::default(),
+            generations: model::DEFAULT_GENERATIONS,
+            size_cm: model::DEFAULT_SIZE_CM,
+            colors: Tiling::default().default_colors(),
+            stroke_width: model::DEFAULT_STROKE_WIDTH,
+            stroke_color: model::DEFAULT_STROKE_COLOR,
+
+            polygons: vec![],
+        }
+    }
+}
+
+impl TilingState {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Vec<Shape> {
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+            self.polygons = ModelBuilder::default()
+                .with_tiling(self.tiling)
+                .with_generations(self.generations)
+                .with_size(self.size_cm)
+                .with_colors(self.colors.clone())
+                .with_stroke(self.stroke_width, self.stroke_color)
+                .build()
+                .polygons();
+        }
+
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.to_screen(params).to_shape())
+            .collect()
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        validation::generations_range(self.tiling, self.generations)?;
+        validation::size_range(self.size_cm)?;
+        validation::colors_count(self.tiling, self.colors.len())?;
+        validation::stroke_width_range(self.stroke_width)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn tiles_count(&self) -> usize {
+        self.polygons.len()
+    }
+}
@@ -0,0 +1,238 @@
+use crate::context::Context;
+use crate::fractals::tiling::model::Tiling;
+use crate::fractals::tiling::serialization;
+use crate::fractals::tiling::state::TilingState;
+use crate::io;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+const MAX_GENERATIONS: u32 = 14;
+
+pub struct TilingSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for TilingSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl TilingSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.tiling_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Tiles: ");
+            ui.label(context.tiling_state.tiles_count().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.tiling_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Tiling:");
+            egui::ComboBox::from_id_salt("TilingTiling")
+                .selected_text(format!("{}", state.tiling))
+                .show_ui(ui, |ui| {
+                    for tiling in Tiling::iter() {
+                        if ui
+                            .selectable_value(
+                                &mut state.tiling,
+                                tiling,
+                                tiling.to_string(),
+                            )
+                            .changed()
+                        {
+                            state.colors = tiling.default_colors();
+                            changed = true;
+                        }
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Generations:");
+            changed |= ui
+                .add(Slider::new(&mut state.generations, 0..=MAX_GENERATIONS))
+                .on_hover_text(
+                    "Every generation inflates the patch and splits its tiles.",
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Size:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.size_cm)
+                        .speed(0.1)
+                        .range(0.1..=1000.0)
+                        .suffix(" cm"),
+                )
+                .on_hover_text("Diameter of the patch.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Stroke Width:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.stroke_width)
+                        .speed(0.1)
+                        .range(0.0..=10.0),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Stroke Color:");
+            changed |= egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut state.stroke_color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed();
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Prototiles", |ui| {
+            Grid::new("PrototilesGrid").num_columns(2).show(ui, |ui| {
+                let prototiles = state.tiling.prototiles();
+                for (prototile, color) in prototiles.iter().zip(state.colors.iter_mut()) {
+                    ui.label(format!("{}:", prototile.name));
+                    changed |= egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        color,
+                        egui::color_picker::Alpha::Opaque,
+                    )
+                    .changed();
+                    ui.end_row();
+                }
+            });
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.tiling_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.tiling_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.tiling_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.tiling_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.tiling_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.tiling_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.tiling_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Tiling\": \"Penrose P2\",
+                                \"Generations\": 6,
+                                \"Colors\": [[230, 140, 60], [60, 110, 180]]
+                            }
+
+                            Tiling: \"Penrose P2\", \"Penrose P3\", \"Pinwheel\", \"Chair\",
+                            \"Sphinx\" or \"Ammann-Beenker\".
+                            Colors: one color per prototile, in the order of the
+                            \"Prototiles\" section. Default colors are used, if omitted.
+
+                            Other fields: \"Size\" (cm), \"Stroke Width\", \"Stroke Color\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut TilingState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use crate::fractals::tiling::model;
+use crate::fractals::tiling::model::Tiling;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub fn generations_range(
+    tiling: Tiling, generations: u32,
+) -> Result<(), ValidationError> {
+    let count = model::tiles_count(tiling, generations);
+    if count > model::MAX_TILES {
+        return Err(ValidationError::TooManyTiles(format!(
+            "Tiles: {}, maximum: {}",
+            count,
+            model::MAX_TILES
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn size_range(size_cm: f32) -> Result<(), ValidationError> {
+    if !(size_cm > 0.0 && size_cm.is_finite()) {
+        return Err(ValidationError::BadSizeValue);
+    }
+
+    Ok(())
+}
+
+pub fn colors_count(tiling: Tiling, count: usize) -> Result<(), ValidationError> {
+    let prototiles = tiling.prototiles();
+    if count != prototiles.len() {
+        let names: Vec<String> = prototiles
+            .into_iter()
+            .map(|prototile| prototile.name)
+            .collect();
+        return Err(ValidationError::BadColorsCount(format!(
+            "Prototiles of the {} tiling: {}",
+            tiling,
+            names.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn stroke_width_range(width: f32) -> Result<(), ValidationError> {
+    if !(width >= 0.0 && width.is_finite()) {
+        return Err(ValidationError::BadStrokeWidth);
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The tiling has too many tiles, decrease the number of generations.")]
+    TooManyTiles(String),
+
+    #[error("The size has to be greater than 0.")]
+    BadSizeValue,
+
+    #[error("There has to be one color per prototile.")]
+    BadColorsCount(String),
+
+    #[error("The stroke width can't be negative.")]
+    BadStrokeWidth,
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::TooManyTiles(value) | Self::BadColorsCount(value) => {
+                Some(value.clone())
+            },
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::tiling::state::TilingState;
+
+    #[test]
+    fn tiles_count_is_reported() {
+        let mut state = TilingState::default();
+        state.tiling = Tiling::AmmannBeenker;
+        state.colors = Tiling::AmmannBeenker.default_colors();
+        state.generations = 10;
+
+        let result = state.initialize();
+
+        let count = model::tiles_count(Tiling::AmmannBeenker, 10);
+        assert!(count > model::MAX_TILES);
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some(format!("Tiles: {}, maximum: {}", count, model::MAX_TILES))
+        );
+    }
+
+    #[test]
+    fn tiles_count_saturates() {
+        for tiling in Tiling::iter() {
+            assert_eq!(model::tiles_count(tiling, 200), usize::MAX);
+            assert!(generations_range(tiling, 200).is_err());
+        }
+    }
+
+    #[test]
+    fn colors_of_other_tiling() {
+        // Colors are given per prototile, so only their number has to match
+        let mut state = TilingState::default();
+        state.tiling = Tiling::PenroseKites;
+        state.colors = Tiling::PenroseRhombs.default_colors();
+        assert!(state.initialize().is_ok());
+
+        state.tiling = Tiling::Chair;
+        let result = state.initialize();
+
+        assert!(matches!(result, Err(ValidationError::BadColorsCount(_))));
+    }
+
+    #[test]
+    fn prototile_names_are_listed() {
+        let result = colors_count(Tiling::PenroseKites, 1);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some(
+                "Prototiles of the Penrose P2 (Kites and Darts) tiling: Kite, Dart"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn zero_stroke_width_hides_outlines() {
+        assert!(stroke_width_range(0.0).is_ok());
+        assert!(stroke_width_range(-1.0).is_err());
+    }
+}
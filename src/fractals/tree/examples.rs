@@ -0,0 +1,69 @@
+use include_dir::{include_dir, Dir};
+use std::path::PathBuf;
+use strum_macros::Display;
+use thiserror::Error;
+
+static TREE_EXAMPLES_DIR: Dir<'_> = include_dir!("./assets/fractals/tree/");
+
+#[derive(Copy, Clone, Display)]
+pub enum Example {
+    #[strum(serialize = "Asymmetric Tree")]
+    AsymmetricTree,
+
+    #[strum(serialize = "Bush")]
+    Bush,
+
+    #[strum(serialize = "Pythagoras Tree")]
+    PythagorasTree,
+
+    #[strum(serialize = "Symmetric Tree")]
+    SymmetricTree,
+
+    #[strum(serialize = "Windswept Pythagoras Tree")]
+    WindsweptPythagorasTree,
+}
+
+impl Example {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Example::AsymmetricTree => PathBuf::from(r"Asymmetric-Tree.json"),
+            Example::Bush => PathBuf::from(r"Bush.json"),
+            Example::PythagorasTree => PathBuf::from(r"Pythagoras-Tree.json"),
+            Example::SymmetricTree => PathBuf::from(r"Symmetric-Tree.json"),
+            Example::WindsweptPythagorasTree => {
+                PathBuf::from(r"Windswept-Pythagoras-Tree.json")
+            },
+        }
+    }
+
+    pub fn contents(&self) -> Result<String, ExampleLoadingError> {
+        let file = TREE_EXAMPLES_DIR
+            .get_file(self.path())
+            .ok_or(ExampleLoadingError::FileNotFound)?;
+
+        Ok(file
+            .contents_utf8()
+            .ok_or(ExampleLoadingError::NonValidUtf8)?
+            .to_string())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Example::AsymmetricTree,
+            Example::Bush,
+            Example::PythagorasTree,
+            Example::SymmetricTree,
+            Example::WindsweptPythagorasTree,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ExampleLoadingError {
+    #[error("File not found.")]
+    FileNotFound,
+
+    #[error("Not valid UTF-8 (or file is empty)")]
+    NonValidUtf8,
+}
@@ -0,0 +1,394 @@
+use crate::geometry::line2d::Line2D;
+use crate::geometry::point2d::Point2D;
+use crate::geometry::polygon2d::Polygon2D;
+use crate::math::random;
+use crate::ui::styles::gradient::Gradient;
+use egui::{Color32, Stroke};
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub const DEFAULT_ANGLES: [f32; 2] = [25.0, -25.0];
+pub const DEFAULT_LENGTH_RATIO: f32 = 0.72;
+pub const DEFAULT_TRUNK_LENGTH_CM: f32 = 6.0;
+pub const DEFAULT_TRUNK_WIDTH: f32 = 8.0;
+pub const DEFAULT_TAPER: f32 = 0.7;
+pub const DEFAULT_DEPTH: u32 = 10;
+pub const DEFAULT_PYTHAGORAS_ANGLE: f32 = 45.0;
+pub const MAX_SEGMENTS: usize = 1_000_000;
+
+// Jitter of 1.0 turns the branch by up to this angle and changes its length by up to half
+const MAX_JITTER_ANGLE: f32 = 45.0;
+const MAX_JITTER_LENGTH: f32 = 0.5;
+
+#[derive(Copy, Clone, Debug, Display, Default, PartialEq, Serialize, Deserialize)]
+pub enum Kind {
+    // Every branch splits into the branches with the given angles
+    #[default]
+    #[strum(serialize = "Branching Tree")]
+    #[serde(rename = "Branching Tree")]
+    Branching,
+
+    // Two squares stand on the legs of a right triangle on top of every square
+    #[strum(serialize = "Pythagoras Tree")]
+    #[serde(rename = "Pythagoras Tree")]
+    Pythagoras,
+}
+
+impl Kind {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Kind::Branching, Kind::Pythagoras].into_iter()
+    }
+}
+
+// From the brown trunk to the green leaves
+pub fn default_gradient() -> Gradient {
+    Gradient::new(vec![
+        (0.0, Color32::from_rgb(90, 55, 25)),
+        (0.45, Color32::from_rgb(130, 90, 40)),
+        (0.75, Color32::from_rgb(70, 140, 40)),
+        (1.0, Color32::from_rgb(150, 210, 70)),
+    ])
+}
+
+// Segments (branches or squares) of the tree with the given number of children
+pub fn segments_count(children: usize, depth: u32) -> usize {
+    let mut count: usize = 0;
+    let mut level: usize = 1;
+    for _ in 0..=depth {
+        count = count.saturating_add(level);
+        level = level.saturating_mul(children);
+    }
+    count
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Branch {
+    start: (f32, f32),
+    // In radians, counterclockwise from the x axis
+    direction: f32,
+    length: f32,
+    depth: u32,
+}
+
+impl Branch {
+    fn end(&self) -> (f32, f32) {
+        (
+            self.start.0 + self.length * self.direction.cos(),
+            self.start.1 + self.length * self.direction.sin(),
+        )
+    }
+}
+
+// Base of the square goes from the first point to the second one, the square is on its left
+#[derive(Copy, Clone, Debug)]
+struct Square {
+    base: [(f32, f32); 2],
+    depth: u32,
+}
+
+impl Square {
+    fn corners(&self) -> [(f32, f32); 4] {
+        let [(x0, y0), (x1, y1)] = self.base;
+        let (nx, ny) = (y0 - y1, x1 - x0);
+        [(x0, y0), (x1, y1), (x1 + nx, y1 + ny), (x0 + nx, y0 + ny)]
+    }
+}
+
+pub struct Model {
+    angles: Vec<f32>,
+    length_ratio: f32,
+    trunk_length_cm: f32,
+    trunk_width: f32,
+    taper: f32,
+    asymmetry: f32,
+    jitter: f32,
+    seed: Option<u64>,
+    depth: u32,
+    pythagoras_angle: f32,
+    gradient: Gradient,
+}
+
+impl Model {
+    pub fn lines(&self) -> Vec<Line2D> {
+        let mut rng = random::rng(self.seed);
+        let mut lines = Vec::with_capacity(segments_count(self.angles.len(), self.depth));
+
+        let trunk = Branch {
+            start: (0.0, -self.trunk_length_cm),
+            direction: std::f32::consts::FRAC_PI_2,
+            length: self.trunk_length_cm,
+            depth: 0,
+        };
+
+        let mut stack = vec![trunk];
+        while let Some(branch) = stack.pop() {
+            let end = branch.end();
+            let width = self.trunk_width * self.taper.powi(branch.depth as i32);
+            lines.push(Line2D::new(
+                Point2D::new(branch.start.0, branch.start.1),
+                Point2D::new(end.0, end.1),
+                Stroke::new(width, self.color(branch.depth)),
+            ));
+
+            if branch.depth >= self.depth {
+                continue;
+            }
+
+            for (index, angle) in self.angles.iter().enumerate() {
+                let (angle_jitter, length_jitter) = self.jitter(&mut rng);
+                let length = branch.length
+                    * self.length_ratio
+                    * self.asymmetry_factor(index)
+                    * (1.0 + length_jitter);
+
+                stack.push(Branch {
+                    start: end,
+                    direction: branch.direction + (angle + angle_jitter).to_radians(),
+                    length,
+                    depth: branch.depth + 1,
+                });
+            }
+        }
+
+        lines
+    }
+
+    pub fn squares(&self) -> Vec<Polygon2D> {
+        let mut rng = random::rng(self.seed);
+        let mut squares = Vec::with_capacity(segments_count(2, self.depth));
+
+        let half = self.trunk_length_cm / 2.0;
+        let trunk = Square {
+            base: [
+                (-half, -self.trunk_length_cm),
+                (half, -self.trunk_length_cm),
+            ],
+            depth: 0,
+        };
+
+        let mut stack = vec![trunk];
+        while let Some(square) = stack.pop() {
+            let corners = square.corners();
+            squares.push(Polygon2D::new(
+                corners.iter().map(|(x, y)| Point2D::new(*x, *y)).collect(),
+                self.color(square.depth),
+                Stroke::NONE,
+            ));
+
+            if square.depth >= self.depth {
+                continue;
+            }
+
+            // Right angle of the triangle is at the apex, the left leg is rotated by alpha
+            let (angle_jitter, _) = self.jitter(&mut rng);
+            let alpha = (self.pythagoras_angle + angle_jitter)
+                .clamp(1.0, 89.0)
+                .to_radians();
+            let (left, right) = (corners[3], corners[2]);
+            let (dx, dy) = (right.0 - left.0, right.1 - left.1);
+            let (sin, cos) = alpha.sin_cos();
+            let apex = (
+                left.0 + cos * (dx * cos - dy * sin),
+                left.1 + cos * (dx * sin + dy * cos),
+            );
+
+            let depth = square.depth + 1;
+            stack.push(Square {
+                base: [left, apex],
+                depth,
+            });
+            stack.push(Square {
+                base: [apex, right],
+                depth,
+            });
+        }
+
+        squares
+    }
+
+    fn color(&self, depth: u32) -> Color32 {
+        let t = match self.depth {
+            0 => 0.0,
+            max_depth => depth as f32 / max_depth as f32,
+        };
+        self.gradient.sample(t)
+    }
+
+    // First branch gets (1 + asymmetry) of the length, the last one gets (1 - asymmetry)
+    fn asymmetry_factor(&self, index: usize) -> f32 {
+        match self.angles.len() {
+            0 | 1 => 1.0,
+            count => {
+                let position = 2.0 * index as f32 / (count - 1) as f32 - 1.0;
+                1.0 - self.asymmetry * position
+            },
+        }
+    }
+
+    // Random deviations of the angle (in degrees) and of the relative length
+    fn jitter(&self, rng: &mut StdRng) -> (f32, f32) {
+        if self.jitter <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        (
+            self.jitter * MAX_JITTER_ANGLE * rng.gen_range(-1.0..=1.0),
+            self.jitter * MAX_JITTER_LENGTH * rng.gen_range(-1.0..=1.0),
+        )
+    }
+}
+
+pub struct ModelBuilder {
+    angles: Vec<f32>,
+    length_ratio: f32,
+    trunk_length_cm: f32,
+    trunk_width: f32,
+    taper: f32,
+    asymmetry: f32,
+    jitter: f32,
+    seed: Option<u64>,
+    depth: u32,
+    pythagoras_angle: f32,
+    gradient: Gradient,
+}
+
+impl Default for ModelBuilder {
+    fn default() -> Self {
+        Self {
+            angles: DEFAULT_ANGLES.to_vec(),
+            length_ratio: DEFAULT_LENGTH_RATIO,
+            trunk_length_cm: DEFAULT_TRUNK_LENGTH_CM,
+            trunk_width: DEFAULT_TRUNK_WIDTH,
+            taper: DEFAULT_TAPER,
+            asymmetry: 0.0,
+            jitter: 0.0,
+            seed: None,
+            depth: DEFAULT_DEPTH,
+            pythagoras_angle: DEFAULT_PYTHAGORAS_ANGLE,
+            gradient: default_gradient(),
+        }
+    }
+}
+
+impl ModelBuilder {
+    pub fn with_angles(mut self, angles: Vec<f32>) -> Self {
+        self.angles = angles;
+        self
+    }
+
+    pub fn with_length_ratio(mut self, length_ratio: f32) -> Self {
+        self.length_ratio = length_ratio;
+        self
+    }
+
+    pub fn with_trunk(mut self, length_cm: f32, width: f32, taper: f32) -> Self {
+        self.trunk_length_cm = length_cm;
+        self.trunk_width = width;
+        self.taper = taper;
+        self
+    }
+
+    pub fn with_asymmetry(mut self, asymmetry: f32) -> Self {
+        self.asymmetry = asymmetry;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f32, seed: Option<u64>) -> Self {
+        self.jitter = jitter;
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn with_pythagoras_angle(mut self, angle: f32) -> Self {
+        self.pythagoras_angle = angle;
+        self
+    }
+
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn build(self) -> Model {
+        Model {
+            angles: self.angles,
+            length_ratio: self.length_ratio,
+            trunk_length_cm: self.trunk_length_cm,
+            trunk_width: self.trunk_width,
+            taper: self.taper,
+            asymmetry: self.asymmetry,
+            jitter: self.jitter,
+            seed: self.seed,
+            depth: self.depth,
+            pythagoras_angle: self.pythagoras_angle,
+            gradient: self.gradient,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn branching_tree_tapers_with_depth() {
+        let lines = ModelBuilder::default()
+            .with_angles(vec![30.0, 0.0, -30.0])
+            .with_depth(4)
+            .build()
+            .lines();
+
+        assert_eq!(lines.len(), segments_count(3, 4));
+        assert_eq!(lines.len(), 1 + 3 + 9 + 27 + 81);
+
+        // Trunk is the widest one, the thinnest are DEFAULT_TAPER^4 times thinner
+        let widths: Vec<f32> = lines.iter().map(|line| line.stroke.width).collect();
+        assert!((widths[0] - DEFAULT_TRUNK_WIDTH).abs() < 1e-6);
+        let min = widths.iter().cloned().fold(f32::MAX, f32::min);
+        assert!((min - DEFAULT_TRUNK_WIDTH * DEFAULT_TAPER.powi(4)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn same_seed_gives_same_tree() {
+        let tree = |seed| {
+            ModelBuilder::default()
+                .with_jitter(0.5, Some(seed))
+                .with_depth(6)
+                .build()
+                .lines()
+                .iter()
+                .map(|line| (line.end.x, line.end.y))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(tree(7), tree(7));
+        assert_ne!(tree(7), tree(8));
+    }
+
+    #[test]
+    fn pythagoras_children_fit_on_the_parent() {
+        let squares = ModelBuilder::default()
+            .with_pythagoras_angle(30.0)
+            .with_depth(1)
+            .build()
+            .squares();
+
+        // The legs of the right triangle are cos and sin of the angle times the side
+        let side = |polygon: &Polygon2D| {
+            let (a, b) = (&polygon.points[0], &polygon.points[1]);
+            ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt()
+        };
+        assert_eq!(squares.len(), 3);
+        let trunk = side(&squares[0]);
+        let sides = [side(&squares[1]), side(&squares[2])];
+        assert!((sides[0].powi(2) + sides[1].powi(2) - trunk.powi(2)).abs() < 1e-4);
+        assert!((sides[0].max(sides[1]) - trunk * 30f32.to_radians().cos()).abs() < 1e-4);
+    }
+}
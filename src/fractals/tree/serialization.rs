@@ -0,0 +1,124 @@
+use crate::fractals::tree::model;
+use crate::fractals::tree::model::Kind;
+use crate::fractals::tree::state::TreeState;
+use crate::fractals::tree::validation::ValidationError;
+use crate::ui::styles::gradient::GradientStopDto;
+use serde::{Deserialize, Serialize};
+
+pub fn deserialize(json: String) -> serde_json::Result<TreeDto> {
+    serde_json::from_str(&json)
+}
+
+pub fn serialize(state: &TreeState) -> serde_json::Result<String> {
+    let dto = TreeDto {
+        kind: state.kind,
+        angles: state.angles.clone(),
+        length_ratio: state.length_ratio,
+        trunk_length_cm: state.trunk_length_cm,
+        trunk_width: state.trunk_width,
+        taper: state.taper,
+        asymmetry: state.asymmetry,
+        jitter: state.jitter,
+        seed: state.seed,
+        depth: state.depth,
+        pythagoras_angle: state.pythagoras_angle,
+        gradient: (&state.gradient).into(),
+    };
+
+    serde_json::to_string_pretty(&dto)
+}
+
+fn default_angles() -> Vec<f32> {
+    model::DEFAULT_ANGLES.to_vec()
+}
+
+fn default_length_ratio() -> f32 {
+    model::DEFAULT_LENGTH_RATIO
+}
+
+fn default_trunk_length() -> f32 {
+    model::DEFAULT_TRUNK_LENGTH_CM
+}
+
+fn default_trunk_width() -> f32 {
+    model::DEFAULT_TRUNK_WIDTH
+}
+
+fn default_taper() -> f32 {
+    model::DEFAULT_TAPER
+}
+
+fn default_depth() -> u32 {
+    model::DEFAULT_DEPTH
+}
+
+fn default_pythagoras_angle() -> f32 {
+    model::DEFAULT_PYTHAGORAS_ANGLE
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TreeDto {
+    #[serde(rename = "Kind", default)]
+    kind: Kind,
+
+    #[serde(rename = "Branch Angles", default = "default_angles")]
+    angles: Vec<f32>,
+
+    #[serde(rename = "Length Ratio", default = "default_length_ratio")]
+    length_ratio: f32,
+
+    #[serde(rename = "Trunk Length", default = "default_trunk_length")]
+    trunk_length_cm: f32,
+
+    #[serde(rename = "Trunk Width", default = "default_trunk_width")]
+    trunk_width: f32,
+
+    #[serde(rename = "Taper", default = "default_taper")]
+    taper: f32,
+
+    #[serde(rename = "Asymmetry", default)]
+    asymmetry: f32,
+
+    #[serde(rename = "Jitter", default)]
+    jitter: f32,
+
+    #[serde(rename = "Seed", default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+
+    #[serde(rename = "Depth", default = "default_depth")]
+    depth: u32,
+
+    #[serde(rename = "Pythagoras Angle", default = "default_pythagoras_angle")]
+    pythagoras_angle: f32,
+
+    #[serde(rename = "Gradient", default)]
+    gradient: Vec<GradientStopDto>,
+}
+
+impl TreeDto {
+    pub fn load(self, state: &mut TreeState) -> Result<(), ValidationError> {
+        *state = Default::default();
+
+        state.kind = self.kind;
+        state.angles = self.angles;
+        state.length_ratio = self.length_ratio;
+        state.trunk_length_cm = self.trunk_length_cm;
+        state.trunk_width = self.trunk_width;
+        state.taper = self.taper;
+        state.asymmetry = self.asymmetry;
+        state.jitter = self.jitter;
+        state.seed = self.seed;
+        state.depth = self.depth;
+        state.pythagoras_angle = self.pythagoras_angle;
+        state.gradient = match self.gradient.is_empty() {
+            true => model::default_gradient(),
+            false => self.gradient.into(),
+        };
+
+        let result = state.initialize();
+        if result.is_err() {
+            *state = Default::default();
+        }
+        result
+    }
+}
@@ -0,0 +1,125 @@
+use crate::fractals::tree::model::{Kind, ModelBuilder};
+use crate::fractals::tree::validation::ValidationError;
+use crate::fractals::tree::{model, validation};
+use crate::geometry::line2d::Line2D;
+use crate::geometry::polygon2d::Polygon2D;
+use crate::ui::components::canvas::CanvasParams;
+use crate::ui::styles::gradient::Gradient;
+use egui::Shape;
+
+pub struct TreeState {
+    is_initialized: bool,
+    is_drawing_requested: bool,
+
+    pub kind: Kind,
+    pub angles: Vec<f32>,
+    pub length_ratio: f32,
+    pub trunk_length_cm: f32,
+    pub trunk_width: f32,
+    pub taper: f32,
+    pub asymmetry: f32,
+    pub jitter: f32,
+    pub seed: Option<u64>,
+    pub depth: u32,
+    pub pythagoras_angle: f32,
+    pub gradient: Gradient,
+
+    lines: Vec<Line2D>,
+    squares: Vec<Polygon2D>,
+}
+
+impl Default for TreeState {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            is_drawing_requested: false,
+
+            kind: Kind::default(),
+            angles: model::DEFAULT_ANGLES.to_vec(),
+            length_ratio: model::DEFAULT_LENGTH_RATIO,
+            trunk_length_cm: model::DEFAULT_TRUNK_LENGTH_CM,
+            trunk_width: model::DEFAULT_TRUNK_WIDTH,
+            taper: model::DEFAULT_TAPER,
+            asymmetry: 0.0,
+            jitter: 0.0,
+            seed: None,
+            depth: model::DEFAULT_DEPTH,
+            pythagoras_angle: model::DEFAULT_PYTHAGORAS_ANGLE,
+            gradient: model::default_gradient(),
+
+            lines: vec![],
+            squares: vec![],
+        }
+    }
+}
+
+impl TreeState {
+    pub fn shapes(&mut self, params: &CanvasParams) -> Vec<Shape> {
+        if self.is_drawing_requested {
+            self.is_drawing_requested = false;
+
+            let model = ModelBuilder::default()
+                .with_angles(self.angles.clone())
+                .with_length_ratio(self.length_ratio)
+                .with_trunk(self.trunk_length_cm, self.trunk_width, self.taper)
+                .with_asymmetry(self.asymmetry)
+                .with_jitter(self.jitter, self.seed)
+                .with_depth(self.depth)
+                .with_pythagoras_angle(self.pythagoras_angle)
+                .with_gradient(self.gradient.clone())
+                .build();
+
+            (self.lines, self.squares) = match self.kind {
+                Kind::Branching => (model.lines(), vec![]),
+                Kind::Pythagoras => (vec![], model.squares()),
+            };
+        }
+
+        self.lines
+            .iter()
+            .map(|line| line.to_screen(params).to_shape())
+            .chain(
+                self.squares
+                    .iter()
+                    .map(|square| square.to_screen(params).to_shape()),
+            )
+            .collect()
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    pub fn initialize(&mut self) -> Result<(), ValidationError> {
+        match self.kind {
+            Kind::Branching => {
+                validation::angles_are_valid(&self.angles)?;
+                validation::length_ratio_range(self.length_ratio)?;
+                validation::asymmetry_range(self.asymmetry)?;
+                validation::depth_range(self.depth, self.angles.len())?;
+            },
+            Kind::Pythagoras => {
+                validation::pythagoras_angle_range(self.pythagoras_angle)?;
+                validation::depth_range(self.depth, 2)?;
+            },
+        }
+        validation::trunk_is_valid(self.trunk_length_cm, self.trunk_width, self.taper)?;
+        validation::jitter_range(self.jitter)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    pub fn reset_initialization(&mut self) {
+        self.is_initialized = false;
+    }
+
+    pub fn request_drawing(&mut self) {
+        self.is_drawing_requested = true;
+    }
+
+    pub fn segments_count(&self) -> usize {
+        self.lines.len() + self.squares.len()
+    }
+}
@@ -0,0 +1,332 @@
+use crate::context::Context;
+use crate::fractals::tree::examples::Example;
+use crate::fractals::tree::model::Kind;
+use crate::fractals::tree::serialization;
+use crate::fractals::tree::state::TreeState;
+use crate::fractals::tree::validation::{MAX_BRANCHES, MAX_DEPTH};
+use crate::io;
+use crate::math::random;
+use crate::ui::styles::colors;
+use crate::ui::windows::message::MessageWindow;
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use egui::{Button, DragValue, Grid, RichText, Slider, Ui};
+use indoc::indoc;
+
+pub struct TreeSettingsBlock {
+    json_sender: Sender<String>,
+    json_receiver: Receiver<String>,
+}
+
+impl Default for TreeSettingsBlock {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded::<String>();
+        Self {
+            json_sender: sender,
+            json_receiver: receiver,
+        }
+    }
+}
+
+impl TreeSettingsBlock {
+    pub fn show(&mut self, ui: &mut Ui, context: &mut Context) {
+        Grid::new("StatusGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Status: ");
+            if context.tree_state.is_initialized() {
+                ui.label(RichText::new("Initialized!").color(colors::LIME));
+            } else {
+                ui.label(RichText::new("Not initialized.").color(colors::RED));
+            }
+            ui.end_row();
+
+            ui.label("Segments: ");
+            ui.label(context.tree_state.segments_count().to_string());
+            ui.end_row();
+        });
+
+        ui.add_space(10.0);
+
+        let state = &mut context.tree_state;
+        let mut changed = false;
+
+        Grid::new("SettingsGrid").num_columns(2).show(ui, |ui| {
+            ui.label("Kind:");
+            egui::ComboBox::from_id_salt("TreeKind")
+                .selected_text(format!("{}", state.kind))
+                .show_ui(ui, |ui| {
+                    for kind in Kind::iter() {
+                        changed |= ui
+                            .selectable_value(&mut state.kind, kind, kind.to_string())
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            match state.kind {
+                Kind::Branching => {
+                    for (index, angle) in state.angles.iter_mut().enumerate() {
+                        ui.label(format!("Branch {} Angle:", index + 1));
+                        changed |= ui
+                            .add(
+                                DragValue::new(angle)
+                                    .speed(0.5)
+                                    .range(-180.0..=180.0)
+                                    .suffix("°"),
+                            )
+                            .on_hover_text("Counterclockwise from the parent branch.")
+                            .changed();
+                        ui.end_row();
+                    }
+
+                    ui.label("");
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                state.angles.len() < MAX_BRANCHES,
+                                Button::new("Add Branch"),
+                            )
+                            .clicked()
+                        {
+                            state.angles.push(0.0);
+                            changed = true;
+                        }
+                        if ui
+                            .add_enabled(state.angles.len() > 1, Button::new("Remove"))
+                            .clicked()
+                        {
+                            state.angles.pop();
+                            changed = true;
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Length Ratio:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.length_ratio, 0.1..=0.95))
+                        .on_hover_text("Length of the child relative to its parent.")
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Asymmetry:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.asymmetry, 0.0..=0.9))
+                        .on_hover_text(
+                            "The first branch grows longer, the last one grows shorter.",
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Trunk Width:");
+                    changed |= ui
+                        .add(
+                            DragValue::new(&mut state.trunk_width)
+                                .speed(0.1)
+                                .range(0.1..=50.0),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Taper:");
+                    changed |= ui
+                        .add(Slider::new(&mut state.taper, 0.1..=1.0))
+                        .on_hover_text("Width of the child relative to its parent.")
+                        .changed();
+                    ui.end_row();
+                },
+                Kind::Pythagoras => {
+                    ui.label("Angle:");
+                    changed |= ui
+                        .add(
+                            Slider::new(&mut state.pythagoras_angle, 1.0..=89.0)
+                                .suffix("°"),
+                        )
+                        .on_hover_text(
+                            "Angle between the base and the left leg of the triangle.",
+                        )
+                        .changed();
+                    ui.end_row();
+                },
+            }
+
+            ui.label("Trunk Length:");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut state.trunk_length_cm)
+                        .speed(0.1)
+                        .range(0.1..=100.0)
+                        .suffix(" cm"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Depth:");
+            changed |= ui
+                .add(Slider::new(&mut state.depth, 0..=MAX_DEPTH))
+                .changed();
+            ui.end_row();
+
+            ui.label("Jitter:");
+            changed |= ui
+                .add(Slider::new(&mut state.jitter, 0.0..=1.0))
+                .on_hover_text("Random deviations of the angles and the lengths.")
+                .changed();
+            ui.end_row();
+
+            ui.label("Seed: ");
+            ui.horizontal(|ui| {
+                let mut is_seeded = state.seed.is_some();
+                if ui
+                    .checkbox(&mut is_seeded, "")
+                    .on_hover_text("With a fixed seed, every tree is identical.")
+                    .changed()
+                {
+                    state.seed = is_seeded.then_some(random::DEFAULT_SEED);
+                    changed = true;
+                }
+                if let Some(seed) = &mut state.seed {
+                    changed |= ui.add(DragValue::new(seed).speed(1)).changed();
+                }
+            });
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.collapsing("Gradient", |ui| {
+            changed |= state.gradient.show_editor(ui, "Tree");
+        });
+
+        if changed {
+            state.reset_initialization();
+        }
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(
+                    !context.tree_state.is_initialized(),
+                    Button::new("Initialize"),
+                )
+                .clicked()
+            {
+                if let Err(err) = context.tree_state.initialize() {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                }
+            }
+        });
+
+        ui.add_space(2.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add_enabled(context.tree_state.is_initialized(), Button::new("Draw"))
+                .clicked()
+            {
+                context.tree_state.request_drawing();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Settings").clicked() {
+                context.tree_state = Default::default();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Deserializing from Json, if needed
+        if let Ok(json) = self.json_receiver.try_recv() {
+            let result = self.deserialize_state(&mut context.tree_state, json);
+            if let Err(err) = result {
+                let _ = context
+                    .windows_sender
+                    .send(Box::new(MessageWindow::error(&err)));
+            }
+        }
+
+        ui.collapsing("Load from Example", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                for example in Example::iter() {
+                    if ui.button(example.to_string()).clicked() {
+                        let json = match example.contents() {
+                            Ok(json) => json,
+                            Err(err) => {
+                                let message = format!("File Error: {}", err);
+                                let _ = context
+                                    .windows_sender
+                                    .send(Box::new(MessageWindow::error(&message)));
+                                return;
+                            },
+                        };
+
+                        let _ = self.json_sender.send(json);
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.collapsing("File Settings", |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Load from File").clicked() {
+                    io::json::load_with_file_pick(
+                        &self.json_sender,
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Save to File").clicked() {
+                    io::json::save_with_file_pick(
+                        serialization::serialize(&context.tree_state),
+                        &context.windows_sender,
+                    );
+                }
+                if ui.button("Help").clicked() {
+                    let message = indoc! {"
+                            File format: JSON.
+
+                            Example:
+
+                            {
+                                \"Kind\": \"Branching Tree\",
+                                \"Branch Angles\": [35.0, -20.0],
+                                \"Length Ratio\": 0.75,
+                                \"Asymmetry\": 0.2,
+                                \"Depth\": 12
+                            }
+
+                            Kind: \"Branching Tree\" or \"Pythagoras Tree\".
+                            Branch Angles: degrees, counterclockwise from the parent branch.
+                            Trunk Width: in pixels, every level is \"Taper\" times thinner.
+                            Pythagoras Angle: angle of the triangle on top of every square.
+
+                            Other fields: \"Trunk Length\" (cm), \"Jitter\", \"Seed\",
+                            \"Gradient\".
+                        "};
+                    let _ = context
+                        .windows_sender
+                        .send(Box::new(MessageWindow::help(message)));
+                }
+            });
+        });
+    }
+
+    fn deserialize_state(
+        &mut self, state: &mut TreeState, json: String,
+    ) -> Result<(), String> {
+        let dto = match serialization::deserialize(json) {
+            Ok(value) => value,
+            Err(err) => {
+                return Err(format!("JSON Error: {}", err));
+            },
+        };
+
+        if let Err(err) = dto.load(state) {
+            return Err(err.to_string());
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use crate::fractals::tree::model;
+use crate::ui::windows::message::MessageWindow;
+use thiserror::Error;
+
+pub const MAX_BRANCHES: usize = 8;
+pub const MAX_DEPTH: u32 = 24;
+
+pub fn angles_are_valid(angles: &[f32]) -> Result<(), ValidationError> {
+    if angles.is_empty() || angles.len() > MAX_BRANCHES {
+        return Err(ValidationError::BadBranchesCount);
+    }
+    if let Some(angle) = angles
+        .iter()
+        .find(|angle| !(-180.0..=180.0).contains(*angle))
+    {
+        return Err(ValidationError::BadAngleValue(format!("Angle: {}", angle)));
+    }
+
+    Ok(())
+}
+
+pub fn length_ratio_range(ratio: f32) -> Result<(), ValidationError> {
+    if !(ratio > 0.0 && ratio < 1.0) {
+        return Err(ValidationError::BadLengthRatio);
+    }
+
+    Ok(())
+}
+
+pub fn trunk_is_valid(
+    length_cm: f32, width: f32, taper: f32,
+) -> Result<(), ValidationError> {
+    if !(length_cm > 0.0 && length_cm.is_finite()) {
+        return Err(ValidationError::BadTrunkLength);
+    }
+    if !(width > 0.0 && width.is_finite()) {
+        return Err(ValidationError::BadTrunkWidth);
+    }
+    if !(taper > 0.0 && taper <= 1.0) {
+        return Err(ValidationError::BadTaperValue);
+    }
+
+    Ok(())
+}
+
+pub fn asymmetry_range(asymmetry: f32) -> Result<(), ValidationError> {
+    if !(0.0..1.0).contains(&asymmetry) {
+        return Err(ValidationError::BadAsymmetryValue);
+    }
+
+    Ok(())
+}
+
+pub fn jitter_range(jitter: f32) -> Result<(), ValidationError> {
+    if !(0.0..=1.0).contains(&jitter) {
+        return Err(ValidationError::BadJitterValue);
+    }
+
+    Ok(())
+}
+
+pub fn pythagoras_angle_range(angle: f32) -> Result<(), ValidationError> {
+    if !(angle > 0.0 && angle < 90.0) {
+        return Err(ValidationError::BadPythagorasAngle);
+    }
+
+    Ok(())
+}
+
+pub fn depth_range(depth: u32, children: usize) -> Result<(), ValidationError> {
+    if depth > MAX_DEPTH {
+        return Err(ValidationError::BadDepthValue);
+    }
+
+    let count = model::segments_count(children, depth);
+    if count > model::MAX_SEGMENTS {
+        return Err(ValidationError::TooManySegments(format!(
+            "Segments: {}, maximum: {}",
+            count,
+            model::MAX_SEGMENTS
+        )));
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ValidationError {
+    #[error("The number of branches has to be in range 1..8.")]
+    BadBranchesCount,
+
+    #[error("Branch angles have to be in range -180..180 degrees.")]
+    BadAngleValue(String),
+
+    #[error("The length ratio has to be in range 0..1 (both are exclusive).")]
+    BadLengthRatio,
+
+    #[error("The trunk length has to be greater than 0.")]
+    BadTrunkLength,
+
+    #[error("The trunk width has to be greater than 0.")]
+    BadTrunkWidth,
+
+    #[error("The taper has to be in range 0..1 (0 is exclusive).")]
+    BadTaperValue,
+
+    #[error("The asymmetry has to be in range 0..1 (1 is exclusive).")]
+    BadAsymmetryValue,
+
+    #[error("The jitter has to be in range 0..1.")]
+    BadJitterValue,
+
+    #[error(
+        "The angle of the Pythagoras tree has to be in range 0..90 (both are exclusive)."
+    )]
+    BadPythagorasAngle,
+
+    #[error("The depth has to be in range 0..24.")]
+    BadDepthValue,
+
+    #[error(
+        "The tree has too many segments, decrease the depth or the number of branches."
+    )]
+    TooManySegments(String),
+}
+
+impl ValidationError {
+    pub fn additional_info(&self) -> Option<String> {
+        match self {
+            Self::BadAngleValue(value) | Self::TooManySegments(value) => {
+                Some(value.clone())
+            },
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> MessageWindow {
+        let mut message = format!("Validation Error: {}", self);
+        if let Some(additional_info) = self.additional_info() {
+            message += &format!("\n\nAdditional Info:\n{}", additional_info);
+        }
+
+        MessageWindow::error(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::tree::examples::Example;
+    use crate::fractals::tree::model::Kind;
+    use crate::fractals::tree::serialization;
+    use crate::fractals::tree::state::TreeState;
+
+    #[test]
+    fn segments_count_is_reported() {
+        // Binary tree of depth 19 has 2^20 - 1 segments
+        assert!(depth_range(18, 2).is_ok());
+
+        let result = depth_range(19, 2);
+
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Segments: 1048575, maximum: 1000000".to_string())
+        );
+    }
+
+    #[test]
+    fn single_branch_reaches_max_depth() {
+        assert!(depth_range(MAX_DEPTH, 1).is_ok());
+        assert!(matches!(
+            depth_range(MAX_DEPTH + 1, 1),
+            Err(ValidationError::BadDepthValue)
+        ));
+    }
+
+    #[test]
+    fn segments_count_saturates() {
+        // 8^24 doesn't fit into 64 bits
+        assert_eq!(model::segments_count(MAX_BRANCHES, MAX_DEPTH), usize::MAX);
+        assert!(matches!(
+            depth_range(MAX_DEPTH, MAX_BRANCHES),
+            Err(ValidationError::TooManySegments(_))
+        ));
+    }
+
+    #[test]
+    fn pythagoras_ignores_branch_angles() {
+        let mut state = TreeState::default();
+        state.kind = Kind::Pythagoras;
+        state.angles = vec![];
+        assert!(state.initialize().is_ok());
+
+        // Pythagoras tree always has two children, whatever the angles are
+        state.angles = vec![0.0];
+        state.depth = 19;
+        assert!(matches!(
+            state.initialize(),
+            Err(ValidationError::TooManySegments(_))
+        ));
+    }
+
+    #[test]
+    fn angles_bounds_are_inclusive() {
+        assert!(angles_are_valid(&[-180.0, 180.0]).is_ok());
+        assert!(matches!(
+            angles_are_valid(&[]),
+            Err(ValidationError::BadBranchesCount)
+        ));
+        assert!(matches!(
+            angles_are_valid(&[0.0; MAX_BRANCHES + 1]),
+            Err(ValidationError::BadBranchesCount)
+        ));
+
+        let result = angles_are_valid(&[10.0, 180.5]);
+        assert_eq!(
+            result.err().and_then(|error| error.additional_info()),
+            Some("Angle: 180.5".to_string())
+        );
+        assert!(angles_are_valid(&[f32::NAN]).is_err());
+    }
+
+    #[test]
+    fn open_and_closed_unit_ranges() {
+        assert!(asymmetry_range(0.0).is_ok());
+        assert!(asymmetry_range(1.0).is_err());
+        assert!(jitter_range(1.0).is_ok());
+        assert!(jitter_range(-0.1).is_err());
+        assert!(trunk_is_valid(1.0, 1.0, 1.0).is_ok());
+        assert!(matches!(
+            trunk_is_valid(1.0, 1.0, 0.0),
+            Err(ValidationError::BadTaperValue)
+        ));
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for example in Example::iter() {
+            let mut state = TreeState::default();
+
+            let result = example
+                .contents()
+                .map_err(|err| err.to_string())
+                .and_then(|json| {
+                    serialization::deserialize(json).map_err(|err| err.to_string())
+                })
+                .and_then(|dto| dto.load(&mut state).map_err(|err| err.to_string()));
+
+            assert!(result.is_ok(), "{}: {:?}", example, result.err());
+        }
+    }
+}
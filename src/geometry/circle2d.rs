@@ -0,0 +1,44 @@
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use egui::epaint::CircleShape;
+use egui::{Color32, Shape, Stroke};
+
+#[derive(Debug, Clone)]
+pub struct Circle2D {
+    pub center: Point2D,
+    pub radius: f32,
+
+    pub fill: Color32,
+    pub stroke: Stroke,
+}
+
+impl Circle2D {
+    pub fn new(center: Point2D, radius: f32, fill: Color32, stroke: Stroke) -> Self {
+        Self {
+            center,
+            radius,
+            fill,
+            stroke,
+        }
+    }
+
+    pub fn to_shape(&self) -> Shape {
+        debug_assert!(self.center.converted_to_screen);
+
+        Shape::Circle(CircleShape {
+            center: self.center.to_pos2(),
+            radius: self.radius,
+            fill: self.fill,
+            stroke: self.stroke,
+        })
+    }
+
+    pub fn to_screen(&self, canvas_params: &CanvasParams) -> Self {
+        Self {
+            center: self.center.to_screen(canvas_params),
+            radius: canvas_params.value_cm_to_px(self.radius),
+            fill: self.fill,
+            stroke: self.stroke,
+        }
+    }
+}
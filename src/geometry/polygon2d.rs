@@ -0,0 +1,240 @@
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use egui::epaint::Mesh;
+use egui::{Color32, Pos2, Shape, Stroke};
+
+// Simple polygon, either convex or not
+#[derive(Debug, Clone)]
+pub struct Polygon2D {
+    pub points: Vec<Point2D>,
+
+    pub fill: Color32,
+    pub stroke: Stroke,
+}
+
+impl Polygon2D {
+    pub fn new(points: Vec<Point2D>, fill: Color32, stroke: Stroke) -> Self {
+        Self {
+            points,
+            fill,
+            stroke,
+        }
+    }
+
+    pub fn to_shape(&self) -> Shape {
+        let mut points: Vec<Pos2> =
+            self.points.iter().map(|point| point.to_pos2()).collect();
+
+        // Egui expects clockwise order on the screen
+        if signed_area(&points) < 0.0 {
+            points.reverse();
+        }
+
+        if is_convex(&points) {
+            return Shape::convex_polygon(points, self.fill, self.stroke);
+        }
+
+        let mut mesh = Mesh::default();
+        for point in &points {
+            mesh.colored_vertex(*point, self.fill);
+        }
+        for [a, b, c] in triangulate(&points) {
+            mesh.add_triangle(a, b, c);
+        }
+
+        Shape::Vec(vec![
+            Shape::mesh(mesh),
+            Shape::closed_line(points, self.stroke),
+        ])
+    }
+
+    pub fn to_screen(&self, canvas_params: &CanvasParams) -> Self {
+        Self {
+            points: self
+                .points
+                .iter()
+                .map(|point| point.to_screen(canvas_params))
+                .collect(),
+            fill: self.fill,
+            stroke: self.stroke,
+        }
+    }
+}
+
+// Positive for the clockwise order on the screen, where y goes down
+fn signed_area(points: &[Pos2]) -> f32 {
+    let count = points.len();
+    (0..count)
+        .map(|index| {
+            let (a, b) = (points[index], points[(index + 1) % count]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+fn cross(a: Pos2, b: Pos2, c: Pos2) -> f32 {
+    (b - a).x * (c - a).y - (b - a).y * (c - a).x
+}
+
+fn is_convex(points: &[Pos2]) -> bool {
+    let count = points.len();
+    (0..count).all(|index| {
+        cross(
+            points[index],
+            points[(index + 1) % count],
+            points[(index + 2) % count],
+        ) >= 0.0
+    })
+}
+
+// Ear clipping, the points go clockwise on the screen
+fn triangulate(points: &[Pos2]) -> Vec<[u32; 3]> {
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+
+    while indices.len() > 3 {
+        let count = indices.len();
+        let ear = (0..count).find(|&index| {
+            let (a, b, c) = (
+                points[indices[(index + count - 1) % count]],
+                points[indices[index]],
+                points[indices[(index + 1) % count]],
+            );
+            cross(a, b, c) > 0.0
+                && indices.iter().all(|&other| {
+                    let point = points[other];
+                    point == a
+                        || point == b
+                        || point == c
+                        || !(cross(a, b, point) >= 0.0
+                            && cross(b, c, point) >= 0.0
+                            && cross(c, a, point) >= 0.0)
+                })
+        });
+
+        // Degenerate polygon, the rest is dropped
+        let Some(ear) = ear else {
+            return triangles;
+        };
+
+        triangles.push([
+            indices[(ear + count - 1) % count] as u32,
+            indices[ear] as u32,
+            indices[(ear + 1) % count] as u32,
+        ]);
+        indices.remove(ear);
+    }
+
+    if let [a, b, c] = indices[..] {
+        triangles.push([a as u32, b as u32, c as u32]);
+    }
+
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const H: f32 = 0.866_025_4;
+
+    fn polygon(points: &[(f32, f32)]) -> Vec<Pos2> {
+        let mut points: Vec<Pos2> =
+            points.iter().map(|(x, y)| Pos2::new(*x, *y)).collect();
+        // Same order, as the shape gets
+        if signed_area(&points) < 0.0 {
+            points.reverse();
+        }
+        points
+    }
+
+    fn contains(points: &[Pos2], point: Pos2) -> bool {
+        let count = points.len();
+        (0..count)
+            .filter(|index| {
+                let (a, b) = (points[*index], points[(index + 1) % count]);
+                (a.y > point.y) != (b.y > point.y)
+                    && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+            })
+            .count()
+            % 2
+            == 1
+    }
+
+    // Triangles cover the polygon: they fill its area, and every one is inside it
+    fn assert_triangulated(points: &[(f32, f32)], area: f32) {
+        for points in [points.to_vec(), points.iter().rev().copied().collect()] {
+            let points = polygon(&points);
+            let triangles = triangulate(&points);
+
+            assert_eq!(triangles.len(), points.len() - 2);
+            assert!((signed_area(&points) - area).abs() < 1e-4);
+
+            let mut total = 0.0;
+            for [a, b, c] in triangles {
+                let (a, b, c) =
+                    (points[a as usize], points[b as usize], points[c as usize]);
+                let triangle_area = cross(a, b, c) / 2.0;
+                assert!(triangle_area > 0.0);
+                total += triangle_area;
+
+                let centroid =
+                    Pos2::new((a.x + b.x + c.x) / 3.0, (a.y + b.y + c.y) / 3.0);
+                assert!(contains(&points, centroid), "{:?}", [a, b, c]);
+            }
+            assert!((total - area).abs() < 1e-4, "{} != {}", total, area);
+        }
+    }
+
+    #[test]
+    fn dart() {
+        let dart = [(0.0, 0.0), (4.0, 2.0), (0.0, 4.0), (1.0, 2.0)];
+
+        assert!(!is_convex(&polygon(&dart)));
+        assert_triangulated(&dart, 6.0);
+    }
+
+    #[test]
+    fn chair() {
+        let chair = [
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 2.0),
+            (0.0, 2.0),
+        ];
+
+        assert!(!is_convex(&polygon(&chair)));
+        assert_triangulated(&chair, 3.0);
+    }
+
+    #[test]
+    fn sphinx() {
+        // Six unit triangles, the reflex vertex is at the neck
+        let sphinx = [
+            (0.0, 0.0),
+            (3.0, 0.0),
+            (2.5, H),
+            (1.5, H),
+            (1.0, 2.0 * H),
+            (0.5, H),
+        ];
+
+        assert!(!is_convex(&polygon(&sphinx)));
+        assert_triangulated(&sphinx, 3.0 * H);
+    }
+
+    #[test]
+    fn convex_polygons() {
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+
+        assert!(is_convex(&polygon(&square)));
+        assert_triangulated(&square, 1.0);
+        assert_eq!(
+            signed_area(&polygon(&square)),
+            -signed_area(&polygon(&square).into_iter().rev().collect::<Vec<_>>())
+        );
+    }
+}
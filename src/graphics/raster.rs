@@ -0,0 +1,194 @@
+use crate::geometry::point2d::Point2D;
+use crate::ui::components::canvas::CanvasParams;
+use egui::{pos2, Color32, ColorImage, Rect, Shape, TextureHandle, TextureOptions};
+
+// Pixel image that covers the whole canvas, uploaded to GPU as a texture
+pub struct Raster {
+    name: String,
+
+    width: usize,
+    height: usize,
+    pixels: Vec<Color32>,
+
+    texture: Option<TextureHandle>,
+    is_texture_outdated: bool,
+
+    rendered_params: Option<CanvasParams>,
+}
+
+impl Raster {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+
+            width: 0,
+            height: 0,
+            pixels: vec![],
+
+            texture: None,
+            is_texture_outdated: false,
+
+            rendered_params: None,
+        }
+    }
+
+    pub fn set_pixels(&mut self, width: usize, height: usize, pixels: Vec<Color32>) {
+        debug_assert!(width * height == pixels.len());
+
+        self.width = width;
+        self.height = height;
+        self.pixels = pixels;
+        self.is_texture_outdated = true;
+    }
+
+    // Computes color of every canvas pixel from its coordinates in centimeters
+    pub fn render<F>(&mut self, params: &CanvasParams, color: F)
+    where
+        F: Fn(Point2D) -> Color32 + Sync,
+    {
+        let rect = params.screen_rect();
+        let (width, height) = (
+            rect.width().max(0.0) as usize,
+            rect.height().max(0.0) as usize,
+        );
+
+        let pixels = render_rows(width, height, |x, y| {
+            let screen =
+                Point2D::new(rect.min.x + x as f32 + 0.5, rect.min.y + y as f32 + 0.5);
+            color(params.point_px_to_cm(screen))
+        });
+
+        self.set_pixels(width, height, pixels);
+    }
+
+    // Renders on request, and again on every pan or zoom after the first rendering
+    pub fn render_on_change<F>(
+        &mut self, params: &CanvasParams, is_requested: bool, color: F,
+    ) where
+        F: Fn(Point2D) -> Color32 + Sync,
+    {
+        let is_params_changed = self
+            .rendered_params
+            .as_ref()
+            .is_some_and(|rendered_params| rendered_params != params);
+
+        if is_requested || is_params_changed {
+            self.rendered_params = Some(params.clone());
+            self.render(params, color);
+        }
+    }
+
+    pub fn shape(&mut self, ctx: &egui::Context, rect: Rect) -> Shape {
+        if self.pixels.is_empty() {
+            return Shape::Noop;
+        }
+
+        if self.is_texture_outdated || self.texture.is_none() {
+            self.is_texture_outdated = false;
+
+            let image = ColorImage {
+                size: [self.width, self.height],
+                pixels: self.pixels.clone(),
+            };
+            match &mut self.texture {
+                Some(texture) => texture.set(image, TextureOptions::NEAREST),
+                None => {
+                    self.texture = Some(ctx.load_texture(
+                        &self.name,
+                        image,
+                        TextureOptions::NEAREST,
+                    ));
+                },
+            }
+        }
+
+        match &self.texture {
+            Some(texture) => Shape::image(
+                texture.id(),
+                rect,
+                Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                Color32::WHITE,
+            ),
+            None => Shape::Noop,
+        }
+    }
+}
+
+// Computes pixels row by row, on native platforms rows are split between threads
+pub fn render_rows<F>(width: usize, height: usize, color: F) -> Vec<Color32>
+where
+    F: Fn(usize, usize) -> Color32 + Sync,
+{
+    let mut pixels = vec![Color32::TRANSPARENT; width * height];
+    if pixels.is_empty() {
+        return pixels;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let threads = std::thread::available_parallelism().map_or(1, |value| value.get());
+        let rows_per_thread = height.div_ceil(threads);
+
+        std::thread::scope(|scope| {
+            for (chunk_index, chunk) in
+                pixels.chunks_mut(rows_per_thread * width).enumerate()
+            {
+                let color = &color;
+                scope.spawn(move || {
+                    for (index, pixel) in chunk.iter_mut().enumerate() {
+                        let y = chunk_index * rows_per_thread + index / width;
+                        *pixel = color(index % width, y);
+                    }
+                });
+            }
+        });
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        for (index, pixel) in pixels.iter_mut().enumerate() {
+            *pixel = color(index % width, index / width);
+        }
+    }
+
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::resolution::Resolution;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn rendering_follows_requests_and_view_changes() {
+        let mut raster = Raster::new("Test");
+        let mut params = CanvasParams {
+            center: Point2D::new(2.0, 2.0),
+            resolution: Resolution {
+                width: 4.0,
+                height: 4.0,
+            },
+            ..Default::default()
+        };
+        let calls = AtomicUsize::new(0);
+        let render = |raster: &mut Raster, params: &CanvasParams, is_requested| {
+            calls.store(0, Ordering::Relaxed);
+            raster.render_on_change(params, is_requested, |_| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Color32::WHITE
+            });
+            calls.load(Ordering::Relaxed)
+        };
+
+        // Nothing is drawn before the first request
+        assert_eq!(render(&mut raster, &params, false), 0);
+        assert_eq!(render(&mut raster, &params, true), 16);
+        assert_eq!(render(&mut raster, &params, false), 0);
+
+        params.offset = (1.0, 0.0);
+        assert_eq!(render(&mut raster, &params, false), 16);
+        assert_eq!(render(&mut raster, &params, false), 0);
+        assert_eq!(render(&mut raster, &params, true), 16);
+    }
+}
@@ -0,0 +1,397 @@
+use egui::Color32;
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crossbeam::channel::{unbounded, Receiver};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+pub const TILE_SIZE: usize = 32;
+
+// Every pass renders the picture with smaller blocks of the same color
+const BLOCK_SIZES: [usize; 5] = [16, 8, 4, 2, 1];
+
+// Tiles rendered on every frame on the web
+#[cfg(target_arch = "wasm32")]
+const TILES_PER_FRAME: usize = 4;
+
+// Steps of the model preparation made on every frame on the web
+#[cfg(target_arch = "wasm32")]
+const PREPARE_STEPS_PER_FRAME: usize = 8;
+
+// Picture, that is slow enough to be rendered in the background
+pub trait PixelModel: Send + Sync + 'static {
+    // Color of the pixel of an image with the given size
+    fn color(&self, x: f64, y: f64, width: usize, height: usize) -> Color32;
+
+    // Slow setup, that goes before the pixels, e.g. a reference orbit.
+    // It's made in small steps, is_stopped is checked between them, and the setup
+    // is resumed on the next call, until it returns true.
+    fn prepare(&self, _is_stopped: &dyn Fn() -> bool) -> bool {
+        true
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Job {
+    pass: usize,
+    x: usize,
+    y: usize,
+}
+
+struct Tile {
+    pass: usize,
+    x: usize,
+    y: usize,
+    width: usize,
+    pixels: Vec<Color32>,
+}
+
+// All tiles of the coarse passes go first, so the whole picture shows up quickly
+fn jobs(width: usize, height: usize) -> Vec<Job> {
+    (0..BLOCK_SIZES.len())
+        .flat_map(|pass| {
+            (0..height.div_ceil(TILE_SIZE)).flat_map(move |row| {
+                (0..width.div_ceil(TILE_SIZE)).map(move |column| Job {
+                    pass,
+                    x: column * TILE_SIZE,
+                    y: row * TILE_SIZE,
+                })
+            })
+        })
+        .collect()
+}
+
+// Rendering is cut short, when the tile is not needed anymore
+fn render(
+    model: &impl PixelModel, job: Job, width: usize, height: usize,
+    is_stopped: impl Fn() -> bool,
+) -> Option<Tile> {
+    let block = BLOCK_SIZES[job.pass];
+    let tile_width = TILE_SIZE.min(width - job.x);
+    let tile_height = TILE_SIZE.min(height - job.y);
+    let mut pixels = vec![Color32::TRANSPARENT; tile_width * tile_height];
+
+    for block_y in (0..tile_height).step_by(block) {
+        if is_stopped() {
+            return None;
+        }
+        for block_x in (0..tile_width).step_by(block) {
+            let color = model.color(
+                (job.x + block_x) as f64 + 0.5,
+                (job.y + block_y) as f64 + 0.5,
+                width,
+                height,
+            );
+            for y in block_y..(block_y + block).min(tile_height) {
+                for x in block_x..(block_x + block).min(tile_width) {
+                    pixels[y * tile_width + x] = color;
+                }
+            }
+        }
+    }
+
+    Some(Tile {
+        pass: job.pass,
+        x: job.x,
+        y: job.y,
+        width: tile_width,
+        pixels,
+    })
+}
+
+fn tiles_count(width: usize, height: usize) -> usize {
+    BLOCK_SIZES.len() * width.div_ceil(TILE_SIZE) * height.div_ceil(TILE_SIZE)
+}
+
+// Renders the picture tile by tile, with progressive refinement.
+// On native platforms the tiles are shared by the worker threads,
+// on the web there are no threads, so a few tiles are rendered on every frame.
+struct Worker<M: PixelModel> {
+    #[cfg(not(target_arch = "wasm32"))]
+    is_stopped: Arc<AtomicBool>,
+    #[cfg(not(target_arch = "wasm32"))]
+    receiver: Receiver<Tile>,
+    #[cfg(not(target_arch = "wasm32"))]
+    model: std::marker::PhantomData<M>,
+
+    #[cfg(target_arch = "wasm32")]
+    model: Arc<M>,
+    #[cfg(target_arch = "wasm32")]
+    jobs: std::collections::VecDeque<Job>,
+    #[cfg(target_arch = "wasm32")]
+    size: (usize, usize),
+    #[cfg(target_arch = "wasm32")]
+    tiles: Vec<Tile>,
+}
+
+impl<M: PixelModel> Worker<M> {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start(model: Arc<M>, width: usize, height: usize) -> Self {
+        let is_stopped = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = unbounded();
+        let jobs = Arc::new(jobs(width, height));
+        let next_job = Arc::new(AtomicUsize::new(0));
+
+        let threads = std::thread::available_parallelism().map_or(1, |value| value.get());
+        for _ in 0..threads {
+            let is_stopped = Arc::clone(&is_stopped);
+            let jobs = Arc::clone(&jobs);
+            let next_job = Arc::clone(&next_job);
+            let model = Arc::clone(&model);
+            let sender = sender.clone();
+
+            std::thread::spawn(move || {
+                let is_stopped = || is_stopped.load(Ordering::Relaxed);
+                if !model.prepare(&is_stopped) {
+                    return;
+                }
+                while let Some(job) = jobs.get(next_job.fetch_add(1, Ordering::Relaxed)) {
+                    let Some(tile) = render(&*model, *job, width, height, is_stopped)
+                    else {
+                        return;
+                    };
+                    if sender.send(tile).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        Self {
+            is_stopped,
+            receiver,
+            model: std::marker::PhantomData,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn start(model: Arc<M>, width: usize, height: usize) -> Self {
+        Self {
+            model,
+            jobs: jobs(width, height).into(),
+            size: (width, height),
+            tiles: vec![],
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn step(&mut self) {}
+
+    #[cfg(target_arch = "wasm32")]
+    fn step(&mut self) {
+        let steps = std::cell::Cell::new(0);
+        let is_frame_over = || {
+            steps.set(steps.get() + 1);
+            steps.get() > PREPARE_STEPS_PER_FRAME
+        };
+        if !self.model.prepare(&is_frame_over) {
+            return;
+        }
+
+        let (width, height) = self.size;
+        for _ in 0..TILES_PER_FRAME {
+            if let Some(job) = self.jobs.pop_front() {
+                self.tiles
+                    .extend(render(&*self.model, job, width, height, || false));
+            }
+        }
+    }
+
+    // Tiles rendered since the previous call
+    #[cfg(not(target_arch = "wasm32"))]
+    fn tiles(&mut self) -> Vec<Tile> {
+        self.receiver.try_iter().collect()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn tiles(&mut self) -> Vec<Tile> {
+        std::mem::take(&mut self.tiles)
+    }
+}
+
+// Threads are not joined, so the view can move without waiting for them,
+// they leave after the current row of blocks
+#[cfg(not(target_arch = "wasm32"))]
+impl<M: PixelModel> Drop for Worker<M> {
+    fn drop(&mut self) {
+        self.is_stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+// Picture, that is rendered in the background and assembled from the tiles
+pub struct TiledImage<M: PixelModel> {
+    worker: Option<Worker<M>>,
+
+    pixels: Vec<Color32>,
+    size: (usize, usize),
+    // The finest pass, that every tile got so far
+    tile_passes: Vec<Option<usize>>,
+    rendered_tiles: usize,
+}
+
+impl<M: PixelModel> Default for TiledImage<M> {
+    fn default() -> Self {
+        Self {
+            worker: None,
+
+            pixels: vec![],
+            size: (0, 0),
+            tile_passes: vec![],
+            rendered_tiles: 0,
+        }
+    }
+}
+
+impl<M: PixelModel> TiledImage<M> {
+    // Old picture stays until the new tiles cover it, unless the size is changed
+    pub fn start(&mut self, model: M, width: usize, height: usize, fill: Color32) {
+        if self.size != (width, height) {
+            self.size = (width, height);
+            self.pixels = vec![fill; width * height];
+        }
+        self.tile_passes =
+            vec![None; width.div_ceil(TILE_SIZE) * height.div_ceil(TILE_SIZE)];
+        self.rendered_tiles = 0;
+        self.worker = Some(Worker::start(Arc::new(model), width, height));
+    }
+
+    pub fn stop(&mut self) {
+        self.worker = None;
+    }
+
+    // Collects the rendered tiles, returns whether the picture is changed
+    pub fn update(&mut self) -> bool {
+        let Some(worker) = &mut self.worker else {
+            return false;
+        };
+
+        worker.step();
+        let tiles = worker.tiles();
+        let is_changed = !tiles.is_empty();
+        for tile in tiles {
+            self.apply(tile);
+        }
+
+        if self.rendered_tiles == tiles_count(self.size.0, self.size.1) {
+            self.worker = None;
+        }
+
+        is_changed
+    }
+
+    fn apply(&mut self, tile: Tile) {
+        self.rendered_tiles += 1;
+
+        // Threads may finish the tiles out of order, coarser passes must not win
+        let columns = self.size.0.div_ceil(TILE_SIZE);
+        let index = tile.y / TILE_SIZE * columns + tile.x / TILE_SIZE;
+        if self.tile_passes[index].is_some_and(|pass| pass > tile.pass) {
+            return;
+        }
+        self.tile_passes[index] = Some(tile.pass);
+
+        let width = self.size.0;
+        for (row, pixels) in tile.pixels.chunks(tile.width).enumerate() {
+            let start = (tile.y + row) * width + tile.x;
+            self.pixels[start..start + tile.width].copy_from_slice(pixels);
+        }
+    }
+
+    pub fn pixels(&self) -> &[Color32] {
+        &self.pixels
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn is_rendering(&self) -> bool {
+        self.worker.is_some()
+    }
+
+    // Share of the rendered tiles, all passes included
+    pub fn progress(&self) -> f32 {
+        let total = tiles_count(self.size.0, self.size.1);
+        match total {
+            0 => 0.0,
+            _ => self.rendered_tiles as f32 / total as f32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every pixel gets its own color
+    struct Coordinates;
+
+    impl PixelModel for Coordinates {
+        fn color(&self, x: f64, y: f64, _width: usize, _height: usize) -> Color32 {
+            Color32::from_rgb(x as u8, y as u8, 0)
+        }
+    }
+
+    fn expected(width: usize, height: usize) -> Vec<Color32> {
+        (0..width * height)
+            .map(|index| {
+                Color32::from_rgb((index % width) as u8, (index / width) as u8, 0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn partial_tiles_cover_picture() {
+        // Neither side is a multiple of the tile size
+        let (width, height) = (70, 45);
+        let mut image = TiledImage::default();
+        image.start(Coordinates, width, height, Color32::BLACK);
+
+        for _ in 0..10_000 {
+            image.update();
+            if !image.is_rendering() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        assert!(!image.is_rendering());
+        assert_eq!(image.progress(), 1.0);
+        assert_eq!(image.pixels(), expected(width, height));
+    }
+
+    #[test]
+    fn coarse_tile_doesnt_cover_finer_one() {
+        let mut image = TiledImage::<Coordinates> {
+            pixels: vec![Color32::BLACK; 40 * 40],
+            size: (40, 40),
+            tile_passes: vec![None; 4],
+            ..Default::default()
+        };
+        let tile = |pass| Job { pass, x: 32, y: 32 };
+
+        for job in [tile(BLOCK_SIZES.len() - 1), tile(0)] {
+            if let Some(tile) = render(&Coordinates, job, 40, 40, || false) {
+                image.apply(tile);
+            }
+        }
+
+        let pixels = expected(40, 40);
+        for y in 32..40 {
+            let row = y * 40 + 32..(y + 1) * 40;
+            assert_eq!(image.pixels()[row.clone()], pixels[row]);
+        }
+        assert_eq!(image.rendered_tiles, 2);
+    }
+
+    #[test]
+    fn empty_picture_is_done_at_once() {
+        let mut image = TiledImage::default();
+        image.start(Coordinates, 0, 0, Color32::BLACK);
+
+        assert!(!image.update());
+        assert!(!image.is_rendering());
+        assert_eq!(image.progress(), 0.0);
+    }
+}
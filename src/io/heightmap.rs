@@ -0,0 +1,146 @@
+use crate::io::filter::FileFilter;
+use image::ImageError;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+#[cfg(target_arch = "wasm32")]
+use rfd::FileHandle;
+
+// 16-bit grayscale image, saved the same way as screenshots
+#[derive(Default)]
+pub struct HeightmapImage {
+    file_filter: Option<FileFilter>,
+    width: u32,
+    height: u32,
+    samples: Vec<u16>,
+}
+
+impl HeightmapImage {
+    pub fn with_file_filter(mut self, file_filter: FileFilter) -> Self {
+        self.file_filter = Some(file_filter);
+        self
+    }
+
+    pub fn with_samples(mut self, width: u32, height: u32, samples: Vec<u16>) -> Self {
+        debug_assert!((width * height) as usize == samples.len());
+
+        self.width = width;
+        self.height = height;
+        self.samples = samples;
+        self
+    }
+
+    // Encoders expect 16-bit samples in native byte order
+    fn bytes(&self) -> Vec<u8> {
+        self.samples
+            .iter()
+            .flat_map(|sample| sample.to_ne_bytes())
+            .collect()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HeightmapImage {
+    pub fn save_dialog(&self) -> Result<(), ImageError> {
+        let mut file_dialog = rfd::FileDialog::new();
+
+        if let Some(filter) = &self.file_filter {
+            file_dialog = file_dialog.add_filter(&filter.name, &filter.file_extensions)
+        }
+
+        if let Some(path) = file_dialog.save_file() {
+            Ok(self.save_in(path)?)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn save_in(&self, path: PathBuf) -> Result<(), ImageError> {
+        let result = image::save_buffer_with_format(
+            &path,
+            &self.bytes(),
+            self.width,
+            self.height,
+            image::ColorType::L16,
+            image::ImageFormat::Png,
+        );
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&path);
+        }
+
+        result
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl HeightmapImage {
+    pub async fn save_dialog(&self) -> Result<(), ImageError> {
+        use crate::io;
+
+        let mut file_dialog = rfd::AsyncFileDialog::new();
+
+        if let Some(filter) = &self.file_filter {
+            file_dialog = file_dialog.add_filter(&filter.name, &filter.file_extensions);
+
+            let name = io::filename::generate(6, &filter.file_extensions);
+            file_dialog = file_dialog.set_file_name(&name);
+        }
+
+        let task = file_dialog.save_file();
+
+        if let Some(file) = task.await {
+            Ok(self.save_in(file).await?)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub async fn save_in(&self, file_handle: FileHandle) -> Result<(), ImageError> {
+        use image::codecs::png;
+        use image::ImageEncoder;
+        use std::io::Cursor;
+
+        let mut buffer: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let encoder = png::PngEncoder::new(&mut buffer);
+
+        let result = png::PngEncoder::write_image(
+            encoder,
+            &self.bytes(),
+            self.width,
+            self.height,
+            image::ExtendedColorType::L16,
+        );
+
+        if let Err(err) = result {
+            log::error!("Error saving a heightmap! {}", err);
+            return Err(err);
+        }
+
+        let data = buffer.into_inner();
+        file_handle.write(&data).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_16_bit_png() {
+        let path = std::env::temp_dir().join("fractal-renderer-heightmap-test.png");
+        let image =
+            HeightmapImage::default().with_samples(2, 2, vec![0, 1000, 40000, 65535]);
+
+        let result = image.save_in(path.clone());
+        let loaded = image::open(&path).map(|loaded| loaded.into_luma16());
+        let _ = std::fs::remove_file(&path);
+
+        assert!(result.is_ok());
+        let loaded = loaded.map(|loaded| loaded.into_raw()).unwrap_or_default();
+        assert_eq!(loaded, vec![0, 1000, 40000, 65535]);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::io;
+use crate::io::filter::FileFilter;
+use crate::ui::windows::message::MessageWindow;
+use crate::ui::windows::Window;
+use crossbeam::channel::Sender;
+
+// Picks a JSON file and sends its contents, errors are shown in a message window
+pub fn load_with_file_pick(
+    json_sender: &Sender<String>, windows_sender: &Sender<Box<dyn Window>>,
+) {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let json = match io::ops_native::load_with_file_pick(FileFilter::json()) {
+            Some(Ok(json)) => json,
+            Some(Err(err)) => {
+                let message = format!("File Error: {}", err);
+                let _ = windows_sender.send(Box::new(MessageWindow::error(&message)));
+                return;
+            },
+            None => return,
+        };
+
+        let _ = json_sender.send(json);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let json_sender = json_sender.clone();
+        let windows_sender = windows_sender.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let json = match io::ops_wasm::load_with_file_pick(FileFilter::json()).await {
+                Some(Ok(json)) => json,
+                Some(Err(err)) => {
+                    let message = format!("File Error: {}", err);
+                    let _ = windows_sender.send(Box::new(MessageWindow::error(&message)));
+                    return;
+                },
+                None => return,
+            };
+            let _ = json_sender.send(json);
+        });
+    }
+}
+
+// Saves serialized JSON to a picked file, errors are shown in a message window
+pub fn save_with_file_pick(
+    json: serde_json::Result<String>, windows_sender: &Sender<Box<dyn Window>>,
+) {
+    let json = match json {
+        Ok(value) => value,
+        Err(err) => {
+            let message = format!("JSON Error: {}", err);
+            let _ = windows_sender.send(Box::new(MessageWindow::error(&message)));
+            return;
+        },
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if let Some(Err(err)) =
+            io::ops_native::save_with_file_pick(json, FileFilter::json())
+        {
+            let message = format!("File Error: {}", err);
+            let _ = windows_sender.send(Box::new(MessageWindow::error(&message)));
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let windows_sender = windows_sender.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Some(Err(err)) =
+                io::ops_wasm::save_with_file_pick(json, FileFilter::json()).await
+            {
+                let message = format!("File Error: {}", err);
+                let _ = windows_sender.send(Box::new(MessageWindow::error(&message)));
+            }
+        });
+    }
+}
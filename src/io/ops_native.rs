@@ -13,6 +13,19 @@ pub fn load_with_file_pick(file_filter: FileFilter) -> Option<Result<String, io:
     None
 }
 
+pub fn load_bytes_with_file_pick(
+    file_filter: FileFilter,
+) -> Option<Result<Vec<u8>, io::Error>> {
+    if let Some(path) = rfd::FileDialog::new()
+        .add_filter(file_filter.name, &file_filter.file_extensions)
+        .pick_file()
+    {
+        return Some(fs::read(path));
+    }
+
+    None
+}
+
 pub fn save_with_file_pick(
     text: String, file_filter: FileFilter,
 ) -> Option<Result<(), io::Error>> {
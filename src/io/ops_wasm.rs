@@ -16,6 +16,20 @@ pub async fn load_with_file_pick(
     }
 }
 
+pub async fn load_bytes_with_file_pick(
+    file_filter: FileFilter,
+) -> Option<Result<Vec<u8>, std::io::Error>> {
+    let task = rfd::AsyncFileDialog::new()
+        .add_filter(file_filter.name, &file_filter.file_extensions)
+        .pick_file();
+
+    if let Some(file_handle) = task.await {
+        Some(Ok(file_handle.read().await))
+    } else {
+        None
+    }
+}
+
 pub async fn save_with_file_pick(
     text: String, file_filter: FileFilter,
 ) -> Option<Result<(), std::io::Error>> {
@@ -43,15 +43,21 @@ mod context;
 mod fractals;
 mod graphics {
     pub mod grid;
+    pub mod raster;
     pub mod resolution;
+    pub mod tiles;
 }
 mod geometry {
+    pub mod circle2d;
     pub mod dot;
     pub mod line2d;
     pub mod point2d;
+    pub mod polygon2d;
 }
 mod io {
     pub mod filter;
+    pub mod heightmap;
+    pub mod json;
     pub mod screenshot;
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -64,6 +70,9 @@ mod io {
 }
 mod math {
     pub mod angle;
+    pub mod complex;
+    pub mod fixed;
+    pub mod random;
 }
 mod ui;
 
@@ -0,0 +1,390 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// Fractional digits are computed in groups, that fit into u32
+const DECIMAL_GROUP: u32 = 1_000_000_000;
+const DECIMAL_GROUP_DIGITS: usize = 9;
+
+// Decimal exponents beyond this are not parsed, no number of the plane needs them
+const MAX_DECIMAL_EXPONENT: i64 = 10_000;
+
+// Signed fixed-point number with arbitrary precision.
+// It has 32 integer bits and 32 bits for every fractional limb,
+// so it's precise where f64 has run out of its 53 bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixed {
+    is_negative: bool,
+    // Little-endian limbs of the magnitude, the last one is the integer part
+    limbs: Vec<u32>,
+}
+
+impl Fixed {
+    pub fn zero(fraction_limbs: usize) -> Self {
+        Self {
+            is_negative: false,
+            limbs: vec![0; fraction_limbs + 1],
+        }
+    }
+
+    // Fractional bits of f64 beyond the precision are cut off
+    pub fn from_f64(value: f64, fraction_limbs: usize) -> Self {
+        let mut result = Self::zero(fraction_limbs);
+        if !value.is_finite() {
+            return result;
+        }
+
+        let magnitude = value.abs().min(u32::MAX as f64);
+        result.limbs[fraction_limbs] = magnitude.trunc() as u32;
+
+        // Multiplication by 2^32 is exact, so every limb gets its bits as they are
+        let mut fraction = magnitude.fract();
+        for limb in result.limbs[..fraction_limbs].iter_mut().rev() {
+            if fraction == 0.0 {
+                break;
+            }
+            fraction *= 4_294_967_296.0;
+            *limb = fraction.trunc() as u32;
+            fraction = fraction.fract();
+        }
+
+        result.is_negative = value < 0.0 && !result.is_zero();
+        result
+    }
+
+    // Decimal number like "-0.75", "1.5e-3" or "7", returns None if it's malformed
+    // or doesn't fit into the integer part
+    pub fn parse(text: &str, fraction_limbs: usize) -> Option<Self> {
+        let text = text.trim();
+        let (is_negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(index) => (&text[..index], text[index + 1..].parse::<i64>().ok()?),
+            None => (text, 0),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return None;
+        }
+
+        // Digits as a big integer, where the decimal point is moved by the exponent
+        let mut digits: Vec<u32> = vec![0];
+        for symbol in integer.chars().chain(fraction.chars()) {
+            let digit = symbol.to_digit(10)?;
+            let carry = multiply_small(&mut digits, 10, digit);
+            if carry != 0 {
+                digits.push(carry);
+            }
+        }
+        let exponent = exponent.checked_sub(fraction.len() as i64)?;
+        if exponent.abs() > MAX_DECIMAL_EXPONENT {
+            return None;
+        }
+
+        for _ in 0..exponent.max(0) {
+            if multiply_small(&mut digits, 10, 0) != 0 {
+                return None;
+            }
+        }
+
+        // Fractional limbs are added below the integer, then the powers of 10 are divided
+        let mut limbs = vec![0; fraction_limbs];
+        limbs.extend(digits);
+        let mut divisions = (-exponent).max(0) as usize;
+        while divisions > 0 {
+            let power = divisions.min(DECIMAL_GROUP_DIGITS);
+            divide_small(&mut limbs, 10u32.pow(power as u32));
+            divisions -= power;
+        }
+        if limbs[fraction_limbs + 1..].iter().any(|limb| *limb != 0) {
+            return None;
+        }
+        limbs.truncate(fraction_limbs + 1);
+
+        let mut result = Self { is_negative, limbs };
+        result.is_negative &= !result.is_zero();
+        Some(result)
+    }
+
+    // Decimal representation, rounded to at most the given number of fractional digits
+    pub fn to_decimal(&self, digits: usize) -> String {
+        let fraction_limbs = self.fraction_limbs();
+
+        // Half of the last digit is added, so the truncated digits are rounded
+        let half = Self::parse(&format!("5e-{}", digits + 1), fraction_limbs)
+            .unwrap_or_else(|| Self::zero(fraction_limbs));
+        let magnitude = add_magnitudes(&self.limbs, &half.limbs);
+
+        // Every multiplication of the fraction by 10^9 carries out 9 decimal digits
+        let mut fraction = magnitude[..fraction_limbs].to_vec();
+        let mut decimals = String::new();
+        while decimals.len() < digits && fraction.iter().any(|limb| *limb != 0) {
+            let group = multiply_small(&mut fraction, DECIMAL_GROUP, 0);
+            decimals.push_str(&format!(
+                "{:0width$}",
+                group,
+                width = DECIMAL_GROUP_DIGITS
+            ));
+        }
+        decimals.truncate(digits);
+        let decimals = decimals.trim_end_matches('0');
+
+        let mut text = magnitude[fraction_limbs].to_string();
+        if !decimals.is_empty() {
+            text.push('.');
+            text.push_str(decimals);
+        }
+        if self.is_negative && text != "0" {
+            text.insert(0, '-');
+        }
+
+        text
+    }
+
+    // Nearest f64, precise digits beyond its 53 bits are lost
+    pub fn to_f64(&self) -> f64 {
+        let fraction_limbs = self.fraction_limbs() as i32;
+        let magnitude = self
+            .limbs
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, limb)| {
+                *limb as f64 * 2f64.powi(32 * (index as i32 - fraction_limbs))
+            })
+            .sum::<f64>();
+
+        if self.is_negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    pub fn fraction_limbs(&self) -> usize {
+        self.limbs.len() - 1
+    }
+
+    // Low limbs are cut off or padded with zeros
+    pub fn with_precision(&self, fraction_limbs: usize) -> Self {
+        let current = self.fraction_limbs();
+        let limbs = if fraction_limbs < current {
+            self.limbs[current - fraction_limbs..].to_vec()
+        } else {
+            let mut limbs = vec![0; fraction_limbs - current];
+            limbs.extend_from_slice(&self.limbs);
+            limbs
+        };
+
+        let mut result = Self {
+            is_negative: self.is_negative,
+            limbs,
+        };
+        result.is_negative &= !result.is_zero();
+        result
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|limb| *limb == 0)
+    }
+
+    fn signed_sum(&self, other: &Fixed, is_other_negative: bool) -> Fixed {
+        debug_assert!(self.limbs.len() == other.limbs.len());
+
+        if self.is_negative == is_other_negative {
+            return Fixed {
+                is_negative: self.is_negative,
+                limbs: add_magnitudes(&self.limbs, &other.limbs),
+            };
+        }
+
+        let mut result = match compare_magnitudes(&self.limbs, &other.limbs) {
+            Ordering::Less => Fixed {
+                is_negative: is_other_negative,
+                limbs: subtract_magnitudes(&other.limbs, &self.limbs),
+            },
+            _ => Fixed {
+                is_negative: self.is_negative,
+                limbs: subtract_magnitudes(&self.limbs, &other.limbs),
+            },
+        };
+        result.is_negative &= !result.is_zero();
+        result
+    }
+}
+
+impl Add for &Fixed {
+    type Output = Fixed;
+
+    fn add(self, other: Self) -> Fixed {
+        self.signed_sum(other, other.is_negative)
+    }
+}
+
+impl Sub for &Fixed {
+    type Output = Fixed;
+
+    fn sub(self, other: Self) -> Fixed {
+        self.signed_sum(other, !other.is_negative)
+    }
+}
+
+impl Mul for &Fixed {
+    type Output = Fixed;
+
+    // Integer part of the product has to fit into 32 bits, low bits are cut off
+    fn mul(self, other: Self) -> Fixed {
+        debug_assert!(self.limbs.len() == other.limbs.len());
+
+        let length = self.limbs.len();
+        let fraction_limbs = length - 1;
+        let mut product = vec![0u64; 2 * length];
+        for (i, a) in self.limbs.iter().enumerate() {
+            if *a == 0 {
+                continue;
+            }
+            let mut carry = 0u64;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let value = product[i + j] + *a as u64 * *b as u64 + carry;
+                product[i + j] = value & 0xFFFF_FFFF;
+                carry = value >> 32;
+            }
+            product[i + length] = carry;
+        }
+
+        let mut result = Fixed {
+            is_negative: self.is_negative != other.is_negative,
+            limbs: product[fraction_limbs..fraction_limbs + length]
+                .iter()
+                .map(|limb| *limb as u32)
+                .collect(),
+        };
+        result.is_negative &= !result.is_zero();
+        result
+    }
+}
+
+impl Neg for &Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed {
+            is_negative: !self.is_negative && !self.is_zero(),
+            limbs: self.limbs.clone(),
+        }
+    }
+}
+
+// Multiplies little-endian limbs by a small factor and adds a small value,
+// returns the carry, that doesn't fit into the limbs
+fn multiply_small(limbs: &mut [u32], factor: u32, addend: u32) -> u32 {
+    let mut carry = addend as u64;
+    for limb in limbs.iter_mut() {
+        let value = *limb as u64 * factor as u64 + carry;
+        *limb = value as u32;
+        carry = value >> 32;
+    }
+
+    carry as u32
+}
+
+// Divides little-endian limbs by a small divisor, the remainder is dropped
+fn divide_small(limbs: &mut [u32], divisor: u32) {
+    let mut remainder = 0u64;
+    for limb in limbs.iter_mut().rev() {
+        let value = (remainder << 32) | *limb as u64;
+        *limb = (value / divisor as u64) as u32;
+        remainder = value % divisor as u64;
+    }
+}
+
+fn compare_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+// Overflow of the integer part wraps around
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut carry = 0u64;
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| {
+            let value = *a as u64 + *b as u64 + carry;
+            carry = value >> 32;
+            value as u32
+        })
+        .collect()
+}
+
+// The first magnitude has to be greater or equal to the second
+fn subtract_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut borrow = 0i64;
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| {
+            let mut value = *a as i64 - *b as i64 - borrow;
+            borrow = 0;
+            if value < 0 {
+                value += 1 << 32;
+                borrow = 1;
+            }
+            value as u32
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_round_trip() {
+        let text = "-0.7436438870371587047521915061147741225417144275971";
+        let number = Fixed::parse(text, 8).unwrap_or(Fixed::zero(8));
+
+        assert_eq!(number.to_decimal(49), text);
+        assert_eq!(number.to_decimal(3), "-0.744");
+        assert_eq!(
+            Fixed::parse("1.5e-3", 2).map(|n| n.to_decimal(10)),
+            Some("0.0015".to_string())
+        );
+        assert_eq!(
+            Fixed::parse("25e1", 2).map(|n| n.to_decimal(10)),
+            Some("250".to_string())
+        );
+        assert_eq!(Fixed::parse("1e10", 2), None);
+        assert_eq!(Fixed::parse("1.2.3", 2), None);
+        assert_eq!(Fixed::parse("-", 2), None);
+    }
+
+    #[test]
+    fn arithmetic_keeps_digits_beyond_f64() {
+        let a = Fixed::parse("1.00000000000000000000000000000003", 4)
+            .unwrap_or(Fixed::zero(4));
+        let b = Fixed::parse("-1", 4).unwrap_or(Fixed::zero(4));
+
+        assert_eq!(
+            (&a + &b).to_decimal(32),
+            "0.00000000000000000000000000000003"
+        );
+        assert_eq!(
+            (&b - &a).to_decimal(32),
+            "-2.00000000000000000000000000000003"
+        );
+        assert_eq!(
+            (&a * &b).to_decimal(32),
+            "-1.00000000000000000000000000000003"
+        );
+        assert_eq!((&b * &b).to_decimal(32), "1");
+        assert_eq!((&a + &b).with_precision(2).to_decimal(32), "0");
+    }
+
+    #[test]
+    fn f64_conversions() {
+        for value in [0.0, -1.75, 1e-30, 0.1, -1234.5678] {
+            assert_eq!(Fixed::from_f64(value, 6).to_f64(), value);
+        }
+        assert_eq!((-&Fixed::from_f64(0.0, 1)).to_decimal(5), "0");
+    }
+}
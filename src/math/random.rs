@@ -0,0 +1,11 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+pub const DEFAULT_SEED: u64 = 0;
+
+pub fn rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
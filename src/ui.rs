@@ -86,6 +86,7 @@ pub mod components {
 }
 pub mod styles {
     pub mod colors;
+    pub mod gradient;
     pub mod strokes;
 }
 pub mod windows;
@@ -6,7 +6,7 @@ use crate::io::filter::FileFilter;
 use crate::io::screenshot::Screenshot;
 use crate::ui::styles::colors;
 use crate::ui::windows::message::MessageWindow;
-use egui::{Frame, Painter, Response, Sense, Shape};
+use egui::{pos2, Frame, Painter, Rect, Response, Sense, Shape};
 
 pub struct Canvas {
     pub params: CanvasParams,
@@ -31,8 +31,57 @@ impl Canvas {
 
         let mut grid = context.grid.shapes(&self.params);
         let mut fractal = match context.fractal_type {
-            FractalType::Ifs => context.ifs_state.shapes(&self.params),
+            FractalType::Ifs => match context.ifs_state.shapes(&self.params) {
+                Ok(shapes) => shapes,
+                Err(err) => {
+                    let _ = context.windows_sender.send(Box::new(err.window()));
+                    vec![]
+                },
+            },
             FractalType::LSystem => context.lsystem_state.shapes(&self.params),
+            FractalType::Newton => context.newton_state.shapes(ui.ctx(), &self.params),
+            FractalType::Attractor => {
+                match context.attractor_state.shapes(&self.params) {
+                    Ok(shapes) => shapes,
+                    Err(err) => {
+                        let _ = context.windows_sender.send(Box::new(err.window()));
+                        vec![]
+                    },
+                }
+            },
+            FractalType::Buddhabrot => {
+                context.buddhabrot_state.shapes(ui.ctx(), &self.params)
+            },
+            FractalType::Lyapunov => {
+                context.lyapunov_state.shapes(ui.ctx(), &self.params)
+            },
+            FractalType::Dla => context.dla_state.shapes(ui.ctx(), &self.params),
+            FractalType::Terrain => context.terrain_state.shapes(ui.ctx(), &self.params),
+            FractalType::Curve => context.curve_state.shapes(&self.params, response),
+            FractalType::Apollonian => context.apollonian_state.shapes(&self.params),
+            FractalType::Kleinian => context.kleinian_state.shapes(&self.params),
+            FractalType::Tree => context.tree_state.shapes(&self.params),
+            FractalType::Tiling => context.tiling_state.shapes(&self.params),
+            FractalType::Automaton => {
+                context.automaton_state.shapes(ui.ctx(), &self.params)
+            },
+            FractalType::Sandpile => {
+                context.sandpile_state.shapes(ui.ctx(), &self.params)
+            },
+            FractalType::Graph => context.graph_state.shapes(&self.params),
+            FractalType::Interpolation => {
+                context.interpolation_state.shapes(&self.params, response)
+            },
+            FractalType::Julia => context.julia_state.shapes(&self.params),
+            FractalType::Mandelbulb => {
+                context.mandelbulb_state.shapes(ui.ctx(), &self.params)
+            },
+            FractalType::Mandelbrot => {
+                context
+                    .mandelbrot_state
+                    .shapes(ui.ctx(), &self.params, response)
+            },
+            FractalType::Chaos => context.chaos_state.shapes(&self.params),
         };
 
         self.shapes.append(&mut grid);
@@ -178,6 +227,26 @@ impl CanvasParams {
         value / self.px_per_cm * self.unit_length
     }
 
+    pub fn point_px_to_cm(&self, point: Point2D) -> Point2D {
+        let x =
+            (point.x - self.center.x - self.offset.0) / self.px_per_cm * self.unit_length;
+        let y =
+            (self.center.y - point.y + self.offset.1) / self.px_per_cm * self.unit_length;
+
+        Point2D::new(x, y)
+    }
+
+    // Canvas region on the screen. Resolution holds its bottom right corner.
+    pub fn screen_rect(&self) -> Rect {
+        let min = pos2(
+            2.0 * self.center.x - self.resolution.width,
+            2.0 * self.center.y - self.resolution.height,
+        );
+        let max = pos2(self.resolution.width, self.resolution.height);
+
+        Rect::from_min_max(min, max)
+    }
+
     pub fn update_offset_on_drag(&mut self, ui: &egui::Ui, response: &Response) {
         if self.is_dragging_enabled && response.dragged() {
             ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
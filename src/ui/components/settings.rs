@@ -1,6 +1,25 @@
 use crate::context::Context;
+use crate::fractals::apollonian::ui::settings::ApollonianSettingsBlock;
+use crate::fractals::attractor::ui::settings::AttractorSettingsBlock;
+use crate::fractals::automaton::ui::settings::AutomatonSettingsBlock;
+use crate::fractals::buddhabrot::ui::settings::BuddhabrotSettingsBlock;
+use crate::fractals::chaos::ui::settings::ChaosSettingsBlock;
+use crate::fractals::curve::ui::settings::CurveSettingsBlock;
+use crate::fractals::dla::ui::settings::DlaSettingsBlock;
+use crate::fractals::graph::ui::settings::GraphSettingsBlock;
 use crate::fractals::ifs::ui::settings::IfsSettingsBlock;
+use crate::fractals::interpolation::ui::settings::InterpolationSettingsBlock;
+use crate::fractals::julia::ui::settings::JuliaSettingsBlock;
+use crate::fractals::kleinian::ui::settings::KleinianSettingsBlock;
 use crate::fractals::lsystem::ui::settings::LSystemSettingsBlock;
+use crate::fractals::lyapunov::ui::settings::LyapunovSettingsBlock;
+use crate::fractals::mandelbrot::ui::settings::MandelbrotSettingsBlock;
+use crate::fractals::mandelbulb::ui::settings::MandelbulbSettingsBlock;
+use crate::fractals::newton::ui::settings::NewtonSettingsBlock;
+use crate::fractals::sandpile::ui::settings::SandpileSettingsBlock;
+use crate::fractals::terrain::ui::settings::TerrainSettingsBlock;
+use crate::fractals::tiling::ui::settings::TilingSettingsBlock;
+use crate::fractals::tree::ui::settings::TreeSettingsBlock;
 use crate::fractals::FractalType;
 use crate::ui::components::canvas;
 use crate::ui::components::canvas::Canvas;
@@ -12,6 +31,25 @@ pub struct Settings {
 
     ifs_settings: IfsSettingsBlock,
     lsystem_settings: LSystemSettingsBlock,
+    newton_settings: NewtonSettingsBlock,
+    attractor_settings: AttractorSettingsBlock,
+    buddhabrot_settings: BuddhabrotSettingsBlock,
+    lyapunov_settings: LyapunovSettingsBlock,
+    dla_settings: DlaSettingsBlock,
+    terrain_settings: TerrainSettingsBlock,
+    curve_settings: CurveSettingsBlock,
+    apollonian_settings: ApollonianSettingsBlock,
+    kleinian_settings: KleinianSettingsBlock,
+    tree_settings: TreeSettingsBlock,
+    tiling_settings: TilingSettingsBlock,
+    automaton_settings: AutomatonSettingsBlock,
+    sandpile_settings: SandpileSettingsBlock,
+    graph_settings: GraphSettingsBlock,
+    interpolation_settings: InterpolationSettingsBlock,
+    julia_settings: JuliaSettingsBlock,
+    mandelbulb_settings: MandelbulbSettingsBlock,
+    mandelbrot_settings: MandelbrotSettingsBlock,
+    chaos_settings: ChaosSettingsBlock,
 }
 
 impl Default for Settings {
@@ -22,6 +60,25 @@ impl Default for Settings {
 
             ifs_settings: Default::default(),
             lsystem_settings: Default::default(),
+            newton_settings: Default::default(),
+            attractor_settings: Default::default(),
+            buddhabrot_settings: Default::default(),
+            lyapunov_settings: Default::default(),
+            dla_settings: Default::default(),
+            terrain_settings: Default::default(),
+            curve_settings: Default::default(),
+            apollonian_settings: Default::default(),
+            kleinian_settings: Default::default(),
+            tree_settings: Default::default(),
+            tiling_settings: Default::default(),
+            automaton_settings: Default::default(),
+            sandpile_settings: Default::default(),
+            graph_settings: Default::default(),
+            interpolation_settings: Default::default(),
+            julia_settings: Default::default(),
+            mandelbulb_settings: Default::default(),
+            mandelbrot_settings: Default::default(),
+            chaos_settings: Default::default(),
         }
     }
 }
@@ -39,20 +96,21 @@ impl Settings {
 
             Grid::new("FractalTypeGrid").num_columns(2).show(ui, |ui| {
                 ui.label("Type:");
+                let mut selected = context.fractal_type;
                 egui::ComboBox::from_id_salt("FractalType")
-                    .selected_text(format!("{}", context.fractal_type))
+                    .selected_text(format!("{}", selected))
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut context.fractal_type,
-                            FractalType::Ifs,
-                            FractalType::Ifs.to_string(),
-                        );
-                        ui.selectable_value(
-                            &mut context.fractal_type,
-                            FractalType::LSystem,
-                            FractalType::LSystem.to_string(),
-                        );
+                        for fractal_type in FractalType::iter() {
+                            ui.selectable_value(
+                                &mut selected,
+                                fractal_type,
+                                fractal_type.to_string(),
+                            );
+                        }
                     });
+                if selected != context.fractal_type {
+                    context.set_fractal_type(selected);
+                }
             });
 
             ui.add_space(10.0);
@@ -75,6 +133,25 @@ impl Settings {
             match context.fractal_type {
                 FractalType::Ifs => self.ifs_settings.show(ui, context),
                 FractalType::LSystem => self.lsystem_settings.show(ui, context),
+                FractalType::Newton => self.newton_settings.show(ui, context),
+                FractalType::Attractor => self.attractor_settings.show(ui, context),
+                FractalType::Buddhabrot => self.buddhabrot_settings.show(ui, context),
+                FractalType::Lyapunov => self.lyapunov_settings.show(ui, context),
+                FractalType::Dla => self.dla_settings.show(ui, context),
+                FractalType::Terrain => self.terrain_settings.show(ui, context),
+                FractalType::Curve => self.curve_settings.show(ui, context),
+                FractalType::Apollonian => self.apollonian_settings.show(ui, context),
+                FractalType::Kleinian => self.kleinian_settings.show(ui, context),
+                FractalType::Tree => self.tree_settings.show(ui, context),
+                FractalType::Tiling => self.tiling_settings.show(ui, context),
+                FractalType::Automaton => self.automaton_settings.show(ui, context),
+                FractalType::Sandpile => self.sandpile_settings.show(ui, context),
+                FractalType::Graph => self.graph_settings.show(ui, context),
+                FractalType::Interpolation => self.interpolation_settings.show(ui, context),
+                FractalType::Julia => self.julia_settings.show(ui, context),
+                FractalType::Mandelbulb => self.mandelbulb_settings.show(ui, context),
+                FractalType::Mandelbrot => self.mandelbrot_settings.show(ui, context),
+                FractalType::Chaos => self.chaos_settings.show(ui, context),
             }
 
             ui.separator();
@@ -23,17 +23,14 @@ pub enum ColorScheme {
 }
 
 impl ColorScheme {
-    pub fn get_color(&self) -> Color32 {
+    pub fn get_color(&self, rng: &mut impl Rng) -> Color32 {
         match self {
             ColorScheme::Fixed(color) => *color,
-            ColorScheme::Random => {
-                let mut rng = rand::thread_rng();
-                Color32::from_rgb(
-                    rng.gen_range(0..=255),
-                    rng.gen_range(0..=255),
-                    rng.gen_range(0..=255),
-                )
-            },
+            ColorScheme::Random => Color32::from_rgb(
+                rng.gen_range(0..=255),
+                rng.gen_range(0..=255),
+                rng.gen_range(0..=255),
+            ),
             ColorScheme::Standard => BLACK,
         }
     }
@@ -0,0 +1,180 @@
+use crate::ui::styles::colors;
+use egui::{Color32, DragValue};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    // Sorted by position, except while one is dragged in the editor.
+    // Positions are in range 0..=1.
+    stops: Vec<(f32, Color32)>,
+}
+
+impl Default for Gradient {
+    fn default() -> Self {
+        Self::new(vec![
+            (0.0, Color32::from_rgb(0, 7, 100)),
+            (0.35, Color32::from_rgb(32, 107, 203)),
+            (0.65, Color32::from_rgb(237, 255, 255)),
+            (0.85, Color32::from_rgb(255, 170, 0)),
+            (1.0, colors::DARK_RED),
+        ])
+    }
+}
+
+impl Gradient {
+    pub fn new(stops: Vec<(f32, Color32)>) -> Self {
+        let mut gradient = Self { stops };
+        gradient.sort();
+        gradient
+    }
+
+    pub fn sample(&self, t: f32) -> Color32 {
+        let t = if t.is_finite() {
+            t.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let Some(first) = self.stops.first() else {
+            return colors::BLACK;
+        };
+        if t <= first.0 {
+            return first.1;
+        }
+
+        for window in self.stops.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            if t <= end.0 {
+                let length = end.0 - start.0;
+                if length <= f32::EPSILON {
+                    return end.1;
+                }
+                return lerp(start.1, end.1, (t - start.0) / length);
+            }
+        }
+
+        self.stops.last().map_or(colors::BLACK, |stop| stop.1)
+    }
+
+    // Returns true, if the gradient was changed
+    pub fn show_editor(&mut self, ui: &mut egui::Ui, id_salt: &str) -> bool {
+        let mut changed = false;
+        // Rows are not sorted, while a position is dragged, so they don't jump under it
+        let mut is_dragged = false;
+        let mut removed_stop: Option<usize> = None;
+        let can_remove = self.stops.len() > 2;
+
+        egui::Grid::new(format!("{}GradientGrid", id_salt))
+            .num_columns(3)
+            .show(ui, |ui| {
+                for (index, (position, color)) in self.stops.iter_mut().enumerate() {
+                    let response =
+                        ui.add(DragValue::new(position).speed(0.01).range(0.0..=1.0));
+                    changed |= response.changed() || response.drag_stopped();
+                    is_dragged |= response.dragged();
+                    changed |= egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        color,
+                        egui::color_picker::Alpha::Opaque,
+                    )
+                    .changed();
+                    if ui
+                        .add_enabled(can_remove, egui::Button::new("Remove"))
+                        .clicked()
+                    {
+                        removed_stop = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(index) = removed_stop {
+            self.stops.remove(index);
+            changed = true;
+        }
+
+        if ui.button("Add Color").clicked() {
+            let position = 0.5;
+            self.stops.push((position, self.sample(position)));
+            changed = true;
+        }
+
+        if changed && !is_dragged {
+            self.sort();
+        }
+
+        changed
+    }
+
+    fn sort(&mut self) {
+        self.stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+}
+
+fn lerp(start: Color32, end: Color32, t: f32) -> Color32 {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+
+    Color32::from_rgb(
+        channel(start.r(), end.r()),
+        channel(start.g(), end.g()),
+        channel(start.b(), end.b()),
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GradientStopDto {
+    #[serde(rename = "Position")]
+    pub position: f32,
+
+    #[serde(rename = "Color")]
+    pub color: [u8; 3],
+}
+
+impl From<&Gradient> for Vec<GradientStopDto> {
+    fn from(gradient: &Gradient) -> Self {
+        gradient
+            .stops
+            .iter()
+            .map(|(position, color)| GradientStopDto {
+                position: *position,
+                color: [color.r(), color.g(), color.b()],
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<GradientStopDto>> for Gradient {
+    fn from(stops: Vec<GradientStopDto>) -> Self {
+        if stops.is_empty() {
+            return Gradient::default();
+        }
+
+        Gradient::new(
+            stops
+                .into_iter()
+                .map(|stop| {
+                    let [r, g, b] = stop.color;
+                    (stop.position.clamp(0.0, 1.0), Color32::from_rgb(r, g, b))
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_interpolates_between_stops() {
+        let gradient = Gradient::new(vec![
+            (1.0, Color32::from_rgb(200, 100, 0)),
+            (0.0, Color32::from_rgb(0, 0, 0)),
+        ]);
+
+        assert_eq!(gradient.sample(-1.0), Color32::from_rgb(0, 0, 0));
+        assert_eq!(gradient.sample(0.5), Color32::from_rgb(100, 50, 0));
+        assert_eq!(gradient.sample(f32::NAN), Color32::from_rgb(0, 0, 0));
+        assert_eq!(gradient.sample(2.0), Color32::from_rgb(200, 100, 0));
+    }
+}